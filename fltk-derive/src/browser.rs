@@ -117,6 +117,12 @@ pub fn impl_browser_trait(ast: &DeriveInput) -> TokenStream {
         name.span(),
     );
     let value = Ident::new(format!("{}_{}", name_str, "value").as_str(), name.span());
+    let hide_line = Ident::new(format!("{}_{}", name_str, "hide_line").as_str(), name.span());
+    let show_line = Ident::new(format!("{}_{}", name_str, "show_line").as_str(), name.span());
+    let visible_line = Ident::new(
+        format!("{}_{}", name_str, "visible_line").as_str(),
+        name.span(),
+    );
 
     let gen = quote! {
         unsafe impl BrowserExt for #name {
@@ -134,6 +140,8 @@ pub fn impl_browser_trait(ast: &DeriveInput) -> TokenStream {
                 unsafe {
                     #add(self._inner, item.as_ptr())
                 }
+                let line = self.size();
+                crate::browser::filter_line(self, line);
             }
 
             fn insert(&mut self, line: u32, item: &str) {
@@ -143,6 +151,7 @@ pub fn impl_browser_trait(ast: &DeriveInput) -> TokenStream {
                 unsafe {
                     #insert(self._inner, line as i32, item.as_ptr())
                 }
+                crate::browser::filter_line(self, line);
             }
 
             fn move_item(&mut self, to: u32, from: u32) {
@@ -470,6 +479,72 @@ pub fn impl_browser_trait(ast: &DeriveInput) -> TokenStream {
                 assert!(!self.was_deleted());
                 unsafe { #value(self._inner) as u32 }
             }
+
+            fn hide_line(&mut self, line: u32) {
+                assert!(!self.was_deleted());
+                debug_assert!(line <= std::isize::MAX as u32, "u32 entries have to be < std::isize::MAX for compatibility!");
+                unsafe { #hide_line(self._inner, line as i32) }
+            }
+
+            fn show_line(&mut self, line: u32) {
+                assert!(!self.was_deleted());
+                debug_assert!(line <= std::isize::MAX as u32, "u32 entries have to be < std::isize::MAX for compatibility!");
+                unsafe { #show_line(self._inner, line as i32) }
+            }
+
+            fn line_visible(&self, line: u32) -> bool {
+                assert!(!self.was_deleted());
+                debug_assert!(line <= std::isize::MAX as u32, "u32 entries have to be < std::isize::MAX for compatibility!");
+                unsafe { #visible_line(self._inner, line as i32) != 0 }
+            }
+
+            fn set_type_ahead(&mut self, mode: Option<crate::browser::TypeAheadMode>) {
+                assert!(!self.was_deleted());
+                crate::browser::set_type_ahead(self, mode)
+            }
+
+            fn type_ahead(&self) -> Option<crate::browser::TypeAheadMode> {
+                assert!(!self.was_deleted());
+                crate::browser::type_ahead(self)
+            }
+
+            fn set_item_filter<F: FnMut(&str) -> bool + 'static>(&mut self, filter: F) {
+                assert!(!self.was_deleted());
+                crate::browser::set_item_filter(self, Box::new(filter))
+            }
+
+            fn clear_item_filter(&mut self) {
+                assert!(!self.was_deleted());
+                crate::browser::clear_item_filter(self)
+            }
+
+            fn load_csv<R: std::io::Read>(&mut self, mut reader: R, delimiter: char) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                let mut text = String::new();
+                std::io::Read::read_to_string(&mut reader, &mut text)?;
+                crate::browser::load_csv(self, &text, delimiter);
+                Ok(())
+            }
+
+            fn load_csv_file<P: AsRef<std::path::Path>>(&mut self, path: P, delimiter: char) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                if !path.as_ref().exists() {
+                    return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+                }
+                self.load_csv(std::fs::File::open(path)?, delimiter)
+            }
+
+            fn save_csv<W: std::io::Write>(&self, mut writer: W, delimiter: char) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                let text = crate::browser::csv_text(self, delimiter);
+                std::io::Write::write_all(&mut writer, text.as_bytes())?;
+                Ok(())
+            }
+
+            fn save_csv_file<P: AsRef<std::path::Path>>(&self, path: P, delimiter: char) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                self.save_csv(std::fs::File::create(path)?, delimiter)
+            }
         }
     };
     gen.into()
@@ -24,6 +24,7 @@ pub fn impl_button_trait(ast: &DeriveInput) -> TokenStream {
         format!("{}_{}", name_str, "set_down_box").as_str(),
         name.span(),
     );
+    let setonly = Ident::new(format!("{}_{}", name_str, "setonly").as_str(), name.span());
 
     let gen = quote! {
         unsafe impl ButtonExt for #name {
@@ -62,6 +63,21 @@ pub fn impl_button_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn value(&self) -> bool {
+                self.is_set()
+            }
+
+            fn set_value(&mut self, flag: bool) {
+                self.set(flag)
+            }
+
+            fn setonly(&mut self) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #setonly(self._inner)
+                }
+            }
+
             fn set_down_frame(&mut self, f: FrameType) {
                 assert!(!self.was_deleted());
                 unsafe {
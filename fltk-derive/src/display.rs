@@ -198,6 +198,10 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
         format!("{}_{}", name_str, "in_selection").as_str(),
         name.span(),
     );
+    let xy_to_position = Ident::new(
+        format!("{}_{}", name_str, "xy_to_position").as_str(),
+        name.span(),
+    );
     let wrap_mode = Ident::new(
         format!("{}_{}", name_str, "wrap_mode").as_str(),
         name.span(),
@@ -690,6 +694,44 @@ pub fn impl_display_trait(ast: &DeriveInput) -> TokenStream {
                     #wrapped_row(self._inner, row)
                 }
             }
+
+            fn xy_to_position(&self, x: i32, y: i32) -> u32 {
+                assert!(!self.was_deleted());
+                assert!(self.buffer().is_some());
+                // Fl_Text_Display::CURSOR_POS
+                unsafe { #xy_to_position(self._inner, x, y, 1) as u32 }
+            }
+
+            fn set_linenumber_callback<F: FnMut(&mut Self, u32) + 'static>(&mut self, mut cb: F) {
+                assert!(!self.was_deleted());
+                self.handle2(move |d, ev| {
+                    if ev != Event::Push {
+                        return false;
+                    }
+                    let buf = match d.buffer() {
+                        Some(buf) => buf,
+                        None => return false,
+                    };
+                    let ex = crate::app::event_x();
+                    if ex < d.x() || ex >= d.x() + d.linenumber_width() {
+                        return false;
+                    }
+                    let pos = d.xy_to_position(ex, crate::app::event_y());
+                    let line = buf.count_lines(0, pos) + 1;
+                    cb(d, line);
+                    true
+                });
+            }
+
+            fn set_linenumber_marker(&mut self, line: u32, marker: Option<(char, Color)>) {
+                assert!(!self.was_deleted());
+                crate::text::set_linenumber_marker(self, line, marker);
+            }
+
+            fn linenumber_markers(&self) -> Vec<(u32, char, Color)> {
+                assert!(!self.was_deleted());
+                crate::text::linenumber_markers(self)
+            }
         }
     };
     gen.into()
@@ -0,0 +1,175 @@
+use proc_macro::TokenStream;
+use syn::*;
+
+/// The default label of a field, its name with spaces and a capital first letter
+fn field_label(name: &str) -> String {
+    let name = name.replace('_', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+fn lit_f64(lit: &Lit) -> Option<f64> {
+    match lit {
+        Lit::Float(f) => f.base10_parse().ok(),
+        Lit::Int(i) => i.base10_parse().ok(),
+        Lit::Str(s) => s.value().trim().parse().ok(),
+        _ => None,
+    }
+}
+
+pub fn impl_form(ast: &DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Error::new_spanned(
+                ast,
+                "Form can only be derived for structs with named fields",
+            )
+            .to_compile_error()
+            .into()
+        }
+    };
+
+    let mut descs = vec![];
+    let mut loads = vec![];
+    let mut stores = vec![];
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let fname = ident.to_string();
+        let mut label = field_label(&fname);
+        let mut kind = quote! { <#ty as fltk::form::FormValue>::default_kind() };
+        let mut range = quote! { None };
+        let mut step = quote! { <#ty as fltk::form::FormValue>::default_step() };
+        let mut choices: Vec<String> = vec![];
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("form")) {
+            let list = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list,
+                _ => {
+                    return Error::new_spanned(attr, "Expected #[form(...)]")
+                        .to_compile_error()
+                        .into()
+                }
+            };
+            for nested in &list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(p)) if p.is_ident("skip") => skip = true,
+                    NestedMeta::Meta(Meta::NameValue(nv)) => {
+                        let key = nv
+                            .path
+                            .get_ident()
+                            .map(|i| i.to_string())
+                            .unwrap_or_default();
+                        let val = match &nv.lit {
+                            Lit::Str(s) => Some(s.value()),
+                            _ => None,
+                        };
+                        match (key.as_str(), val) {
+                            ("label", Some(v)) => label = v,
+                            ("widget", Some(v)) => {
+                                let variant = match v.as_str() {
+                                    "input" => "Input",
+                                    "check" => "Check",
+                                    "spinner" => "Spinner",
+                                    "slider" => "Slider",
+                                    "value_input" => "ValueInput",
+                                    "choice" => "Choice",
+                                    _ => {
+                                        return Error::new_spanned(&nv.lit, "Unknown form widget")
+                                            .to_compile_error()
+                                            .into()
+                                    }
+                                };
+                                let variant = Ident::new(variant, nv.lit.span());
+                                kind = quote! { fltk::form::FieldKind::#variant };
+                            }
+                            ("range", Some(v)) => {
+                                let bounds: Vec<Option<f64>> =
+                                    v.splitn(2, "..").map(|b| b.trim().parse().ok()).collect();
+                                match bounds.as_slice() {
+                                    [Some(min), Some(max)] => range = quote! { Some((#min, #max)) },
+                                    _ => {
+                                        return Error::new_spanned(
+                                            &nv.lit,
+                                            "Expected a range like \"0..10\"",
+                                        )
+                                        .to_compile_error()
+                                        .into()
+                                    }
+                                }
+                            }
+                            ("step", _) => match lit_f64(&nv.lit) {
+                                Some(s) => step = quote! { Some(#s) },
+                                None => {
+                                    return Error::new_spanned(&nv.lit, "Expected a number")
+                                        .to_compile_error()
+                                        .into()
+                                }
+                            },
+                            ("choices", Some(v)) => {
+                                choices = v.split('|').map(|c| c.to_string()).collect()
+                            }
+                            _ => {
+                                return Error::new_spanned(nested, "Unknown form attribute")
+                                    .to_compile_error()
+                                    .into()
+                            }
+                        }
+                    }
+                    _ => {
+                        return Error::new_spanned(nested, "Unknown form attribute")
+                            .to_compile_error()
+                            .into()
+                    }
+                }
+            }
+        }
+        if skip {
+            continue;
+        }
+        descs.push(quote! {
+            fltk::form::FieldDesc {
+                name: #fname,
+                label: #label.to_string(),
+                kind: #kind,
+                range: #range,
+                step: #step,
+                choices: vec![#(#choices.to_string()),*],
+            }
+        });
+        loads.push(quote! {
+            form.set_value(#fname, &fltk::form::FormValue::to_field(&self.#ident));
+        });
+        stores.push(quote! {
+            if let Some(v) = form.value(#fname).and_then(|v| fltk::form::FormValue::from_field(&v)) {
+                self.#ident = v;
+            }
+        });
+    }
+
+    let gen = quote! {
+        impl #impl_generics fltk::form::Form for #name #ty_generics #where_clause {
+            fn form_fields() -> Vec<fltk::form::FieldDesc> {
+                vec![#(#descs),*]
+            }
+
+            fn load_form(&self, form: &mut fltk::form::FormWidgets) {
+                #(#loads)*
+            }
+
+            fn store_form(&mut self, form: &fltk::form::FormWidgets) {
+                #(#stores)*
+            }
+        }
+    };
+    gen.into()
+}
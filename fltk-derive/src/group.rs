@@ -20,6 +20,10 @@ pub fn impl_group_trait(ast: &DeriveInput) -> TokenStream {
         format!("{}_{}", name_str, "resizable").as_str(),
         name.span(),
     );
+    let init_sizes = Ident::new(
+        format!("{}_{}", name_str, "init_sizes").as_str(),
+        name.span(),
+    );
 
     let gen = quote! {
         impl IntoIterator for #name {
@@ -49,7 +53,17 @@ pub fn impl_group_trait(ast: &DeriveInput) -> TokenStream {
             fn clear(&mut self) {
                 unsafe {
                     assert!(!self.was_deleted());
+                    let children = #children(self._inner);
+                    for i in (0..children).rev() {
+                        let child = #child(self._inner, i);
+                        if !child.is_null() {
+                            #remove(self._inner, child as *mut _);
+                            // Deferred to the next event loop iteration, so clear can be called from a child's callback
+                            fltk_sys::fl::Fl_delete_widget(child as *mut fltk_sys::fl::Fl_Widget);
+                        }
+                    }
                     #clear(self._inner);
+                    self.redraw();
                 }
             }
 
@@ -73,6 +87,23 @@ pub fn impl_group_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn find_by_label(&self, label: &str) -> Option<Box<dyn WidgetExt>> {
+                assert!(!self.was_deleted());
+                for i in 0..self.children() {
+                    if let Some(c) = self.child(i) {
+                        if c.label() == label {
+                            return Some(c);
+                        }
+                        if let Some(grp) = c.as_group() {
+                            if let Some(found) = grp.find_by_label(label) {
+                                return Some(found);
+                            }
+                        }
+                    }
+                }
+                None
+            }
+
             fn find<W: WidgetExt>(&self, widget: &W) -> u32 {
                 unsafe {
                     assert!(!self.was_deleted());
@@ -121,6 +152,35 @@ pub fn impl_group_trait(ast: &DeriveInput) -> TokenStream {
                     #resizable(self._inner, ptr as *mut _)
                 }
             }
+
+            fn set_resizable<W: WidgetExt>(&mut self, widget: Option<&W>) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    if let Some(widget) = widget {
+                        assert!(!widget.was_deleted());
+                        #resizable(self._inner, widget.as_widget_ptr() as *mut _)
+                    } else {
+                        #resizable(self._inner, std::ptr::null_mut())
+                    }
+                }
+            }
+
+            fn init_sizes(&mut self) {
+                unsafe {
+                    assert!(!self.was_deleted());
+                    #init_sizes(self._inner)
+                }
+            }
+
+            fn set_focus_order(&mut self, order: &[&dyn WidgetExt]) {
+                assert!(!self.was_deleted());
+                crate::group::set_focus_order(self, order)
+            }
+
+            fn focus_order(&self) -> Vec<crate::widget::Widget> {
+                assert!(!self.was_deleted());
+                crate::group::focus_order(self)
+            }
         }
     };
     gen.into()
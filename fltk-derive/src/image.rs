@@ -22,6 +22,31 @@ pub fn impl_image_trait(ast: &DeriveInput) -> TokenStream {
     let d = Ident::new(format!("{}_{}", name_str, "d").as_str(), name.span());
     let ld = Ident::new(format!("{}_{}", name_str, "ld").as_str(), name.span());
     let inactive = Ident::new(format!("{}_{}", name_str, "inactive").as_str(), name.span());
+    let desaturate = Ident::new(
+        format!("{}_{}", name_str, "desaturate").as_str(),
+        name.span(),
+    );
+    let color_average = Ident::new(
+        format!("{}_{}", name_str, "color_average").as_str(),
+        name.span(),
+    );
+
+    // images with a native format are written in it when the extension is missing or unknown
+    let native_format = match name.to_string().as_str() {
+        "PngImage" => Some(quote!(crate::image::ImageFormat::Png)),
+        "JpegImage" => Some(quote!(crate::image::ImageFormat::Jpeg)),
+        "BmpImage" => Some(quote!(crate::image::ImageFormat::Bmp)),
+        _ => None,
+    };
+    let path_format = match native_format {
+        Some(native) => quote! {
+            crate::image::ImageFormat::from_path(path).unwrap_or(#native)
+        },
+        None => quote! {
+            crate::image::ImageFormat::from_path(path)
+                .ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))?
+        },
+    };
 
     let gen = quote! {
         unsafe impl Sync for #name {}
@@ -168,6 +193,21 @@ pub fn impl_image_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn desaturate(&mut self) {
+                assert!(!self.was_deleted());
+                unsafe {
+                    #desaturate(self._inner)
+                }
+            }
+
+            fn color_average(&mut self, color: Color, weight: f32) {
+                assert!(!self.was_deleted());
+                debug_assert!((0.0..=1.0).contains(&weight), "color_average requires a weight between 0 and 1!");
+                unsafe {
+                    #color_average(self._inner, color.bits() as u32, weight)
+                }
+            }
+
             fn into_png(self) -> Result<PngImage, FltkError> {
                 assert!(!self.was_deleted());
                 let tmp_dir = std::path::PathBuf::from(&std::env::var(TMP)?);
@@ -198,6 +238,16 @@ pub fn impl_image_trait(ast: &DeriveInput) -> TokenStream {
                 Ok(ret)
             }
 
+            fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FltkError> {
+                let path = path.as_ref();
+                let format = #path_format;
+                crate::image::write_image(self, path, format)
+            }
+
+            fn encode(&self, format: crate::image::ImageFormat) -> Result<Vec<u8>, FltkError> {
+                crate::image::encode_image(self, format)
+            }
+
             unsafe fn delete(mut img: Self) {
                 assert!(!img._inner.is_null());
                 #delete(img._inner);
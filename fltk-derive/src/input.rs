@@ -188,8 +188,11 @@ pub fn impl_input_trait(ast: &DeriveInput) -> TokenStream {
             }
 
             fn undo(&mut self) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                if crate::input::has_undo_history(self) {
+                    return crate::input::edit_undo(self);
+                }
                 unsafe {
-                    assert!(!self.was_deleted());
                     let x = #undo(self._inner);
                     if x == 0 {
                         return Err(FltkError::Internal(FltkErrorKind::FailedOperation));
@@ -279,6 +282,70 @@ pub fn impl_input_trait(ast: &DeriveInput) -> TokenStream {
                     #set_wrap(self._inner, val as i32)
                 }
             }
+
+            fn utf8_align(&self, pos: u32) -> u32 {
+                assert!(!self.was_deleted());
+                crate::utils::utf8_align(&self.value(), pos as usize) as u32
+            }
+
+            fn char_position(&self) -> u32 {
+                assert!(!self.was_deleted());
+                crate::utils::byte_to_char_index(&self.value(), self.position() as usize) as u32
+            }
+
+            fn set_char_position(&mut self, idx: u32) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                let pos = crate::utils::char_to_byte_index(&self.value(), idx as usize)
+                    .ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+                self.set_position(pos as u32)
+            }
+
+            fn char_at(&self, idx: u32) -> Option<char> {
+                assert!(!self.was_deleted());
+                self.value().chars().nth(idx as usize)
+            }
+
+            fn insert_at_char(&mut self, idx: u32, txt: &str) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                let pos = crate::utils::char_to_byte_index(&self.value(), idx as usize)
+                    .ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+                self.replace(pos as u32, pos as u32, txt)
+            }
+
+            fn set_validator<F: FnMut(&str) -> bool + 'static>(&mut self, cb: F) {
+                assert!(!self.was_deleted());
+                crate::input::set_validator(self, Box::new(cb));
+            }
+
+            fn set_undo_limit(&mut self, limit: usize) {
+                assert!(!self.was_deleted());
+                crate::input::set_undo_limit(self, limit);
+            }
+
+            fn redo(&mut self) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                crate::input::edit_redo(self)
+            }
+
+            fn undo_depth(&self) -> usize {
+                assert!(!self.was_deleted());
+                crate::input::undo_depth(self).0
+            }
+
+            fn redo_depth(&self) -> usize {
+                assert!(!self.was_deleted());
+                crate::input::undo_depth(self).1
+            }
+
+            fn begin_undo_group(&mut self) {
+                assert!(!self.was_deleted());
+                crate::input::begin_undo_group(self);
+            }
+
+            fn end_undo_group(&mut self) {
+                assert!(!self.was_deleted());
+                crate::input::end_undo_group(self);
+            }
         }
     };
     gen.into()
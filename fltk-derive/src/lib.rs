@@ -10,6 +10,7 @@ extern crate quote;
 mod browser;
 mod button;
 mod display;
+mod form;
 mod group;
 mod image;
 mod input;
@@ -23,6 +24,7 @@ mod window;
 use crate::browser::*;
 use crate::button::*;
 use crate::display::*;
+use crate::form::*;
 use crate::group::*;
 use crate::image::*;
 use crate::input::*;
@@ -110,3 +112,9 @@ pub fn image_trait_macro(input: TokenStream) -> TokenStream {
     let ast = syn::parse(input).unwrap();
     impl_image_trait(&ast)
 }
+
+#[proc_macro_derive(Form, attributes(form))]
+pub fn form_macro(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_form(&ast)
+}
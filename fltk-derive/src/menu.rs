@@ -63,6 +63,10 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
     let at = Ident::new(format!("{}_{}", name_str, "at").as_str(), name.span());
     let mode = Ident::new(format!("{}_{}", name_str, "mode").as_str(), name.span());
     let set_mode = Ident::new(format!("{}_{}", name_str, "set_mode").as_str(), name.span());
+    let set_shortcut = Ident::new(
+        format!("{}_{}", name_str, "set_shortcut").as_str(),
+        name.span(),
+    );
     let down_box = Ident::new(format!("{}_{}", name_str, "down_box").as_str(), name.span());
     let set_down_box = Ident::new(
         format!("{}_{}", name_str, "set_down_box").as_str(),
@@ -413,6 +417,32 @@ pub fn impl_menu_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn set_item_shortcut(&mut self, path: &str, shortcut: Shortcut) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                let path = CString::safe_new(path);
+                unsafe {
+                    let idx = #find_index(self._inner, path.as_ptr());
+                    if idx < 0 {
+                        return Err(FltkError::Internal(FltkErrorKind::FailedOperation));
+                    }
+                    #set_shortcut(self._inner, idx, shortcut.bits() as i32);
+                    Ok(())
+                }
+            }
+
+            fn set_item_flag(&mut self, path: &str, flag: crate::menu::MenuFlag) -> Result<(), FltkError> {
+                assert!(!self.was_deleted());
+                let path = CString::safe_new(path);
+                unsafe {
+                    let idx = #find_index(self._inner, path.as_ptr());
+                    if idx < 0 {
+                        return Err(FltkError::Internal(FltkErrorKind::FailedOperation));
+                    }
+                    #set_mode(self._inner, idx, flag as i32);
+                    Ok(())
+                }
+            }
+
             fn end(&mut self) {
                 //
             }
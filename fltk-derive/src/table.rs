@@ -175,10 +175,6 @@ pub fn impl_table_trait(ast: &DeriveInput) -> TokenStream {
         name.span(),
     );
     let resize = Ident::new(format!("{}_{}", name_str, "resize").as_str(), name.span());
-    let init_sizes = Ident::new(
-        format!("{}_{}", name_str, "init_sizes").as_str(),
-        name.span(),
-    );
     let scrollbar_size = Ident::new(
         format!("{}_{}", name_str, "scrollbar_size").as_str(),
         name.span(),
@@ -220,6 +216,11 @@ pub fn impl_table_trait(ast: &DeriveInput) -> TokenStream {
         name.span(),
     );
 
+    let find_cell = Ident::new(
+        format!("{}_{}", name_str, "find_cell").as_str(),
+        name.span(),
+    );
+
     let gen = quote! {
         unsafe impl TableExt for #name {
             fn clear(&mut self) {
@@ -560,13 +561,6 @@ pub fn impl_table_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
-            fn init_sizes(&mut self) {
-                unsafe {
-                    assert!(!self.was_deleted());
-                    #init_sizes(self._inner)
-                }
-            }
-
             fn scrollbar_size(&self) -> u32 {
                 unsafe {
                     assert!(!self.was_deleted());
@@ -664,6 +658,20 @@ pub fn impl_table_trait(ast: &DeriveInput) -> TokenStream {
                     mem::transmute(#callback_context(self._inner))
                 }
             }
+
+            fn find_cell(&self, ctx: TableContext, row: i32, col: i32) -> Option<(i32, i32, i32, i32)> {
+                assert!(!self.was_deleted());
+                let mut x = 0;
+                let mut y = 0;
+                let mut w = 0;
+                let mut h = 0;
+                unsafe {
+                    match #find_cell(self._inner, ctx as i32, row, col, &mut x, &mut y, &mut w, &mut h) {
+                        0 => Some((x, y, w, h)),
+                        _ => None,
+                    }
+                }
+            }
         }
     };
     gen.into()
@@ -20,6 +20,10 @@ pub fn get_fl_name(txt: String) -> String {
     if txt == "RgbImage" {
         return String::from("Fl_RGB_Image");
     }
+    // decoded in Rust into RGB images
+    if txt == "IcoImage" || txt == "WebpImage" {
+        return String::from("Fl_RGB_Image");
+    }
     if txt == "XpmImage" {
         return String::from("Fl_XPM_Image");
     }
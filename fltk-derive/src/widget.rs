@@ -52,19 +52,27 @@ pub fn impl_widget_base_trait(ast: &DeriveInput) -> TokenStream {
                         }
                     }
                     #set_deleter(widget_ptr, Some(shim));
-                    #name {
+                    let mut wid = #name {
                         _inner: widget_ptr,
                         _tracker: tracker,
+                    };
+                    if crate::app::is_rtl() {
+                        crate::utils::mirror_widget(&mut wid);
                     }
+                    wid
                 }
             }
 
             fn delete(mut wid: Self) {
                 assert!(!wid.was_deleted());
                 unsafe {
+                    if let Some(mut parent) = wid.parent() {
+                        fltk_sys::group::Fl_Group_remove(parent.as_widget_ptr() as *mut fltk_sys::group::Fl_Group, wid.as_widget_ptr() as *mut _);
+                        parent.redraw();
+                    }
                     fltk_sys::fl::Fl_delete_widget(wid.as_widget_ptr() as *mut fltk_sys::fl::Fl_Widget);
                     wid._inner = std::ptr::null_mut() as *mut _;
-                    fltk_sys::fl::Fl_Widget_Tracker_delete(wid._tracker);
+                    // The tracker is shared by clones of the widget, so it's kept alive for them to detect the deletion
                     wid._tracker = std::ptr::null_mut() as *mut fltk_sys::fl::Fl_Widget_Tracker;
                 }
             }
@@ -167,6 +175,32 @@ pub fn impl_widget_base_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn on_file_drop<F: FnMut(&mut Self, Vec<std::path::PathBuf>) + 'static>(&mut self, mut cb: F) {
+                assert!(!self.was_deleted());
+                // a paste only carries dropped files right after a drop, otherwise it comes from the clipboard
+                let mut dropped = false;
+                self.handle2(move |w, ev| match ev {
+                    Event::DndEnter | Event::DndDrag => true,
+                    Event::DndLeave => {
+                        dropped = false;
+                        true
+                    }
+                    Event::DndRelease => {
+                        dropped = true;
+                        true
+                    }
+                    Event::Paste if dropped => {
+                        dropped = false;
+                        let paths = crate::app::event_paths();
+                        if !paths.is_empty() {
+                            cb(w, paths);
+                        }
+                        true
+                    }
+                    _ => false,
+                })
+            }
+
             unsafe fn draw_data(&mut self) -> Option<Box<dyn FnMut()>> {
                 let ptr = #draw_data(self._inner);
                 if ptr.is_null() {
@@ -452,6 +486,28 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
+            fn x_root(&self) -> i32 {
+                assert!(!self.was_deleted());
+                let mut x = if self.as_window().is_some() { 0 } else { self.x() };
+                let mut win: Option<Box<dyn WindowExt>> = if let Some(win) = self.as_window() { Some(Box::new(win)) } else { self.window() };
+                while let Some(w) = win {
+                    x += w.x();
+                    win = w.window();
+                }
+                x
+            }
+
+            fn y_root(&self) -> i32 {
+                assert!(!self.was_deleted());
+                let mut y = if self.as_window().is_some() { 0 } else { self.y() };
+                let mut win: Option<Box<dyn WindowExt>> = if let Some(win) = self.as_window() { Some(Box::new(win)) } else { self.window() };
+                while let Some(w) = win {
+                    y += w.y();
+                    win = w.window();
+                }
+                y
+            }
+
             fn measure_label(&self) -> (i32, i32) {
                 assert!(!self.was_deleted());
                 let mut x = 0;
@@ -718,6 +774,41 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
             }
 
 
+            fn set_accessible_name(&mut self, name: &str) {
+                assert!(!self.was_deleted());
+                crate::widget::set_accessible_info(self, Some(name), None)
+            }
+
+            fn accessible_name(&self) -> Option<String> {
+                assert!(!self.was_deleted());
+                crate::widget::accessible_info(self).0.or_else(|| {
+                    let label = self.label();
+                    if label.is_empty() { None } else { Some(label) }
+                })
+            }
+
+            fn set_accessible_description(&mut self, desc: &str) {
+                assert!(!self.was_deleted());
+                crate::widget::set_accessible_info(self, None, Some(desc))
+            }
+
+            fn accessible_description(&self) -> Option<String> {
+                assert!(!self.was_deleted());
+                crate::widget::accessible_info(self).1
+            }
+
+            fn on_focus_gained<F: FnMut(&mut Self) + 'static>(&mut self, mut cb: F) {
+                assert!(!self.was_deleted());
+                let mut wid = self.clone();
+                crate::app::set_widget_focus_callback(self, true, Box::new(move || cb(&mut wid)))
+            }
+
+            fn on_focus_lost<F: FnMut(&mut Self) + 'static>(&mut self, mut cb: F) {
+                assert!(!self.was_deleted());
+                let mut wid = self.clone();
+                crate::app::set_widget_focus_callback(self, false, Box::new(move || cb(&mut wid)))
+            }
+
             fn visible_focus(&mut self, v: bool) {
                 assert!(!self.was_deleted());
                 unsafe {
@@ -764,25 +855,25 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 }
             }
 
-            fn as_window(&self) -> Option<Box<dyn WindowExt>> {
+            fn as_window(&self) -> Option<crate::window::Window> {
                 assert!(!self.was_deleted());
                 unsafe {
                     let ptr = #as_window(self._inner);
                     if ptr.is_null() {
                         return None;
                     }
-                    Some(Box::new(crate::window::Window::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget)))
+                    Some(crate::window::Window::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget))
                 }
             }
 
-            fn as_group(&self) -> Option<Box<dyn GroupExt>> {
+            fn as_group(&self) -> Option<crate::group::Group> {
                 assert!(!self.was_deleted());
                 unsafe {
                     let ptr = #as_group(self._inner);
                     if ptr.is_null() {
                         return None;
                     }
-                    Some(Box::new(crate::group::Group::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget)))
+                    Some(crate::group::Group::from_widget_ptr(ptr as *mut fltk_sys::widget::Fl_Widget))
                 }
             }
 
@@ -790,7 +881,8 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 assert!(!w.was_deleted());
                 assert!(!self.was_deleted());
                 debug_assert!(self.width() != 0 && self.height() != 0, "below_of requires the size of the widget to be known!");
-                self.resize(w.x(), w.y() + w.height() + padding, self.width(), self.height());
+                let x = crate::utils::rtl_x(w.x(), self.width(), w.x(), w.width());
+                self.resize(x, w.y() + w.height() + padding, self.width(), self.height());
                 self
             }
 
@@ -798,7 +890,8 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 assert!(!w.was_deleted());
                 assert!(!self.was_deleted());
                 debug_assert!(self.width() != 0 && self.height() != 0, "above_of requires the size of the widget to be known!");
-                self.resize(w.x(), w.y() - padding - self.height(), self.width(), self.height());
+                let x = crate::utils::rtl_x(w.x(), self.width(), w.x(), w.width());
+                self.resize(x, w.y() - padding - self.height(), self.width(), self.height());
                 self
             }
 
@@ -806,7 +899,8 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 assert!(!w.was_deleted());
                 assert!(!self.was_deleted());
                 debug_assert!(self.width() != 0 && self.height() != 0, "right_of requires the size of the widget to be known!");
-                self.resize(w.x() + w.width() + padding, w.y(), self.width(), self.height());
+                let x = crate::utils::rtl_x(w.x() + w.width() + padding, self.width(), w.x(), w.width());
+                self.resize(x, w.y(), self.width(), self.height());
                 self
             }
 
@@ -814,7 +908,8 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 assert!(!w.was_deleted());
                 assert!(!self.was_deleted());
                 debug_assert!(self.width() != 0 && self.height() != 0, "left_of requires the size of the widget to be known!");
-                self.resize(w.x() - self.width() - padding, w.y(), self.width(), self.height());
+                let x = crate::utils::rtl_x(w.x() - self.width() - padding, self.width(), w.x(), w.width());
+                self.resize(x, w.y(), self.width(), self.height());
                 self
             }
 
@@ -843,6 +938,107 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 self
             }
 
+            fn top_left_of<W: WidgetExt>(mut self, w: &W, padding: i32) -> Self {
+                assert!(!w.was_deleted());
+                assert!(!self.was_deleted());
+                debug_assert!(w.width() != 0 && w.height() != 0, "top_left_of requires the size of the widget to be known!");
+                let wx = if w.as_window().is_some() { 0 } else { w.x() };
+                let wy = if w.as_window().is_some() { 0 } else { w.y() };
+                let x = crate::utils::rtl_x(wx + padding, self.width(), wx, w.width());
+                self.resize(x, wy + padding, self.width(), self.height());
+                self
+            }
+
+            fn top_right_of<W: WidgetExt>(mut self, w: &W, padding: i32) -> Self {
+                assert!(!w.was_deleted());
+                assert!(!self.was_deleted());
+                debug_assert!(w.width() != 0 && w.height() != 0, "top_right_of requires the size of the widget to be known!");
+                let wx = if w.as_window().is_some() { 0 } else { w.x() };
+                let wy = if w.as_window().is_some() { 0 } else { w.y() };
+                let x = crate::utils::rtl_x(wx + w.width() - self.width() - padding, self.width(), wx, w.width());
+                self.resize(x, wy + padding, self.width(), self.height());
+                self
+            }
+
+            fn bottom_left_of<W: WidgetExt>(mut self, w: &W, padding: i32) -> Self {
+                assert!(!w.was_deleted());
+                assert!(!self.was_deleted());
+                debug_assert!(w.width() != 0 && w.height() != 0, "bottom_left_of requires the size of the widget to be known!");
+                let wx = if w.as_window().is_some() { 0 } else { w.x() };
+                let wy = if w.as_window().is_some() { 0 } else { w.y() };
+                let x = crate::utils::rtl_x(wx + padding, self.width(), wx, w.width());
+                self.resize(x, wy + w.height() - self.height() - padding, self.width(), self.height());
+                self
+            }
+
+            fn bottom_right_of<W: WidgetExt>(mut self, w: &W, padding: i32) -> Self {
+                assert!(!w.was_deleted());
+                assert!(!self.was_deleted());
+                debug_assert!(w.width() != 0 && w.height() != 0, "bottom_right_of requires the size of the widget to be known!");
+                let wx = if w.as_window().is_some() { 0 } else { w.x() };
+                let wy = if w.as_window().is_some() { 0 } else { w.y() };
+                let x = crate::utils::rtl_x(wx + w.width() - self.width() - padding, self.width(), wx, w.width());
+                self.resize(x, wy + w.height() - self.height() - padding, self.width(), self.height());
+                self
+            }
+
+            fn top_of_parent(mut self, padding: i32) -> Self {
+                assert!(!self.was_deleted());
+                let (_, py, _, _) = crate::utils::parent_bounds(&self);
+                self.resize(self.x(), py + padding, self.width(), self.height());
+                self
+            }
+
+            fn bottom_of_parent(mut self, padding: i32) -> Self {
+                assert!(!self.was_deleted());
+                let (_, py, _, ph) = crate::utils::parent_bounds(&self);
+                self.resize(self.x(), py + ph - self.height() - padding, self.width(), self.height());
+                self
+            }
+
+            fn left_of_parent(mut self, padding: i32) -> Self {
+                assert!(!self.was_deleted());
+                let (px, _, pw, _) = crate::utils::parent_bounds(&self);
+                let x = crate::utils::rtl_x(px + padding, self.width(), px, pw);
+                self.resize(x, self.y(), self.width(), self.height());
+                self
+            }
+
+            fn right_of_parent(mut self, padding: i32) -> Self {
+                assert!(!self.was_deleted());
+                let (px, _, pw, _) = crate::utils::parent_bounds(&self);
+                let x = crate::utils::rtl_x(px + pw - self.width() - padding, self.width(), px, pw);
+                self.resize(x, self.y(), self.width(), self.height());
+                self
+            }
+
+            fn center_of_parent(mut self) -> Self {
+                assert!(!self.was_deleted());
+                let (px, py, pw, ph) = crate::utils::parent_bounds(&self);
+                self.resize(px + (pw - self.width()) / 2, py + (ph - self.height()) / 2, self.width(), self.height());
+                self
+            }
+
+            fn stretch_width_to_parent(self, padding: i32) -> Self {
+                assert!(!self.was_deleted());
+                let (px, _, pw, _) = crate::utils::parent_bounds(&self);
+                let (y, h) = (self.y(), self.height());
+                self.with_pos(px + padding, y).with_size(pw - 2 * padding, h)
+            }
+
+            fn stretch_height_to_parent(self, padding: i32) -> Self {
+                assert!(!self.was_deleted());
+                let (_, py, _, ph) = crate::utils::parent_bounds(&self);
+                let (x, w) = (self.x(), self.width());
+                self.with_pos(x, py + padding).with_size(w, ph - 2 * padding)
+            }
+
+            fn stretch_to_parent(self) -> Self {
+                assert!(!self.was_deleted());
+                let (px, py, pw, ph) = crate::utils::parent_bounds(&self);
+                self.with_pos(px, py).with_size(pw, ph)
+            }
+
             fn inside<W: WidgetExt>(&self, wid: &W) -> bool {
                 assert!(!self.was_deleted());
                 assert!(!wid.was_deleted());
@@ -879,6 +1075,31 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 self
             }
 
+            fn with_type<T: WidgetType>(mut self, typ: T) -> Self {
+                self.set_type(typ);
+                self
+            }
+
+            fn with_color(mut self, color: Color) -> Self {
+                self.set_color(color);
+                self
+            }
+
+            fn with_label_color(mut self, color: Color) -> Self {
+                self.set_label_color(color);
+                self
+            }
+
+            fn with_label_size(mut self, sz: i32) -> Self {
+                self.set_label_size(sz);
+                self
+            }
+
+            fn with_callback<F: FnMut(&mut Self) + 'static>(mut self, cb: F) -> Self {
+                self.set_callback2(cb);
+                self
+            }
+
             fn get_type<T: WidgetType>(&self) -> T {
                 assert!(!self.was_deleted());
                 unsafe { T::from_i32(#get_type(self._inner)) }
@@ -975,6 +1196,15 @@ pub fn impl_widget_trait(ast: &DeriveInput) -> TokenStream {
                 self.set_callback(move || sender.send(msg.clone()))
             }
 
+            fn emit_with<T: 'static + Send + Sync, F: FnMut(&mut Self) -> Option<T> + 'static>(&mut self, sender: crate::app::Sender<T>, mut f: F) {
+                assert!(!self.was_deleted());
+                self.set_callback2(move |w| {
+                    if let Some(msg) = f(w) {
+                        sender.send(msg)
+                    }
+                })
+            }
+
             unsafe fn into_widget<W: WidgetBase>(&self) -> W where Self: Sized {
                 W::from_widget_ptr(self.as_widget_ptr() as *mut _)
             }
@@ -136,6 +136,7 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
 
             fn make_current(&mut self) {
                 assert!(!self.was_deleted());
+                assert!(self.shown(), "make_current requires the window to be shown!");
                 unsafe { #make_current(self._inner) }
             }
 
@@ -282,6 +283,8 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
 
             fn size_range(&mut self, min_w: i32, min_h: i32, max_w: i32, max_h: i32) {
                 assert!(!self.was_deleted());
+                debug_assert!(max_w == 0 || max_w >= min_w, "size_range requires max_w to be 0 or not less than min_w!");
+                debug_assert!(max_h == 0 || max_h >= min_h, "size_range requires max_h to be 0 or not less than min_h!");
                 unsafe {
                     #size_range(self._inner, min_w, min_h, max_w, max_h);
                 }
@@ -294,6 +297,69 @@ pub fn impl_window_trait(ast: &DeriveInput) -> TokenStream {
                     #hotspot(self._inner, w.as_widget_ptr() as _)
                 }
             }
+
+            fn request_attention(&mut self) {
+                assert!(!self.was_deleted());
+                assert!(self.shown(), "request_attention requires the window to be shown!");
+                request_window_attention(self.raw_handle());
+            }
+
+            fn set_default_button<B: ButtonExt>(&mut self, btn: &mut B) {
+                assert!(!self.was_deleted());
+                assert!(!btn.was_deleted());
+                debug_assert!(btn.inside(self), "set_default_button requires the button to be inside the window!");
+                btn.set_shortcut(Shortcut::None | Key::Enter);
+            }
+
+            fn set_cancel_button<B: ButtonExt>(&mut self, btn: &mut B) {
+                assert!(!self.was_deleted());
+                assert!(!btn.was_deleted());
+                debug_assert!(btn.inside(self), "set_cancel_button requires the button to be inside the window!");
+                btn.set_shortcut(Shortcut::None | Key::Escape);
+            }
+
+            fn show_modal(&mut self) -> Option<i32> {
+                assert!(!self.was_deleted());
+                let result = std::rc::Rc::new(std::cell::Cell::new(None));
+                MODAL_RESULTS.with(|m| m.borrow_mut().insert(self, result.clone()));
+                self.make_modal(true);
+                self.show();
+                while !self.was_deleted() && self.shown() {
+                    crate::app::wait();
+                }
+                if !self.was_deleted() {
+                    MODAL_RESULTS.with(|m| m.borrow_mut().remove(self));
+                }
+                result.get()
+            }
+
+            fn set_modal_result(&mut self, val: i32) {
+                assert!(!self.was_deleted());
+                if let Some(result) = MODAL_RESULTS.with(|m| m.borrow_mut().get(self).cloned()) {
+                    result.set(Some(val));
+                }
+                self.hide();
+            }
+
+            fn save_geometry(&self, prefs: &mut crate::prefs::Preferences, key: &str) {
+                assert!(!self.was_deleted());
+                crate::prefs::save_window_geometry(self, prefs, key)
+            }
+
+            fn restore_geometry(&mut self, prefs: &crate::prefs::Preferences, key: &str) -> bool {
+                assert!(!self.was_deleted());
+                crate::prefs::restore_window_geometry(self, prefs, key)
+            }
+
+            fn set_background_image<I: ImageExt + Clone>(&mut self, image: &I) {
+                assert!(!self.was_deleted());
+                assert!(!image.was_deleted());
+                let tiled = crate::image::TiledImage::new(image.clone(), 0, 0);
+                self.set_image(Some(tiled));
+                let align = self.align();
+                self.set_align(align | Align::ImageBackdrop);
+                self.redraw();
+            }
         }
     };
     gen.into()
@@ -41,6 +41,12 @@ fn main() {
             .current_dir(out_dir.clone())
             .status()
             .expect("Curl and Tar are needed to download and upack the bundled libraries!");
+
+        if out_dir.join("include").join("FL").exists() {
+            build_ext(&out_dir);
+        } else {
+            println!("cargo:warning=The bundled libraries don't ship the fltk headers, so the wrappers in ext which cfltk doesn't provide yet won't link!");
+        }
     } else {
         println!("cargo:rerun-if-env-changed=CC");
         println!("cargo:rerun-if-env-changed=CXX");
@@ -86,6 +92,8 @@ fn main() {
         println!("cargo:rerun-if-changed=cfltk/src/cfl_tree.cpp");
         println!("cargo:rerun-if-changed=cfltk/src/cfl_surface.cpp");
         println!("cargo:rerun-if-changed=cfltk/src/cfl_printer.cpp");
+        println!("cargo:rerun-if-changed=ext/include");
+        println!("cargo:rerun-if-changed=ext/src");
 
         Command::new("git")
             .args(&["submodule", "update", "--init", "--recursive"])
@@ -183,6 +191,8 @@ fn main() {
             .define("OPTION_BUILD_HTML_DOCUMENTATION", "OFF")
             .define("OPTION_BUILD_PDF_DOCUMENTATION", "OFF")
             .build();
+
+        build_ext(&out_dir);
     }
 
     Command::new("git")
@@ -319,6 +329,30 @@ fn main() {
     }
 }
 
+// Builds the wrappers in ext which cfltk doesn't provide yet against the installed fltk headers
+fn build_ext(out_dir: &Path) {
+    let mut build = cc::Build::new();
+    build
+        .cpp(true)
+        .include(out_dir.join("include"))
+        .include("ext/include")
+        .flag_if_supported("-std=c++11")
+        .flag_if_supported("-w");
+
+    if cfg!(feature = "enable-glwindow") {
+        build.define("CFLTK_USE_OPENGL", None);
+    }
+
+    for entry in fs::read_dir("ext/src").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(false, |ext| ext == "cpp") {
+            build.file(path);
+        }
+    }
+
+    build.compile("cfltk_ext");
+}
+
 fn handle_android(triple: &str, dst: &mut cmake::Config) {
     let sdk =
         PathBuf::from(env::var("ANDROID_SDK_ROOT").expect("ANDROID_SDK_ROOT needs to be set!"));
@@ -0,0 +1,30 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_BROWSER_EXT_H__
+#define __CFL_BROWSER_EXT_H__
+
+#include "../../cfltk/include/cfl_browser.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void Fl_Browser_hide_line(Fl_Browser *self, int line);
+void Fl_Browser_show_line(Fl_Browser *self, int line);
+int Fl_Browser_visible_line(const Fl_Browser *self, int line);
+void Fl_Hold_Browser_hide_line(Fl_Hold_Browser *self, int line);
+void Fl_Hold_Browser_show_line(Fl_Hold_Browser *self, int line);
+int Fl_Hold_Browser_visible_line(const Fl_Hold_Browser *self, int line);
+void Fl_Select_Browser_hide_line(Fl_Select_Browser *self, int line);
+void Fl_Select_Browser_show_line(Fl_Select_Browser *self, int line);
+int Fl_Select_Browser_visible_line(const Fl_Select_Browser *self, int line);
+void Fl_Multi_Browser_hide_line(Fl_Multi_Browser *self, int line);
+void Fl_Multi_Browser_show_line(Fl_Multi_Browser *self, int line);
+int Fl_Multi_Browser_visible_line(const Fl_Multi_Browser *self, int line);
+void Fl_File_Browser_hide_line(Fl_File_Browser *self, int line);
+void Fl_File_Browser_show_line(Fl_File_Browser *self, int line);
+int Fl_File_Browser_visible_line(const Fl_File_Browser *self, int line);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,25 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_BUTTON_EXT_H__
+#define __CFL_BUTTON_EXT_H__
+
+#include "../../cfltk/include/cfl_button.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void Fl_Button_setonly(Fl_Button *self);
+void Fl_Check_Button_setonly(Fl_Check_Button *self);
+void Fl_Radio_Button_setonly(Fl_Radio_Button *self);
+void Fl_Toggle_Button_setonly(Fl_Toggle_Button *self);
+void Fl_Round_Button_setonly(Fl_Round_Button *self);
+void Fl_Radio_Round_Button_setonly(Fl_Radio_Round_Button *self);
+void Fl_Radio_Light_Button_setonly(Fl_Radio_Light_Button *self);
+void Fl_Light_Button_setonly(Fl_Light_Button *self);
+void Fl_Repeat_Button_setonly(Fl_Repeat_Button *self);
+void Fl_Return_Button_setonly(Fl_Return_Button *self);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
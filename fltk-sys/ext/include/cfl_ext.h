@@ -0,0 +1,52 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_EXT_H__
+#define __CFL_EXT_H__
+
+#include "../../cfltk/include/cfl.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int Fl_event_original_key(void);
+int Fl_compose(int *del);
+void Fl_compose_reset(void);
+int Fl_compose_state(void);
+void Fl_set_event_clicks(int i);
+int Fl_arg(int argc, char **argv, int *i);
+void Fl_set_event_is_click(int i);
+void Fl_set_event_x(int v);
+void Fl_set_event_y(int v);
+void Fl_set_event_x_root(int v);
+void Fl_set_event_y_root(int v);
+void Fl_set_event_dx(int v);
+void Fl_set_event_dy(int v);
+void Fl_set_event_button(int v);
+void Fl_set_event_key(int v);
+void Fl_set_event_state(int v);
+void Fl_set_event_text(const char *text);
+void Fl_event_dispatch(int (*cb)(int ev, void *win));
+int Fl_handle_(int ev, void *win);
+int Fl_screen_count(void);
+int Fl_screen_num(int x, int y);
+void Fl_screen_work_area(int *x, int *y, int *w, int *h, int n);
+void Fl_copy(const char *stuff, int len, int destination);
+void Fl_copy_image(const unsigned char *data, int w, int h, int destination);
+void Fl_paste_image(Fl_Widget *, int src);
+int Fl_clipboard_contains(const char *type);
+void *Fl_event_clipboard(void);
+const char *Fl_event_clipboard_type(void);
+void Fl_get_color_rgb(unsigned int c, unsigned char *r, unsigned char *g, unsigned char *b);
+int Fl_font_size(void);
+void Fl_set_font_size(int sz);
+int Fl_option(int opt);
+void Fl_set_option(int opt, int val);
+int Fl_test_shortcut(int shortcut);
+int Fl_check(void);
+void Fl_do_widget_deletion(void);
+void Fl_add_check(void (*)(void *), void *);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,23 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_GROUP_EXT_H__
+#define __CFL_GROUP_EXT_H__
+
+#include "../../cfltk/include/cfl_group.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void Fl_Group_init_sizes(Fl_Group *self);
+void Fl_Pack_init_sizes(Fl_Pack *self);
+void Fl_Scroll_init_sizes(Fl_Scroll *self);
+Fl_Widget *Fl_Tabs_which(Fl_Tabs *self, int event_x, int event_y);
+void Fl_Tabs_init_sizes(Fl_Tabs *self);
+void Fl_Tile_init_sizes(Fl_Tile *self);
+void Fl_Wizard_init_sizes(Fl_Wizard *self);
+void Fl_Color_Chooser_init_sizes(Fl_Color_Chooser *self);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,48 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_IMAGE_EXT_H__
+#define __CFL_IMAGE_EXT_H__
+
+#include "../../cfltk/include/cfl_image.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void Fl_Image_desaturate(Fl_Image *self);
+void Fl_Image_color_average(Fl_Image *self, unsigned int c, float i);
+void Fl_JPEG_Image_desaturate(Fl_JPEG_Image *self);
+void Fl_JPEG_Image_color_average(Fl_JPEG_Image *self, unsigned int c, float i);
+void Fl_PNG_Image_desaturate(Fl_PNG_Image *self);
+void Fl_PNG_Image_color_average(Fl_PNG_Image *self, unsigned int c, float i);
+void Fl_SVG_Image_desaturate(Fl_SVG_Image *self);
+void Fl_SVG_Image_color_average(Fl_SVG_Image *self, unsigned int c, float i);
+void Fl_BMP_Image_desaturate(Fl_BMP_Image *self);
+void Fl_BMP_Image_color_average(Fl_BMP_Image *self, unsigned int c, float i);
+void Fl_GIF_Image_desaturate(Fl_GIF_Image *self);
+void Fl_GIF_Image_color_average(Fl_GIF_Image *self, unsigned int c, float i);
+void Fl_Pixmap_desaturate(Fl_Pixmap *self);
+void Fl_Pixmap_color_average(Fl_Pixmap *self, unsigned int c, float i);
+void Fl_XPM_Image_desaturate(Fl_XPM_Image *self);
+void Fl_XPM_Image_color_average(Fl_XPM_Image *self, unsigned int c, float i);
+void Fl_XBM_Image_desaturate(Fl_XBM_Image *self);
+void Fl_XBM_Image_color_average(Fl_XBM_Image *self, unsigned int c, float i);
+void Fl_PNM_Image_desaturate(Fl_PNM_Image *self);
+void Fl_PNM_Image_color_average(Fl_PNM_Image *self, unsigned int c, float i);
+void Fl_Tiled_Image_desaturate(Fl_Tiled_Image *self);
+void Fl_Tiled_Image_color_average(Fl_Tiled_Image *self, unsigned int c, float i);
+void Fl_RGB_Image_desaturate(Fl_RGB_Image *self);
+void Fl_RGB_Image_color_average(Fl_RGB_Image *self, unsigned int c, float i);
+void Fl_Shared_Image_desaturate(Fl_Shared_Image *self);
+void Fl_Shared_Image_color_average(Fl_Shared_Image *self, unsigned int c, float i);
+Fl_Shared_Image *Fl_Shared_Image_find(const char *name, int W, int H);
+const char *Fl_Shared_Image_name(Fl_Shared_Image *self);
+int Fl_Shared_Image_refcount(Fl_Shared_Image *self);
+void Fl_Shared_Image_reload(Fl_Shared_Image *self);
+int Fl_encode_png(const unsigned char *rgb, int w, int h, unsigned char **out, int *len);
+int Fl_encode_jpeg(const unsigned char *rgb, int w, int h, int quality, unsigned char **out, int *len);
+void Fl_free_encoded(unsigned char *data);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,19 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_MENU_EXT_H__
+#define __CFL_MENU_EXT_H__
+
+#include "../../cfltk/include/cfl_menu.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void Fl_Menu_Bar_set_shortcut(Fl_Menu_Bar *self, int i, int s);
+void Fl_Menu_Button_set_shortcut(Fl_Menu_Button *self, int i, int s);
+void Fl_Choice_set_shortcut(Fl_Choice *self, int i, int s);
+void Fl_Sys_Menu_Bar_set_shortcut(Fl_Sys_Menu_Bar *self, int i, int s);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,18 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_TABLE_EXT_H__
+#define __CFL_TABLE_EXT_H__
+
+#include "../../cfltk/include/cfl_table.h"
+#include "cfl_group_ext.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int Fl_Table_find_cell(Fl_Table *self, int ctx, int r, int c, int *x, int *y, int *w, int *h);
+int Fl_Table_Row_find_cell(Fl_Table_Row *self, int ctx, int r, int c, int *x, int *y, int *w, int *h);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
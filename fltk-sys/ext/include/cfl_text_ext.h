@@ -0,0 +1,18 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_TEXT_EXT_H__
+#define __CFL_TEXT_EXT_H__
+
+#include "../../cfltk/include/cfl_text.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int Fl_Text_Display_xy_to_position(const Fl_Text_Display *self, int x, int y, int pos_type);
+int Fl_Text_Editor_xy_to_position(const Fl_Text_Editor *self, int x, int y, int pos_type);
+int Fl_Simple_Terminal_xy_to_position(const Fl_Simple_Terminal *self, int x, int y, int pos_type);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,16 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_WIDGET_EXT_H__
+#define __CFL_WIDGET_EXT_H__
+
+#include "../../cfltk/include/cfl_widget.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int Fl_Widget_handle_event(Fl_Widget *self, int event);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,134 @@
+/* Wrappers which aren't part of cfltk yet, bind.sh generates the bindings of the module from this header */
+#ifndef __CFL_WINDOW_EXT_H__
+#define __CFL_WINDOW_EXT_H__
+
+#include "../../cfltk/include/cfl_window.h"
+#include "cfl_group_ext.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void Fl_Window_init_sizes(Fl_Window *self);
+void Fl_Single_Window_init_sizes(Fl_Single_Window *self);
+void Fl_Double_Window_init_sizes(Fl_Double_Window *self);
+void Fl_Menu_Window_init_sizes(Fl_Menu_Window *self);
+void Fl_Menu_Window_set_tooltip_window(Fl_Menu_Window *self);
+int Fl_Menu_Window_tooltip_window(const Fl_Menu_Window *self);
+void Fl_Menu_Window_set_overlay(Fl_Menu_Window *self);
+void Fl_Menu_Window_clear_overlay(Fl_Menu_Window *self);
+typedef struct Fl_Overlay_Window Fl_Overlay_Window;
+Fl_Overlay_Window *Fl_Overlay_Window_new(int x, int y, int width, int height, const char *title);
+int Fl_Overlay_Window_x(Fl_Overlay_Window *);
+int Fl_Overlay_Window_y(Fl_Overlay_Window *);
+int Fl_Overlay_Window_width(Fl_Overlay_Window *);
+int Fl_Overlay_Window_height(Fl_Overlay_Window *);
+const char *Fl_Overlay_Window_label(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_label(Fl_Overlay_Window *, const char *title);
+void Fl_Overlay_Window_redraw(Fl_Overlay_Window *);
+void Fl_Overlay_Window_show(Fl_Overlay_Window *);
+void Fl_Overlay_Window_hide(Fl_Overlay_Window *);
+void Fl_Overlay_Window_activate(Fl_Overlay_Window *);
+void Fl_Overlay_Window_deactivate(Fl_Overlay_Window *);
+void Fl_Overlay_Window_redraw_label(Fl_Overlay_Window *);
+void Fl_Overlay_Window_resize(Fl_Overlay_Window *, int x, int y, int width, int height);
+void Fl_Overlay_Window_widget_resize(Fl_Overlay_Window *, int x, int y, int width, int height);
+const char *Fl_Overlay_Window_tooltip(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_tooltip(Fl_Overlay_Window *, const char *txt);
+int Fl_Overlay_Window_get_type(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_type(Fl_Overlay_Window *, int typ);
+unsigned int Fl_Overlay_Window_color(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_color(Fl_Overlay_Window *, unsigned int color);
+void Fl_Overlay_Window_measure_label(const Fl_Overlay_Window *, int *, int *);
+unsigned int Fl_Overlay_Window_label_color(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_label_color(Fl_Overlay_Window *, unsigned int color);
+int Fl_Overlay_Window_label_font(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_label_font(Fl_Overlay_Window *, int font);
+int Fl_Overlay_Window_label_size(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_label_size(Fl_Overlay_Window *, int sz);
+int Fl_Overlay_Window_label_type(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_label_type(Fl_Overlay_Window *, int typ);
+int Fl_Overlay_Window_box(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_box(Fl_Overlay_Window *, int typ);
+int Fl_Overlay_Window_changed(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_changed(Fl_Overlay_Window *);
+void Fl_Overlay_Window_clear_changed(Fl_Overlay_Window *);
+int Fl_Overlay_Window_align(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_align(Fl_Overlay_Window *, int typ);
+void Fl_Overlay_Window_delete(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_image(Fl_Overlay_Window *, void *);
+void Fl_Overlay_Window_handle(Fl_Overlay_Window *self, custom_handler_callback cb, void *data);
+void Fl_Overlay_Window_handle2(Fl_Overlay_Window *self, custom_handler_callback2 cb, void *data);
+void Fl_Overlay_Window_draw(Fl_Overlay_Window *self, custom_draw_callback cb, void *data);
+void Fl_Overlay_Window_draw2(Fl_Overlay_Window *self, custom_draw_callback2 cb, void *data);
+void Fl_Overlay_Window_set_when(Fl_Overlay_Window *, int);
+int Fl_Overlay_Window_when(const Fl_Overlay_Window *);
+void *Fl_Overlay_Window_image(const Fl_Overlay_Window *);
+void *Fl_Overlay_Window_parent(const Fl_Overlay_Window *self);
+unsigned int Fl_Overlay_Window_selection_color(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_selection_color(Fl_Overlay_Window *, unsigned int color);
+void Fl_Overlay_Window_do_callback(Fl_Overlay_Window *);
+int Fl_Overlay_Window_inside(const Fl_Overlay_Window *self, void *);
+void *Fl_Overlay_Window_window(const Fl_Overlay_Window *);
+void *Fl_Overlay_Window_top_window(const Fl_Overlay_Window *);
+int Fl_Overlay_Window_takes_events(const Fl_Overlay_Window *);
+void *Fl_Overlay_Window_user_data(const Fl_Overlay_Window *);
+int Fl_Overlay_Window_take_focus(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_set_visible_focus(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_clear_visible_focus(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_visible_focus(Fl_Overlay_Window *self, int v);
+unsigned int Fl_Overlay_Window_has_visible_focus(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_set_user_data(Fl_Overlay_Window *, void *data);
+void *Fl_Overlay_Window_draw_data(const Fl_Overlay_Window *self);
+void *Fl_Overlay_Window_handle_data(const Fl_Overlay_Window *self);
+void Fl_Overlay_Window_set_draw_data(Fl_Overlay_Window *self, void *data);
+void Fl_Overlay_Window_set_handle_data(Fl_Overlay_Window *self, void *data);
+unsigned char Fl_Overlay_Window_damage(const Fl_Overlay_Window *self);
+void Fl_Overlay_Window_set_damage(Fl_Overlay_Window *self, unsigned char flag);
+void Fl_Overlay_Window_clear_damage(Fl_Overlay_Window *self);
+void *Fl_Overlay_Window_as_window(Fl_Overlay_Window *self);
+void *Fl_Overlay_Window_as_group(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_set_deimage(Fl_Overlay_Window *, void *);
+void *Fl_Overlay_Window_deimage(const Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_callback(Fl_Overlay_Window *, Fl_Callback, void *);
+void Fl_Overlay_Window_set_deleter(Fl_Overlay_Window *, void (*)(void *));
+void Fl_Overlay_Window_begin(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_end(Fl_Overlay_Window *self);
+int Fl_Overlay_Window_find(Fl_Overlay_Window *self, const void *);
+void Fl_Overlay_Window_add(Fl_Overlay_Window *self, void *);
+void Fl_Overlay_Window_insert(Fl_Overlay_Window *self, void *, int pos);
+void Fl_Overlay_Window_remove(Fl_Overlay_Window *self, void *wid);
+void Fl_Overlay_Window_clear(Fl_Overlay_Window *self);
+int Fl_Overlay_Window_children(Fl_Overlay_Window *self);
+Fl_Widget *Fl_Overlay_Window_child(Fl_Overlay_Window *, int index);
+void Fl_Overlay_Window_resizable(Fl_Overlay_Window *self, void *);
+void Fl_Overlay_Window_init_sizes(Fl_Overlay_Window *self);
+void Fl_Overlay_Window_make_modal(Fl_Overlay_Window *, unsigned int boolean);
+void Fl_Overlay_Window_fullscreen(Fl_Overlay_Window *, unsigned int boolean);
+void Fl_Overlay_Window_make_current(Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_icon(Fl_Overlay_Window *, const void *);
+void *Fl_Overlay_Window_icon(const Fl_Overlay_Window *);
+void Fl_Overlay_Window_set_cursor(Fl_Overlay_Window *self, int cursor);
+int Fl_Overlay_Window_shown(Fl_Overlay_Window *self);
+void *Fl_Overlay_Window_raw_handle(const Fl_Overlay_Window *w);
+void Fl_Overlay_Window_set_border(Fl_Overlay_Window *, int flag);
+int Fl_Overlay_Window_border(const Fl_Overlay_Window *);
+void *Fl_Overlay_Window_region(const Fl_Overlay_Window *self);
+void Fl_Overlay_Window_set_region(Fl_Overlay_Window *self, void *r);
+void Fl_Overlay_Window_iconize(Fl_Overlay_Window *self);
+unsigned int Fl_Overlay_Window_fullscreen_active(const Fl_Overlay_Window *self);
+void Fl_Overlay_Window_free_position(Fl_Overlay_Window *self);
+int Fl_Overlay_Window_decorated_w(const Fl_Overlay_Window *self);
+int Fl_Overlay_Window_decorated_h(const Fl_Overlay_Window *self);
+void Fl_Overlay_Window_size_range(Fl_Overlay_Window *self, int, int, int, int);
+void Fl_Overlay_Window_hotspot(Fl_Overlay_Window *self, Fl_Widget *wid);
+void Fl_Overlay_Window_draw_overlay(Fl_Overlay_Window *self, custom_draw_callback cb, void *data);
+void *Fl_Overlay_Window_draw_overlay_data(const Fl_Overlay_Window *self);
+void Fl_Overlay_Window_redraw_overlay(Fl_Overlay_Window *self);
+int Fl_Overlay_Window_can_do_overlay(Fl_Overlay_Window *self);
+void Fl_Gl_Window_init_sizes(Fl_Gl_Window *self);
+
+#ifdef __cplusplus
+}
+#endif
+#endif
@@ -0,0 +1,67 @@
+#include <FL/Fl_Browser.H>
+#include <FL/Fl_File_Browser.H>
+#include <FL/Fl_Hold_Browser.H>
+#include <FL/Fl_Multi_Browser.H>
+#include <FL/Fl_Select_Browser.H>
+
+#include "cfl_browser_ext.h"
+
+void Fl_Browser_hide_line(Fl_Browser *self, int line) {
+    self->hide(line);
+}
+
+void Fl_Browser_show_line(Fl_Browser *self, int line) {
+    self->show(line);
+}
+
+int Fl_Browser_visible_line(const Fl_Browser *self, int line) {
+    return self->visible(line);
+}
+
+void Fl_Hold_Browser_hide_line(Fl_Hold_Browser *self, int line) {
+    self->hide(line);
+}
+
+void Fl_Hold_Browser_show_line(Fl_Hold_Browser *self, int line) {
+    self->show(line);
+}
+
+int Fl_Hold_Browser_visible_line(const Fl_Hold_Browser *self, int line) {
+    return self->visible(line);
+}
+
+void Fl_Select_Browser_hide_line(Fl_Select_Browser *self, int line) {
+    self->hide(line);
+}
+
+void Fl_Select_Browser_show_line(Fl_Select_Browser *self, int line) {
+    self->show(line);
+}
+
+int Fl_Select_Browser_visible_line(const Fl_Select_Browser *self, int line) {
+    return self->visible(line);
+}
+
+void Fl_Multi_Browser_hide_line(Fl_Multi_Browser *self, int line) {
+    self->hide(line);
+}
+
+void Fl_Multi_Browser_show_line(Fl_Multi_Browser *self, int line) {
+    self->show(line);
+}
+
+int Fl_Multi_Browser_visible_line(const Fl_Multi_Browser *self, int line) {
+    return self->visible(line);
+}
+
+void Fl_File_Browser_hide_line(Fl_File_Browser *self, int line) {
+    self->hide(line);
+}
+
+void Fl_File_Browser_show_line(Fl_File_Browser *self, int line) {
+    self->show(line);
+}
+
+int Fl_File_Browser_visible_line(const Fl_File_Browser *self, int line) {
+    return self->visible(line);
+}
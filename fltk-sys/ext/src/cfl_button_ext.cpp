@@ -0,0 +1,52 @@
+#include <FL/Fl_Button.H>
+#include <FL/Fl_Check_Button.H>
+#include <FL/Fl_Light_Button.H>
+#include <FL/Fl_Radio_Button.H>
+#include <FL/Fl_Radio_Light_Button.H>
+#include <FL/Fl_Radio_Round_Button.H>
+#include <FL/Fl_Repeat_Button.H>
+#include <FL/Fl_Return_Button.H>
+#include <FL/Fl_Round_Button.H>
+#include <FL/Fl_Toggle_Button.H>
+
+#include "cfl_button_ext.h"
+
+void Fl_Button_setonly(Fl_Button *self) {
+    self->setonly();
+}
+
+void Fl_Check_Button_setonly(Fl_Check_Button *self) {
+    self->setonly();
+}
+
+void Fl_Radio_Button_setonly(Fl_Radio_Button *self) {
+    self->setonly();
+}
+
+void Fl_Toggle_Button_setonly(Fl_Toggle_Button *self) {
+    self->setonly();
+}
+
+void Fl_Round_Button_setonly(Fl_Round_Button *self) {
+    self->setonly();
+}
+
+void Fl_Radio_Round_Button_setonly(Fl_Radio_Round_Button *self) {
+    self->setonly();
+}
+
+void Fl_Radio_Light_Button_setonly(Fl_Radio_Light_Button *self) {
+    self->setonly();
+}
+
+void Fl_Light_Button_setonly(Fl_Light_Button *self) {
+    self->setonly();
+}
+
+void Fl_Repeat_Button_setonly(Fl_Repeat_Button *self) {
+    self->setonly();
+}
+
+void Fl_Return_Button_setonly(Fl_Return_Button *self) {
+    self->setonly();
+}
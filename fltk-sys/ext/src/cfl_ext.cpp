@@ -0,0 +1,162 @@
+#include <FL/Enumerations.H>
+#include <FL/Fl.H>
+#include <FL/Fl_Shared_Image.H>
+#include <FL/Fl_Widget.H>
+#include <FL/Fl_Window.H>
+
+#include <string>
+
+#include "cfl_ext.h"
+
+int Fl_event_original_key(void) {
+    return Fl::event_original_key();
+}
+
+int Fl_compose(int *del) {
+    return Fl::compose(*del);
+}
+
+void Fl_compose_reset(void) {
+    Fl::compose_reset();
+}
+
+int Fl_compose_state(void) {
+    return Fl::compose_state;
+}
+
+void Fl_set_event_clicks(int i) {
+    Fl::event_clicks(i);
+}
+
+int Fl_arg(int argc, char **argv, int *i) {
+    return Fl::arg(argc, argv, *i);
+}
+
+void Fl_set_event_is_click(int i) {
+    Fl::event_is_click(i);
+}
+
+void Fl_set_event_x(int v) {
+    Fl::e_x = v;
+}
+
+void Fl_set_event_y(int v) {
+    Fl::e_y = v;
+}
+
+void Fl_set_event_x_root(int v) {
+    Fl::e_x_root = v;
+}
+
+void Fl_set_event_y_root(int v) {
+    Fl::e_y_root = v;
+}
+
+void Fl_set_event_dx(int v) {
+    Fl::e_dx = v;
+}
+
+void Fl_set_event_dy(int v) {
+    Fl::e_dy = v;
+}
+
+void Fl_set_event_button(int v) {
+    Fl::e_keysym = FL_Button + v;
+}
+
+void Fl_set_event_key(int v) {
+    Fl::e_keysym = v;
+    Fl::e_original_keysym = v;
+}
+
+void Fl_set_event_state(int v) {
+    Fl::e_state = v;
+}
+
+// Fl::event_text() points to FLTK's own buffers, so an injected text needs to outlive the call
+void Fl_set_event_text(const char *text) {
+    static std::string event_text;
+    event_text = text ? text : "";
+    Fl::e_text = const_cast<char *>(event_text.c_str());
+    Fl::e_length = static_cast<int>(event_text.size());
+}
+
+void Fl_event_dispatch(int (*cb)(int ev, void *win)) {
+    Fl::event_dispatch(reinterpret_cast<Fl_Event_Dispatch>(cb));
+}
+
+int Fl_handle_(int ev, void *win) {
+    return Fl::handle_(ev, static_cast<Fl_Window *>(win));
+}
+
+int Fl_screen_count(void) {
+    return Fl::screen_count();
+}
+
+int Fl_screen_num(int x, int y) {
+    return Fl::screen_num(x, y);
+}
+
+void Fl_screen_work_area(int *x, int *y, int *w, int *h, int n) {
+    Fl::screen_work_area(*x, *y, *w, *h, n);
+}
+
+void Fl_copy(const char *stuff, int len, int destination) {
+    Fl::copy(stuff, len, destination);
+}
+
+void Fl_copy_image(const unsigned char *data, int w, int h, int destination) {
+    Fl::copy_image(data, w, h, destination);
+}
+
+void Fl_paste_image(Fl_Widget *widget, int src) {
+    Fl::paste(*widget, src, Fl::clipboard_image);
+}
+
+int Fl_clipboard_contains(const char *type) {
+    return Fl::clipboard_contains(type);
+}
+
+void *Fl_event_clipboard(void) {
+    return Fl::event_clipboard();
+}
+
+const char *Fl_event_clipboard_type(void) {
+    return Fl::event_clipboard_type();
+}
+
+void Fl_get_color_rgb(unsigned int c, unsigned char *r, unsigned char *g, unsigned char *b) {
+    Fl::get_color(static_cast<Fl_Color>(c), *r, *g, *b);
+}
+
+int Fl_font_size(void) {
+    return FL_NORMAL_SIZE;
+}
+
+void Fl_set_font_size(int sz) {
+    FL_NORMAL_SIZE = sz;
+}
+
+int Fl_option(int opt) {
+    return Fl::option(static_cast<Fl::Fl_Option>(opt));
+}
+
+void Fl_set_option(int opt, int val) {
+    Fl::option(static_cast<Fl::Fl_Option>(opt), val != 0);
+}
+
+int Fl_test_shortcut(int shortcut) {
+    return Fl::test_shortcut(shortcut);
+}
+
+int Fl_check(void) {
+    return Fl::check();
+}
+
+void Fl_do_widget_deletion(void) {
+    Fl::do_widget_deletion();
+}
+
+void Fl_add_check(void (*cb)(void *), void *data) {
+    Fl::add_check(cb, data);
+}
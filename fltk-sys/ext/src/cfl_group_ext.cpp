@@ -0,0 +1,41 @@
+#include <FL/Fl_Color_Chooser.H>
+#include <FL/Fl_Group.H>
+#include <FL/Fl_Pack.H>
+#include <FL/Fl_Scroll.H>
+#include <FL/Fl_Tabs.H>
+#include <FL/Fl_Tile.H>
+#include <FL/Fl_Wizard.H>
+
+#include "cfl_group_ext.h"
+
+void Fl_Group_init_sizes(Fl_Group *self) {
+    self->init_sizes();
+}
+
+void Fl_Pack_init_sizes(Fl_Pack *self) {
+    self->init_sizes();
+}
+
+void Fl_Scroll_init_sizes(Fl_Scroll *self) {
+    self->init_sizes();
+}
+
+Fl_Widget *Fl_Tabs_which(Fl_Tabs *self, int event_x, int event_y) {
+    return self->which(event_x, event_y);
+}
+
+void Fl_Tabs_init_sizes(Fl_Tabs *self) {
+    self->init_sizes();
+}
+
+void Fl_Tile_init_sizes(Fl_Tile *self) {
+    self->init_sizes();
+}
+
+void Fl_Wizard_init_sizes(Fl_Wizard *self) {
+    self->init_sizes();
+}
+
+void Fl_Color_Chooser_init_sizes(Fl_Color_Chooser *self) {
+    self->init_sizes();
+}
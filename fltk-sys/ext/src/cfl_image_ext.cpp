@@ -0,0 +1,246 @@
+#include <FL/Fl_BMP_Image.H>
+#include <FL/Fl_GIF_Image.H>
+#include <FL/Fl_Image.H>
+#include <FL/Fl_JPEG_Image.H>
+#include <FL/Fl_PNG_Image.H>
+#include <FL/Fl_PNM_Image.H>
+#include <FL/Fl_Pixmap.H>
+#include <FL/Fl_SVG_Image.H>
+#include <FL/Fl_Shared_Image.H>
+#include <FL/Fl_Tiled_Image.H>
+#include <FL/Fl_XBM_Image.H>
+#include <FL/Fl_XPM_Image.H>
+
+#include <setjmp.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <vector>
+
+// fltk installs the headers of its bundled image libraries, which prefix their symbols, under FL/images
+#if defined(__has_include)
+#if __has_include(<FL/images/png.h>)
+#include <FL/images/png.h>
+#else
+#include <png.h>
+#endif
+extern "C" {
+#if __has_include(<FL/images/jpeglib.h>)
+#include <FL/images/jpeglib.h>
+#else
+#include <jpeglib.h>
+#endif
+}
+#else
+#include <png.h>
+extern "C" {
+#include <jpeglib.h>
+}
+#endif
+
+#include "cfl_image_ext.h"
+
+void Fl_Image_desaturate(Fl_Image *self) {
+    self->desaturate();
+}
+
+void Fl_Image_color_average(Fl_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_JPEG_Image_desaturate(Fl_JPEG_Image *self) {
+    self->desaturate();
+}
+
+void Fl_JPEG_Image_color_average(Fl_JPEG_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_PNG_Image_desaturate(Fl_PNG_Image *self) {
+    self->desaturate();
+}
+
+void Fl_PNG_Image_color_average(Fl_PNG_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_SVG_Image_desaturate(Fl_SVG_Image *self) {
+    self->desaturate();
+}
+
+void Fl_SVG_Image_color_average(Fl_SVG_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_BMP_Image_desaturate(Fl_BMP_Image *self) {
+    self->desaturate();
+}
+
+void Fl_BMP_Image_color_average(Fl_BMP_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_GIF_Image_desaturate(Fl_GIF_Image *self) {
+    self->desaturate();
+}
+
+void Fl_GIF_Image_color_average(Fl_GIF_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_Pixmap_desaturate(Fl_Pixmap *self) {
+    self->desaturate();
+}
+
+void Fl_Pixmap_color_average(Fl_Pixmap *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_XPM_Image_desaturate(Fl_XPM_Image *self) {
+    self->desaturate();
+}
+
+void Fl_XPM_Image_color_average(Fl_XPM_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_XBM_Image_desaturate(Fl_XBM_Image *self) {
+    self->desaturate();
+}
+
+void Fl_XBM_Image_color_average(Fl_XBM_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_PNM_Image_desaturate(Fl_PNM_Image *self) {
+    self->desaturate();
+}
+
+void Fl_PNM_Image_color_average(Fl_PNM_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_Tiled_Image_desaturate(Fl_Tiled_Image *self) {
+    self->desaturate();
+}
+
+void Fl_Tiled_Image_color_average(Fl_Tiled_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_RGB_Image_desaturate(Fl_RGB_Image *self) {
+    self->desaturate();
+}
+
+void Fl_RGB_Image_color_average(Fl_RGB_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+void Fl_Shared_Image_desaturate(Fl_Shared_Image *self) {
+    self->desaturate();
+}
+
+void Fl_Shared_Image_color_average(Fl_Shared_Image *self, unsigned int c, float i) {
+    self->color_average(static_cast<Fl_Color>(c), i);
+}
+
+Fl_Shared_Image *Fl_Shared_Image_find(const char *name, int W, int H) {
+    return Fl_Shared_Image::find(name, W, H);
+}
+
+const char *Fl_Shared_Image_name(Fl_Shared_Image *self) {
+    return self->name();
+}
+
+int Fl_Shared_Image_refcount(Fl_Shared_Image *self) {
+    return self->refcount();
+}
+
+void Fl_Shared_Image_reload(Fl_Shared_Image *self) {
+    self->reload();
+}
+
+static void png_write_vector(png_structp png, png_bytep data, png_size_t len) {
+    auto out = static_cast<std::vector<unsigned char> *>(png_get_io_ptr(png));
+    out->insert(out->end(), data, data + len);
+}
+
+static void png_flush_vector(png_structp) {
+}
+
+int Fl_encode_png(const unsigned char *rgb, int w, int h, unsigned char **out, int *len) {
+    png_structp png = png_create_write_struct(PNG_LIBPNG_VER_STRING, NULL, NULL, NULL);
+    if (!png)
+        return -1;
+    png_infop info = png_create_info_struct(png);
+    if (!info) {
+        png_destroy_write_struct(&png, NULL);
+        return -1;
+    }
+    std::vector<unsigned char> buf;
+    if (setjmp(png_jmpbuf(png))) {
+        png_destroy_write_struct(&png, &info);
+        return -1;
+    }
+    png_set_write_fn(png, &buf, png_write_vector, png_flush_vector);
+    png_set_IHDR(png, info, w, h, 8, PNG_COLOR_TYPE_RGB, PNG_INTERLACE_NONE,
+                 PNG_COMPRESSION_TYPE_DEFAULT, PNG_FILTER_TYPE_DEFAULT);
+    png_write_info(png, info);
+    for (int y = 0; y < h; y++)
+        png_write_row(png, const_cast<png_bytep>(rgb + (size_t)y * w * 3));
+    png_write_end(png, NULL);
+    png_destroy_write_struct(&png, &info);
+    *out = static_cast<unsigned char *>(malloc(buf.size()));
+    if (!*out)
+        return -1;
+    memcpy(*out, buf.data(), buf.size());
+    *len = static_cast<int>(buf.size());
+    return 0;
+}
+
+struct jpeg_error {
+    struct jpeg_error_mgr mgr;
+    jmp_buf jmp;
+};
+
+// the default handler exits the process
+static void jpeg_error_jump(j_common_ptr cinfo) {
+    longjmp(reinterpret_cast<jpeg_error *>(cinfo->err)->jmp, 1);
+}
+
+int Fl_encode_jpeg(const unsigned char *rgb, int w, int h, int quality, unsigned char **out,
+                   int *len) {
+    struct jpeg_compress_struct cinfo;
+    struct jpeg_error err;
+    unsigned char *mem = NULL;
+    unsigned long size = 0;
+    cinfo.err = jpeg_std_error(&err.mgr);
+    err.mgr.error_exit = jpeg_error_jump;
+    if (setjmp(err.jmp)) {
+        jpeg_destroy_compress(&cinfo);
+        free(mem);
+        return -1;
+    }
+    jpeg_create_compress(&cinfo);
+    jpeg_mem_dest(&cinfo, &mem, &size);
+    cinfo.image_width = w;
+    cinfo.image_height = h;
+    cinfo.input_components = 3;
+    cinfo.in_color_space = JCS_RGB;
+    jpeg_set_defaults(&cinfo);
+    jpeg_set_quality(&cinfo, quality, TRUE);
+    jpeg_start_compress(&cinfo, TRUE);
+    while (cinfo.next_scanline < cinfo.image_height) {
+        JSAMPROW row = const_cast<JSAMPROW>(rgb + (size_t)cinfo.next_scanline * w * 3);
+        jpeg_write_scanlines(&cinfo, &row, 1);
+    }
+    jpeg_finish_compress(&cinfo);
+    jpeg_destroy_compress(&cinfo);
+    *out = mem;
+    *len = static_cast<int>(size);
+    return 0;
+}
+
+void Fl_free_encoded(unsigned char *data) {
+    free(data);
+}
@@ -0,0 +1,22 @@
+#include <FL/Fl_Choice.H>
+#include <FL/Fl_Menu_Bar.H>
+#include <FL/Fl_Menu_Button.H>
+#include <FL/Fl_Sys_Menu_Bar.H>
+
+#include "cfl_menu_ext.h"
+
+void Fl_Menu_Bar_set_shortcut(Fl_Menu_Bar *self, int i, int s) {
+    self->shortcut(i, s);
+}
+
+void Fl_Menu_Button_set_shortcut(Fl_Menu_Button *self, int i, int s) {
+    self->shortcut(i, s);
+}
+
+void Fl_Choice_set_shortcut(Fl_Choice *self, int i, int s) {
+    self->shortcut(i, s);
+}
+
+void Fl_Sys_Menu_Bar_set_shortcut(Fl_Sys_Menu_Bar *self, int i, int s) {
+    self->shortcut(i, s);
+}
@@ -0,0 +1,24 @@
+#include <FL/Fl_Table.H>
+#include <FL/Fl_Table_Row.H>
+
+#include "cfl_table_ext.h"
+
+// find_cell is protected, but a member pointer taken through a subclass can still call it
+struct Fl_Table_Access : public Fl_Table {
+    using Fl_Table::find_cell;
+};
+
+static int find_cell(Fl_Table *self, int ctx, int r, int c, int *x, int *y, int *w, int *h) {
+    int (Fl_Table::*f)(Fl_Table::TableContext, int, int, int &, int &, int &, int &) =
+        &Fl_Table_Access::find_cell;
+    return (self->*f)(static_cast<Fl_Table::TableContext>(ctx), r, c, *x, *y, *w, *h);
+}
+
+int Fl_Table_find_cell(Fl_Table *self, int ctx, int r, int c, int *x, int *y, int *w, int *h) {
+    return find_cell(self, ctx, r, c, x, y, w, h);
+}
+
+int Fl_Table_Row_find_cell(Fl_Table_Row *self, int ctx, int r, int c, int *x, int *y, int *w,
+                           int *h) {
+    return find_cell(self, ctx, r, c, x, y, w, h);
+}
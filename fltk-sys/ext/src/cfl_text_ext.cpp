@@ -0,0 +1,28 @@
+#include <FL/Fl_Simple_Terminal.H>
+#include <FL/Fl_Text_Display.H>
+#include <FL/Fl_Text_Editor.H>
+
+#include "cfl_text_ext.h"
+
+// xy_to_position is protected, but a member pointer taken through a subclass can still call it
+struct Fl_Text_Display_Access : public Fl_Text_Display {
+    using Fl_Text_Display::xy_to_position;
+};
+
+static int xy_to_position(const Fl_Text_Display *self, int x, int y, int pos_type) {
+    int (Fl_Text_Display::*f)(int, int, int) const = &Fl_Text_Display_Access::xy_to_position;
+    return (self->*f)(x, y, pos_type);
+}
+
+int Fl_Text_Display_xy_to_position(const Fl_Text_Display *self, int x, int y, int pos_type) {
+    return xy_to_position(self, x, y, pos_type);
+}
+
+int Fl_Text_Editor_xy_to_position(const Fl_Text_Editor *self, int x, int y, int pos_type) {
+    return xy_to_position(self, x, y, pos_type);
+}
+
+int Fl_Simple_Terminal_xy_to_position(const Fl_Simple_Terminal *self, int x, int y,
+                                      int pos_type) {
+    return xy_to_position(self, x, y, pos_type);
+}
@@ -0,0 +1,7 @@
+#include <FL/Fl_Widget.H>
+
+#include "cfl_widget_ext.h"
+
+int Fl_Widget_handle_event(Fl_Widget *self, int event) {
+    return self->handle(event);
+}
@@ -0,0 +1,547 @@
+#include <FL/Fl_Double_Window.H>
+#include <FL/Fl_Group.H>
+#include <FL/Fl_Menu_Window.H>
+#include <FL/Fl_Overlay_Window.H>
+#include <FL/Fl_Single_Window.H>
+#include <FL/Fl_Window.H>
+
+#ifdef CFLTK_USE_OPENGL
+#include <FL/Fl_Gl_Window.H>
+#endif
+
+#include "cfl_window_ext.h"
+
+void Fl_Window_init_sizes(Fl_Window *self) {
+    self->init_sizes();
+}
+
+void Fl_Single_Window_init_sizes(Fl_Single_Window *self) {
+    self->init_sizes();
+}
+
+void Fl_Double_Window_init_sizes(Fl_Double_Window *self) {
+    self->init_sizes();
+}
+
+void Fl_Menu_Window_init_sizes(Fl_Menu_Window *self) {
+    self->init_sizes();
+}
+
+void Fl_Menu_Window_set_tooltip_window(Fl_Menu_Window *self) {
+    self->set_tooltip_window();
+}
+
+int Fl_Menu_Window_tooltip_window(const Fl_Menu_Window *self) {
+    return const_cast<Fl_Menu_Window *>(self)->tooltip_window();
+}
+
+void Fl_Menu_Window_set_overlay(Fl_Menu_Window *self) {
+    self->set_overlay();
+}
+
+void Fl_Menu_Window_clear_overlay(Fl_Menu_Window *self) {
+    self->clear_overlay();
+}
+
+#ifdef CFLTK_USE_OPENGL
+void Fl_Gl_Window_init_sizes(Fl_Gl_Window *self) {
+    self->init_sizes();
+}
+#endif
+
+// The handle and draw methods call the custom ones set from Rust, like the cfltk widgets do:
+// the handler runs before the window's and consumes the event by returning nonzero,
+// and the drawer runs after the window is drawn
+struct Fl_Overlay_Window_Derived : public Fl_Overlay_Window {
+    custom_handler_callback inner_handler = NULL;
+    custom_handler_callback2 inner_handler2 = NULL;
+    custom_draw_callback inner_drawer = NULL;
+    custom_draw_callback2 inner_drawer2 = NULL;
+    custom_draw_callback overlay_drawer = NULL;
+    void *ev_data_ = NULL;
+    void *draw_data_ = NULL;
+    void *overlay_data_ = NULL;
+    void (*deleter)(void *) = NULL;
+
+    Fl_Overlay_Window_Derived(int x, int y, int w, int h, const char *title)
+        : Fl_Overlay_Window(x, y, w, h, title) {
+    }
+
+    ~Fl_Overlay_Window_Derived() {
+        if (deleter)
+            deleter(user_data());
+    }
+
+    int handle(int event) override {
+        int local = 0;
+        if (inner_handler)
+            local = inner_handler(event, ev_data_);
+        else if (inner_handler2)
+            local = inner_handler2(this, event, ev_data_);
+        return local ? local : Fl_Overlay_Window::handle(event);
+    }
+
+    void draw() override {
+        Fl_Overlay_Window::draw();
+        if (inner_drawer)
+            inner_drawer(draw_data_);
+        else if (inner_drawer2)
+            inner_drawer2(this, draw_data_);
+    }
+
+    void draw_overlay() override {
+        if (overlay_drawer)
+            overlay_drawer(overlay_data_);
+    }
+};
+
+static Fl_Overlay_Window_Derived *derived(Fl_Overlay_Window *self) {
+    return static_cast<Fl_Overlay_Window_Derived *>(self);
+}
+
+static const Fl_Overlay_Window_Derived *derived(const Fl_Overlay_Window *self) {
+    return static_cast<const Fl_Overlay_Window_Derived *>(self);
+}
+
+Fl_Overlay_Window *Fl_Overlay_Window_new(int x, int y, int width, int height, const char *title) {
+    return new Fl_Overlay_Window_Derived(x, y, width, height, title);
+}
+
+void Fl_Overlay_Window_handle(Fl_Overlay_Window *self, custom_handler_callback cb, void *data) {
+    derived(self)->inner_handler = cb;
+    derived(self)->inner_handler2 = NULL;
+    derived(self)->ev_data_ = data;
+}
+
+void Fl_Overlay_Window_handle2(Fl_Overlay_Window *self, custom_handler_callback2 cb, void *data) {
+    derived(self)->inner_handler = NULL;
+    derived(self)->inner_handler2 = cb;
+    derived(self)->ev_data_ = data;
+}
+
+void Fl_Overlay_Window_draw(Fl_Overlay_Window *self, custom_draw_callback cb, void *data) {
+    derived(self)->inner_drawer = cb;
+    derived(self)->inner_drawer2 = NULL;
+    derived(self)->draw_data_ = data;
+}
+
+void Fl_Overlay_Window_draw2(Fl_Overlay_Window *self, custom_draw_callback2 cb, void *data) {
+    derived(self)->inner_drawer = NULL;
+    derived(self)->inner_drawer2 = cb;
+    derived(self)->draw_data_ = data;
+}
+
+void *Fl_Overlay_Window_draw_data(const Fl_Overlay_Window *self) {
+    return derived(self)->draw_data_;
+}
+
+void *Fl_Overlay_Window_handle_data(const Fl_Overlay_Window *self) {
+    return derived(self)->ev_data_;
+}
+
+void Fl_Overlay_Window_set_draw_data(Fl_Overlay_Window *self, void *data) {
+    derived(self)->draw_data_ = data;
+}
+
+void Fl_Overlay_Window_set_handle_data(Fl_Overlay_Window *self, void *data) {
+    derived(self)->ev_data_ = data;
+}
+
+void Fl_Overlay_Window_set_deleter(Fl_Overlay_Window *self, void (*deleter)(void *)) {
+    derived(self)->deleter = deleter;
+}
+
+void Fl_Overlay_Window_draw_overlay(Fl_Overlay_Window *self, custom_draw_callback cb, void *data) {
+    derived(self)->overlay_drawer = cb;
+    derived(self)->overlay_data_ = data;
+}
+
+void *Fl_Overlay_Window_draw_overlay_data(const Fl_Overlay_Window *self) {
+    return derived(self)->overlay_data_;
+}
+
+void Fl_Overlay_Window_redraw_overlay(Fl_Overlay_Window *self) {
+    self->redraw_overlay();
+}
+
+int Fl_Overlay_Window_can_do_overlay(Fl_Overlay_Window *self) {
+    return self->can_do_overlay();
+}
+
+// The rest isn't specific to overlay windows, so it uses the cfltk wrappers of Fl_Window
+
+int Fl_Overlay_Window_x(Fl_Overlay_Window *self) {
+    return Fl_Window_x(self);
+}
+
+int Fl_Overlay_Window_y(Fl_Overlay_Window *self) {
+    return Fl_Window_y(self);
+}
+
+int Fl_Overlay_Window_width(Fl_Overlay_Window *self) {
+    return Fl_Window_width(self);
+}
+
+int Fl_Overlay_Window_height(Fl_Overlay_Window *self) {
+    return Fl_Window_height(self);
+}
+
+const char *Fl_Overlay_Window_label(Fl_Overlay_Window *self) {
+    return Fl_Window_label(self);
+}
+
+void Fl_Overlay_Window_set_label(Fl_Overlay_Window *self, const char *title) {
+    Fl_Window_set_label(self, title);
+}
+
+void Fl_Overlay_Window_redraw(Fl_Overlay_Window *self) {
+    Fl_Window_redraw(self);
+}
+
+void Fl_Overlay_Window_show(Fl_Overlay_Window *self) {
+    Fl_Window_show(self);
+}
+
+void Fl_Overlay_Window_hide(Fl_Overlay_Window *self) {
+    Fl_Window_hide(self);
+}
+
+void Fl_Overlay_Window_activate(Fl_Overlay_Window *self) {
+    Fl_Window_activate(self);
+}
+
+void Fl_Overlay_Window_deactivate(Fl_Overlay_Window *self) {
+    Fl_Window_deactivate(self);
+}
+
+void Fl_Overlay_Window_redraw_label(Fl_Overlay_Window *self) {
+    Fl_Window_redraw_label(self);
+}
+
+void Fl_Overlay_Window_resize(Fl_Overlay_Window *self, int x, int y, int width, int height) {
+    Fl_Window_resize(self, x, y, width, height);
+}
+
+void Fl_Overlay_Window_widget_resize(Fl_Overlay_Window *self, int x, int y, int width, int height) {
+    Fl_Window_widget_resize(self, x, y, width, height);
+}
+
+const char *Fl_Overlay_Window_tooltip(Fl_Overlay_Window *self) {
+    return Fl_Window_tooltip(self);
+}
+
+void Fl_Overlay_Window_set_tooltip(Fl_Overlay_Window *self, const char *txt) {
+    Fl_Window_set_tooltip(self, txt);
+}
+
+int Fl_Overlay_Window_get_type(Fl_Overlay_Window *self) {
+    return Fl_Window_get_type(self);
+}
+
+void Fl_Overlay_Window_set_type(Fl_Overlay_Window *self, int typ) {
+    Fl_Window_set_type(self, typ);
+}
+
+unsigned int Fl_Overlay_Window_color(Fl_Overlay_Window *self) {
+    return Fl_Window_color(self);
+}
+
+void Fl_Overlay_Window_set_color(Fl_Overlay_Window *self, unsigned int color) {
+    Fl_Window_set_color(self, color);
+}
+
+void Fl_Overlay_Window_measure_label(const Fl_Overlay_Window *self, int *w, int *h) {
+    Fl_Window_measure_label(self, w, h);
+}
+
+unsigned int Fl_Overlay_Window_label_color(Fl_Overlay_Window *self) {
+    return Fl_Window_label_color(self);
+}
+
+void Fl_Overlay_Window_set_label_color(Fl_Overlay_Window *self, unsigned int color) {
+    Fl_Window_set_label_color(self, color);
+}
+
+int Fl_Overlay_Window_label_font(Fl_Overlay_Window *self) {
+    return Fl_Window_label_font(self);
+}
+
+void Fl_Overlay_Window_set_label_font(Fl_Overlay_Window *self, int font) {
+    Fl_Window_set_label_font(self, font);
+}
+
+int Fl_Overlay_Window_label_size(Fl_Overlay_Window *self) {
+    return Fl_Window_label_size(self);
+}
+
+void Fl_Overlay_Window_set_label_size(Fl_Overlay_Window *self, int sz) {
+    Fl_Window_set_label_size(self, sz);
+}
+
+int Fl_Overlay_Window_label_type(Fl_Overlay_Window *self) {
+    return Fl_Window_label_type(self);
+}
+
+void Fl_Overlay_Window_set_label_type(Fl_Overlay_Window *self, int typ) {
+    Fl_Window_set_label_type(self, typ);
+}
+
+int Fl_Overlay_Window_box(Fl_Overlay_Window *self) {
+    return Fl_Window_box(self);
+}
+
+void Fl_Overlay_Window_set_box(Fl_Overlay_Window *self, int typ) {
+    Fl_Window_set_box(self, typ);
+}
+
+int Fl_Overlay_Window_changed(Fl_Overlay_Window *self) {
+    return Fl_Window_changed(self);
+}
+
+void Fl_Overlay_Window_set_changed(Fl_Overlay_Window *self) {
+    Fl_Window_set_changed(self);
+}
+
+void Fl_Overlay_Window_clear_changed(Fl_Overlay_Window *self) {
+    Fl_Window_clear_changed(self);
+}
+
+int Fl_Overlay_Window_align(Fl_Overlay_Window *self) {
+    return Fl_Window_align(self);
+}
+
+void Fl_Overlay_Window_set_align(Fl_Overlay_Window *self, int typ) {
+    Fl_Window_set_align(self, typ);
+}
+
+void Fl_Overlay_Window_delete(Fl_Overlay_Window *self) {
+    Fl_Window_delete(self);
+}
+
+void Fl_Overlay_Window_set_image(Fl_Overlay_Window *self, void *image) {
+    Fl_Window_set_image(self, image);
+}
+
+void Fl_Overlay_Window_set_when(Fl_Overlay_Window *self, int when) {
+    Fl_Window_set_when(self, when);
+}
+
+int Fl_Overlay_Window_when(const Fl_Overlay_Window *self) {
+    return Fl_Window_when(self);
+}
+
+void *Fl_Overlay_Window_image(const Fl_Overlay_Window *self) {
+    return Fl_Window_image(self);
+}
+
+void *Fl_Overlay_Window_parent(const Fl_Overlay_Window *self) {
+    return Fl_Window_parent(self);
+}
+
+unsigned int Fl_Overlay_Window_selection_color(Fl_Overlay_Window *self) {
+    return Fl_Window_selection_color(self);
+}
+
+void Fl_Overlay_Window_set_selection_color(Fl_Overlay_Window *self, unsigned int color) {
+    Fl_Window_set_selection_color(self, color);
+}
+
+void Fl_Overlay_Window_do_callback(Fl_Overlay_Window *self) {
+    Fl_Window_do_callback(self);
+}
+
+int Fl_Overlay_Window_inside(const Fl_Overlay_Window *self, void *wid) {
+    return Fl_Window_inside(self, wid);
+}
+
+void *Fl_Overlay_Window_window(const Fl_Overlay_Window *self) {
+    return Fl_Window_window(self);
+}
+
+void *Fl_Overlay_Window_top_window(const Fl_Overlay_Window *self) {
+    return Fl_Window_top_window(self);
+}
+
+int Fl_Overlay_Window_takes_events(const Fl_Overlay_Window *self) {
+    return Fl_Window_takes_events(self);
+}
+
+void *Fl_Overlay_Window_user_data(const Fl_Overlay_Window *self) {
+    return Fl_Window_user_data(self);
+}
+
+int Fl_Overlay_Window_take_focus(Fl_Overlay_Window *self) {
+    return Fl_Window_take_focus(self);
+}
+
+void Fl_Overlay_Window_set_visible_focus(Fl_Overlay_Window *self) {
+    Fl_Window_set_visible_focus(self);
+}
+
+void Fl_Overlay_Window_clear_visible_focus(Fl_Overlay_Window *self) {
+    Fl_Window_clear_visible_focus(self);
+}
+
+void Fl_Overlay_Window_visible_focus(Fl_Overlay_Window *self, int v) {
+    Fl_Window_visible_focus(self, v);
+}
+
+unsigned int Fl_Overlay_Window_has_visible_focus(Fl_Overlay_Window *self) {
+    return Fl_Window_has_visible_focus(self);
+}
+
+void Fl_Overlay_Window_set_user_data(Fl_Overlay_Window *self, void *data) {
+    Fl_Window_set_user_data(self, data);
+}
+
+unsigned char Fl_Overlay_Window_damage(const Fl_Overlay_Window *self) {
+    return Fl_Window_damage(self);
+}
+
+void Fl_Overlay_Window_set_damage(Fl_Overlay_Window *self, unsigned char flag) {
+    Fl_Window_set_damage(self, flag);
+}
+
+void Fl_Overlay_Window_clear_damage(Fl_Overlay_Window *self) {
+    Fl_Window_clear_damage(self);
+}
+
+void *Fl_Overlay_Window_as_window(Fl_Overlay_Window *self) {
+    return Fl_Window_as_window(self);
+}
+
+void *Fl_Overlay_Window_as_group(Fl_Overlay_Window *self) {
+    return Fl_Window_as_group(self);
+}
+
+void Fl_Overlay_Window_set_deimage(Fl_Overlay_Window *self, void *image) {
+    Fl_Window_set_deimage(self, image);
+}
+
+void *Fl_Overlay_Window_deimage(const Fl_Overlay_Window *self) {
+    return Fl_Window_deimage(self);
+}
+
+void Fl_Overlay_Window_set_callback(Fl_Overlay_Window *self, Fl_Callback cb, void *data) {
+    Fl_Window_set_callback(self, cb, data);
+}
+
+void Fl_Overlay_Window_begin(Fl_Overlay_Window *self) {
+    Fl_Window_begin(self);
+}
+
+void Fl_Overlay_Window_end(Fl_Overlay_Window *self) {
+    Fl_Window_end(self);
+}
+
+int Fl_Overlay_Window_find(Fl_Overlay_Window *self, const void *wid) {
+    return Fl_Window_find(self, wid);
+}
+
+void Fl_Overlay_Window_add(Fl_Overlay_Window *self, void *wid) {
+    Fl_Window_add(self, wid);
+}
+
+void Fl_Overlay_Window_insert(Fl_Overlay_Window *self, void *wid, int pos) {
+    Fl_Window_insert(self, wid, pos);
+}
+
+void Fl_Overlay_Window_remove(Fl_Overlay_Window *self, void *wid) {
+    Fl_Window_remove(self, wid);
+}
+
+void Fl_Overlay_Window_clear(Fl_Overlay_Window *self) {
+    Fl_Window_clear(self);
+}
+
+int Fl_Overlay_Window_children(Fl_Overlay_Window *self) {
+    return Fl_Window_children(self);
+}
+
+Fl_Widget *Fl_Overlay_Window_child(Fl_Overlay_Window *self, int index) {
+    return Fl_Window_child(self, index);
+}
+
+void Fl_Overlay_Window_resizable(Fl_Overlay_Window *self, void *wid) {
+    Fl_Window_resizable(self, wid);
+}
+
+void Fl_Overlay_Window_init_sizes(Fl_Overlay_Window *self) {
+    Fl_Window_init_sizes(self);
+}
+
+void Fl_Overlay_Window_make_modal(Fl_Overlay_Window *self, unsigned int boolean) {
+    Fl_Window_make_modal(self, boolean);
+}
+
+void Fl_Overlay_Window_fullscreen(Fl_Overlay_Window *self, unsigned int boolean) {
+    Fl_Window_fullscreen(self, boolean);
+}
+
+void Fl_Overlay_Window_make_current(Fl_Overlay_Window *self) {
+    Fl_Window_make_current(self);
+}
+
+void Fl_Overlay_Window_set_icon(Fl_Overlay_Window *self, const void *image) {
+    Fl_Window_set_icon(self, image);
+}
+
+void *Fl_Overlay_Window_icon(const Fl_Overlay_Window *self) {
+    return Fl_Window_icon(self);
+}
+
+void Fl_Overlay_Window_set_cursor(Fl_Overlay_Window *self, int cursor) {
+    Fl_Window_set_cursor(self, cursor);
+}
+
+int Fl_Overlay_Window_shown(Fl_Overlay_Window *self) {
+    return Fl_Window_shown(self);
+}
+
+void *Fl_Overlay_Window_raw_handle(const Fl_Overlay_Window *self) {
+    return Fl_Window_raw_handle(self);
+}
+
+void Fl_Overlay_Window_set_border(Fl_Overlay_Window *self, int flag) {
+    Fl_Window_set_border(self, flag);
+}
+
+int Fl_Overlay_Window_border(const Fl_Overlay_Window *self) {
+    return Fl_Window_border(self);
+}
+
+void *Fl_Overlay_Window_region(const Fl_Overlay_Window *self) {
+    return Fl_Window_region(self);
+}
+
+void Fl_Overlay_Window_set_region(Fl_Overlay_Window *self, void *r) {
+    Fl_Window_set_region(self, r);
+}
+
+void Fl_Overlay_Window_iconize(Fl_Overlay_Window *self) {
+    Fl_Window_iconize(self);
+}
+
+unsigned int Fl_Overlay_Window_fullscreen_active(const Fl_Overlay_Window *self) {
+    return Fl_Window_fullscreen_active(self);
+}
+
+void Fl_Overlay_Window_free_position(Fl_Overlay_Window *self) {
+    Fl_Window_free_position(self);
+}
+
+int Fl_Overlay_Window_decorated_w(const Fl_Overlay_Window *self) {
+    return Fl_Window_decorated_w(self);
+}
+
+int Fl_Overlay_Window_decorated_h(const Fl_Overlay_Window *self) {
+    return Fl_Window_decorated_h(self);
+}
+
+void Fl_Overlay_Window_size_range(Fl_Overlay_Window *self, int min_w, int min_h, int max_w, int max_h) {
+    Fl_Window_size_range(self, min_w, min_h, max_w, max_h);
+}
+
+void Fl_Overlay_Window_hotspot(Fl_Overlay_Window *self, Fl_Widget *wid) {
+    Fl_Window_hotspot(self, wid);
+}
@@ -2767,3 +2767,60 @@ extern "C" {
 extern "C" {
     pub fn Fl_Check_Browser_text_size(self_: *mut Fl_Check_Browser) -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_Browser_hide_line(self_: *mut Fl_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Browser_show_line(self_: *mut Fl_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Browser_visible_line(self_: *const Fl_Browser, line: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Hold_Browser_hide_line(self_: *mut Fl_Hold_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Hold_Browser_show_line(self_: *mut Fl_Hold_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Hold_Browser_visible_line(
+        self_: *const Fl_Hold_Browser,
+        line: libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Select_Browser_hide_line(self_: *mut Fl_Select_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Select_Browser_show_line(self_: *mut Fl_Select_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Select_Browser_visible_line(
+        self_: *const Fl_Select_Browser,
+        line: libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Multi_Browser_hide_line(self_: *mut Fl_Multi_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Multi_Browser_show_line(self_: *mut Fl_Multi_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Multi_Browser_visible_line(
+        self_: *const Fl_Multi_Browser,
+        line: libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_File_Browser_hide_line(self_: *mut Fl_File_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_File_Browser_show_line(self_: *mut Fl_File_Browser, line: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_File_Browser_visible_line(
+        self_: *const Fl_File_Browser,
+        line: libc::c_int,
+    ) -> libc::c_int;
+}
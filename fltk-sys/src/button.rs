@@ -3355,3 +3355,33 @@ extern "C" {
 extern "C" {
     pub fn Fl_Return_Button_down_box(self_: *const Fl_Return_Button) -> libc::c_int;
 }
+extern "C" {
+    pub fn Fl_Button_setonly(self_: *mut Fl_Button);
+}
+extern "C" {
+    pub fn Fl_Check_Button_setonly(self_: *mut Fl_Check_Button);
+}
+extern "C" {
+    pub fn Fl_Radio_Button_setonly(self_: *mut Fl_Radio_Button);
+}
+extern "C" {
+    pub fn Fl_Toggle_Button_setonly(self_: *mut Fl_Toggle_Button);
+}
+extern "C" {
+    pub fn Fl_Round_Button_setonly(self_: *mut Fl_Round_Button);
+}
+extern "C" {
+    pub fn Fl_Radio_Round_Button_setonly(self_: *mut Fl_Radio_Round_Button);
+}
+extern "C" {
+    pub fn Fl_Radio_Light_Button_setonly(self_: *mut Fl_Radio_Light_Button);
+}
+extern "C" {
+    pub fn Fl_Light_Button_setonly(self_: *mut Fl_Light_Button);
+}
+extern "C" {
+    pub fn Fl_Repeat_Button_setonly(self_: *mut Fl_Repeat_Button);
+}
+extern "C" {
+    pub fn Fl_Return_Button_setonly(self_: *mut Fl_Return_Button);
+}
@@ -278,3 +278,141 @@ extern "C" {
         arg2: *mut libc::c_void,
     );
 }
+extern "C" {
+    pub fn Fl_event_original_key() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_compose(del: *mut libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_compose_reset();
+}
+extern "C" {
+    pub fn Fl_compose_state() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_event_clicks(i: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_arg(
+        argc: libc::c_int,
+        argv: *mut *mut libc::c_char,
+        i: *mut libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_event_is_click(i: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_x(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_y(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_x_root(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_y_root(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_dx(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_dy(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_button(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_key(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_state(v: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_set_event_text(text: *const libc::c_char);
+}
+extern "C" {
+    pub fn Fl_event_dispatch(
+        cb: ::core::option::Option<
+            unsafe extern "C" fn(ev: libc::c_int, win: *mut libc::c_void) -> libc::c_int,
+        >,
+    );
+}
+extern "C" {
+    pub fn Fl_handle_(ev: libc::c_int, win: *mut libc::c_void) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_screen_count() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_screen_num(x: libc::c_int, y: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_screen_work_area(
+        x: *mut libc::c_int,
+        y: *mut libc::c_int,
+        w: *mut libc::c_int,
+        h: *mut libc::c_int,
+        n: libc::c_int,
+    );
+}
+extern "C" {
+    pub fn Fl_copy(stuff: *const libc::c_char, len: libc::c_int, destination: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_copy_image(
+        data: *const libc::c_uchar,
+        w: libc::c_int,
+        h: libc::c_int,
+        destination: libc::c_int,
+    );
+}
+extern "C" {
+    pub fn Fl_paste_image(arg1: *mut Fl_Widget, src: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_clipboard_contains(type_: *const libc::c_char) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_event_clipboard() -> *mut libc::c_void;
+}
+extern "C" {
+    pub fn Fl_event_clipboard_type() -> *const libc::c_char;
+}
+extern "C" {
+    pub fn Fl_get_color_rgb(
+        c: libc::c_uint,
+        r: *mut libc::c_uchar,
+        g: *mut libc::c_uchar,
+        b: *mut libc::c_uchar,
+    );
+}
+extern "C" {
+    pub fn Fl_font_size() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_font_size(sz: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_option(opt: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_set_option(opt: libc::c_int, val: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_test_shortcut(shortcut: libc::c_int) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_check() -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_do_widget_deletion();
+}
+extern "C" {
+    pub fn Fl_add_check(
+        arg1: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
+        arg2: *mut libc::c_void,
+    );
+}
@@ -2424,3 +2424,31 @@ extern "C" {
 extern "C" {
     pub fn Fl_Color_Chooser_resizable(self_: *mut Fl_Color_Chooser, arg1: *mut libc::c_void);
 }
+extern "C" {
+    pub fn Fl_Group_init_sizes(self_: *mut Fl_Group);
+}
+extern "C" {
+    pub fn Fl_Pack_init_sizes(self_: *mut Fl_Pack);
+}
+extern "C" {
+    pub fn Fl_Scroll_init_sizes(self_: *mut Fl_Scroll);
+}
+extern "C" {
+    pub fn Fl_Tabs_which(
+        self_: *mut Fl_Tabs,
+        event_x: libc::c_int,
+        event_y: libc::c_int,
+    ) -> *mut Fl_Widget;
+}
+extern "C" {
+    pub fn Fl_Tabs_init_sizes(self_: *mut Fl_Tabs);
+}
+extern "C" {
+    pub fn Fl_Tile_init_sizes(self_: *mut Fl_Tile);
+}
+extern "C" {
+    pub fn Fl_Wizard_init_sizes(self_: *mut Fl_Wizard);
+}
+extern "C" {
+    pub fn Fl_Color_Chooser_init_sizes(self_: *mut Fl_Color_Chooser);
+}
@@ -848,3 +848,119 @@ extern "C" {
 extern "C" {
     pub fn Fl_register_images();
 }
+extern "C" {
+    pub fn Fl_Image_desaturate(self_: *mut Fl_Image);
+}
+extern "C" {
+    pub fn Fl_Image_color_average(self_: *mut Fl_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_JPEG_Image_desaturate(self_: *mut Fl_JPEG_Image);
+}
+extern "C" {
+    pub fn Fl_JPEG_Image_color_average(self_: *mut Fl_JPEG_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_PNG_Image_desaturate(self_: *mut Fl_PNG_Image);
+}
+extern "C" {
+    pub fn Fl_PNG_Image_color_average(self_: *mut Fl_PNG_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_SVG_Image_desaturate(self_: *mut Fl_SVG_Image);
+}
+extern "C" {
+    pub fn Fl_SVG_Image_color_average(self_: *mut Fl_SVG_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_BMP_Image_desaturate(self_: *mut Fl_BMP_Image);
+}
+extern "C" {
+    pub fn Fl_BMP_Image_color_average(self_: *mut Fl_BMP_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_GIF_Image_desaturate(self_: *mut Fl_GIF_Image);
+}
+extern "C" {
+    pub fn Fl_GIF_Image_color_average(self_: *mut Fl_GIF_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_Pixmap_desaturate(self_: *mut Fl_Pixmap);
+}
+extern "C" {
+    pub fn Fl_Pixmap_color_average(self_: *mut Fl_Pixmap, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_XPM_Image_desaturate(self_: *mut Fl_XPM_Image);
+}
+extern "C" {
+    pub fn Fl_XPM_Image_color_average(self_: *mut Fl_XPM_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_XBM_Image_desaturate(self_: *mut Fl_XBM_Image);
+}
+extern "C" {
+    pub fn Fl_XBM_Image_color_average(self_: *mut Fl_XBM_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_PNM_Image_desaturate(self_: *mut Fl_PNM_Image);
+}
+extern "C" {
+    pub fn Fl_PNM_Image_color_average(self_: *mut Fl_PNM_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_Tiled_Image_desaturate(self_: *mut Fl_Tiled_Image);
+}
+extern "C" {
+    pub fn Fl_Tiled_Image_color_average(self_: *mut Fl_Tiled_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_RGB_Image_desaturate(self_: *mut Fl_RGB_Image);
+}
+extern "C" {
+    pub fn Fl_RGB_Image_color_average(self_: *mut Fl_RGB_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_Shared_Image_desaturate(self_: *mut Fl_Shared_Image);
+}
+extern "C" {
+    pub fn Fl_Shared_Image_color_average(self_: *mut Fl_Shared_Image, c: libc::c_uint, i: f32);
+}
+extern "C" {
+    pub fn Fl_Shared_Image_find(
+        name: *const libc::c_char,
+        W: libc::c_int,
+        H: libc::c_int,
+    ) -> *mut Fl_Shared_Image;
+}
+extern "C" {
+    pub fn Fl_Shared_Image_name(self_: *mut Fl_Shared_Image) -> *const libc::c_char;
+}
+extern "C" {
+    pub fn Fl_Shared_Image_refcount(self_: *mut Fl_Shared_Image) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Shared_Image_reload(self_: *mut Fl_Shared_Image);
+}
+extern "C" {
+    pub fn Fl_encode_png(
+        rgb: *const libc::c_uchar,
+        w: libc::c_int,
+        h: libc::c_int,
+        out: *mut *mut libc::c_uchar,
+        len: *mut libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_encode_jpeg(
+        rgb: *const libc::c_uchar,
+        w: libc::c_int,
+        h: libc::c_int,
+        quality: libc::c_int,
+        out: *mut *mut libc::c_uchar,
+        len: *mut libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_free_encoded(data: *mut libc::c_uchar);
+}
@@ -1879,3 +1879,19 @@ extern "C" {
 extern "C" {
     pub fn Fl_Menu_Item_set_user_data(arg1: *mut Fl_Menu_Item, data: *mut libc::c_void);
 }
+extern "C" {
+    pub fn Fl_Menu_Bar_set_shortcut(self_: *mut Fl_Menu_Bar, i: libc::c_int, s: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Menu_Button_set_shortcut(self_: *mut Fl_Menu_Button, i: libc::c_int, s: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Choice_set_shortcut(self_: *mut Fl_Choice, i: libc::c_int, s: libc::c_int);
+}
+extern "C" {
+    pub fn Fl_Sys_Menu_Bar_set_shortcut(
+        self_: *mut Fl_Sys_Menu_Bar,
+        i: libc::c_int,
+        s: libc::c_int,
+    );
+}
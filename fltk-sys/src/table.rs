@@ -3497,3 +3497,55 @@ extern "C" {
 extern "C" {
     pub fn Fl_Table_Row_select_all_rows(self_: *mut Fl_Table_Row, flag: libc::c_int);
 }
+extern "C" {
+    pub fn Fl_Group_init_sizes(self_: *mut Fl_Group);
+}
+extern "C" {
+    pub fn Fl_Pack_init_sizes(self_: *mut Fl_Pack);
+}
+extern "C" {
+    pub fn Fl_Scroll_init_sizes(self_: *mut Fl_Scroll);
+}
+extern "C" {
+    pub fn Fl_Tabs_which(
+        self_: *mut Fl_Tabs,
+        event_x: libc::c_int,
+        event_y: libc::c_int,
+    ) -> *mut Fl_Widget;
+}
+extern "C" {
+    pub fn Fl_Tabs_init_sizes(self_: *mut Fl_Tabs);
+}
+extern "C" {
+    pub fn Fl_Tile_init_sizes(self_: *mut Fl_Tile);
+}
+extern "C" {
+    pub fn Fl_Wizard_init_sizes(self_: *mut Fl_Wizard);
+}
+extern "C" {
+    pub fn Fl_Color_Chooser_init_sizes(self_: *mut Fl_Color_Chooser);
+}
+extern "C" {
+    pub fn Fl_Table_find_cell(
+        self_: *mut Fl_Table,
+        ctx: libc::c_int,
+        r: libc::c_int,
+        c: libc::c_int,
+        x: *mut libc::c_int,
+        y: *mut libc::c_int,
+        w: *mut libc::c_int,
+        h: *mut libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Table_Row_find_cell(
+        self_: *mut Fl_Table_Row,
+        ctx: libc::c_int,
+        r: libc::c_int,
+        c: libc::c_int,
+        x: *mut libc::c_int,
+        y: *mut libc::c_int,
+        w: *mut libc::c_int,
+        h: *mut libc::c_int,
+    ) -> libc::c_int;
+}
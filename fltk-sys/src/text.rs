@@ -2113,3 +2113,27 @@ extern "C" {
 extern "C" {
     pub fn Fl_delete_stable(arg1: *mut libc::c_void);
 }
+extern "C" {
+    pub fn Fl_Text_Display_xy_to_position(
+        self_: *const Fl_Text_Display,
+        x: libc::c_int,
+        y: libc::c_int,
+        pos_type: libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Text_Editor_xy_to_position(
+        self_: *const Fl_Text_Editor,
+        x: libc::c_int,
+        y: libc::c_int,
+        pos_type: libc::c_int,
+    ) -> libc::c_int;
+}
+extern "C" {
+    pub fn Fl_Simple_Terminal_xy_to_position(
+        self_: *const Fl_Simple_Terminal,
+        x: libc::c_int,
+        y: libc::c_int,
+        pos_type: libc::c_int,
+    ) -> libc::c_int;
+}
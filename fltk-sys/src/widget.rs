@@ -280,3 +280,6 @@ extern "C" {
         arg2: ::core::option::Option<unsafe extern "C" fn(arg1: *mut libc::c_void)>,
     );
 }
+extern "C" {
+    pub fn Fl_Widget_handle_event(self_: *mut Fl_Widget, event: libc::c_int) -> libc::c_int;
+}
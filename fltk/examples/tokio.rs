@@ -79,4 +79,29 @@
 //     }
 // }
 
+// // Example3 using the async feature of fltk
+//
+// use fltk::{app::*, button::*, frame::*, window::*};
+// use std::time::Duration;
+//
+// #[tokio::main(flavor = "current_thread")]
+// async fn main() {
+//     let app = App::default();
+//     let mut wind = Window::new(100, 100, 400, 300, "Hello from rust");
+//     let mut frame = Frame::new(0, 0, 400, 200, "");
+//     let mut but = Button::new(160, 210, 80, 40, "Click me!");
+//     wind.end();
+//     wind.show();
+//
+//     but.set_callback(move || {
+//         let mut frame = frame.clone();
+//         tokio::task::spawn_local(async move {
+//             tokio::time::sleep(Duration::from_secs(1)).await;
+//             frame.set_label("Hello, world!");
+//         });
+//     });
+//
+//     tokio::task::LocalSet::new().run_until(app.run_async()).await.unwrap();
+// }
+
 fn main() {}
@@ -0,0 +1,293 @@
+use crate::app;
+use crate::menu::MenuFlag;
+use crate::prelude::*;
+use std::{
+    cell::RefCell,
+    fmt,
+    rc::{Rc, Weak},
+};
+
+type IconSetter = Rc<dyn Fn(&mut dyn ButtonExt)>;
+
+struct ActionState {
+    label: String,
+    icon: Option<IconSetter>,
+    shortcut: Shortcut,
+    shortcut_id: Option<String>,
+    enabled: bool,
+    callback: Option<Rc<RefCell<dyn FnMut()>>>,
+    menu_items: Vec<(Box<dyn MenuExt>, String)>,
+    buttons: Vec<Box<dyn ButtonExt>>,
+}
+
+/// Removes the `&` marking the accelerator of a label, `&&` being a literal `&`
+fn plain_label(label: &str) -> String {
+    let mut out = String::with_capacity(label.len());
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Returns the path of a menu item with its last component relabeled
+fn relabeled_path(path: &str, label: &str) -> String {
+    match path.rfind('/') {
+        Some(idx) => format!("{}/{}", &path[..idx], label),
+        None => label.to_string(),
+    }
+}
+
+/// A command shared by menu items, buttons and shortcuts, which keeps their label, icon, shortcut
+/// and enabled state in sync. Disabling an action grays out all of its widgets.
+/// The widgets only hold a weak reference to the action, which stops triggering once all its clones are dropped
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut menu = menu::MenuBar::new(0, 0, 400, 30, "");
+/// let mut btn = button::Button::new(10, 40, 80, 30, "");
+/// win.end();
+/// win.show();
+/// let mut save = action::Action::new("&Save");
+/// save.set_shortcut(Shortcut::Ctrl | 's').unwrap();
+/// save.set_callback(|| println!("Saved"));
+/// save.add_to_menu(&mut menu, "File/&Save");
+/// save.attach_button(&mut btn);
+/// save.set_enabled(false);
+/// app.run().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Action {
+    state: Rc<RefCell<ActionState>>,
+}
+
+impl fmt::Debug for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let st = self.state.borrow();
+        f.debug_struct("Action")
+            .field("label", &st.label)
+            .field("shortcut", &st.shortcut)
+            .field("shortcut_id", &st.shortcut_id)
+            .field("enabled", &st.enabled)
+            .field(
+                "menu_items",
+                &st.menu_items.iter().map(|(_, p)| p).collect::<Vec<_>>(),
+            )
+            .field("buttons", &st.buttons.len())
+            .finish()
+    }
+}
+
+impl Action {
+    /// Creates an enabled action, the label can mark an accelerator using `&`
+    pub fn new(label: &str) -> Action {
+        Action {
+            state: Rc::new(RefCell::new(ActionState {
+                label: label.to_string(),
+                icon: None,
+                shortcut: Shortcut::None,
+                shortcut_id: None,
+                enabled: true,
+                callback: None,
+                menu_items: vec![],
+                buttons: vec![],
+            })),
+        }
+    }
+
+    /// Returns the label
+    pub fn label(&self) -> String {
+        self.state.borrow().label.clone()
+    }
+
+    /// Sets the label of the action and of its menu items, and the tooltip of its buttons
+    pub fn set_label(&mut self, label: &str) {
+        let mut st = self.state.borrow_mut();
+        st.label = label.to_string();
+        st.menu_items.retain(|(m, _)| !m.was_deleted());
+        for (menu, path) in &mut st.menu_items {
+            if let Some(mut item) = menu.find_item(path) {
+                item.set_label(label);
+                *path = relabeled_path(path, label);
+            }
+            menu.redraw();
+        }
+        st.buttons.retain(|b| !b.was_deleted());
+        for b in &mut st.buttons {
+            b.set_tooltip(&plain_label(label));
+        }
+    }
+
+    /// Sets the icon shown by the buttons of the action
+    pub fn set_icon<I: ImageExt + Clone + 'static>(&mut self, icon: Option<I>) {
+        // set_image() needs a sized widget, so the image is set like it does through the raw pointer
+        let setter: Option<IconSetter> = icon.map(|icon| {
+            Rc::new(move |b: &mut dyn ButtonExt| unsafe {
+                let mut icon = icon.clone();
+                icon.increment_arc();
+                fltk_sys::widget::Fl_Widget_set_image(
+                    b.as_widget_ptr(),
+                    icon.as_image_ptr() as *mut _,
+                )
+            }) as IconSetter
+        });
+        let mut st = self.state.borrow_mut();
+        st.buttons.retain(|b| !b.was_deleted());
+        for b in &mut st.buttons {
+            match &setter {
+                Some(setter) => setter(&mut **b),
+                None => unsafe {
+                    fltk_sys::widget::Fl_Widget_set_image(b.as_widget_ptr(), std::ptr::null_mut())
+                },
+            }
+            b.redraw();
+        }
+        st.icon = setter;
+    }
+
+    /// Returns the shortcut
+    pub fn shortcut(&self) -> Shortcut {
+        self.state.borrow().shortcut
+    }
+
+    /// Sets the shortcut of the action, its menu items and buttons, and of its app::Shortcuts binding if registered.
+    /// Fails if the shortcut is bound to another id in app::Shortcuts
+    pub fn set_shortcut(&mut self, shortcut: Shortcut) -> Result<(), FltkError> {
+        let id = self.state.borrow().shortcut_id.clone();
+        if let Some(id) = id {
+            app::Shortcuts::rebind(&id, shortcut)?;
+        }
+        let mut st = self.state.borrow_mut();
+        st.shortcut = shortcut;
+        st.menu_items.retain(|(m, _)| !m.was_deleted());
+        for (menu, path) in &mut st.menu_items {
+            let _ = menu.set_item_shortcut(path, shortcut);
+        }
+        st.buttons.retain(|b| !b.was_deleted());
+        for b in &mut st.buttons {
+            b.set_shortcut(shortcut);
+        }
+        Ok(())
+    }
+
+    /// Sets the callback run when the action is triggered
+    pub fn set_callback<F: FnMut() + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().callback = Some(Rc::new(RefCell::new(cb)));
+    }
+
+    /// Returns a callback triggering the action while it's alive, for the widgets of the action
+    fn weak_trigger(&self) -> impl FnMut() + 'static {
+        let state: Weak<RefCell<ActionState>> = Rc::downgrade(&self.state);
+        move || {
+            if let Some(state) = state.upgrade() {
+                Action { state }.trigger();
+            }
+        }
+    }
+
+    /// Runs the callback if the action is enabled
+    pub fn trigger(&self) {
+        let cb = {
+            let st = self.state.borrow();
+            if !st.enabled {
+                return;
+            }
+            st.callback.clone()
+        };
+        if let Some(cb) = cb {
+            (*cb.borrow_mut())();
+        }
+    }
+
+    /// Returns whether the action is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.state.borrow().enabled
+    }
+
+    /// Enables or disables the action, activating or graying out its menu items and buttons
+    pub fn set_enabled(&mut self, flag: bool) {
+        let mut st = self.state.borrow_mut();
+        st.enabled = flag;
+        st.menu_items.retain(|(m, _)| !m.was_deleted());
+        for (menu, path) in &mut st.menu_items {
+            if let Some(mut item) = menu.find_item(path) {
+                if flag {
+                    item.activate();
+                } else {
+                    item.deactivate();
+                }
+            }
+            menu.redraw();
+        }
+        st.buttons.retain(|b| !b.was_deleted());
+        for b in &mut st.buttons {
+            if flag {
+                b.activate();
+            } else {
+                b.deactivate();
+            }
+        }
+    }
+
+    /// Adds a menu item triggering the action, at a path like "File/&Save", taking its shortcut and enabled state
+    pub fn add_to_menu<M: MenuExt + Clone + 'static>(&self, menu: &mut M, path: &str) {
+        let (shortcut, enabled) = {
+            let st = self.state.borrow();
+            (st.shortcut, st.enabled)
+        };
+        menu.add(path, shortcut, MenuFlag::Normal, self.weak_trigger());
+        if !enabled {
+            if let Some(mut item) = menu.find_item(path) {
+                item.deactivate();
+            }
+        }
+        self.state
+            .borrow_mut()
+            .menu_items
+            .push((Box::new(menu.clone()), path.to_string()));
+    }
+
+    /// Sets a button, like a toolbar's tool, to trigger the action, taking its icon, shortcut and enabled state.
+    /// The label of the action is used as the tooltip
+    pub fn attach_button<B: ButtonExt + Clone + 'static>(&self, btn: &mut B) {
+        btn.set_callback(self.weak_trigger());
+        let mut st = self.state.borrow_mut();
+        btn.set_shortcut(st.shortcut);
+        btn.set_tooltip(&plain_label(&st.label));
+        if !st.enabled {
+            btn.deactivate();
+        }
+        if let Some(setter) = &st.icon {
+            setter(btn);
+        }
+        st.buttons.push(Box::new(btn.clone()));
+    }
+
+    /// Registers the shortcut of the action in app::Shortcuts under an id,
+    /// so that it triggers the action when no widget used it.
+    /// Fails if the shortcut is bound to another id
+    pub fn register_shortcut(&self, id: &str) -> Result<(), FltkError> {
+        app::Shortcuts::add(id, self.shortcut(), self.weak_trigger())?;
+        self.state.borrow_mut().shortcut_id = Some(id.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod action {
+    use super::*;
+    #[test]
+    fn labels() {
+        assert_eq!(plain_label("&Save"), "Save");
+        assert_eq!(plain_label("Save && Quit"), "Save & Quit");
+        assert_eq!(relabeled_path("File/&Save", "Save &As"), "File/Save &As");
+        assert_eq!(relabeled_path("Quit", "Exit"), "Exit");
+    }
+}
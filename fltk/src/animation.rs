@@ -0,0 +1,345 @@
+use crate::prelude::*;
+use fltk_sys::fl::*;
+use std::{cell::RefCell, fmt, mem, os::raw, ptr, rc::Rc, time::Instant};
+
+/// The interval between two frames, about 60 frames per second
+const FRAME_INTERVAL: f64 = 1.0 / 60.0;
+
+/// Defines the easing curves, mapping the elapsed fraction of an animation's duration to its progress
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Starts slowly and accelerates
+    EaseIn,
+    /// Starts fast and decelerates
+    EaseOut,
+    /// Accelerates then decelerates
+    EaseInOut,
+    /// Bounces at the end, like a dropped ball
+    Bounce,
+}
+
+impl Easing {
+    /// Returns the progress, from 0 to 1, after a fraction of the duration
+    pub fn apply(self, t: f64) -> f64 {
+        let t = t.max(0.).min(1.);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1. - (1. - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+            Easing::Bounce => {
+                let (n, d) = (7.5625, 2.75);
+                if t < 1. / d {
+                    n * t * t
+                } else if t < 2. / d {
+                    let t = t - 1.5 / d;
+                    n * t * t + 0.75
+                } else if t < 2.5 / d {
+                    let t = t - 2.25 / d;
+                    n * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / d;
+                    n * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+struct AnimationState {
+    duration: f64,
+    elapsed: f64,
+    last: Option<Instant>,
+    easing: Easing,
+    running: bool,
+    step: Option<Box<dyn FnMut(f64)>>,
+    on_done: Option<Box<dyn FnMut()>>,
+}
+
+thread_local! {
+    static ANIMATIONS: RefCell<Vec<Rc<RefCell<AnimationState>>>> = RefCell::new(Vec::new());
+    static TIMER_ADDED: RefCell<bool> = RefCell::new(false);
+    static PAUSED: RefCell<bool> = RefCell::new(false);
+}
+
+fn add_timer() {
+    let added = TIMER_ADDED.with(|a| mem::replace(&mut *a.borrow_mut(), true));
+    if !added {
+        unsafe { Fl_add_timeout(FRAME_INTERVAL, Some(animation_tick), ptr::null_mut()) }
+    }
+}
+
+/// Advances the running animations, the timer stops once none is left or they're paused
+unsafe extern "C" fn animation_tick(_data: *mut raw::c_void) {
+    let paused = is_paused();
+    let anims: Vec<_> = ANIMATIONS.with(|a| {
+        let mut a = a.borrow_mut();
+        a.retain(|st| st.borrow().running);
+        a.clone()
+    });
+    if !paused {
+        let now = Instant::now();
+        for anim in &anims {
+            Animation::advance(anim, now);
+        }
+    }
+    let running = ANIMATIONS.with(|a| a.borrow().iter().any(|st| st.borrow().running));
+    if paused || !running {
+        TIMER_ADDED.with(|a| *a.borrow_mut() = false);
+    } else {
+        Fl_repeat_timeout(FRAME_INTERVAL, Some(animation_tick), ptr::null_mut());
+    }
+}
+
+/// Pauses or resumes all animations, paused animations resume where they stopped
+pub fn set_paused(flag: bool) {
+    PAUSED.with(|p| *p.borrow_mut() = flag);
+    if !flag {
+        ANIMATIONS.with(|a| {
+            for st in a.borrow().iter() {
+                st.borrow_mut().last = None;
+            }
+        });
+        if ANIMATIONS.with(|a| !a.borrow().is_empty()) {
+            add_timer();
+        }
+    }
+}
+
+/// Returns whether the animations are paused
+pub fn is_paused() -> bool {
+    PAUSED.with(|p| *p.borrow())
+}
+
+/// Interpolates between two integers
+fn lerp(a: i32, b: i32, t: f64) -> i32 {
+    a + ((b - a) as f64 * t).round() as i32
+}
+
+/// Interpolates between two colors
+fn lerp_color(a: (u8, u8, u8), b: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
+    let c = |a: u8, b: u8| lerp(a as i32, b as i32, t).max(0).min(255) as u8;
+    (c(a.0, b.0), c(a.1, b.1), c(a.2, b.2))
+}
+
+/// Redraws the area a widget moved or shrank from, the parent being taken once by the animation
+fn redraw_around<W: WidgetExt>(wid: &mut W, parent: &mut Option<Box<dyn GroupExt>>) {
+    match parent {
+        Some(p) if !p.was_deleted() => p.redraw(),
+        _ => wid.redraw(),
+    }
+}
+
+/// An animation calling a step function with its eased progress, from 0 to 1, on every frame
+/// until its duration elapsed. Clones refer to the same animation
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut panel = group::Pack::new(-150, 0, 150, 300, "");
+/// panel.end();
+/// win.end();
+/// win.show();
+/// let mut slide = animation::move_to(&panel, 0, 0, 0.3, animation::Easing::EaseOut);
+/// slide.set_done_callback(|| println!("Panel shown"));
+/// app.run().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Animation {
+    state: Rc<RefCell<AnimationState>>,
+}
+
+impl fmt::Debug for Animation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let st = self.state.borrow();
+        f.debug_struct("Animation")
+            .field("duration", &st.duration)
+            .field("elapsed", &st.elapsed)
+            .field("easing", &st.easing)
+            .field("running", &st.running)
+            .finish()
+    }
+}
+
+impl Animation {
+    /// Starts an animation lasting a duration in seconds
+    pub fn new<F: FnMut(f64) + 'static>(duration: f64, easing: Easing, step: F) -> Animation {
+        let state = Rc::new(RefCell::new(AnimationState {
+            duration: duration.max(0.),
+            elapsed: 0.,
+            last: None,
+            easing,
+            running: true,
+            step: Some(Box::new(step)),
+            on_done: None,
+        }));
+        ANIMATIONS.with(|a| a.borrow_mut().push(state.clone()));
+        if !is_paused() {
+            add_timer();
+        }
+        Animation { state }
+    }
+
+    fn advance(state: &Rc<RefCell<AnimationState>>, now: Instant) {
+        let (progress, step) = {
+            let mut st = state.borrow_mut();
+            if !st.running {
+                return;
+            }
+            if let Some(last) = st.last {
+                st.elapsed += now.duration_since(last).as_secs_f64();
+            }
+            st.last = Some(now);
+            let t = if st.duration > 0. {
+                st.elapsed / st.duration
+            } else {
+                1.
+            };
+            (t.min(1.), st.step.take())
+        };
+        // the step and done callbacks can stop or restart animations, so they're called without borrowing the state
+        if let Some(mut step) = step {
+            let eased = state.borrow().easing.apply(progress);
+            step(eased);
+            state.borrow_mut().step = Some(step);
+        }
+        if progress >= 1. && state.borrow().running {
+            state.borrow_mut().running = false;
+            let done = state.borrow_mut().on_done.take();
+            if let Some(mut done) = done {
+                done();
+                state.borrow_mut().on_done = Some(done);
+            }
+        }
+    }
+
+    /// Sets the callback called once the animation completes, it isn't called if the animation is stopped
+    pub fn set_done_callback<F: FnMut() + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_done = Some(Box::new(cb));
+    }
+
+    /// Stops the animation where it is
+    pub fn stop(&mut self) {
+        self.state.borrow_mut().running = false;
+    }
+
+    /// Jumps to the end of the animation, completing it
+    pub fn finish(&mut self) {
+        if self.is_running() {
+            let duration = self.state.borrow().duration;
+            self.state.borrow_mut().elapsed = duration;
+            self.state.borrow_mut().last = None;
+            Animation::advance(&self.state, Instant::now());
+        }
+    }
+
+    /// Returns whether the animation is running, paused animations being still running
+    pub fn is_running(&self) -> bool {
+        self.state.borrow().running
+    }
+
+    /// Returns the elapsed fraction of the duration, from 0 to 1
+    pub fn progress(&self) -> f64 {
+        let st = self.state.borrow();
+        if st.duration > 0. {
+            (st.elapsed / st.duration).min(1.)
+        } else {
+            1.
+        }
+    }
+}
+
+/// Moves a widget from its position to another over a duration in seconds
+pub fn move_to<W: WidgetExt + Clone + 'static>(
+    wid: &W,
+    x: i32,
+    y: i32,
+    duration: f64,
+    easing: Easing,
+) -> Animation {
+    let (x0, y0) = (wid.x(), wid.y());
+    let mut w = wid.clone();
+    let mut parent = wid.parent();
+    Animation::new(duration, easing, move |t| {
+        if !w.was_deleted() {
+            w.set_pos(lerp(x0, x, t), lerp(y0, y, t));
+            redraw_around(&mut w, &mut parent);
+        }
+    })
+}
+
+/// Resizes a widget from its size to another over a duration in seconds, keeping its position
+pub fn resize_to<W: WidgetExt + Clone + 'static>(
+    wid: &W,
+    width: i32,
+    height: i32,
+    duration: f64,
+    easing: Easing,
+) -> Animation {
+    let (w0, h0) = (wid.width(), wid.height());
+    let mut w = wid.clone();
+    let mut parent = wid.parent();
+    Animation::new(duration, easing, move |t| {
+        if !w.was_deleted() {
+            let (x, y) = (w.x(), w.y());
+            w.resize(x, y, lerp(w0, width, t), lerp(h0, height, t));
+            redraw_around(&mut w, &mut parent);
+        }
+    })
+}
+
+/// Changes the color of a widget to another over a duration in seconds
+pub fn color_to<W: WidgetExt + Clone + 'static>(
+    wid: &W,
+    color: Color,
+    duration: f64,
+    easing: Easing,
+) -> Animation {
+    let (from, to) = (wid.color().to_rgb(), color.to_rgb());
+    let mut w = wid.clone();
+    Animation::new(duration, easing, move |t| {
+        if !w.was_deleted() {
+            let (r, g, b) = lerp_color(from, to, t);
+            w.set_color(Color::from_rgb(r, g, b));
+            w.redraw();
+        }
+    })
+}
+
+#[cfg(test)]
+mod animation {
+    use super::*;
+    #[test]
+    fn easings() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::Bounce,
+        ]
+        .iter()
+        {
+            assert!(easing.apply(0.).abs() < 1e-9);
+            assert!((easing.apply(1.) - 1.).abs() < 1e-9);
+            assert!((easing.apply(2.) - 1.).abs() < 1e-9);
+        }
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-9);
+        assert_eq!(lerp(10, 20, 0.25), 13);
+        assert_eq!(lerp(20, 10, 1.), 10);
+        assert_eq!(
+            lerp_color((0, 100, 255), (255, 100, 0), 0.5),
+            (128, 100, 127)
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub use crate::dialog::{beep, BeepType};
 pub use crate::enums::*;
 use crate::prelude::*;
 use crate::window::*;
@@ -11,8 +12,8 @@ use std::{
     os::raw,
     panic, path, ptr,
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Mutex,
+        atomic::{AtomicBool, AtomicI32, Ordering},
+        Arc, Mutex,
     },
     thread, time,
 };
@@ -33,8 +34,23 @@ lazy_static! {
     /// Basically a check for global locking
     static ref IS_INIT: AtomicBool = AtomicBool::new(false);
 
+    /// The exit code set by quit_with_code
+    static ref EXIT_CODE: AtomicI32 = AtomicI32::new(0);
+
+    /// Whether widget bounds are drawn over the windows
+    static ref LAYOUT_DEBUG: AtomicBool = AtomicBool::new(false);
+
+    /// Whether new widgets are laid out right-to-left
+    static ref RTL: AtomicBool = AtomicBool::new(false);
+
+    /// Whether the app was instantiated using App::test_mode()
+    static ref TEST_MODE: AtomicBool = AtomicBool::new(false);
+
     /// The fonts associated with the application
     pub(crate) static ref FONTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// The translator used by tr()
+    static ref TRANSLATOR: Mutex<Option<Arc<dyn Fn(&str) -> String + Send + Sync>>> = Mutex::new(None);
 }
 
 /// Runs the event loop
@@ -43,14 +59,35 @@ pub fn run() -> Result<(), FltkError> {
         if !IS_INIT.load(Ordering::Relaxed) {
             init_all();
         }
-        match Fl_run() {
+        let ret = Fl_run();
+        update_lifecycle();
+        match ret {
             0 => Ok(()),
             _ => Err(FltkError::Internal(FltkErrorKind::FailedToRun)),
         }
     }
 }
 
-/// Locks the main UI thread
+/// Locks the main UI thread.
+/// Calling it once in the main thread before running the event loop enables multithreading support.
+/// Other threads can then wrap widget mutations between app::lock() and app::unlock(), followed by app::awake(),
+/// or schedule them to run on the main thread using app::awake_callback_once()
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "");
+/// win.end();
+/// win.show();
+/// app::lock().unwrap();
+/// std::thread::spawn(move || {
+///     app::lock().unwrap();
+///     frame.set_label("Updated from another thread");
+///     app::unlock();
+///     app::awake();
+/// });
+/// app.run().unwrap();
+/// ```
 pub fn lock() -> Result<(), FltkError> {
     unsafe {
         match Fl_lock() {
@@ -124,6 +161,31 @@ pub fn awake_callback<F: FnMut() + 'static>(cb: F) {
     }
 }
 
+/// Registers a function that will be called once by the main thread during the next message handling cycle,
+/// and freed afterwards. Unlike app::awake_callback(), it can be called from other threads.
+/// It also wakes up the main thread, so it's safe to mutate widgets within the callback
+/// ```no_run
+/// use fltk::*;
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "");
+/// std::thread::spawn(move || {
+///     let result = String::from("Done");
+///     app::awake_callback_once(move || frame.set_label(&result));
+/// });
+/// ```
+pub fn awake_callback_once<F: FnOnce() + Send + 'static>(cb: F) {
+    unsafe {
+        unsafe extern "C" fn shim(data: *mut raw::c_void) {
+            let a: *mut Box<dyn FnOnce()> = data as *mut Box<dyn FnOnce()>;
+            let f = Box::from_raw(a);
+            let _ = panic::catch_unwind(panic::AssertUnwindSafe(move || f()));
+        }
+        let a: *mut Box<dyn FnOnce()> = Box::into_raw(Box::new(Box::new(cb)));
+        let data: *mut raw::c_void = a as *mut raw::c_void;
+        let callback: Fl_Awake_Handler = Some(shim);
+        Fl_awake_callback(callback, data);
+    }
+}
+
 /// Trigger event loop handling in the main thread
 pub fn awake() {
     unsafe { Fl_awake() }
@@ -140,6 +202,25 @@ impl App {
         App {}
     }
 
+    /// Instantiates an App type for testing widgets without a display, e.g. in CI without xvfb.
+    /// FLTK only connects to the display when a window is shown or something is drawn or measured,
+    /// so tests should create widgets without showing their windows and drive them using app::send_event().
+    /// In test mode, send_event() places mouse events at the center of the target widget,
+    /// using the left button, so that pushes and releases land inside it
+    /// ```no_run
+    /// use fltk::*;
+    /// let _app = app::App::test_mode();
+    /// let mut but = button::Button::new(0, 0, 80, 40, "Click");
+    /// but.set_callback(|| println!("clicked"));
+    /// app::send_event(&mut but, Event::Push);
+    /// app::send_event(&mut but, Event::Released);
+    /// ```
+    pub fn test_mode() -> App {
+        init_all();
+        TEST_MODE.store(true, Ordering::Relaxed);
+        App {}
+    }
+
     /// Sets the scheme of the application
     pub fn set_scheme(&mut self, scheme: Scheme) {
         set_scheme(scheme);
@@ -161,12 +242,42 @@ impl App {
         run()
     }
 
+    /// Runs the event loop until app::quit() or app::quit_with_code() is called, or all windows are hidden.
+    /// Returns the exit code passed to app::quit_with_code(), or 0
+    /// ```no_run
+    /// use fltk::*;
+    /// let app = app::App::default();
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// let mut btn = button::Button::new(160, 200, 80, 40, "Quit");
+    /// win.end();
+    /// win.show();
+    /// btn.set_callback(|| app::quit_with_code(2));
+    /// let code = app.run_with_code().unwrap();
+    /// std::process::exit(code);
+    /// ```
+    pub fn run_with_code(self) -> Result<i32, FltkError> {
+        EXIT_CODE.store(0, Ordering::Relaxed);
+        run()?;
+        Ok(exit_code())
+    }
+
+    /// Runs the event loop from within an async executor, see app::run_async()
+    #[cfg(feature = "async")]
+    pub async fn run_async(self) -> Result<(), FltkError> {
+        run_async().await
+    }
+
     /// Wait for incoming messages
     /// Calls to redraw within wait require an explicit sleep
     pub fn wait(self) -> bool {
         wait()
     }
 
+    /// Waits a maximum of `dur` seconds or until "something happens"
+    pub fn wait_for(self, dur: f64) -> Result<(), FltkError> {
+        wait_for(dur)
+    }
+
     /// Loads system fonts
     pub fn load_system_fonts(self) -> Self {
         *FONTS.lock().unwrap() = get_font_names();
@@ -215,6 +326,50 @@ impl App {
     pub fn quit(self) {
         quit()
     }
+
+    /// Quit the application, with the exit code returned by App::run_with_code()
+    pub fn quit_with_code(self, code: i32) {
+        quit_with_code(code)
+    }
+
+    /// Parses the standard FLTK switches (-display, -scheme, -geometry, -title, -bg, -fg...)
+    /// out of a command line which includes the program name, as returned by std::env::args().
+    /// Returns the remaining arguments, excluding the program name, for the application to handle.
+    /// The parsed options are applied when the main window is shown using show_with_args(&[])
+    /// ```no_run
+    /// use fltk::*;
+    /// let app = app::App::default();
+    /// let args: Vec<String> = std::env::args().collect();
+    /// let rest = app.parse_args(&args);
+    /// let verbose = rest.iter().any(|a| a == "--verbose");
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// win.end();
+    /// win.show_with_args(&[]);
+    /// app.run().unwrap();
+    /// ```
+    pub fn parse_args(self, args: &[String]) -> Vec<String> {
+        let mut rest = vec![];
+        if args.is_empty() {
+            return rest;
+        }
+        // FLTK keeps pointers to the switch values, so the strings are leaked
+        let mut v: Vec<*mut raw::c_char> = args
+            .iter()
+            .map(|a| CString::safe_new(a.as_str()).into_raw() as *mut raw::c_char)
+            .collect();
+        v.push(ptr::null_mut());
+        let mut v = mem::ManuallyDrop::new(v);
+        let argc = args.len() as i32;
+        let mut i: i32 = 1;
+        while i < argc {
+            let consumed = unsafe { Fl_arg(argc, v.as_mut_ptr(), &mut i) };
+            if consumed == 0 {
+                rest.push(args[i as usize].clone());
+                i += 1;
+            }
+        }
+        rest
+    }
 }
 
 /// Set the application's scrollbar size
@@ -268,6 +423,14 @@ pub fn event_key() -> Key {
     }
 }
 
+/// Returns the original key pressed, before any modifiers (like NumLock) were applied
+pub fn event_original_key() -> Key {
+    unsafe {
+        let x = Fl_event_original_key();
+        mem::transmute(x)
+    }
+}
+
 /// Returns whether the  key is pressed or held down during the last event
 pub fn event_key_down(key: Key) -> bool {
     unsafe { Fl_event_key_down(mem::transmute(key)) != 0 }
@@ -287,6 +450,112 @@ pub fn event_text() -> String {
     }
 }
 
+/// Parses the text of a file drop, a list of paths or of file URIs depending on the platform
+fn dropped_paths(text: &str) -> Vec<path::PathBuf> {
+    fn hex(c: u8) -> Option<u8> {
+        (c as char).to_digit(16).map(|d| d as u8)
+    }
+    text.lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| match l.strip_prefix("file://") {
+            Some(uri) => {
+                // skip the host, usually empty or localhost
+                let uri = &uri[uri.find('/').unwrap_or(0)..];
+                let bytes = uri.as_bytes();
+                let mut decoded = Vec::with_capacity(bytes.len());
+                let mut i = 0;
+                while i < bytes.len() {
+                    match (bytes[i], bytes.get(i + 1), bytes.get(i + 2)) {
+                        (b'%', Some(&h), Some(&l)) if hex(h).is_some() && hex(l).is_some() => {
+                            decoded.push(hex(h).unwrap() * 16 + hex(l).unwrap());
+                            i += 3;
+                        }
+                        (c, _, _) => {
+                            decoded.push(c);
+                            i += 1;
+                        }
+                    }
+                }
+                let path = String::from_utf8_lossy(&decoded).to_string();
+                // file:///C:/dir on Windows
+                let drive = path.as_bytes().get(2) == Some(&b':')
+                    && path.as_bytes()[1].is_ascii_alphabetic();
+                path::PathBuf::from(if drive { &path[1..] } else { &path })
+            }
+            None => path::PathBuf::from(l),
+        })
+        .collect()
+}
+
+/// Returns the paths of the files dropped onto a widget, during the Event::Paste following an Event::DndRelease
+/// ```no_run
+/// use fltk::*;
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "Drop files here");
+/// let mut released = false;
+/// frame.handle(move |ev| match ev {
+///     Event::DndEnter | Event::DndDrag | Event::DndLeave => true,
+///     Event::DndRelease => {
+///         released = true;
+///         true
+///     }
+///     Event::Paste if released => {
+///         released = false;
+///         println!("{:?}", app::event_paths());
+///         true
+///     }
+///     _ => false,
+/// });
+/// ```
+pub fn event_paths() -> Vec<path::PathBuf> {
+    dropped_paths(&event_text())
+}
+
+/// Checks whether the current key event should be inserted as text by a text editing widget.
+/// Returns the number of bytes preceding the insertion point which should be deleted before
+/// inserting app::event_text(), this supports composed characters, dead keys and input methods (IME).
+/// Returns None if the event should not be treated as text insertion.
+/// Custom widgets can use draw::set_spot() to position the input method's candidate window
+/// ```no_run
+/// use fltk::*;
+/// let mut frame = frame::Frame::new(0, 0, 400, 100, "");
+/// let mut txt = String::new();
+/// frame.handle2(move |f, ev| match ev {
+///     Event::KeyDown => {
+///         if let Some(del) = app::compose() {
+///             for _ in 0..del {
+///                 txt.pop();
+///             }
+///             txt.push_str(&app::event_text());
+///             f.set_label(&txt);
+///             true
+///         } else {
+///             false
+///         }
+///     }
+///     _ => false,
+/// });
+/// ```
+pub fn compose() -> Option<i32> {
+    unsafe {
+        let mut del = 0;
+        if Fl_compose(&mut del) != 0 {
+            Some(del)
+        } else {
+            None
+        }
+    }
+}
+
+/// Resets the compose state, should be called when the insertion point changes or focus is lost
+pub fn compose_reset() {
+    unsafe { Fl_compose_reset() }
+}
+
+/// Returns whether text is currently being composed, i.e. using an input method
+pub fn compose_state() -> bool {
+    unsafe { Fl_compose_state() != 0 }
+}
+
 /// Returns the captured button event
 /// 1 for left key, 2 for middle, 3 for right
 pub fn event_button() -> i32 {
@@ -298,11 +567,39 @@ pub fn event_mouse_button() -> Mouse {
     unsafe { mem::transmute(Fl_event_button()) }
 }
 
-/// Returns the number of clicks
+/// Returns whether the event is a multi-click (double-click or more)
 pub fn event_clicks() -> bool {
     unsafe { Fl_event_clicks() != 0 }
 }
 
+/// Returns the number of consecutive clicks minus one,
+/// 0 for a single click, 1 for a double-click and so on
+pub fn event_clicks_num() -> i32 {
+    unsafe { Fl_event_clicks() }
+}
+
+/// Sets the number of consecutive clicks minus one.
+/// Setting it to 0 prevents the next click from being counted as a double-click
+pub fn set_event_clicks(i: i32) {
+    unsafe { Fl_set_event_clicks(i) }
+}
+
+/// Returns whether the current Push/Released event is a double-click
+/// ```no_run
+/// use fltk::*;
+/// let mut b = browser::HoldBrowser::new(0, 0, 400, 300, "");
+/// b.handle2(|b, ev| match ev {
+///     Event::Released if app::is_double_click() => {
+///         println!("activated line {}", b.value());
+///         true
+///     }
+///     _ => false,
+/// });
+/// ```
+pub fn is_double_click() -> bool {
+    unsafe { Fl_event_clicks() == 1 }
+}
+
 /// Gets the x coordinate of the mouse in the window
 pub fn event_x() -> i32 {
     unsafe { Fl_event_x() }
@@ -323,16 +620,42 @@ pub fn event_y_root() -> i32 {
     unsafe { Fl_event_y_root() }
 }
 
-/// Gets the difference in x axis of the mouse coordinates from the screen to the window
+/// Gets the horizontal scroll amount of a MouseWheel event,
+/// positive when scrolling right and negative when scrolling left.
+/// Touchpads report finer-grained (and possibly several) values per gesture
 pub fn event_dx() -> i32 {
     unsafe { Fl_event_dx() }
 }
 
-/// Gets the difference in y axis of the mouse coordinates from the screen to the window
+/// Gets the vertical scroll amount of a MouseWheel event,
+/// positive when scrolling down and negative when scrolling up
+/// ```no_run
+/// use fltk::*;
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "");
+/// let mut offset = 0;
+/// frame.handle2(move |_, ev| match ev {
+///     Event::MouseWheel => {
+///         offset += app::event_dy();
+///         true
+///     }
+///     _ => false,
+/// });
+/// ```
 pub fn event_dy() -> i32 {
     unsafe { Fl_event_dy() }
 }
 
+/// Returns the zoom step of a MouseWheel event when the Ctrl key is held,
+/// which is also how touchpad pinches are delivered on Windows.
+/// Positive values zoom in and negative values zoom out, None is returned for other events
+pub fn event_zoom() -> Option<i32> {
+    if event() == Event::MouseWheel && event_state().contains(Shortcut::Ctrl) {
+        Some(-event_dy())
+    } else {
+        None
+    }
+}
+
 /// Gets the mouse coordinates relative to the screen
 pub fn get_mouse() -> (i32, i32) {
     unsafe {
@@ -348,12 +671,44 @@ pub fn event_coords() -> (i32, i32) {
     unsafe { (Fl_event_x(), Fl_event_y()) }
 }
 
-/// Determines whether an event was a click
+/// Returns the x and y coordinates of the captured event relative to the screen
+pub fn event_coords_root() -> (i32, i32) {
+    unsafe { (Fl_event_x_root(), Fl_event_y_root()) }
+}
+
+/// Returns the x and y coordinates of the captured event relative to the widget's top-left corner.
+/// Useful for hit-testing inside custom widgets
+pub fn event_coords_relative<W: WidgetExt>(wid: &W) -> (i32, i32) {
+    assert!(!wid.was_deleted());
+    let (x, y) = event_coords_root();
+    (x - wid.x_root(), y - wid.y_root())
+}
+
+/// Converts screen coordinates into coordinates relative to the widget's top-left corner
+pub fn screen_to_widget<W: WidgetExt>(wid: &W, x: i32, y: i32) -> (i32, i32) {
+    assert!(!wid.was_deleted());
+    (x - wid.x_root(), y - wid.y_root())
+}
+
+/// Converts coordinates relative to the widget's top-left corner into screen coordinates
+pub fn widget_to_screen<W: WidgetExt>(wid: &W, x: i32, y: i32) -> (i32, i32) {
+    assert!(!wid.was_deleted());
+    (x + wid.x_root(), y + wid.y_root())
+}
+
+/// Determines whether an event was a click,
+/// i.e. the mouse was released without being moved much since the Push
 pub fn event_is_click() -> bool {
     unsafe { Fl_event_is_click() != 0 }
 }
 
-/// Returns the duration of an event
+/// Only accepts false, which prevents the next Push from being counted as part of a multi-click
+pub fn set_event_is_click(flag: bool) {
+    debug_assert!(!flag, "set_event_is_click only accepts false!");
+    unsafe { Fl_set_event_is_click(flag as i32) }
+}
+
+/// Returns the length in bytes of the text returned by app::event_text()
 pub fn event_length() -> u32 {
     unsafe { Fl_event_length() as u32 }
 }
@@ -368,6 +723,23 @@ pub fn screen_size() -> (f64, f64) {
     unsafe { ((Fl_screen_w() as f64 / 0.96), (Fl_screen_h() as f64 / 0.96)) }
 }
 
+/// Returns the number of screens
+pub fn screen_count() -> i32 {
+    unsafe { Fl_screen_count() }
+}
+
+/// Returns the number of the screen containing a point
+pub fn screen_num(x: i32, y: i32) -> i32 {
+    unsafe { Fl_screen_num(x, y) }
+}
+
+/// Returns the work area (x, y, width, height) of a screen, which excludes the task bars and docks
+pub fn screen_work_area(n: i32) -> (i32, i32, i32, i32) {
+    let (mut x, mut y, mut w, mut h) = (0, 0, 0, 0);
+    unsafe { Fl_screen_work_area(&mut x, &mut y, &mut w, &mut h, n) }
+    (x, y, w, h)
+}
+
 /// Used for widgets implementing the InputExt, pastes content from the clipboard
 pub fn paste<T>(widget: &T)
 where
@@ -379,6 +751,135 @@ where
     }
 }
 
+/// Copies text to the clipboard
+pub fn copy(stuff: &str) {
+    unsafe { Fl_copy(stuff.as_ptr() as *const raw::c_char, stuff.len() as i32, 1) }
+}
+
+/// Copies text to the selection buffer, which is pasted using the middle mouse button on X11
+pub fn copy2(stuff: &str) {
+    unsafe { Fl_copy(stuff.as_ptr() as *const raw::c_char, stuff.len() as i32, 0) }
+}
+
+/// Defines the types of data held by the clipboard
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ClipboardContent {
+    /// Plain text
+    Text,
+    /// An image
+    Image,
+}
+
+impl ClipboardContent {
+    /// The type names used by FLTK, Fl::clipboard_plain_text and Fl::clipboard_image
+    fn type_name(self) -> &'static [u8] {
+        match self {
+            ClipboardContent::Text => b"text/plain\0",
+            ClipboardContent::Image => b"image\0",
+        }
+    }
+}
+
+/// Returns whether the clipboard holds data of a type, which other applications may have copied
+pub fn clipboard_contains(content: ClipboardContent) -> bool {
+    unsafe { Fl_clipboard_contains(content.type_name().as_ptr() as *const raw::c_char) != 0 }
+}
+
+/// Converts pixels of any depth to RGB, compositing the transparent ones over white
+pub(crate) fn rgb_pixels(data: &[u8], depth: u32) -> Vec<u8> {
+    let blend = |c: u8, a: u8| ((c as u32 * a as u32 + 255 * (255 - a as u32)) / 255) as u8;
+    match depth {
+        0 | 1 => data.iter().flat_map(|l| vec![*l, *l, *l]).collect(),
+        2 => data
+            .chunks_exact(2)
+            .flat_map(|p| {
+                let l = blend(p[0], p[1]);
+                vec![l, l, l]
+            })
+            .collect(),
+        4 => data
+            .chunks_exact(4)
+            .flat_map(|p| vec![blend(p[0], p[3]), blend(p[1], p[3]), blend(p[2], p[3])])
+            .collect(),
+        _ => data.to_vec(),
+    }
+}
+
+/// Copies an image to the clipboard, so that it can be pasted in other applications
+/// ```no_run
+/// use fltk::*;
+/// let img = image::PngImage::load("screenshot.png").unwrap();
+/// app::copy_image(&img).unwrap();
+/// ```
+pub fn copy_image<I: ImageExt>(img: &I) -> Result<(), FltkError> {
+    let rgb = img.to_rgb()?;
+    let (w, h) = (rgb.data_w(), rgb.data_h());
+    let mut data = rgb_pixels(&rgb.to_rgb_data(), rgb.depth());
+    if data.len() < (w * h * 3) as usize {
+        return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+    }
+    data.truncate((w * h * 3) as usize);
+    unsafe { Fl_copy_image(data.as_ptr(), w as i32, h as i32, 1) }
+    Ok(())
+}
+
+/// Requests pasting an image from the clipboard into a widget, which receives an Event::Paste
+/// if the clipboard holds an image. The image is then returned by event_clipboard_image() in the widget's handler
+/// ```no_run
+/// use fltk::*;
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "");
+/// frame.handle2(|f, ev| match ev {
+///     Event::Paste => {
+///         if let Some(img) = app::event_clipboard_image() {
+///             f.set_image(Some(img));
+///             f.redraw();
+///         }
+///         true
+///     }
+///     Event::KeyDown if app::event_key() == Key::from_char('v') && app::is_event_ctrl() => {
+///         app::paste_image(f);
+///         true
+///     }
+///     _ => false,
+/// });
+/// ```
+pub fn paste_image<T: WidgetBase>(widget: &T) {
+    assert!(!widget.was_deleted());
+    unsafe { Fl_paste_image(widget.as_widget_ptr() as *mut fltk_sys::fl::Fl_Widget, 1) }
+}
+
+/// Returns the type of the data being pasted during an Event::Paste
+pub fn event_clipboard_content() -> Option<ClipboardContent> {
+    unsafe {
+        let typ = Fl_event_clipboard_type();
+        if typ.is_null() {
+            return None;
+        }
+        let typ = CStr::from_ptr(typ).to_bytes_with_nul();
+        [ClipboardContent::Text, ClipboardContent::Image]
+            .iter()
+            .copied()
+            .find(|c| c.type_name() == typ)
+    }
+}
+
+/// Returns the image being pasted during an Event::Paste, its text being returned by event_text()
+pub fn event_clipboard_image() -> Option<crate::image::RgbImage> {
+    if event_clipboard_content() != Some(ClipboardContent::Image) {
+        return None;
+    }
+    unsafe {
+        let img = Fl_event_clipboard();
+        if img.is_null() {
+            None
+        } else {
+            Some(crate::image::RgbImage::from_image_ptr(
+                img as *mut fltk_sys::image::Fl_Image,
+            ))
+        }
+    }
+}
+
 /// Sets the callback of a widget
 pub fn set_callback<F, W>(widget: &mut W, cb: F)
 where
@@ -449,6 +950,53 @@ pub fn set_font(new_font: Font) {
     }
 }
 
+/// Get the app's font
+pub fn font() -> Font {
+    let f = CURRENT_FONT.lock().unwrap();
+    unsafe { mem::transmute(*f) }
+}
+
+/// Set the app's default font size, which is used by widgets created afterwards
+pub fn set_font_size(sz: u32) {
+    unsafe { Fl_set_font_size(sz as i32) }
+}
+
+/// Get the app's default font size
+pub fn font_size() -> u32 {
+    unsafe { Fl_font_size() as u32 }
+}
+
+/// Defines application wide options
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AppOption {
+    /// Allows navigating between widgets using the arrow keys
+    ArrowFocus = 0,
+    /// Draws the focus box around the focused widget
+    VisibleFocus,
+    /// Allows text to be dragged and dropped from text widgets
+    DndText,
+    /// Shows tooltips
+    ShowTooltips,
+    /// Uses the GTK file chooser in the native file dialog when available
+    FnfcUsesGtk,
+}
+
+/// Gets the value of an application wide option
+pub fn option(opt: AppOption) -> bool {
+    unsafe { Fl_option(opt as i32) != 0 }
+}
+
+/// Sets the value of an application wide option, should be called before creating widgets
+/// ```no_run
+/// use fltk::*;
+/// app::set_option(app::AppOption::ArrowFocus, false);
+/// app::set_font_size(16);
+/// ```
+pub fn set_option(opt: AppOption, val: bool) {
+    unsafe { Fl_set_option(opt as i32, val as i32) }
+}
+
 /// Get the font's name
 pub fn get_font(font: Font) -> String {
     unsafe {
@@ -519,8 +1067,78 @@ pub fn wait() -> bool {
         if !IS_INIT.load(Ordering::Relaxed) {
             init_all();
         }
-        Fl_wait() != 0
+        let ret = Fl_wait() != 0;
+        update_lifecycle();
+        ret
+    }
+}
+
+/// Processes the pending events without blocking, returns false when no windows are shown anymore.
+/// Useful to drive the event loop from another loop or an async executor
+/// ```no_run
+/// use fltk::*;
+/// let _app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// win.end();
+/// win.show();
+/// while app::poll_events() {
+///     // do other work
+///     app::sleep(0.016);
+/// }
+/// ```
+pub fn poll_events() -> bool {
+    unsafe {
+        if !IS_INIT.load(Ordering::Relaxed) {
+            init_all();
+        }
+        Fl_check() != 0
+    }
+}
+
+/// Runs the event loop from within an async executor, yielding to the other tasks between event polls.
+/// Each poll waits up to 10ms for events, so the other tasks of the thread still run when the app is idle without the loop spinning.
+/// The future resolves when all windows are hidden or app::quit() is called.
+/// It should be awaited on the main thread, e.g. in a single threaded tokio runtime
+/// ```no_run
+/// use fltk::*;
+/// async fn gui() {
+///     let _app = app::App::default();
+///     let mut win = window::Window::new(100, 100, 400, 300, "");
+///     win.end();
+///     win.show();
+///     app::run_async().await.unwrap();
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub async fn run_async() -> Result<(), FltkError> {
+    struct YieldNow(bool);
+
+    impl std::future::Future for YieldNow {
+        type Output = ();
+
+        fn poll(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<()> {
+            if self.0 {
+                std::task::Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        }
+    }
+
+    loop {
+        // block a little while waiting for events instead of spinning between polls
+        wait_for(0.01)?;
+        if unsafe { Fl_first_window().is_null() } {
+            break;
+        }
+        YieldNow(false).await;
     }
+    Ok(())
 }
 
 /// Put the thread to sleep for `dur` seconds
@@ -545,13 +1163,107 @@ pub fn add_idle<F: FnMut() + 'static>(cb: F) {
     }
 }
 
+#[derive(Default)]
+struct Lifecycle {
+    check_added: bool,
+    had_windows: bool,
+    on_start: Vec<Box<dyn FnOnce()>>,
+    on_last_window_closed: Vec<Box<dyn FnMut()>>,
+}
+
+thread_local! {
+    static LIFECYCLE: std::cell::RefCell<Lifecycle> = std::cell::RefCell::new(Lifecycle::default());
+}
+
+/// Runs the pending lifecycle callbacks according to whether windows are shown
+fn update_lifecycle() {
+    let shown = unsafe { !Fl_first_window().is_null() };
+    let (start, closed) = LIFECYCLE.with(|l| {
+        let mut l = l.borrow_mut();
+        let start = if shown {
+            mem::take(&mut l.on_start)
+        } else {
+            vec![]
+        };
+        let closed = if l.had_windows && !shown {
+            mem::take(&mut l.on_last_window_closed)
+        } else {
+            vec![]
+        };
+        l.had_windows = shown;
+        (start, closed)
+    });
+    for cb in start {
+        let _ = panic::catch_unwind(panic::AssertUnwindSafe(cb));
+    }
+    if closed.is_empty() {
+        return;
+    }
+    let mut closed = closed;
+    for cb in closed.iter_mut() {
+        let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| cb()));
+    }
+    // Callbacks registered while running the others are kept after the existing ones
+    LIFECYCLE.with(|l| {
+        let mut l = l.borrow_mut();
+        closed.append(&mut l.on_last_window_closed);
+        l.on_last_window_closed = closed;
+    });
+}
+
+fn add_lifecycle_check() {
+    let added = LIFECYCLE.with(|l| mem::replace(&mut l.borrow_mut().check_added, true));
+    if added {
+        return;
+    }
+    unsafe extern "C" fn shim(_data: *mut raw::c_void) {
+        update_lifecycle();
+    }
+    unsafe { Fl_add_check(Some(shim), ptr::null_mut()) }
+}
+
+/// Registers a callback which runs once, in the event loop, as soon as a window is shown.
+/// It's the place for initialization which requires a shown window, like using a GlWindow's context or raw handles.
+/// If a window is already shown, the callback runs in the next event loop iteration
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// win.end();
+/// win.show();
+/// app::on_start(move || println!("{:?}", win.raw_handle()));
+/// app.run().unwrap();
+/// ```
+pub fn on_start<F: FnOnce() + 'static>(cb: F) {
+    LIFECYCLE.with(|l| l.borrow_mut().on_start.push(Box::new(cb)));
+    add_lifecycle_check();
+}
+
+/// Registers a callback which runs each time the last shown window is closed or hidden,
+/// which is also when app::run() returns
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// app::on_last_window_closed(|| println!("Saving settings"));
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// win.end();
+/// win.show();
+/// app.run().unwrap();
+/// ```
+pub fn on_last_window_closed<F: FnMut() + 'static>(cb: F) {
+    LIFECYCLE.with(|l| l.borrow_mut().on_last_window_closed.push(Box::new(cb)));
+    add_lifecycle_check();
+}
+
 /// Waits a maximum of `dur` seconds or until "something happens".
 pub fn wait_for(dur: f64) -> Result<(), FltkError> {
     unsafe {
         if !IS_INIT.load(Ordering::Relaxed) {
             init_all();
         }
-        if Fl_wait_for(dur) >= 0.0 {
+        let ret = Fl_wait_for(dur);
+        update_lifecycle();
+        if ret >= 0.0 {
             Ok(())
         } else {
             Err(FltkError::Unknown(String::from(
@@ -698,6 +1410,17 @@ pub fn quit() {
     }
 }
 
+/// Quit the app, with the exit code returned by App::run_with_code()
+pub fn quit_with_code(code: i32) {
+    EXIT_CODE.store(code, Ordering::Relaxed);
+    quit();
+}
+
+/// Returns the exit code set by the last call to app::quit_with_code()
+pub fn exit_code() -> i32 {
+    EXIT_CODE.load(Ordering::Relaxed)
+}
+
 /// Adds a one-shot timeout callback. The timeout duration `tm` is indicated in seconds
 pub fn add_timeout<F: FnMut() + 'static>(tm: f64, cb: F) {
     unsafe {
@@ -791,11 +1514,18 @@ pub fn belowmouse<Wid: WidgetExt>() -> Option<impl WidgetExt> {
 }
 
 /// Deletes widgets and their children.
+/// The widget is removed from its parent group, and its deletion is scheduled for the next event loop cycle
 pub fn delete_widget<Wid: WidgetBase>(wid: Wid) {
     assert!(!wid.was_deleted());
     WidgetBase::delete(wid)
 }
 
+/// Deletes the widgets scheduled for deletion using app::delete_widget() immediately.
+/// This is also done automatically by the event loop
+pub fn do_widget_deletion() {
+    unsafe { Fl_do_widget_deletion() }
+}
+
 /// Registers all images supported by SharedImage
 pub fn register_images() {
     unsafe { fltk_sys::image::Fl_register_images() }
@@ -842,37 +1572,134 @@ pub fn redraw() {
     unsafe { Fl_redraw() }
 }
 
-/// Returns whether the event is a shift press
-pub fn is_event_shift() -> bool {
-    unsafe { Fl_event_shift() != 0 }
-}
-
-/// Returns whether the event is a control key press
-pub fn is_event_ctrl() -> bool {
-    unsafe { Fl_event_ctrl() != 0 }
+struct RedrawScheduler {
+    max_fps: f64,
+    pending: Vec<crate::widget::Widget>,
+    last_flush: Option<time::Instant>,
+    timer_added: bool,
 }
 
-/// Returns whether the event is a command key press
-pub fn is_event_command() -> bool {
-    unsafe { Fl_event_command() != 0 }
+impl Default for RedrawScheduler {
+    fn default() -> Self {
+        RedrawScheduler {
+            max_fps: 60.,
+            pending: vec![],
+            last_flush: None,
+            timer_added: false,
+        }
+    }
 }
 
-/// Returns whether the event is a alt key press
-pub fn is_event_alt() -> bool {
-    unsafe { Fl_event_alt() != 0 }
+thread_local! {
+    static REDRAWS: std::cell::RefCell<RedrawScheduler> = std::cell::RefCell::new(RedrawScheduler::default());
 }
 
-/// Sets the damage to true or false, illiciting a redraw by the application
-pub fn set_damage(flag: bool) {
-    unsafe { Fl_set_damage(flag as i32) }
+/// Returns the delay before the next flush, so that flushes are at least a frame apart
+fn redraw_delay(max_fps: f64, since_last: Option<f64>) -> f64 {
+    let frame = 1. / max_fps;
+    since_last.map_or(0., |since| (frame - since).max(0.))
 }
 
-/// Returns whether any of the widgets were damaged
-pub fn damage() -> bool {
-    unsafe { Fl_damage() != 0 }
+unsafe extern "C" fn redraw_flush_shim(_data: *mut raw::c_void) {
+    flush_redraws();
 }
 
-/// Sets the visual mode of the application
+/// Requests a redraw of a widget, coalescing the requests made before the next flush so that each widget
+/// is redrawn at most once per frame. Useful when many widgets are updated from a fast data feed
+/// ```no_run
+/// use fltk::*;
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "");
+/// for i in 0..1000 {
+///     frame.set_label(&i.to_string());
+///     app::request_redraw(&frame);
+/// }
+/// ```
+pub fn request_redraw<W: WidgetExt>(wid: &W) {
+    assert!(!wid.was_deleted());
+    let ptr = unsafe { wid.as_widget_ptr() };
+    let delay = REDRAWS.with(|r| {
+        let mut r = r.borrow_mut();
+        if !r
+            .pending
+            .iter()
+            .any(|w| unsafe { w.as_widget_ptr() } == ptr)
+        {
+            // only a new entry is wrapped, since wrapping a widget allocates
+            r.pending
+                .push(unsafe { crate::widget::Widget::from_widget_ptr(ptr) });
+        }
+        if mem::replace(&mut r.timer_added, true) {
+            None
+        } else {
+            let since = r.last_flush.map(|t| t.elapsed().as_secs_f64());
+            Some(redraw_delay(r.max_fps, since))
+        }
+    });
+    if let Some(delay) = delay {
+        unsafe { Fl_add_timeout(delay, Some(redraw_flush_shim), ptr::null_mut()) }
+    }
+}
+
+/// Redraws the widgets whose redraw was requested using request_redraw() without waiting for the next frame
+pub fn flush_redraws() {
+    let pending = REDRAWS.with(|r| {
+        let mut r = r.borrow_mut();
+        if r.timer_added {
+            unsafe { Fl_remove_timeout(Some(redraw_flush_shim), ptr::null_mut()) }
+            r.timer_added = false;
+        }
+        r.last_flush = Some(time::Instant::now());
+        mem::take(&mut r.pending)
+    });
+    for mut w in pending {
+        if !w.was_deleted() {
+            w.redraw();
+        }
+    }
+}
+
+/// Sets the maximum number of times per second the widgets are redrawn by request_redraw(), 60 by default
+pub fn set_max_redraw_rate(fps: f64) {
+    assert!(fps > 0.);
+    REDRAWS.with(|r| r.borrow_mut().max_fps = fps);
+}
+
+/// Returns the maximum number of times per second the widgets are redrawn by request_redraw()
+pub fn max_redraw_rate() -> f64 {
+    REDRAWS.with(|r| r.borrow().max_fps)
+}
+
+/// Returns whether the event is a shift press
+pub fn is_event_shift() -> bool {
+    unsafe { Fl_event_shift() != 0 }
+}
+
+/// Returns whether the event is a control key press
+pub fn is_event_ctrl() -> bool {
+    unsafe { Fl_event_ctrl() != 0 }
+}
+
+/// Returns whether the event is a command key press
+pub fn is_event_command() -> bool {
+    unsafe { Fl_event_command() != 0 }
+}
+
+/// Returns whether the event is a alt key press
+pub fn is_event_alt() -> bool {
+    unsafe { Fl_event_alt() != 0 }
+}
+
+/// Sets the damage to true or false, illiciting a redraw by the application
+pub fn set_damage(flag: bool) {
+    unsafe { Fl_set_damage(flag as i32) }
+}
+
+/// Returns whether any of the widgets were damaged
+pub fn damage() -> bool {
+    unsafe { Fl_damage() != 0 }
+}
+
+/// Sets the visual mode of the application
 pub fn set_visual(mode: Mode) -> Result<(), FltkError> {
     unsafe {
         match Fl_visual(mode.bits() as i32) {
@@ -913,9 +1740,133 @@ pub fn focus() -> Option<impl WidgetExt> {
     }
 }
 
-/// Sets the widget which has focus
+/// Sets the widget which has focus, running the focus callbacks right away
 pub fn set_focus<W: WidgetExt>(wid: &W) {
     unsafe { Fl_set_focus(wid.as_widget_ptr() as *mut raw::c_void) }
+    update_focus();
+}
+
+type FocusCallback = std::rc::Rc<std::cell::RefCell<dyn FnMut()>>;
+type FocusChangedCallback = std::rc::Rc<
+    std::cell::RefCell<dyn FnMut(Option<crate::widget::Widget>, Option<crate::widget::Widget>)>,
+>;
+
+#[derive(Default)]
+struct FocusState {
+    last: Option<crate::widget::Widget>,
+    on_changed: Option<FocusChangedCallback>,
+    // the widget, whether it's run on gaining the focus, and the callback
+    callbacks: Vec<(crate::widget::Widget, bool, FocusCallback)>,
+    check_added: bool,
+}
+
+thread_local! {
+    static FOCUS: std::cell::RefCell<FocusState> = std::cell::RefCell::new(FocusState::default());
+}
+
+/// Runs the focus callbacks if the focus changed since the last check
+fn update_focus() {
+    let ptr = unsafe { Fl_focus() };
+    let ptr_of =
+        |w: &Option<crate::widget::Widget>| w.as_ref().map(|w| unsafe { w.as_widget_ptr() });
+    let (lost, current, to_run, on_changed) = FOCUS.with(|f| {
+        let mut f = f.borrow_mut();
+        let last = f.last.take().filter(|w| !w.was_deleted());
+        let last_ptr = ptr_of(&last).unwrap_or(std::ptr::null_mut());
+        if last_ptr as *mut raw::c_void == ptr as *mut raw::c_void {
+            f.last = last;
+            return (None, None, vec![], None);
+        }
+        // wrapping allocates a tracker, while this runs on every event loop iteration
+        let current = if ptr.is_null() {
+            None
+        } else {
+            Some(unsafe { crate::widget::Widget::from_widget_ptr(ptr as *mut _) })
+        };
+        f.last = current.clone();
+        f.callbacks.retain(|(w, _, _)| !w.was_deleted());
+        let mut to_run: Vec<FocusCallback> = vec![];
+        // the lost callbacks run before the gained ones
+        for gained in [false, true].iter() {
+            let target = ptr_of(if *gained { &current } else { &last });
+            to_run.extend(
+                f.callbacks
+                    .iter()
+                    .filter(|(w, g, _)| g == gained && target == Some(unsafe { w.as_widget_ptr() }))
+                    .map(|(_, _, cb)| cb.clone()),
+            );
+        }
+        (Some(last), current, to_run, f.on_changed.clone())
+    });
+    let lost = match lost {
+        Some(lost) => lost,
+        None => return,
+    };
+    for cb in to_run {
+        (*cb.borrow_mut())();
+    }
+    if let Some(cb) = on_changed {
+        (*cb.borrow_mut())(lost, current);
+    }
+}
+
+fn add_focus_check() {
+    let added = FOCUS.with(|f| mem::replace(&mut f.borrow_mut().check_added, true));
+    if added {
+        return;
+    }
+    FOCUS.with(|f| {
+        f.borrow_mut().last = focus().map(|w| unsafe { w.into_widget() });
+    });
+    unsafe extern "C" fn shim(_data: *mut raw::c_void) {
+        update_focus();
+    }
+    unsafe { Fl_add_check(Some(shim), ptr::null_mut()) }
+}
+
+/// Sets a callback run when the keyboard focus moves, taking the widget which lost the focus and the one which gained it.
+/// The focus changes are checked after the events are handled, or when using app::set_focus()
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// app::set_focus_changed_callback(|lost, gained| {
+///     if let Some(mut w) = lost {
+///         w.set_color(enums::Color::BackGround2);
+///         w.redraw();
+///     }
+///     if let Some(mut w) = gained {
+///         w.set_color(enums::Color::from_u32(0xffffe0));
+///         w.redraw();
+///     }
+/// });
+/// ```
+pub fn set_focus_changed_callback<
+    F: FnMut(Option<crate::widget::Widget>, Option<crate::widget::Widget>) + 'static,
+>(
+    cb: F,
+) {
+    FOCUS.with(|f| f.borrow_mut().on_changed = Some(std::rc::Rc::new(std::cell::RefCell::new(cb))));
+    add_focus_check();
+}
+
+/// Removes the callback set using set_focus_changed_callback()
+pub fn clear_focus_changed_callback() {
+    FOCUS.with(|f| f.borrow_mut().on_changed = None);
+}
+
+/// Sets the callback run when a widget gains or loses the focus, replacing the previous one
+pub(crate) fn set_widget_focus_callback(wid: &dyn WidgetExt, gained: bool, cb: Box<dyn FnMut()>) {
+    let wid = unsafe { crate::widget::Widget::from_widget_ptr(wid.as_widget_ptr()) };
+    FOCUS.with(|f| {
+        let mut f = f.borrow_mut();
+        f.callbacks.retain(|(w, g, _)| {
+            !w.was_deleted()
+                && !(*g == gained && unsafe { w.as_widget_ptr() == wid.as_widget_ptr() })
+        });
+        f.callbacks
+            .push((wid, gained, std::rc::Rc::new(std::cell::RefCell::new(cb))));
+    });
+    add_focus_check();
 }
 
 /// Delays the current thread by millis. Because std::thread::sleep isn't accurate on windows!
@@ -1038,6 +1989,952 @@ pub fn windows() -> Option<Vec<impl WindowExt>> {
     }
 }
 
+/// Returns an iterator over the shown top-level windows, starting with the first_window()
+/// ```no_run
+/// use fltk::*;
+/// for win in app::windows_iter() {
+///     println!("{}", win.label());
+/// }
+/// ```
+pub fn windows_iter() -> impl Iterator<Item = Window> {
+    let first: Option<Window> = first_window().map(|w| unsafe { w.into_widget() });
+    std::iter::successors(first, |win| {
+        next_window(win).map(|w| unsafe { w.into_widget::<Window>() })
+    })
+}
+
+/// Sets the translator used by tr() and the tr! macro, which maps a key to the text of the current language.
+/// Changing it at runtime can be followed by a call to relabel_all()
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// app::set_translator(|key| match key {
+///     "hello" => "Bonjour".to_string(),
+///     _ => key.to_string(),
+/// });
+/// let mut win = window::Window::new(100, 100, 400, 300, &tr!("hello"));
+/// let mut but = button::Button::new(160, 200, 80, 40, "");
+/// app::set_tr_label(&mut but, "quit");
+/// win.show();
+/// app.run().unwrap();
+/// ```
+pub fn set_translator<F: Fn(&str) -> String + Send + Sync + 'static>(f: F) {
+    *TRANSLATOR.lock().unwrap() = Some(Arc::new(f));
+}
+
+/// Removes the translator, tr() then returning its keys
+pub fn clear_translator() {
+    *TRANSLATOR.lock().unwrap() = None;
+}
+
+/// Translates a key using the current translator, returning the key if there's none
+pub fn tr(key: &str) -> String {
+    let translator = TRANSLATOR.lock().unwrap().clone();
+    match translator {
+        Some(f) => f(key),
+        None => key.to_string(),
+    }
+}
+
+/// Translates a key using the current translator, like app::tr()
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::app::tr($key)
+    };
+}
+
+/// The translation keys of a widget's label and tooltip
+type TrKeys = (Option<String>, Option<String>);
+
+thread_local! {
+    /// The keys of the labels and tooltips set using set_tr_label() and set_tr_tooltip()
+    static TR_KEYS: std::cell::RefCell<crate::utils::WidgetMap<TrKeys>> = std::cell::RefCell::new(Default::default());
+}
+
+fn set_tr_key<W: WidgetExt, F: FnOnce(&mut TrKeys)>(wid: &W, f: F) {
+    TR_KEYS.with(|k| {
+        let mut k = k.borrow_mut();
+        match k.get(wid) {
+            Some(keys) => f(keys),
+            None => {
+                let mut keys = TrKeys::default();
+                f(&mut keys);
+                k.insert(wid, keys);
+            }
+        }
+    });
+}
+
+/// Sets the label of a widget to the translation of a key, which relabel_all() translates again
+pub fn set_tr_label<W: WidgetExt>(wid: &mut W, key: &str) {
+    wid.set_label(&tr(key));
+    set_tr_key(wid, |k| k.0 = Some(key.to_string()));
+}
+
+/// Sets the tooltip of a widget to the translation of a key, which relabel_all() translates again
+pub fn set_tr_tooltip<W: WidgetExt>(wid: &mut W, key: &str) {
+    wid.set_tooltip(&tr(key));
+    set_tr_key(wid, |k| k.1 = Some(key.to_string()));
+}
+
+/// Translates again the labels and tooltips set using set_tr_label() and set_tr_tooltip() of a widget
+/// and its children, which is needed for hidden windows, relabel_all() only walking the shown ones
+pub fn relabel(wid: &mut dyn WidgetExt) {
+    let keys = TR_KEYS.with(|k| k.borrow_mut().get(wid).cloned());
+    if let Some((label, tip)) = keys {
+        if let Some(key) = label {
+            wid.set_label(&tr(&key));
+            wid.redraw_label();
+        }
+        if let Some(key) = tip {
+            wid.set_tooltip(&tr(&key));
+        }
+    }
+    if let Some(grp) = wid.as_group() {
+        for i in 0..grp.children() {
+            if let Some(mut child) = grp.child(i) {
+                relabel(&mut *child);
+            }
+        }
+    }
+}
+
+/// Translates again the labels and tooltips set using set_tr_label() and set_tr_tooltip() in the shown windows,
+/// after the language changed using set_translator()
+pub fn relabel_all() {
+    for mut win in windows_iter() {
+        relabel(&mut win);
+        win.redraw();
+    }
+}
+
+struct RegisteredWindow {
+    win: Window,
+    group: Option<String>,
+    data: Option<Box<dyn any::Any>>,
+    on_message: Option<Box<dyn FnMut(&mut Window, &dyn any::Any)>>,
+}
+
+thread_local! {
+    static WINDOWS: std::cell::RefCell<Vec<(String, RegisteredWindow)>> = std::cell::RefCell::new(Vec::new());
+}
+
+/// A registry of the application's windows, looked up by id and optionally sorted in groups,
+/// which helps with document-per-window applications.
+/// Windows are removed from the registry when they're closed using it or deleted
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// for doc in &["a.txt", "b.txt"] {
+///     let mut win = app::Windows::create(doc, Some("documents"), || {
+///         let win = window::Window::new(100, 100, 400, 300, doc);
+///         win.end();
+///         win
+///     });
+///     app::Windows::set_message_handler(doc, |win, msg| {
+///         if let Some(theme) = msg.downcast_ref::<&str>() {
+///             println!("{} switches to the {} theme", win.label(), theme);
+///         }
+///     });
+///     win.show();
+/// }
+/// app::Windows::broadcast(&"dark");
+/// app.run().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Windows {}
+
+impl Windows {
+    fn prune() {
+        WINDOWS.with(|w| w.borrow_mut().retain(|(_, r)| !r.win.was_deleted()));
+    }
+
+    /// Creates a window using a closure, outside of any current group, and registers it
+    pub fn create<W: WindowExt, F: FnOnce() -> W>(id: &str, group: Option<&str>, f: F) -> W {
+        let win = unsafe {
+            let current = fltk_sys::group::Fl_Group_current();
+            fltk_sys::group::Fl_Group_set_current(ptr::null_mut());
+            let win = f();
+            fltk_sys::group::Fl_Group_set_current(current);
+            win
+        };
+        Windows::register(id, group, &win);
+        win
+    }
+
+    /// Registers a window under an id, replacing any window registered with the same id
+    pub fn register<W: WindowExt>(id: &str, group: Option<&str>, win: &W) {
+        assert!(!win.was_deleted());
+        let win = unsafe { Window::from_widget_ptr(win.as_widget_ptr()) };
+        Windows::prune();
+        WINDOWS.with(|w| {
+            let mut w = w.borrow_mut();
+            w.retain(|(i, _)| i != id);
+            w.push((
+                id.to_string(),
+                RegisteredWindow {
+                    win,
+                    group: group.map(|g| g.to_string()),
+                    data: None,
+                    on_message: None,
+                },
+            ));
+        });
+    }
+
+    /// Removes a window from the registry, without closing it
+    pub fn unregister(id: &str) {
+        WINDOWS.with(|w| w.borrow_mut().retain(|(i, _)| i != id));
+    }
+
+    /// Returns the window registered under an id
+    pub fn get(id: &str) -> Option<Window> {
+        Windows::prune();
+        WINDOWS.with(|w| {
+            w.borrow()
+                .iter()
+                .find(|(i, _)| i == id)
+                .map(|(_, r)| r.win.clone())
+        })
+    }
+
+    /// Returns the ids of the registered windows, in registration order
+    pub fn ids() -> Vec<String> {
+        Windows::prune();
+        WINDOWS.with(|w| w.borrow().iter().map(|(i, _)| i.clone()).collect())
+    }
+
+    /// Returns the ids of the windows of a group
+    pub fn group_ids(group: &str) -> Vec<String> {
+        Windows::prune();
+        WINDOWS.with(|w| {
+            w.borrow()
+                .iter()
+                .filter(|(_, r)| r.group.as_deref() == Some(group))
+                .map(|(i, _)| i.clone())
+                .collect()
+        })
+    }
+
+    /// Returns the group of a window
+    pub fn group(id: &str) -> Option<String> {
+        WINDOWS.with(|w| {
+            w.borrow()
+                .iter()
+                .find(|(i, _)| i == id)
+                .and_then(|(_, r)| r.group.clone())
+        })
+    }
+
+    /// Sets the group of a window, None removes it from its group
+    pub fn set_group(id: &str, group: Option<&str>) {
+        WINDOWS.with(|w| {
+            if let Some((_, r)) = w.borrow_mut().iter_mut().find(|(i, _)| i == id) {
+                r.group = group.map(|g| g.to_string());
+            }
+        });
+    }
+
+    /// Sets the user data of a window
+    pub fn set_user_data<T: any::Any>(id: &str, data: T) {
+        WINDOWS.with(|w| {
+            if let Some((_, r)) = w.borrow_mut().iter_mut().find(|(i, _)| i == id) {
+                r.data = Some(Box::new(data));
+            }
+        });
+    }
+
+    /// Returns a copy of the user data of a window, if it's of type T
+    pub fn user_data<T: any::Any + Clone>(id: &str) -> Option<T> {
+        WINDOWS.with(|w| {
+            w.borrow()
+                .iter()
+                .find(|(i, _)| i == id)
+                .and_then(|(_, r)| r.data.as_ref())
+                .and_then(|d| d.downcast_ref::<T>())
+                .cloned()
+        })
+    }
+
+    /// Runs a closure on the user data of a window, if it's of type T, and returns its result
+    pub fn with_user_data<T: any::Any, R, F: FnOnce(&mut T) -> R>(id: &str, f: F) -> Option<R> {
+        // The data is taken out while the closure runs, so the closure can use the registry
+        let mut data = WINDOWS.with(|w| {
+            w.borrow_mut()
+                .iter_mut()
+                .find(|(i, _)| i == id)
+                .and_then(|(_, r)| r.data.take())
+        })?;
+        let ret = data.downcast_mut::<T>().map(f);
+        WINDOWS.with(|w| {
+            if let Some((_, r)) = w.borrow_mut().iter_mut().find(|(i, _)| i == id) {
+                if r.data.is_none() {
+                    r.data = Some(data);
+                }
+            }
+        });
+        ret
+    }
+
+    /// Sets the handler of the messages sent to a window
+    pub fn set_message_handler<F: FnMut(&mut Window, &dyn any::Any) + 'static>(id: &str, cb: F) {
+        WINDOWS.with(|w| {
+            if let Some((_, r)) = w.borrow_mut().iter_mut().find(|(i, _)| i == id) {
+                r.on_message = Some(Box::new(cb));
+            }
+        });
+    }
+
+    /// Sends a message to a window, returns whether the window has a message handler
+    pub fn send<T: any::Any>(id: &str, msg: &T) -> bool {
+        Windows::prune();
+        let handler = WINDOWS.with(|w| {
+            w.borrow_mut()
+                .iter_mut()
+                .find(|(i, _)| i == id)
+                .and_then(|(_, r)| Some((r.win.clone(), r.on_message.take()?)))
+        });
+        let (mut win, mut cb) = match handler {
+            Some(h) => h,
+            None => return false,
+        };
+        cb(&mut win, msg);
+        WINDOWS.with(|w| {
+            if let Some((_, r)) = w.borrow_mut().iter_mut().find(|(i, _)| i == id) {
+                if r.on_message.is_none() {
+                    r.on_message = Some(cb);
+                }
+            }
+        });
+        true
+    }
+
+    /// Sends a message to all the registered windows
+    pub fn broadcast<T: any::Any>(msg: &T) {
+        for id in Windows::ids() {
+            Windows::send(&id, msg);
+        }
+    }
+
+    /// Sends a message to the windows of a group
+    pub fn broadcast_group<T: any::Any>(group: &str, msg: &T) {
+        for id in Windows::group_ids(group) {
+            Windows::send(&id, msg);
+        }
+    }
+
+    /// Hides and deletes a window, removing it from the registry
+    pub fn close(id: &str) {
+        let win = WINDOWS.with(|w| {
+            let mut w = w.borrow_mut();
+            let idx = w.iter().position(|(i, _)| i == id)?;
+            Some(w.remove(idx).1.win)
+        });
+        if let Some(mut win) = win {
+            if !win.was_deleted() {
+                win.hide();
+                Window::delete(win);
+            }
+        }
+    }
+
+    /// Closes the windows of a group
+    pub fn close_group(group: &str) {
+        for id in Windows::group_ids(group) {
+            Windows::close(&id);
+        }
+    }
+}
+
+struct UndoCommand<T> {
+    name: String,
+    merge_key: Option<String>,
+    redo: Box<dyn FnMut(&mut T)>,
+    undo: Box<dyn FnMut(&mut T)>,
+}
+
+/// A stack of undoable commands acting on an application state of type T,
+/// with merging of consecutive commands, tracking of unsaved changes,
+/// and activation of the Undo and Redo menu items
+/// ```no_run
+/// use fltk::*;
+/// use std::{cell::RefCell, rc::Rc};
+/// let mut doc = String::new();
+/// let stack = Rc::new(RefCell::new(app::UndoStack::new()));
+/// let mut menu = menu::MenuBar::new(0, 0, 400, 30, "");
+/// menu.add("Edit/Undo", Shortcut::Ctrl | 'z', menu::MenuFlag::Normal, || ());
+/// menu.add("Edit/Redo", Shortcut::Ctrl | 'y', menu::MenuFlag::Normal, || ());
+/// stack.borrow_mut().bind_menu(&menu, "Edit/Undo", "Edit/Redo");
+/// // typing merges into one command
+/// for c in "hi".chars() {
+///     stack.borrow_mut().push(&mut doc, "Typing", Some("typing"), move |d: &mut String| d.push(c), |d: &mut String| { d.pop(); });
+/// }
+/// assert!(stack.borrow().is_dirty());
+/// stack.borrow_mut().undo(&mut doc);
+/// assert_eq!(doc, "");
+/// ```
+pub struct UndoStack<T> {
+    undo: Vec<UndoCommand<T>>,
+    redo: Vec<UndoCommand<T>>,
+    clean: Option<usize>,
+    limit: Option<usize>,
+    menus: Vec<(Box<dyn MenuExt>, String, String)>,
+}
+
+impl<T> std::fmt::Debug for UndoStack<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UndoStack")
+            .field(
+                "undo",
+                &self.undo.iter().map(|c| &c.name).collect::<Vec<_>>(),
+            )
+            .field(
+                "redo",
+                &self.redo.iter().map(|c| &c.name).collect::<Vec<_>>(),
+            )
+            .field("clean", &self.clean)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<T> Default for UndoStack<T> {
+    fn default() -> Self {
+        UndoStack {
+            undo: vec![],
+            redo: vec![],
+            clean: Some(0),
+            limit: None,
+            menus: vec![],
+        }
+    }
+}
+
+impl<T: 'static> UndoStack<T> {
+    /// Creates an empty stack, in the clean state
+    pub fn new() -> UndoStack<T> {
+        UndoStack::default()
+    }
+
+    /// Runs a command and pushes it, clearing the redo history.
+    /// A command with the same merge key as the last one is merged into it, so that they're undone together, like typed characters
+    pub fn push<R: FnMut(&mut T) + 'static, U: FnMut(&mut T) + 'static>(
+        &mut self,
+        state: &mut T,
+        name: &str,
+        merge_key: Option<&str>,
+        mut redo: R,
+        undo: U,
+    ) {
+        redo(state);
+        self.redo.clear();
+        if self.clean.map_or(false, |c| c > self.undo.len()) {
+            // the clean state was undone and can't be reached anymore
+            self.clean = None;
+        }
+        let mergeable = merge_key.is_some()
+            && self.clean != Some(self.undo.len())
+            && self.undo.last().map(|c| c.merge_key.as_deref()) == Some(merge_key);
+        if mergeable {
+            let last = self.undo.last_mut().unwrap();
+            let mut prev_redo = mem::replace(&mut last.redo, Box::new(|_| ()));
+            let mut prev_undo = mem::replace(&mut last.undo, Box::new(|_| ()));
+            let mut redo = redo;
+            let mut undo = undo;
+            last.redo = Box::new(move |s| {
+                prev_redo(s);
+                redo(s);
+            });
+            last.undo = Box::new(move |s| {
+                undo(s);
+                prev_undo(s);
+            });
+        } else {
+            self.undo.push(UndoCommand {
+                name: name.to_string(),
+                merge_key: merge_key.map(|k| k.to_string()),
+                redo: Box::new(redo),
+                undo: Box::new(undo),
+            });
+            if let Some(limit) = self.limit {
+                if self.undo.len() > limit {
+                    self.undo.remove(0);
+                    self.clean = self.clean.and_then(|c| c.checked_sub(1));
+                }
+            }
+        }
+        self.update_menus();
+    }
+
+    /// Undoes the last command, returns false if there's none
+    pub fn undo(&mut self, state: &mut T) -> bool {
+        match self.undo.pop() {
+            Some(mut cmd) => {
+                (cmd.undo)(state);
+                self.redo.push(cmd);
+                self.update_menus();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redoes the last undone command, returns false if there's none
+    pub fn redo(&mut self, state: &mut T) -> bool {
+        match self.redo.pop() {
+            Some(mut cmd) => {
+                (cmd.redo)(state);
+                self.undo.push(cmd);
+                self.update_menus();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether there's a command to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns whether there's a command to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Returns the name of the command undo() would undo
+    pub fn undo_name(&self) -> Option<String> {
+        self.undo.last().map(|c| c.name.clone())
+    }
+
+    /// Returns the name of the command redo() would redo
+    pub fn redo_name(&self) -> Option<String> {
+        self.redo.last().map(|c| c.name.clone())
+    }
+
+    /// Returns whether the state changed since the last call to set_clean(), like unsaved changes
+    pub fn is_dirty(&self) -> bool {
+        self.clean != Some(self.undo.len())
+    }
+
+    /// Marks the current state as clean, like after saving
+    pub fn set_clean(&mut self) {
+        self.clean = Some(self.undo.len());
+    }
+
+    /// Limits the number of commands which can be undone, None being unlimited
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        if let Some(limit) = limit {
+            let excess = self.undo.len().saturating_sub(limit);
+            self.undo.drain(..excess);
+            self.clean = self.clean.and_then(|c| c.checked_sub(excess));
+            self.update_menus();
+        }
+    }
+
+    /// Removes all the commands, the current state becoming clean
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.clean = Some(0);
+        self.update_menus();
+    }
+
+    /// Activates the Undo and Redo menu items, found by their paths like "Edit/Undo",
+    /// only when there's a command to undo or redo
+    pub fn bind_menu<M: MenuExt + Clone + 'static>(
+        &mut self,
+        menu: &M,
+        undo_path: &str,
+        redo_path: &str,
+    ) {
+        self.menus.push((
+            Box::new(menu.clone()),
+            undo_path.to_string(),
+            redo_path.to_string(),
+        ));
+        self.update_menus();
+    }
+
+    fn update_menus(&mut self) {
+        let (can_undo, can_redo) = (self.can_undo(), self.can_redo());
+        self.menus.retain(|(m, _, _)| !m.was_deleted());
+        for (menu, undo_path, redo_path) in &mut self.menus {
+            for (path, flag) in &[(&*undo_path, can_undo), (&*redo_path, can_redo)] {
+                if let Some(mut item) = menu.find_item(path) {
+                    if *flag {
+                        item.activate();
+                    } else {
+                        item.deactivate();
+                    }
+                }
+            }
+            menu.redraw();
+        }
+    }
+}
+
+struct ShortcutBinding {
+    id: String,
+    shortcut: Shortcut,
+    action: std::rc::Rc<std::cell::RefCell<dyn FnMut()>>,
+    menu_items: Vec<(Box<dyn MenuExt>, String)>,
+}
+
+#[derive(Default)]
+struct ShortcutRegistry {
+    bindings: Vec<ShortcutBinding>,
+    handler_added: bool,
+}
+
+thread_local! {
+    static SHORTCUTS: std::cell::RefCell<ShortcutRegistry> = std::cell::RefCell::new(ShortcutRegistry::default());
+}
+
+unsafe extern "C" fn shortcuts_handler(ev: raw::c_int) -> raw::c_int {
+    if ev != Event::Shortcut as i32 {
+        return 0;
+    }
+    let action = SHORTCUTS.with(|s| {
+        s.borrow()
+            .bindings
+            .iter()
+            .find(|b| Fl_test_shortcut(b.shortcut.bits()) != 0)
+            .map(|b| b.action.clone())
+    });
+    match action {
+        Some(action) => {
+            let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| (*action.borrow_mut())()));
+            1
+        }
+        None => 0,
+    }
+}
+
+/// A registry of the application's keyboard shortcuts, binding an id to a shortcut and an action.
+/// The actions run when no widget used the shortcut, and a shortcut can only be bound once.
+/// Menu items bound to an id take its shortcut, so rebinding it at runtime updates the menus
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut menu = menu::MenuBar::new(0, 0, 400, 30, "");
+/// menu.add("File/Save", Shortcut::None, menu::MenuFlag::Normal, || println!("Saved"));
+/// win.end();
+/// win.show();
+/// app::Shortcuts::add("save", Shortcut::Ctrl | 's', || println!("Saved")).unwrap();
+/// app::Shortcuts::bind_menu_item("save", &menu, "File/Save").unwrap();
+/// // a second binding of Ctrl+S fails
+/// assert!(app::Shortcuts::add("export", Shortcut::Ctrl | 's', || ()).is_err());
+/// app::Shortcuts::rebind("save", Shortcut::Ctrl | Shortcut::Shift | 's').unwrap();
+/// app.run().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Shortcuts {}
+
+impl Shortcuts {
+    fn check_conflict(id: &str, shortcut: Shortcut) -> Result<(), FltkError> {
+        match Shortcuts::bound_to(shortcut) {
+            Some(other) if other != id && shortcut != Shortcut::None => Err(FltkError::Unknown(
+                format!("The shortcut {:?} is already bound to {}", shortcut, other),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Binds an id to a shortcut and an action, replacing the action of an existing id.
+    /// Fails if the shortcut is bound to another id
+    pub fn add<F: FnMut() + 'static>(
+        id: &str,
+        shortcut: Shortcut,
+        action: F,
+    ) -> Result<(), FltkError> {
+        Shortcuts::check_conflict(id, shortcut)?;
+        let action = std::rc::Rc::new(std::cell::RefCell::new(action));
+        let exists = SHORTCUTS.with(|s| {
+            let mut s = s.borrow_mut();
+            match s.bindings.iter_mut().find(|b| b.id == id) {
+                Some(b) => {
+                    b.action = action.clone();
+                    true
+                }
+                None => {
+                    s.bindings.push(ShortcutBinding {
+                        id: id.to_string(),
+                        shortcut,
+                        action: action.clone(),
+                        menu_items: vec![],
+                    });
+                    false
+                }
+            }
+        });
+        if exists {
+            Shortcuts::rebind(id, shortcut)?;
+        }
+        let added = SHORTCUTS.with(|s| mem::replace(&mut s.borrow_mut().handler_added, true));
+        if !added {
+            unsafe { Fl_add_handler(Some(shortcuts_handler)) }
+        }
+        Ok(())
+    }
+
+    /// Changes the shortcut of an id, updating its menu items.
+    /// Fails if the id isn't bound or the shortcut is bound to another id
+    pub fn rebind(id: &str, shortcut: Shortcut) -> Result<(), FltkError> {
+        Shortcuts::check_conflict(id, shortcut)?;
+        SHORTCUTS.with(|s| {
+            let mut s = s.borrow_mut();
+            let b = s
+                .bindings
+                .iter_mut()
+                .find(|b| b.id == id)
+                .ok_or(FltkError::Internal(FltkErrorKind::ResourceNotFound))?;
+            b.shortcut = shortcut;
+            b.menu_items.retain(|(m, _)| !m.was_deleted());
+            for (menu, path) in &mut b.menu_items {
+                // items removed from the menu are skipped
+                let _ = menu.set_item_shortcut(path, shortcut);
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes the binding of an id, clearing the shortcuts of its menu items
+    pub fn remove(id: &str) {
+        let removed = SHORTCUTS.with(|s| {
+            let mut s = s.borrow_mut();
+            let idx = s.bindings.iter().position(|b| b.id == id);
+            idx.map(|idx| s.bindings.remove(idx))
+        });
+        if let Some(mut b) = removed {
+            for (menu, path) in &mut b.menu_items {
+                if !menu.was_deleted() {
+                    let _ = menu.set_item_shortcut(path, Shortcut::None);
+                }
+            }
+        }
+    }
+
+    /// Sets the shortcut of an id on a menu item, found by its path like "File/Save".
+    /// The menu item keeps its own callback, the menu handling the shortcut before the registry
+    pub fn bind_menu_item<M: MenuExt + Clone + 'static>(
+        id: &str,
+        menu: &M,
+        path: &str,
+    ) -> Result<(), FltkError> {
+        let shortcut =
+            Shortcuts::shortcut(id).ok_or(FltkError::Internal(FltkErrorKind::ResourceNotFound))?;
+        let mut menu = menu.clone();
+        menu.set_item_shortcut(path, shortcut)?;
+        SHORTCUTS.with(|s| {
+            if let Some(b) = s.borrow_mut().bindings.iter_mut().find(|b| b.id == id) {
+                b.menu_items.push((Box::new(menu), path.to_string()));
+            }
+        });
+        Ok(())
+    }
+
+    /// Returns the shortcut of an id
+    pub fn shortcut(id: &str) -> Option<Shortcut> {
+        SHORTCUTS.with(|s| {
+            s.borrow()
+                .bindings
+                .iter()
+                .find(|b| b.id == id)
+                .map(|b| b.shortcut)
+        })
+    }
+
+    /// Returns the id a shortcut is bound to
+    pub fn bound_to(shortcut: Shortcut) -> Option<String> {
+        SHORTCUTS.with(|s| {
+            s.borrow()
+                .bindings
+                .iter()
+                .find(|b| b.shortcut == shortcut)
+                .map(|b| b.id.clone())
+        })
+    }
+
+    /// Returns the bound ids and their shortcuts
+    pub fn bindings() -> Vec<(String, Shortcut)> {
+        SHORTCUTS.with(|s| {
+            s.borrow()
+                .bindings
+                .iter()
+                .map(|b| (b.id.clone(), b.shortcut))
+                .collect()
+        })
+    }
+}
+
+/// Enables or disables drawing the bounds and labels of all widgets over the shown windows.
+/// Widgets with a zero width or height are marked with a red cross, which helps finding widgets created with default() but never sized.
+/// The bounds are drawn by an overlay frame added last to each currently shown window, which is removed when it's disabled
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let _btn = button::Button::default().with_label("Where am I?");
+/// win.end();
+/// win.show();
+/// app::set_layout_debug(true);
+/// app.run().unwrap();
+/// ```
+pub fn set_layout_debug(flag: bool) {
+    LAYOUT_DEBUG.store(flag, Ordering::Relaxed);
+    let old = LAYOUT_OVERLAYS.with(|o| mem::take(&mut o.borrow_mut().overlays));
+    for o in old {
+        if !o.was_deleted() {
+            delete_widget(o);
+        }
+    }
+    if flag {
+        for mut win in windows_iter() {
+            let mut o = crate::frame::Frame::default();
+            // an inactive widget doesn't take events, so the widgets below it keep getting them
+            o.deactivate();
+            win.add(&o);
+            o.resize(0, 0, win.width(), win.height());
+            let ptr = unsafe { o.as_widget_ptr() };
+            o.draw(move || unsafe {
+                let font = crate::draw::font();
+                let size = crate::draw::size();
+                crate::draw::set_font(Font::Helvetica, 10);
+                draw_layout_bounds(fltk_sys::widget::Fl_Widget_parent(ptr) as _, ptr);
+                crate::draw::set_font(font, size);
+            });
+            LAYOUT_OVERLAYS.with(|l| l.borrow_mut().overlays.push(o));
+        }
+        let add_check = LAYOUT_OVERLAYS.with(|l| {
+            let mut l = l.borrow_mut();
+            !mem::replace(&mut l.check_added, true)
+        });
+        if add_check {
+            unsafe extern "C" fn shim(_data: *mut raw::c_void) {
+                update_layout_overlays();
+            }
+            unsafe { Fl_add_check(Some(shim), ptr::null_mut()) }
+        }
+    }
+    redraw();
+}
+
+/// Returns whether widget bounds are drawn over the windows
+pub fn layout_debug() -> bool {
+    LAYOUT_DEBUG.load(Ordering::Relaxed)
+}
+
+/// Sets the right-to-left mode, for languages like Arabic or Hebrew, which affects widgets created afterwards:
+/// the position helpers (right_of, left_of, top_left_of, left_of_parent...) are mirrored,
+/// the default label alignments are mirrored, and vertical scrollbars of scrolls, browsers and text widgets are placed on the left.
+/// Menu bars keep their items in FLTK's left-to-right order
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// app::set_rtl(true);
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let name = input::Input::new(0, 0, 200, 30, "الاسم").right_of_parent(100);
+/// // placed to the left of the input
+/// let _ok = button::Button::new(0, 0, 80, 30, "حسنا").right_of(&name, 10);
+/// win.end();
+/// win.show();
+/// app.run().unwrap();
+/// ```
+pub fn set_rtl(flag: bool) {
+    RTL.store(flag, Ordering::Relaxed);
+}
+
+/// Returns whether the right-to-left mode is set
+pub fn is_rtl() -> bool {
+    RTL.load(Ordering::Relaxed)
+}
+
+#[derive(Default)]
+struct LayoutOverlays {
+    check_added: bool,
+    overlays: Vec<crate::frame::Frame>,
+}
+
+thread_local! {
+    static LAYOUT_OVERLAYS: std::cell::RefCell<LayoutOverlays> = std::cell::RefCell::new(LayoutOverlays::default());
+}
+
+/// Keeps the layout debug overlays on top of their windows and redrawn along with them
+fn update_layout_overlays() {
+    LAYOUT_OVERLAYS.with(|l| {
+        let mut l = l.borrow_mut();
+        l.overlays.retain(|o| !o.was_deleted());
+        for o in l.overlays.iter_mut() {
+            unsafe {
+                let win = fltk_sys::widget::Fl_Widget_parent(o.as_widget_ptr())
+                    as *mut fltk_sys::widget::Fl_Widget;
+                if win.is_null() || fltk_sys::widget::Fl_Widget_damage(win) == 0 {
+                    continue;
+                }
+                let (w, h) = (
+                    fltk_sys::widget::Fl_Widget_width(win),
+                    fltk_sys::widget::Fl_Widget_height(win),
+                );
+                if (o.width(), o.height()) != (w, h) {
+                    o.resize(0, 0, w, h);
+                }
+                let grp = win as *mut fltk_sys::group::Fl_Group;
+                let last = fltk_sys::group::Fl_Group_children(grp) - 1;
+                if fltk_sys::group::Fl_Group_child(grp, last) != o.as_widget_ptr() {
+                    fltk_sys::group::Fl_Group_add(grp, o.as_widget_ptr() as _);
+                }
+            }
+            o.redraw();
+        }
+    });
+}
+
+/// Draws the bounds of the group's children recursively, skipping the overlay drawing them
+unsafe fn draw_layout_bounds(
+    grp: *mut fltk_sys::group::Fl_Group,
+    overlay: *mut fltk_sys::widget::Fl_Widget,
+) {
+    use fltk_sys::widget::*;
+    for i in 0..fltk_sys::group::Fl_Group_children(grp) {
+        let c = fltk_sys::group::Fl_Group_child(grp, i);
+        if c.is_null() || c == overlay {
+            continue;
+        }
+        let (x, y, w, h) = (
+            Fl_Widget_x(c),
+            Fl_Widget_y(c),
+            Fl_Widget_width(c),
+            Fl_Widget_height(c),
+        );
+        if w == 0 || h == 0 {
+            crate::draw::set_draw_color(Color::Red);
+            crate::draw::draw_line(x - 4, y - 4, x + 4, y + 4);
+            crate::draw::draw_line(x - 4, y + 4, x + 4, y - 4);
+        } else {
+            crate::draw::draw_rect_with_color(x, y, w, h, Color::Blue);
+            crate::draw::set_draw_color(Color::Blue);
+        }
+        let label = Fl_Widget_label(c);
+        let label = if label.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(label).to_string_lossy().to_string()
+        };
+        let label = if label.is_empty() { format!("{}x{}", w, h) } else { label };
+        crate::draw::draw_text(&label, x + 2, y + 10);
+        if Fl_Widget_as_window(c).is_null() {
+            let g = Fl_Widget_as_group(c);
+            if !g.is_null() {
+                draw_layout_bounds(g as _, overlay);
+            }
+        }
+    }
+}
+
 /// Set the foreground color
 pub fn foreground(r: u8, g: u8, b: u8) {
     unsafe { Fl_foreground(r, g, b) }
@@ -1082,6 +2979,39 @@ pub unsafe fn handle<I: Into<i32>, W: WindowExt>(msg: I, w: &W) -> bool {
     Fl_handle(msg.into(), w.as_widget_ptr() as _) != 0
 }
 
+/// Sends an event directly to a widget's handle method, bypassing the event loop.
+/// Returns whether the widget handled the event.
+/// Widgets can be created and exercised without showing any window,
+/// which allows testing handle and callback logic in CI without a display, see App::test_mode()
+/// ```no_run
+/// use fltk::*;
+/// let _app = app::App::test_mode();
+/// let mut but = button::Button::new(0, 0, 80, 40, "Click");
+/// but.set_callback(|| println!("clicked"));
+/// app::send_event(&mut but, Event::Push);
+/// app::send_event(&mut but, Event::Released);
+/// ```
+pub fn send_event<W: WidgetExt>(w: &mut W, ev: Event) -> bool {
+    assert!(!w.was_deleted());
+    unsafe {
+        if TEST_MODE.load(Ordering::Relaxed) {
+            match ev {
+                Event::Push | Event::Released | Event::Drag | Event::Move | Event::Enter => {
+                    let x = w.x() + w.width() / 2;
+                    let y = w.y() + w.height() / 2;
+                    Fl_set_event_x(x);
+                    Fl_set_event_y(y);
+                    Fl_set_event_x_root(x);
+                    Fl_set_event_y_root(y);
+                    Fl_set_event_button(1);
+                }
+                _ => (),
+            }
+        }
+        fltk_sys::widget::Fl_Widget_handle_event(w.as_widget_ptr(), ev as i32) != 0
+    }
+}
+
 /// Send a signal to the main window
 /// returns false if the event was not handled
 /// ```no_run
@@ -1108,3 +3038,101 @@ pub unsafe fn handle_main<I: Into<i32>>(msg: I) -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod app {
+    use super::*;
+    #[test]
+    fn undo_stack() {
+        let mut n = 0;
+        let mut stack = UndoStack::new();
+        assert!(!stack.is_dirty());
+        stack.push(
+            &mut n,
+            "Add",
+            None,
+            |n: &mut i32| *n += 1,
+            |n: &mut i32| *n -= 1,
+        );
+        stack.set_clean();
+        for _ in 0..3 {
+            stack.push(
+                &mut n,
+                "Double",
+                Some("double"),
+                |n: &mut i32| *n *= 2,
+                |n: &mut i32| *n /= 2,
+            );
+        }
+        assert_eq!(n, 8);
+        assert!(stack.is_dirty());
+        assert!(stack.undo(&mut n));
+        assert_eq!(n, 1);
+        assert!(!stack.is_dirty());
+        assert_eq!(stack.redo_name().as_deref(), Some("Double"));
+        assert!(stack.undo(&mut n));
+        assert_eq!(n, 0);
+        assert!(stack.is_dirty());
+        stack.push(
+            &mut n,
+            "Sub",
+            None,
+            |n: &mut i32| *n -= 1,
+            |n: &mut i32| *n += 1,
+        );
+        assert!(!stack.can_redo());
+        assert!(stack.undo(&mut n));
+        // the clean state was discarded with the redo history
+        assert!(stack.is_dirty());
+        assert!(!stack.undo(&mut n));
+    }
+
+    #[test]
+    fn clipboard_pixels() {
+        assert_eq!(rgb_pixels(&[10, 20], 1), vec![10, 10, 10, 20, 20, 20]);
+        assert_eq!(rgb_pixels(&[0, 0], 2), vec![255, 255, 255]);
+        assert_eq!(
+            rgb_pixels(&[1, 2, 3, 255, 9, 9, 9, 0], 4),
+            vec![1, 2, 3, 255, 255, 255]
+        );
+        assert_eq!(rgb_pixels(&[1, 2, 3], 3), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn file_drops() {
+        let paths = dropped_paths(
+            "file:///home/me/My%20Docs/a.txt\r\n# comment\nfile://localhost/tmp/b\n\nfile:///C:/x%2fy\n/plain/path",
+        );
+        let expected: Vec<path::PathBuf> = vec![
+            "/home/me/My Docs/a.txt".into(),
+            "/tmp/b".into(),
+            "C:/x/y".into(),
+            "/plain/path".into(),
+        ];
+        assert_eq!(paths, expected);
+        assert_eq!(
+            dropped_paths("file:///bad%zz"),
+            vec![path::PathBuf::from("/bad%zz")]
+        );
+    }
+
+    #[test]
+    fn redraw_delays() {
+        assert_eq!(redraw_delay(50., None), 0.);
+        assert!((redraw_delay(50., Some(0.005)) - 0.015).abs() < 1e-9);
+        assert_eq!(redraw_delay(50., Some(1.)), 0.);
+    }
+
+    #[test]
+    fn test_mode_events() {
+        let _app = App::test_mode();
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        let mut but = crate::button::Button::new(100, 50, 80, 40, "Click");
+        let c = count.clone();
+        but.set_callback(move || c.set(c.get() + 1));
+        assert!(send_event(&mut but, Event::Push));
+        assert_eq!(event_x(), 140);
+        assert!(send_event(&mut but, Event::Released));
+        assert_eq!(count.get(), 1);
+    }
+}
@@ -2,9 +2,12 @@ use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::browser::*;
 use std::{
+    cell::RefCell,
     ffi::{CStr, CString},
     mem,
     os::raw,
+    rc::Rc,
+    time::{Duration, Instant},
 };
 
 /// Creates a normal browser
@@ -50,6 +53,15 @@ pub enum BrowserScrollbar {
     BothAlways = 7,
 }
 
+/// Defines how typed text is matched against the items of a browser with type-ahead enabled
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TypeAheadMode {
+    /// Matches items starting with the typed text
+    Prefix,
+    /// Matches items containing the typed text
+    Substring,
+}
+
 /// Creates a select browser
 #[derive(WidgetBase, WidgetExt, BrowserExt, Debug)]
 pub struct SelectBrowser {
@@ -361,3 +373,266 @@ impl CheckBrowser {
         }
     }
 }
+
+type ItemFilter = Box<dyn FnMut(&str) -> bool>;
+
+/// Typed keys further apart than this start a new search
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+#[derive(Default)]
+struct BrowserAssist {
+    type_ahead: Option<TypeAheadMode>,
+    typed: String,
+    last_key: Option<Instant>,
+    filter: Option<ItemFilter>,
+}
+
+thread_local! {
+    /// The type-ahead and filter state of the browsers, shared with their handle methods
+    static BROWSER_ASSISTS: RefCell<crate::utils::WidgetMap<Rc<RefCell<BrowserAssist>>>> = RefCell::new(Default::default());
+}
+
+fn assist<B: BrowserExt>(b: &B) -> Option<Rc<RefCell<BrowserAssist>>> {
+    BROWSER_ASSISTS.with(|m| m.borrow_mut().get(b).cloned())
+}
+
+/// Returns the state of a browser, creating it if needed
+fn assist_or_default<B: BrowserExt>(b: &B) -> Rc<RefCell<BrowserAssist>> {
+    if let Some(a) = assist(b) {
+        return a;
+    }
+    let a = Rc::new(RefCell::new(BrowserAssist::default()));
+    BROWSER_ASSISTS.with(|m| m.borrow_mut().insert(b, a.clone()));
+    a
+}
+
+/// Strips the leading format codes (such as `@b` or `@C1`) from the text of an item
+fn strip_format(text: &str, fc: char) -> &str {
+    let mut s = text;
+    while let Some(rest) = s.strip_prefix(fc) {
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some('.') => return chars.as_str(),
+            Some(c) if c == fc => return rest,
+            Some('B') | Some('C') | Some('F') | Some('S') => {
+                s = chars
+                    .as_str()
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+            }
+            Some(_) => s = chars.as_str(),
+            None => return "",
+        }
+    }
+    s
+}
+
+fn type_ahead_matches(text: &str, query: &str, mode: TypeAheadMode) -> bool {
+    let text = text.to_lowercase();
+    let query = query.to_lowercase();
+    match mode {
+        TypeAheadMode::Prefix => text.starts_with(&query),
+        TypeAheadMode::Substring => text.contains(&query),
+    }
+}
+
+/// Returns the first visible line from `start` on, wrapping around, whose text matches
+fn find_type_ahead<B: BrowserExt>(
+    b: &B,
+    query: &str,
+    mode: TypeAheadMode,
+    start: u32,
+) -> Option<u32> {
+    let size = b.size();
+    let fc = b.format_char();
+    (0..size)
+        .map(|i| (start - 1 + i) % size + 1)
+        .filter(|line| b.line_visible(*line))
+        .find(|line| {
+            b.text(*line)
+                .map(|t| type_ahead_matches(strip_format(&t, fc), query, mode))
+                .unwrap_or(false)
+        })
+}
+
+fn handle_type_ahead<B: BrowserExt>(a: &RefCell<BrowserAssist>, b: &mut B, ev: Event) -> bool {
+    if ev != Event::KeyDown || b.size() == 0 {
+        return false;
+    }
+    let mode = match a.borrow().type_ahead {
+        Some(mode) => mode,
+        None => return false,
+    };
+    let txt = crate::app::event_text();
+    if txt.is_empty()
+        || txt.chars().any(|c| c.is_control())
+        || crate::app::is_event_command()
+        || crate::app::event_state().contains(Shortcut::Alt)
+    {
+        return false;
+    }
+    let query = {
+        let mut a = a.borrow_mut();
+        let now = Instant::now();
+        if a.last_key
+            .map(|t| now - t > TYPE_AHEAD_TIMEOUT)
+            .unwrap_or(true)
+        {
+            a.typed.clear();
+        }
+        a.last_key = Some(now);
+        a.typed.push_str(&txt);
+        a.typed.clone()
+    };
+    if query == " " {
+        // leaves the space key to the browser when no search is under way
+        a.borrow_mut().typed.clear();
+        return false;
+    }
+    let current = b.value();
+    let mut first = query.chars();
+    let single = first.next().map(|c| first.all(|d| d == c)).unwrap_or(false);
+    let found = if query.chars().count() == 1 {
+        find_type_ahead(b, &query, mode, current % b.size() + 1)
+    } else {
+        find_type_ahead(b, &query, mode, current.max(1)).or_else(|| {
+            if single {
+                // repeating a key cycles through the items matching it
+                let c = &query[..query.chars().next().unwrap().len_utf8()];
+                find_type_ahead(b, c, mode, current % b.size() + 1)
+            } else {
+                None
+            }
+        })
+    };
+    if let Some(line) = found {
+        if line != current {
+            b.select(line);
+            b.make_visible(line);
+            b.do_callback();
+        }
+    }
+    true
+}
+
+pub(crate) fn set_type_ahead<B: BrowserExt>(b: &mut B, mode: Option<TypeAheadMode>) {
+    let a = assist_or_default(b);
+    {
+        let mut a = a.borrow_mut();
+        a.type_ahead = mode;
+        a.typed.clear();
+    }
+    // installed every time, so that type-ahead can be enabled again after a handler was set using handle() or handle2()
+    if mode.is_some() {
+        b.handle2(move |b, ev| handle_type_ahead(&a, b, ev));
+    }
+}
+
+pub(crate) fn type_ahead<B: BrowserExt>(b: &B) -> Option<TypeAheadMode> {
+    assist(b).and_then(|a| a.borrow().type_ahead)
+}
+
+/// Shows or hides a line according to the browser's filter, if any
+pub(crate) fn filter_line<B: BrowserExt>(b: &mut B, line: u32) {
+    let a = match assist(b) {
+        Some(a) => a,
+        None => return,
+    };
+    let mut filter = match a.borrow_mut().filter.take() {
+        Some(f) => f,
+        None => return,
+    };
+    apply_filter(b, &mut filter, line);
+    a.borrow_mut().filter = Some(filter);
+}
+
+fn apply_filter<B: BrowserExt>(b: &mut B, filter: &mut ItemFilter, line: u32) {
+    if line == 0 || line > b.size() {
+        return;
+    }
+    let fc = b.format_char();
+    let txt = b.text(line).unwrap_or_default();
+    if filter(strip_format(&txt, fc)) {
+        b.show_line(line);
+    } else {
+        b.hide_line(line);
+    }
+}
+
+pub(crate) fn set_item_filter<B: BrowserExt>(b: &mut B, mut filter: ItemFilter) {
+    for line in 1..=b.size() {
+        apply_filter(b, &mut filter, line);
+    }
+    assist_or_default(b).borrow_mut().filter = Some(filter);
+    b.redraw();
+}
+
+pub(crate) fn clear_item_filter<B: BrowserExt>(b: &mut B) {
+    if let Some(a) = assist(b) {
+        a.borrow_mut().filter = None;
+    }
+    for line in 1..=b.size() {
+        b.show_line(line);
+    }
+    b.redraw();
+}
+
+/// Escapes a CSV field shown as a column, so that it's neither split nor read as format codes
+fn csv_column(field: &str, fc: char, cc: char) -> String {
+    let field = field.replace(|c| c == cc || c == '\n' || c == '\r', " ");
+    if field.starts_with(fc) {
+        format!("{}.{}", fc, field)
+    } else {
+        field
+    }
+}
+
+pub(crate) fn load_csv<B: BrowserExt>(b: &mut B, text: &str, delimiter: char) {
+    let (fc, cc) = (b.format_char(), b.column_char());
+    b.clear();
+    for record in crate::utils::parse_csv(text, delimiter) {
+        let cols: Vec<String> = record.iter().map(|f| csv_column(f, fc, cc)).collect();
+        b.add(&cols.join(&cc.to_string()));
+    }
+    b.redraw();
+}
+
+pub(crate) fn csv_text<B: BrowserExt>(b: &B, delimiter: char) -> String {
+    let (fc, cc) = (b.format_char(), b.column_char());
+    let records: Vec<Vec<String>> = (1..=b.size())
+        .map(|line| {
+            b.text(line)
+                .unwrap_or_default()
+                .split(cc)
+                .map(|col| strip_format(col, fc).to_string())
+                .collect()
+        })
+        .collect();
+    crate::utils::write_csv(&records, delimiter)
+}
+
+#[cfg(test)]
+mod browser {
+    use super::*;
+
+    #[test]
+    fn format_codes() {
+        assert_eq!(strip_format("@b@C1Hello", '@'), "Hello");
+        assert_eq!(strip_format("@.@bHello", '@'), "@bHello");
+        assert_eq!(strip_format("@@home", '@'), "@home");
+        assert_eq!(strip_format("plain", '@'), "plain");
+    }
+
+    #[test]
+    fn csv_columns() {
+        assert_eq!(csv_column("@home", '@', '\t'), "@.@home");
+        assert_eq!(strip_format(&csv_column("@home", '@', '\t'), '@'), "@home");
+        assert_eq!(csv_column("a\tb\nc", '@', '\t'), "a b c");
+    }
+
+    #[test]
+    fn type_ahead_modes() {
+        assert!(type_ahead_matches("Hello", "he", TypeAheadMode::Prefix));
+        assert!(!type_ahead_matches("Hello", "ll", TypeAheadMode::Prefix));
+        assert!(type_ahead_matches("Hello", "LL", TypeAheadMode::Substring));
+    }
+}
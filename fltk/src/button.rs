@@ -1,10 +1,14 @@
+use crate::draw;
 use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::button::*;
 use std::{
+    cell::{Cell, RefCell},
     ffi::{CStr, CString},
-    mem,
+    fmt, mem,
+    ops::{Deref, DerefMut},
     os::raw,
+    rc::Rc,
 };
 
 /// Creates a normal button
@@ -127,6 +131,22 @@ pub struct CheckButton {
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
 
+/// Defines the states of a CheckButton
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CheckState {
+    /// Unchecked
+    Unchecked,
+    /// Checked
+    Checked,
+    /// Neither checked nor unchecked, clicking the button checks it
+    Indeterminate,
+}
+
+thread_local! {
+    /// The indeterminate flags of the check buttons, shared with their draw methods
+    static INDETERMINATE: RefCell<crate::utils::WidgetMap<Rc<Cell<bool>>>> = RefCell::new(Default::default());
+}
+
 impl CheckButton {
     /// Check whether a CheckButton is checked
     pub fn is_checked(&self) -> bool {
@@ -136,13 +156,89 @@ impl CheckButton {
         }
     }
 
-    /// Set whether CheckButton is checked or not
+    /// Set whether CheckButton is checked or not, this clears the indeterminate state
     pub fn set_checked(&self, checked: bool) {
         unsafe {
             assert!(!self.was_deleted());
+            if let Some(flag) = self.indeterminate_flag() {
+                flag.set(false);
+            }
             Fl_Check_Button_set_checked(self._inner, checked as i32);
         }
     }
+
+    /// Returns the state of the CheckButton
+    pub fn state(&self) -> CheckState {
+        assert!(!self.was_deleted());
+        if self.is_checked() {
+            CheckState::Checked
+        } else if self.indeterminate_flag().map_or(false, |flag| flag.get()) {
+            CheckState::Indeterminate
+        } else {
+            CheckState::Unchecked
+        }
+    }
+
+    /// Sets the state of the CheckButton.
+    /// The indeterminate state is drawn as a dash using a custom draw method, installed the first time it's set,
+    /// which replaces any custom draw method of the button
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut check = button::CheckButton::new(0, 0, 150, 30, "Select all");
+    /// check.set_state(button::CheckState::Indeterminate);
+    /// check.set_state_callback(|_, state| println!("{:?}", state));
+    /// ```
+    pub fn set_state(&mut self, state: CheckState) {
+        assert!(!self.was_deleted());
+        match state {
+            CheckState::Checked => self.set_checked(true),
+            CheckState::Unchecked => self.set_checked(false),
+            CheckState::Indeterminate => {
+                self.set_checked(false);
+                let flag = match self.indeterminate_flag() {
+                    Some(flag) => flag,
+                    None => {
+                        let flag = Rc::new(Cell::new(false));
+                        let f = flag.clone();
+                        self.draw2(move |b| {
+                            // a click checks the button, which ends the indeterminate state
+                            if b.is_checked() {
+                                f.set(false);
+                            } else if f.get() {
+                                let sz = b.label_size();
+                                let dx = 2;
+                                let dy = (b.height() - sz) / 2;
+                                crate::draw::draw_rect_fill(
+                                    b.x() + dx + 3,
+                                    b.y() + dy + sz / 2 - 1,
+                                    sz - 6,
+                                    3,
+                                    b.selection_color(),
+                                );
+                            }
+                        });
+                        INDETERMINATE.with(|m| m.borrow_mut().insert(self, flag.clone()));
+                        flag
+                    }
+                };
+                flag.set(true);
+            }
+        }
+        self.redraw();
+    }
+
+    fn indeterminate_flag(&self) -> Option<Rc<Cell<bool>>> {
+        INDETERMINATE.with(|m| m.borrow_mut().get(self).cloned())
+    }
+
+    /// Sets a callback which is called with the new state of the CheckButton whenever it changes
+    pub fn set_state_callback<F: FnMut(&mut Self, CheckState) + 'static>(&mut self, mut cb: F) {
+        assert!(!self.was_deleted());
+        self.set_callback2(move |b| {
+            let state = b.state();
+            cb(b, state)
+        });
+    }
 }
 
 /// Creates a toggle button
@@ -205,6 +301,162 @@ pub struct ReturnButton {
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
 
+/// Defines the states of an ImageButton, each of which can show its own image
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ImageState {
+    /// The button is idle
+    Normal = 0,
+    /// The mouse is over the button
+    Hover = 1,
+    /// The button is pushed down
+    Pressed = 2,
+    /// The button is deactivated
+    Disabled = 3,
+}
+
+/// Returns the state whose image is shown, falling back to the hover then normal images
+fn shown_image_state(has: [bool; 4], state: ImageState) -> Option<ImageState> {
+    let fallbacks: &[ImageState] = match state {
+        ImageState::Normal => &[ImageState::Normal],
+        ImageState::Hover => &[ImageState::Hover, ImageState::Normal],
+        ImageState::Pressed => &[ImageState::Pressed, ImageState::Hover, ImageState::Normal],
+        ImageState::Disabled => &[ImageState::Disabled, ImageState::Normal],
+    };
+    fallbacks.iter().copied().find(|s| has[*s as usize])
+}
+
+#[derive(Default)]
+struct ImageButtonState {
+    images: [Option<Box<dyn ImageExt>>; 4],
+    hovered: bool,
+}
+
+impl fmt::Debug for ImageButtonState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageButtonState")
+            .field(
+                "images",
+                &self.images.iter().map(|i| i.is_some()).collect::<Vec<_>>(),
+            )
+            .field("hovered", &self.hovered)
+            .finish()
+    }
+}
+
+/// Creates a button (custom widget) showing an image for each of its states: normal, hover, pressed and disabled.
+/// A missing pressed image falls back to the hover image, and any missing image to the normal one.
+/// The image is drawn centered, then the label using the widget's alignment
+/// ```no_run
+/// use fltk::*;
+/// let mut btn = button::ImageButton::new(5, 5, 48, 48, "");
+/// btn.set_state_image(button::ImageState::Normal, image::PngImage::load("open.png").ok());
+/// btn.set_state_image(button::ImageState::Hover, image::PngImage::load("open_hover.png").ok());
+/// btn.set_callback(|| println!("Open"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImageButton {
+    btn: Button,
+    state: Rc<RefCell<ImageButtonState>>,
+}
+
+impl ImageButton {
+    /// Creates a new image button
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> ImageButton {
+        let mut btn = Button::new(x, y, w, h, label);
+        let state = Rc::new(RefCell::new(ImageButtonState::default()));
+        let st = state.clone();
+        btn.draw2(move |b| ImageButton::draw_button(b, &mut st.borrow_mut()));
+        let st = state.clone();
+        btn.handle2(move |b, ev| {
+            let hovered = match ev {
+                Event::Enter => true,
+                Event::Leave | Event::Hide => false,
+                _ => return false,
+            };
+            st.borrow_mut().hovered = hovered;
+            b.redraw();
+            // lets the button handle the event too
+            false
+        });
+        ImageButton { btn, state }
+    }
+
+    fn current_state(b: &Button, st: &ImageButtonState) -> ImageState {
+        if !b.takes_events() {
+            ImageState::Disabled
+        } else if b.is_set() {
+            ImageState::Pressed
+        } else if st.hovered {
+            ImageState::Hover
+        } else {
+            ImageState::Normal
+        }
+    }
+
+    fn draw_button(b: &mut Button, st: &mut ImageButtonState) {
+        let (x, y, w, h) = (b.x(), b.y(), b.width(), b.height());
+        let state = ImageButton::current_state(b, st);
+        let frame = if state == ImageState::Pressed {
+            b.down_frame()
+        } else {
+            b.frame()
+        };
+        draw::draw_box(frame, x, y, w, h, b.color());
+        let mut has = [false; 4];
+        for (i, img) in st.images.iter().enumerate() {
+            has[i] = img.is_some();
+        }
+        if let Some(s) = shown_image_state(has, state) {
+            if let Some(img) = st.images[s as usize].as_mut() {
+                let (iw, ih) = (img.width(), img.height());
+                img.draw(x + (w - iw) / 2, y + (h - ih) / 2, iw, ih);
+            }
+        }
+        let label = b.label();
+        if !label.is_empty() {
+            draw::set_font(b.label_font(), b.label_size() as u32);
+            draw::set_draw_color(if state == ImageState::Disabled {
+                Color::Inactive
+            } else {
+                b.label_color()
+            });
+            draw::draw_text2(&label, x, y, w, h, b.align());
+        }
+    }
+
+    /// Sets the image shown in a state, `None` removes it
+    pub fn set_state_image<I: ImageExt + 'static>(&mut self, state: ImageState, image: Option<I>) {
+        assert!(!self.btn.was_deleted());
+        self.state.borrow_mut().images[state as usize] =
+            image.map(|i| Box::new(i) as Box<dyn ImageExt>);
+        self.btn.redraw();
+    }
+
+    /// Returns whether an image was set for a state
+    pub fn has_state_image(&self, state: ImageState) -> bool {
+        self.state.borrow().images[state as usize].is_some()
+    }
+
+    /// Returns the state the button is currently shown in
+    pub fn image_state(&self) -> ImageState {
+        ImageButton::current_state(&self.btn, &self.state.borrow())
+    }
+}
+
+impl Deref for ImageButton {
+    type Target = Button;
+
+    fn deref(&self) -> &Self::Target {
+        &self.btn
+    }
+}
+
+impl DerefMut for ImageButton {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.btn
+    }
+}
+
 #[cfg(test)]
 mod button {
     use super::*;
@@ -214,4 +466,23 @@ mod button {
         but.set_tooltip("tooltip");
         assert!(but.tooltip().unwrap() == "tooltip");
     }
+
+    #[test]
+    fn image_fallbacks() {
+        let normal_only = [true, false, false, false];
+        assert_eq!(
+            shown_image_state(normal_only, ImageState::Pressed),
+            Some(ImageState::Normal)
+        );
+        let hover = [true, true, false, false];
+        assert_eq!(
+            shown_image_state(hover, ImageState::Pressed),
+            Some(ImageState::Hover)
+        );
+        assert_eq!(
+            shown_image_state(hover, ImageState::Disabled),
+            Some(ImageState::Normal)
+        );
+        assert_eq!(shown_image_state([false; 4], ImageState::Normal), None);
+    }
 }
@@ -1,9 +1,11 @@
 pub use crate::prelude::*;
 use fltk_sys::dialog::*;
 use std::{
+    cell::RefCell,
     ffi::{CStr, CString},
     mem,
     os::raw,
+    rc::Rc,
 };
 
 /// Creates a file button
@@ -218,22 +220,126 @@ impl Drop for FileDialog {
     }
 }
 
-/// Displays a message box
+/// Displays a message box, a native one if the native-dialogs feature is enabled
 pub fn message(x: i32, y: i32, txt: &str) {
+    #[cfg(feature = "native-dialogs")]
+    {
+        if native::message_box(txt, false) {
+            return;
+        }
+    }
     unsafe {
         let txt = CString::safe_new(txt);
         Fl_message(x, y, txt.as_ptr())
     }
 }
 
-/// Displays an alert box
+/// Displays an alert box, a native one if the native-dialogs feature is enabled
 pub fn alert(x: i32, y: i32, txt: &str) {
+    #[cfg(feature = "native-dialogs")]
+    {
+        if native::message_box(txt, true) {
+            return;
+        }
+    }
     unsafe {
         let txt = CString::safe_new(txt);
         Fl_alert(x, y, txt.as_ptr())
     }
 }
 
+#[cfg(feature = "native-dialogs")]
+mod native {
+    /// Returns the title of the message boxes, the name of the executable
+    fn title() -> String {
+        std::env::current_exe()
+            .ok()
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .unwrap_or_default()
+    }
+
+    /// Shows a native message box, returns false if it couldn't be shown
+    #[cfg(target_os = "windows")]
+    pub fn message_box(txt: &str, alert: bool) -> bool {
+        use std::os::raw;
+        #[link(name = "user32")]
+        extern "system" {
+            fn MessageBoxW(
+                hwnd: *mut raw::c_void,
+                text: *const u16,
+                caption: *const u16,
+                utype: u32,
+            ) -> i32;
+        }
+        const MB_ICONWARNING: u32 = 0x30;
+        const MB_ICONINFORMATION: u32 = 0x40;
+        const MB_TASKMODAL: u32 = 0x2000;
+        let wide = |s: &str| s.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+        let (txt, caption) = (wide(txt), wide(&title()));
+        let icon = if alert {
+            MB_ICONWARNING
+        } else {
+            MB_ICONINFORMATION
+        };
+        unsafe {
+            MessageBoxW(
+                std::ptr::null_mut(),
+                txt.as_ptr(),
+                caption.as_ptr(),
+                icon | MB_TASKMODAL,
+            ) != 0
+        }
+    }
+
+    /// Shows a native message box, returns false if it couldn't be shown
+    #[cfg(target_os = "macos")]
+    pub fn message_box(txt: &str, alert: bool) -> bool {
+        use objc::runtime::Object;
+        const NS_UTF8_STRING_ENCODING: usize = 4;
+        unsafe {
+            let ns_txt: *mut Object = msg_send![class!(NSString), alloc];
+            let ns_txt: *mut Object = msg_send![ns_txt, initWithBytes: txt.as_ptr() length: txt.len() encoding: NS_UTF8_STRING_ENCODING];
+            let ns_alert: *mut Object = msg_send![class!(NSAlert), new];
+            if ns_txt.is_null() || ns_alert.is_null() {
+                return false;
+            }
+            // NSAlertStyleWarning and NSAlertStyleInformational
+            let style: usize = if alert { 0 } else { 1 };
+            let _: () = msg_send![ns_alert, setMessageText: ns_txt];
+            let _: () = msg_send![ns_alert, setAlertStyle: style];
+            let _: isize = msg_send![ns_alert, runModal];
+            let _: () = msg_send![ns_alert, release];
+            let _: () = msg_send![ns_txt, release];
+        }
+        true
+    }
+
+    /// Shows a message box using zenity or kdialog, returns false if neither is installed
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    pub fn message_box(txt: &str, alert: bool) -> bool {
+        use std::process::Command;
+        let title = title();
+        let zenity = Command::new("zenity")
+            .arg(if alert { "--warning" } else { "--info" })
+            .arg("--no-markup")
+            .arg("--title")
+            .arg(&title)
+            .arg("--text")
+            .arg(txt)
+            .status();
+        if zenity.map_or(false, |s| s.success()) {
+            return true;
+        }
+        Command::new("kdialog")
+            .arg("--title")
+            .arg(&title)
+            .arg(if alert { "--sorry" } else { "--msgbox" })
+            .arg(txt)
+            .status()
+            .map_or(false, |s| s.success())
+    }
+}
+
 /// Displays a choice box with upto three choices
 /// An empty choice will not be shown
 pub fn choice(x: i32, y: i32, txt: &str, b0: &str, b1: &str, b2: &str) -> u32 {
@@ -283,16 +389,28 @@ pub fn password(x: i32, y: i32, txt: &str, deflt: &str) -> Option<String> {
     }
 }
 
-/// Displays a message box
+/// Displays a message box, a native one if the native-dialogs feature is enabled
 pub fn message_default(txt: &str) {
+    #[cfg(feature = "native-dialogs")]
+    {
+        if native::message_box(txt, false) {
+            return;
+        }
+    }
     unsafe {
         let txt = CString::safe_new(txt);
         Fl_message2(txt.as_ptr())
     }
 }
 
-/// Displays an alert box
+/// Displays an alert box, a native one if the native-dialogs feature is enabled
 pub fn alert_default(txt: &str) {
+    #[cfg(feature = "native-dialogs")]
+    {
+        if native::message_box(txt, true) {
+            return;
+        }
+    }
     unsafe {
         let txt = CString::safe_new(txt);
         Fl_alert2(txt.as_ptr())
@@ -489,7 +607,11 @@ pub enum BeepType {
     Notification,
 }
 
-/// Emits a beep
+/// Emits a beep, also available as `app::beep`
+/// ```no_run
+/// use fltk::*;
+/// app::beep(app::BeepType::Error);
+/// ```
 pub fn beep(tp: BeepType) {
     unsafe { Fl_beep(tp as i32) }
 }
@@ -1076,3 +1198,292 @@ pub fn color_chooser_with_default(name: &str, cmode: i32, col: (u8, u8, u8)) ->
         }
     }
 }
+
+/// Shows a modal date picker, initially selecting a date or today, and returns the chosen date.
+/// A date is chosen by double-clicking it or with the OK button, and None is returned if the dialog is cancelled
+/// ```no_run
+/// use fltk::*;
+/// if let Some(date) = dialog::date_picker("Due date", None) {
+///     println!("{}", date);
+/// }
+/// ```
+pub fn date_picker(title: &str, initial: Option<crate::misc::Date>) -> Option<crate::misc::Date> {
+    use crate::{
+        button::{Button, ReturnButton},
+        misc::Calendar,
+        window::Window,
+    };
+    let mut win = Window::default()
+        .with_size(260, 250)
+        .with_label(title)
+        .center_screen();
+    let mut cal = Calendar::new(5, 5, 250, 205, "");
+    let mut ok = ReturnButton::new(105, 215, 70, 30, "OK");
+    let mut cancel = Button::new(185, 215, 70, 30, "Cancel");
+    win.end();
+    cal.set_value(Some(initial.unwrap_or_else(crate::misc::Date::today)));
+    let mut w = win.clone();
+    cal.set_selection_callback(move |_| {
+        if crate::app::event() == Event::Push && crate::app::event_clicks() {
+            w.set_modal_result(1);
+        }
+    });
+    let mut w = win.clone();
+    ok.set_callback(move || w.set_modal_result(1));
+    let mut w = win.clone();
+    cancel.set_callback(move || w.set_modal_result(0));
+    let ret = match win.show_modal() {
+        Some(1) => cal.value(),
+        _ => None,
+    };
+    Window::delete(win);
+    ret
+}
+
+/// Defines the corner of a window where toasts are stacked
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ToastCorner {
+    /// Top left corner
+    TopLeft,
+    /// Top right corner
+    TopRight,
+    /// Bottom left corner
+    BottomLeft,
+    /// Bottom right corner
+    BottomRight,
+}
+
+/// Returns the screen position of each stacked toast, the first one being nearest to the corner
+fn toast_positions(
+    area: (i32, i32, i32, i32),
+    corner: ToastCorner,
+    width: i32,
+    heights: &[i32],
+) -> Vec<(i32, i32)> {
+    const MARGIN: i32 = 10;
+    let (x, y, w, h) = area;
+    let tx = match corner {
+        ToastCorner::TopLeft | ToastCorner::BottomLeft => x + MARGIN,
+        ToastCorner::TopRight | ToastCorner::BottomRight => x + w - width - MARGIN,
+    };
+    let mut offset = MARGIN;
+    heights
+        .iter()
+        .map(|th| {
+            let ty = match corner {
+                ToastCorner::TopLeft | ToastCorner::TopRight => y + offset,
+                ToastCorner::BottomLeft | ToastCorner::BottomRight => y + h - offset - th,
+            };
+            offset += th + MARGIN / 2;
+            (tx, ty)
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct ToastManagerState {
+    parent: crate::window::Window,
+    corner: ToastCorner,
+    duration: f64,
+    width: i32,
+    toasts: Vec<crate::window::MenuWindow>,
+}
+
+impl ToastManagerState {
+    fn layout(&mut self) {
+        self.toasts.retain(|t| !t.was_deleted());
+        if self.parent.was_deleted() {
+            return;
+        }
+        let heights: Vec<i32> = self.toasts.iter().map(|t| t.height()).collect();
+        let area = (
+            self.parent.x_root(),
+            self.parent.y_root(),
+            self.parent.width(),
+            self.parent.height(),
+        );
+        let positions = toast_positions(area, self.corner, self.width, &heights);
+        for (t, (x, y)) in self.toasts.iter_mut().zip(positions) {
+            t.resize(x, y, t.width(), t.height());
+        }
+    }
+}
+
+/// Shows transient notifications, stacked in a corner of a window and dismissed after a delay or when clicked
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// win.end();
+/// win.show();
+/// let mut toasts = dialog::ToastManager::new(&win);
+/// toasts.show("File saved");
+/// toasts.show_with_callback("Update available, click to install", || println!("Installing"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToastManager {
+    state: Rc<RefCell<ToastManagerState>>,
+}
+
+impl ToastManager {
+    /// Creates a toast manager showing toasts over a window
+    pub fn new<W: WindowExt>(win: &W) -> ToastManager {
+        assert!(!win.was_deleted());
+        let parent = unsafe { crate::window::Window::from_widget_ptr(win.as_widget_ptr()) };
+        ToastManager {
+            state: Rc::new(RefCell::new(ToastManagerState {
+                parent,
+                corner: ToastCorner::BottomRight,
+                duration: 3.,
+                width: 260,
+                toasts: vec![],
+            })),
+        }
+    }
+
+    /// Sets the corner where toasts are stacked, the bottom right corner by default
+    pub fn set_corner(&mut self, corner: ToastCorner) {
+        let mut st = self.state.borrow_mut();
+        st.corner = corner;
+        st.layout();
+    }
+
+    /// Returns the corner where toasts are stacked
+    pub fn corner(&self) -> ToastCorner {
+        self.state.borrow().corner
+    }
+
+    /// Sets the time in seconds before toasts are dismissed, 0 keeps them until they're clicked
+    pub fn set_duration(&mut self, secs: f64) {
+        self.state.borrow_mut().duration = secs;
+    }
+
+    /// Returns the time in seconds before toasts are dismissed
+    pub fn duration(&self) -> f64 {
+        self.state.borrow().duration
+    }
+
+    /// Sets the width of the toasts
+    pub fn set_toast_width(&mut self, width: i32) {
+        self.state.borrow_mut().width = width;
+    }
+
+    /// Shows a toast
+    pub fn show(&mut self, message: &str) -> Toast {
+        self.show_toast(message, None)
+    }
+
+    /// Shows a toast running a callback when it's clicked, before being dismissed
+    pub fn show_with_callback<F: FnMut() + 'static>(&mut self, message: &str, cb: F) -> Toast {
+        self.show_toast(message, Some(Box::new(cb)))
+    }
+
+    fn show_toast(&mut self, message: &str, mut cb: Option<Box<dyn FnMut()>>) -> Toast {
+        use crate::{frame::Frame, window::MenuWindow};
+        let (width, duration) = {
+            let st = self.state.borrow();
+            (st.width, st.duration)
+        };
+        crate::draw::set_font(Font::Helvetica, 14);
+        let lines =
+            crate::frame::wrap_lines(message, Some((width - 20) as f64), crate::draw::width);
+        let height = lines.len() as i32 * crate::draw::height() + 20;
+        let mut win = unsafe {
+            let current = fltk_sys::group::Fl_Group_current();
+            fltk_sys::group::Fl_Group_set_current(std::ptr::null_mut());
+            let win = MenuWindow::new(0, 0, width, height, "");
+            fltk_sys::group::Fl_Group_set_current(current);
+            win
+        };
+        win.set_color(Color::from_u32(0x303030));
+        win.set_frame(FrameType::FlatBox);
+        let mut frame = Frame::new(10, 10, width - 20, height - 20, message);
+        frame.set_label_color(Color::White);
+        frame.set_label_size(14);
+        frame.set_align(Align::Left | Align::Inside | Align::Wrap);
+        win.end();
+        let toast = Toast {
+            win: win.clone(),
+            manager: self.state.clone(),
+        };
+        let mut t = toast.clone();
+        win.handle2(move |_, ev| {
+            if ev == Event::Push {
+                if let Some(cb) = cb.as_mut() {
+                    cb();
+                }
+                t.dismiss();
+                true
+            } else {
+                false
+            }
+        });
+        {
+            let mut st = self.state.borrow_mut();
+            st.toasts.push(win.clone());
+            st.layout();
+        }
+        win.show();
+        if duration > 0. {
+            let mut t = toast.clone();
+            crate::app::add_timeout(duration, move || t.dismiss());
+        }
+        toast
+    }
+
+    /// Dismisses all the toasts
+    pub fn dismiss_all(&mut self) {
+        let toasts = mem::take(&mut self.state.borrow_mut().toasts);
+        for t in toasts {
+            if !t.was_deleted() {
+                crate::window::MenuWindow::delete(t);
+            }
+        }
+    }
+
+    /// Returns the number of toasts shown
+    pub fn count(&self) -> usize {
+        let mut st = self.state.borrow_mut();
+        st.toasts.retain(|t| !t.was_deleted());
+        st.toasts.len()
+    }
+}
+
+/// A notification shown by a ToastManager
+#[derive(Debug, Clone)]
+pub struct Toast {
+    win: crate::window::MenuWindow,
+    manager: Rc<RefCell<ToastManagerState>>,
+}
+
+impl Toast {
+    /// Dismisses the toast, the remaining toasts moving to fill its place
+    pub fn dismiss(&mut self) {
+        if self.win.was_deleted() {
+            return;
+        }
+        crate::window::MenuWindow::delete(self.win.clone());
+        self.manager.borrow_mut().layout();
+    }
+
+    /// Returns whether the toast is still shown
+    pub fn is_shown(&self) -> bool {
+        !self.win.was_deleted()
+    }
+}
+
+#[cfg(test)]
+mod dialog {
+    use super::*;
+    #[test]
+    fn toast_stacking() {
+        let area = (100, 100, 400, 300);
+        assert_eq!(
+            toast_positions(area, ToastCorner::BottomRight, 200, &[40, 50]),
+            vec![(290, 350), (290, 295)]
+        );
+        assert_eq!(
+            toast_positions(area, ToastCorner::TopLeft, 200, &[40, 50]),
+            vec![(110, 110), (110, 155)]
+        );
+    }
+}
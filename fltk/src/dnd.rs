@@ -0,0 +1,257 @@
+use crate::app;
+use crate::prelude::*;
+use crate::utils::{DragEvent, DragHelper, WidgetMap};
+use crate::widget::Widget;
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+type DropCallback = Rc<RefCell<dyn FnMut(Box<dyn Any>)>>;
+type HoverCallback = Rc<RefCell<dyn FnMut(bool)>>;
+
+struct DropTarget {
+    accepts: Box<dyn Fn(&dyn Any) -> bool>,
+    on_drop: DropCallback,
+    on_hover: Option<HoverCallback>,
+}
+
+struct Drag {
+    payload: Box<dyn Any>,
+    source: Widget,
+    hovered: Option<Widget>,
+}
+
+thread_local! {
+    static TARGETS: RefCell<WidgetMap<DropTarget>> = RefCell::new(Default::default());
+    static DRAG: RefCell<Option<Drag>> = RefCell::new(None);
+}
+
+fn same_widget<W: WidgetExt, V: WidgetExt>(a: &W, b: &V) -> bool {
+    unsafe { a.as_widget_ptr() == b.as_widget_ptr() }
+}
+
+/// Returns whether the window of a widget is shown, without wrapping the window
+fn window_shown(w: &Widget) -> bool {
+    unsafe {
+        let win = fltk_sys::widget::Fl_Widget_top_window(w.as_widget_ptr());
+        !win.is_null() && fltk_sys::window::Fl_Window_shown(win as *mut _) != 0
+    }
+}
+
+/// Returns the innermost drop target under the mouse accepting the payload
+fn target_at(payload: &dyn Any, x: i32, y: i32) -> Option<Widget> {
+    TARGETS.with(|t| {
+        t.borrow_mut()
+            .iter()
+            .filter(|(w, t)| {
+                x >= w.x_root()
+                    && x < w.x_root() + w.width()
+                    && y >= w.y_root()
+                    && y < w.y_root() + w.height()
+                    && window_shown(w)
+                    && (t.accepts)(payload)
+            })
+            .min_by_key(|(w, _)| w.width() * w.height())
+            .map(|(w, _)| w.clone())
+    })
+}
+
+fn hover_callback(wid: &Widget) -> Option<HoverCallback> {
+    TARGETS.with(|t| t.borrow_mut().get(wid).and_then(|t| t.on_hover.clone()))
+}
+
+fn set_hovered(hovered: Option<Widget>) {
+    let old = DRAG.with(|d| {
+        d.borrow_mut()
+            .as_mut()
+            .map(|d| std::mem::replace(&mut d.hovered, hovered.clone()))
+    });
+    let old = match old {
+        Some(old) => old,
+        None => return,
+    };
+    let changed = match (&old, &hovered) {
+        (Some(a), Some(b)) => !same_widget(a, b),
+        (None, None) => false,
+        _ => true,
+    };
+    if !changed {
+        return;
+    }
+    // the callbacks are called without borrowing the registry, since they can add targets
+    if let Some(cb) = old.as_ref().and_then(hover_callback) {
+        (*cb.borrow_mut())(false);
+    }
+    if let Some(cb) = hovered.as_ref().and_then(hover_callback) {
+        (*cb.borrow_mut())(true);
+    }
+    let cursor = if hovered.is_some() {
+        Cursor::Move
+    } else {
+        Cursor::Default
+    };
+    DRAG.with(|d| {
+        if let Some(d) = &*d.borrow() {
+            if let Some(mut win) = d.source.top_window() {
+                win.set_cursor(cursor);
+            }
+        }
+    });
+}
+
+/// Starts dragging a payload from a source widget, usually called on an Event::Drag
+/// from the source's handler. make_drag_source() does this along with the following steps
+pub fn start_drag<W: WidgetExt>(source: &W, payload: Box<dyn Any>) {
+    cancel_drag();
+    let source = unsafe { Widget::from_widget_ptr(source.as_widget_ptr()) };
+    DRAG.with(|d| {
+        *d.borrow_mut() = Some(Drag {
+            payload,
+            source,
+            hovered: None,
+        })
+    });
+    update_drag();
+}
+
+/// Updates the hovered drop target from the mouse position, called on the source's Event::Drag
+pub fn update_drag() {
+    let (x, y) = (app::event_x_root(), app::event_y_root());
+    let hovered = DRAG.with(|d| match &*d.borrow() {
+        Some(d) => Some(target_at(d.payload.as_ref(), x, y)),
+        None => None,
+    });
+    if let Some(hovered) = hovered {
+        set_hovered(hovered);
+    }
+}
+
+/// Ends the drag, dropping the payload on the hovered drop target, called on the source's Event::Released.
+/// Returns whether the payload was dropped
+pub fn finish_drag() -> bool {
+    update_drag();
+    let target = DRAG.with(|d| d.borrow().as_ref().and_then(|d| d.hovered.clone()));
+    set_hovered(None);
+    let drag = match DRAG.with(|d| d.borrow_mut().take()) {
+        Some(drag) => drag,
+        None => return false,
+    };
+    let on_drop = target.and_then(|target| {
+        TARGETS.with(|t| t.borrow_mut().get(&target).map(|t| t.on_drop.clone()))
+    });
+    match on_drop {
+        Some(cb) => {
+            (*cb.borrow_mut())(drag.payload);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Ends the drag without dropping its payload
+pub fn cancel_drag() {
+    set_hovered(None);
+    DRAG.with(|d| *d.borrow_mut() = None);
+}
+
+/// Returns whether a payload is being dragged
+pub fn is_dragging() -> bool {
+    DRAG.with(|d| d.borrow().is_some())
+}
+
+/// Makes a widget a drag source: once the mouse moves with a button pressed over it,
+/// the callback is asked for a payload, and dragging it stops if it returns None.
+/// The payload is dropped on the drop target under the mouse when the button is released.
+/// Replaces any handler set using handle() or handle2(), custom handlers can call
+/// start_drag(), update_drag() and finish_drag() instead
+/// ```no_run
+/// use fltk::*;
+/// #[derive(Debug)]
+/// struct Card(u32);
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut card = frame::Frame::new(10, 10, 100, 150, "Card 7");
+/// let mut pile = frame::Frame::new(200, 10, 100, 150, "Pile");
+/// pile.set_frame(FrameType::DownBox);
+/// win.end();
+/// win.show();
+/// dnd::make_drag_source(&mut card, |_| Some(Box::new(Card(7))));
+/// dnd::add_drop_target(&pile, |p, card: Card| p.set_label(&format!("Card {}", card.0)));
+/// dnd::set_drop_hover_callback(&pile, |p, hovered| {
+///     p.set_color(if hovered { Color::Yellow } else { Color::BackGround });
+///     p.redraw();
+/// });
+/// ```
+pub fn make_drag_source<W, F>(wid: &mut W, mut payload: F)
+where
+    W: WidgetBase + 'static,
+    F: FnMut(&mut W) -> Option<Box<dyn Any>> + 'static,
+{
+    assert!(!wid.was_deleted());
+    let mut drag = DragHelper::default();
+    wid.handle2(move |w, ev| match drag.handle(ev) {
+        Some(DragEvent::Started { .. }) => {
+            if let Some(p) = payload(w) {
+                start_drag(&*w, p);
+            }
+            true
+        }
+        Some(DragEvent::Moved { .. }) => {
+            if is_dragging() {
+                update_drag();
+            }
+            true
+        }
+        Some(DragEvent::Finished { .. }) => {
+            if is_dragging() {
+                finish_drag();
+            }
+            true
+        }
+        Some(_) => true,
+        None => false,
+    });
+}
+
+/// Registers a widget as a drop target for payloads of type T, replacing its previous registration.
+/// The callback is called with the payload dropped on the widget
+pub fn add_drop_target<W, T, F>(wid: &W, mut on_drop: F)
+where
+    W: WidgetExt + Clone + 'static,
+    T: 'static,
+    F: FnMut(&mut W, T) + 'static,
+{
+    assert!(!wid.was_deleted());
+    let mut w = wid.clone();
+    let on_drop: DropCallback = Rc::new(RefCell::new(move |payload: Box<dyn Any>| {
+        if let Ok(payload) = payload.downcast::<T>() {
+            on_drop(&mut w, *payload);
+        }
+    }));
+    let target = DropTarget {
+        accepts: Box::new(|payload: &dyn Any| payload.is::<T>()),
+        on_drop,
+        on_hover: None,
+    };
+    TARGETS.with(|t| t.borrow_mut().insert(wid, target));
+}
+
+/// Sets the callback of a drop target called with true when an accepted payload is dragged over it
+/// and with false when it leaves it or is dropped, to show some feedback like highlighting the target.
+/// Does nothing if the widget isn't a drop target
+pub fn set_drop_hover_callback<W, F>(wid: &W, mut cb: F)
+where
+    W: WidgetExt + Clone + 'static,
+    F: FnMut(&mut W, bool) + 'static,
+{
+    assert!(!wid.was_deleted());
+    let mut w = wid.clone();
+    let cb: HoverCallback = Rc::new(RefCell::new(move |hovered| cb(&mut w, hovered)));
+    TARGETS.with(|t| {
+        if let Some(t) = t.borrow_mut().get(wid) {
+            t.on_hover = Some(cb);
+        }
+    });
+}
+
+/// Unregisters a drop target
+pub fn remove_drop_target<W: WidgetExt>(wid: &W) {
+    TARGETS.with(|t| t.borrow_mut().remove(wid));
+}
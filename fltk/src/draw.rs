@@ -682,23 +682,44 @@ pub fn reset_spot() {
     unsafe { Fl_reset_spot() }
 }
 
-/// Captures part of the window and returns raw data
+/// Captures the content of a window as an RgbImage, the window is shown if it isn't
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// win.end();
+/// win.show();
+/// let img = draw::capture_window(&mut win).unwrap();
+/// draw::write_to_png_file(&img, "screenshot.png").unwrap();
+/// ```
 pub fn capture_window<Win: WindowExt>(win: &mut Win) -> Result<RgbImage, FltkError> {
+    let (w, h) = (win.width(), win.height());
+    capture_window_part(win, 0, 0, w, h)
+}
+
+/// Captures part of a window as an RgbImage, x and y are relative to the window
+pub fn capture_window_part<Win: WindowExt>(
+    win: &mut Win,
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+) -> Result<RgbImage, FltkError> {
     assert!(!win.was_deleted());
-    let cp = win.width() as u32 * win.height() as u32 * 3;
-    win.show();
+    if w <= 0 || h <= 0 {
+        return Err(FltkError::Internal(FltkErrorKind::FailedOperation));
+    }
+    if !win.shown() {
+        win.show();
+    }
+    win.make_current();
+    let mut data = vec![0u8; w as usize * h as usize * 3];
     unsafe {
-        let x = Fl_read_image(std::ptr::null_mut(), 0, 0, win.width(), win.height(), 0);
-        if x.is_null() {
+        // The data is written into the passed buffer, which the function returns on success
+        let ret = Fl_read_image(data.as_mut_ptr(), x, y, w, h, 0);
+        if ret.is_null() {
             Err(FltkError::Internal(FltkErrorKind::FailedOperation))
         } else {
-            let x = std::slice::from_raw_parts(x, cp as usize);
-            Ok(RgbImage::new(
-                x,
-                win.width() as u32,
-                win.height() as u32,
-                3,
-            )?)
+            RgbImage::new(&data, w as u32, h as u32, 3)
         }
     }
 }
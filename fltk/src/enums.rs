@@ -1,5 +1,5 @@
 use crate::app::*;
-use fltk_sys::fl::Fl_get_rgb_color;
+use fltk_sys::fl::{Fl_get_color_rgb, Fl_get_rgb_color};
 
 /// Defines label types
 #[repr(i32)]
@@ -169,17 +169,17 @@ bitflags! {
         /// Inside
         const Inside = 16;
         /// Text Over Image
-        const TextOverImage = 20;
+        const TextOverImage = 0x0020;
         /// Clip
-        const Clip = 40;
+        const Clip = 0x0040;
         /// Wrap
-        const Wrap = 80;
+        const Wrap = 0x0080;
         /// Image Next To Text
-        const ImageNextToText = 100;
+        const ImageNextToText = 0x0100;
         /// Text Next To Image
-        const TextNextToImage = 120;
+        const TextNextToImage = 0x0120;
         /// Image Backdrop
-        const ImageBackdrop = 200;
+        const ImageBackdrop = 0x0200;
         /// Top Left
         const TopLeft = 1 | 4;
         /// Top Right
@@ -347,6 +347,13 @@ impl Color {
     pub fn by_index(idx: u8) -> Color {
         unsafe { std::mem::transmute(idx as u32) }
     }
+
+    /// Returns the red, green and blue components of a color, looking up indexed colors in the colormap
+    pub fn to_rgb(&self) -> (u8, u8, u8) {
+        let (mut r, mut g, mut b) = (0, 0, 0);
+        unsafe { Fl_get_color_rgb(self.bits(), &mut r, &mut g, &mut b) }
+        (r, g, b)
+    }
 }
 
 #[allow(unreachable_patterns)]
@@ -566,6 +573,30 @@ bitflags! {
         const KPEnter = 0xff8d;
         /// Keypad Last
         const KPLast = 0xffbd;
+        /// F1
+        const F1 = 0xffbd + 1;
+        /// F2
+        const F2 = 0xffbd + 2;
+        /// F3
+        const F3 = 0xffbd + 3;
+        /// F4
+        const F4 = 0xffbd + 4;
+        /// F5
+        const F5 = 0xffbd + 5;
+        /// F6
+        const F6 = 0xffbd + 6;
+        /// F7
+        const F7 = 0xffbd + 7;
+        /// F8
+        const F8 = 0xffbd + 8;
+        /// F9
+        const F9 = 0xffbd + 9;
+        /// F10
+        const F10 = 0xffbd + 10;
+        /// F11
+        const F11 = 0xffbd + 11;
+        /// F12
+        const F12 = 0xffbd + 12;
         /// FLast
         const FLast = 0xffe0;
         /// Shift Left
@@ -601,6 +632,38 @@ impl Key {
     pub fn from_char(val: char) -> Key {
         unsafe { std::mem::transmute(val) }
     }
+
+    /// Gets the function key F(n), n should be between 1 and 35
+    pub fn fn_key(val: i32) -> Key {
+        Key::from_i32(0xffbd + val)
+    }
+
+    /// Gets the keypad key corresponding to a char, i.e. '0'-'9', '*', '+', '-', '.', '/' and '='
+    pub fn kp(val: char) -> Key {
+        Key::from_i32(0xff80 + val as i32)
+    }
+
+    /// Returns whether the key is a function key
+    pub fn is_fn_key(&self) -> bool {
+        self.bits > 0xffbd && self.bits <= Key::FLast.bits
+    }
+
+    /// Returns whether the key is a keypad key
+    pub fn is_keypad(&self) -> bool {
+        self.bits >= Key::KP.bits && self.bits <= Key::KPLast.bits
+    }
+
+    /// Returns the character of printable keys, or None otherwise.
+    /// Keypad keys return their corresponding char
+    pub fn to_char(&self) -> Option<char> {
+        if self.bits > 0 && self.bits < 0xfe00 {
+            std::char::from_u32(self.bits as u32)
+        } else if self.is_keypad() && *self != Key::KPEnter {
+            std::char::from_u32((self.bits - Key::KP.bits) as u32)
+        } else {
+            None
+        }
+    }
 }
 
 bitflags! {
@@ -655,6 +718,8 @@ bitflags! {
         const EnterKeyAlways = 10;
         /// Enter Key and Changed
         const EnterKeyChanged = 11;
+        /// Closed, used by windows to trigger the callback when closed
+        const Closed = 16;
     }
 }
 
@@ -795,3 +860,27 @@ impl std::ops::BitOr<i32> for Align {
         unsafe { std::mem::transmute(self.bits | rhs as i32) }
     }
 }
+
+#[cfg(test)]
+mod enums {
+    use super::*;
+    #[test]
+    fn keys() {
+        assert!(Key::fn_key(1) == Key::F1);
+        assert!(Key::F12.is_fn_key());
+        assert!(Key::kp('5').is_keypad());
+        assert!(Key::kp('5').to_char() == Some('5'));
+        assert!(Key::from_char('a').to_char() == Some('a'));
+        assert!(Key::Escape.to_char().is_none());
+    }
+
+    #[test]
+    fn triggers() {
+        let t = CallbackTrigger::Changed | CallbackTrigger::EnterKey;
+        assert!(t.contains(CallbackTrigger::Changed));
+        assert!(t.contains(CallbackTrigger::EnterKey));
+        assert!(!t.contains(CallbackTrigger::NotChanged));
+        assert!(CallbackTrigger::EnterKeyChanged.contains(t));
+        assert!(CallbackTrigger::ReleaseAlways.contains(CallbackTrigger::Release));
+    }
+}
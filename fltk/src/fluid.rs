@@ -0,0 +1,884 @@
+use crate::prelude::*;
+use crate::widget::Widget;
+use crate::window::Window;
+use std::{any::TypeId, collections::HashMap, fs, path, ptr};
+
+/// An item of a FLUID file: a widget, a menu item, or a code item like a function or declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct FluidNode {
+    /// The FLUID class, like `Fl_Button`, `Function` or `MenuItem`
+    pub class: String,
+    /// The name of the item, which is the variable name of widgets, empty if unnamed
+    pub name: String,
+    /// The properties, flags having no value
+    pub props: Vec<(String, Option<String>)>,
+    /// The child items
+    pub children: Vec<FluidNode>,
+}
+
+impl FluidNode {
+    /// Returns the value of a property
+    pub fn prop(&self, key: &str) -> Option<&str> {
+        self.props
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    /// Returns whether a flag or property is set
+    pub fn has(&self, key: &str) -> bool {
+        self.props.iter().any(|(k, _)| k == key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Braced(String),
+}
+
+impl Token {
+    fn text(&self) -> &str {
+        match self {
+            Token::Word(s) | Token::Braced(s) => s,
+        }
+    }
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, FltkError> {
+    let mut tokens = vec![];
+    let mut chars = src.chars().peekable();
+    let mut line_start = true;
+    while let Some(&c) = chars.peek() {
+        if c == '#' && line_start {
+            while let Some(c) = chars.next() {
+                if c == '\n' {
+                    break;
+                }
+            }
+        } else if c.is_whitespace() {
+            line_start = c == '\n';
+            chars.next();
+        } else if c == '{' {
+            chars.next();
+            let mut depth = 1;
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => {
+                        // escaped braces and backslashes keep their backslash for nested blocks
+                        if let Some(n) = chars.next() {
+                            if depth > 1 {
+                                text.push('\\');
+                            }
+                            text.push(n);
+                        }
+                    }
+                    Some('{') => {
+                        depth += 1;
+                        text.push('{');
+                    }
+                    Some('}') => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                        text.push('}');
+                    }
+                    Some(c) => text.push(c),
+                    None => return Err(FltkError::Unknown("Unbalanced braces".to_string())),
+                }
+            }
+            tokens.push(Token::Braced(text));
+            line_start = false;
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '{' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+            line_start = false;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Properties written without a value
+pub(crate) const FLUID_FLAGS: &[&str] = &[
+    "open",
+    "selected",
+    "visible",
+    "hide",
+    "deactivate",
+    "resizable",
+    "hotspot",
+    "divider",
+    "modal",
+    "non_modal",
+    "noborder",
+    "border",
+    "set_xid",
+    "private",
+    "public",
+    "protected",
+    "local",
+    "global",
+    "in_source",
+    "in_header",
+    "C",
+];
+
+fn parse_props(src: &str) -> Result<Vec<(String, Option<String>)>, FltkError> {
+    let tokens = tokenize(src)?;
+    let mut props = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let key = tokens[i].text().to_string();
+        if FLUID_FLAGS.contains(&key.as_str()) || i + 1 == tokens.len() {
+            props.push((key, None));
+            i += 1;
+        } else {
+            props.push((key, Some(tokens[i + 1].text().to_string())));
+            i += 2;
+        }
+    }
+    Ok(props)
+}
+
+fn parse_items(tokens: &[Token]) -> Result<Vec<FluidNode>, FltkError> {
+    let mut items = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+            (Token::Word(class), Some(name), Some(Token::Braced(props))) => {
+                let mut node = FluidNode {
+                    class: class.clone(),
+                    name: name.text().to_string(),
+                    props: parse_props(props)?,
+                    children: vec![],
+                };
+                i += 3;
+                if let Some(Token::Braced(children)) = tokens.get(i) {
+                    node.children = parse_items(&tokenize(children)?)?;
+                    i += 1;
+                }
+                items.push(node);
+            }
+            // settings of the file, like its version
+            (Token::Word(_), Some(_), _) => i += 2,
+            _ => {
+                return Err(FltkError::Unknown(format!(
+                    "Unexpected token in FLUID file: {}",
+                    tokens[i].text()
+                )))
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Parses the items of a FLUID file
+pub fn parse(src: &str) -> Result<Vec<FluidNode>, FltkError> {
+    parse_items(&tokenize(src)?)
+}
+
+/// The FLUID names of the box types, in the order of FrameType
+const FLUID_BOXES: &[&str] = &[
+    "NO_BOX",
+    "FLAT_BOX",
+    "UP_BOX",
+    "DOWN_BOX",
+    "UP_FRAME",
+    "DOWN_FRAME",
+    "THIN_UP_BOX",
+    "THIN_DOWN_BOX",
+    "THIN_UP_FRAME",
+    "THIN_DOWN_FRAME",
+    "ENGRAVED_BOX",
+    "EMBOSSED_BOX",
+    "ENGRAVED_FRAME",
+    "EMBOSSED_FRAME",
+    "BORDER_BOX",
+    "SHADOW_BOX",
+    "BORDER_FRAME",
+    "SHADOW_FRAME",
+    "ROUNDED_BOX",
+    "RSHADOW_BOX",
+    "ROUNDED_FRAME",
+    "RFLAT_BOX",
+    "ROUND_UP_BOX",
+    "ROUND_DOWN_BOX",
+    "DIAMOND_UP_BOX",
+    "DIAMOND_DOWN_BOX",
+    "OVAL_BOX",
+    "OSHADOW_BOX",
+    "OVAL_FRAME",
+    "OFLAT_BOX",
+    "PLASTIC_UP_BOX",
+    "PLASTIC_DOWN_BOX",
+    "PLASTIC_UP_FRAME",
+    "PLASTIC_DOWN_FRAME",
+    "PLASTIC_THIN_UP_BOX",
+    "PLASTIC_THIN_DOWN_BOX",
+    "PLASTIC_ROUND_UP_BOX",
+    "PLASTIC_ROUND_DOWN_BOX",
+    "GTK_UP_BOX",
+    "GTK_DOWN_BOX",
+    "GTK_UP_FRAME",
+    "GTK_DOWN_FRAME",
+    "GTK_THIN_UP_BOX",
+    "GTK_THIN_DOWN_BOX",
+    "GTK_THIN_UP_FRAME",
+    "GTK_THIN_DOWN_FRAME",
+    "GTK_ROUND_UP_BOX",
+    "GTK_ROUND_DOWN_BOX",
+];
+
+fn fluid_box(name: &str) -> Option<FrameType> {
+    let name = name.trim_start_matches("FL_");
+    FLUID_BOXES
+        .iter()
+        .position(|b| *b == name)
+        .map(|i| unsafe { std::mem::transmute(i as i32) })
+}
+
+/// FLUID writes indexed colors as is and RGB colors as 0xRRGGBB00
+fn fluid_color(val: &str) -> Option<Color> {
+    let val: u32 = val.parse().ok()?;
+    if val < 256 {
+        Some(Color::by_index(val as u8))
+    } else {
+        Some(Color::from_u32(val >> 8))
+    }
+}
+
+fn fluid_xywh(node: &FluidNode) -> (i32, i32, i32, i32) {
+    let v: Vec<i32> = node
+        .prop("xywh")
+        .unwrap_or("")
+        .split_whitespace()
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    match v.as_slice() {
+        [x, y, w, h] => (*x, *y, *w, *h),
+        _ => (0, 0, 0, 0),
+    }
+}
+
+fn apply_props<W: WidgetExt>(w: &mut W, node: &FluidNode) {
+    for (key, val) in &node.props {
+        let val = val.as_deref().unwrap_or("");
+        match key.as_str() {
+            "label" => w.set_label(val),
+            "tooltip" => w.set_tooltip(val),
+            "box" => {
+                if let Some(f) = fluid_box(val) {
+                    w.set_frame(f);
+                }
+            }
+            "color" => {
+                if let Some(c) = fluid_color(val.split_whitespace().next().unwrap_or("")) {
+                    w.set_color(c);
+                }
+            }
+            "selection_color" => {
+                if let Some(c) = fluid_color(val) {
+                    w.set_selection_color(c);
+                }
+            }
+            "labelcolor" => {
+                if let Some(c) = fluid_color(val) {
+                    w.set_label_color(c);
+                }
+            }
+            "labelsize" => {
+                if let Ok(s) = val.parse() {
+                    w.set_label_size(s);
+                }
+            }
+            "labelfont" => {
+                if let Ok(f) = val.parse::<usize>() {
+                    w.set_label_font(Font::by_index(f));
+                }
+            }
+            "align" => {
+                if let Ok(a) = val.parse() {
+                    w.set_align(Align::from_bits_truncate(a));
+                }
+            }
+            "hide" => w.hide(),
+            "deactivate" => w.deactivate(),
+            _ => (),
+        }
+    }
+}
+
+fn apply_valuator_props<V: ValuatorExt>(v: &mut V, node: &FluidNode) {
+    let num = |k: &str| node.prop(k).and_then(|s| s.parse::<f64>().ok());
+    if let Some(m) = num("minimum") {
+        v.set_minimum(m);
+    }
+    if let Some(m) = num("maximum") {
+        v.set_maximum(m);
+    }
+    if let Some(s) = num("step") {
+        v.set_step(s, 1);
+    }
+    if let Some(val) = num("value") {
+        v.set_value(val);
+    }
+}
+
+fn apply_input_props<I: InputExt>(i: &mut I, node: &FluidNode) {
+    if let Some(v) = node.prop("value") {
+        i.set_value(v);
+    }
+    if let Some(s) = node.prop("textsize").and_then(|s| s.parse().ok()) {
+        i.set_text_size(s);
+    }
+    if let Some(c) = node.prop("textcolor").and_then(fluid_color) {
+        i.set_text_color(c);
+    }
+}
+
+fn apply_button_props<B: ButtonExt>(b: &mut B, node: &FluidNode) {
+    if node.prop("value") == Some("1") {
+        b.set(true);
+    }
+    if let Some(f) = node.prop("down_box").and_then(fluid_box) {
+        b.set_down_frame(f);
+    }
+}
+
+fn add_menu_items<M: MenuExt>(m: &mut M, prefix: &str, items: &[FluidNode]) {
+    for item in items {
+        let label = item.prop("label").unwrap_or("").replace('/', "\\/");
+        let path = format!("{}{}", prefix, label);
+        if item.class == "Submenu" {
+            add_menu_items(m, &format!("{}/", path), &item.children);
+        } else {
+            let flag = if item.has("divider") {
+                crate::menu::MenuFlag::MenuDivider
+            } else {
+                crate::menu::MenuFlag::Normal
+            };
+            m.add(&path, Shortcut::None, flag, || ());
+        }
+    }
+}
+
+/// The value of a widget, kept when a user interface is rebuilt
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum WidgetState {
+    Text(String),
+    Number(f64),
+    Flag(bool),
+}
+
+/// A user interface loaded from a FLUID file, its named widgets being looked up by name
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut ui = fluid::FluidUi::load("hello.fl").unwrap();
+/// let mut btn: button::Button = ui.get("btn").unwrap();
+/// btn.set_callback(|| println!("Clicked"));
+/// ui.bind_callback("quit_cb()", app::quit);
+/// ui.windows()[0].show();
+/// app.run().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FluidUi {
+    windows: Vec<Window>,
+    widgets: HashMap<String, (Widget, TypeId)>,
+    callbacks: Vec<(Widget, String)>,
+}
+
+impl FluidUi {
+    /// Loads a FLUID file and builds its windows, which are hidden until shown
+    pub fn load<P: AsRef<path::Path>>(path: P) -> Result<FluidUi, FltkError> {
+        FluidUi::from_source(&fs::read_to_string(path)?)
+    }
+
+    /// Builds the windows described by the content of a FLUID file
+    pub fn from_source(src: &str) -> Result<FluidUi, FltkError> {
+        Ok(FluidUi::from_nodes(&parse(src)?))
+    }
+
+    /// Builds the windows described by FLUID items
+    pub fn from_nodes(items: &[FluidNode]) -> FluidUi {
+        FluidUi::build(items, false)
+    }
+
+    /// Builds FLUID items, inside the current group if `in_current_group` is set
+    pub(crate) fn build(items: &[FluidNode], in_current_group: bool) -> FluidUi {
+        let mut ui = FluidUi {
+            windows: vec![],
+            widgets: HashMap::new(),
+            callbacks: vec![],
+        };
+        if in_current_group {
+            ui.build_items(items, true);
+        } else {
+            unsafe {
+                let current = fltk_sys::group::Fl_Group_current();
+                fltk_sys::group::Fl_Group_set_current(ptr::null_mut());
+                ui.build_items(items, true);
+                fltk_sys::group::Fl_Group_set_current(current);
+            }
+        }
+        ui
+    }
+
+    fn build_items(&mut self, items: &[FluidNode], top_level: bool) {
+        for item in items {
+            match item.class.as_str() {
+                // code items can hold the windows, like the functions creating them
+                "Function" | "class" | "widget_class" => self.build_items(&item.children, true),
+                c if c.starts_with("Fl_") => self.build_widget(item, top_level),
+                _ => (),
+            }
+        }
+    }
+
+    fn build_widget(&mut self, node: &FluidNode, top_level: bool) {
+        use crate::{browser::*, button::*, frame::*, group::*, input::*, menu::*, misc::*};
+        use crate::{output::*, text::*, tree::*, valuator::*, window::*};
+        let (x, y, w, h) = fluid_xywh(node);
+        let class = node.class.as_str();
+        macro_rules! widget {
+            ($t:ty) => {{
+                let mut wid = <$t>::new(x, y, w, h, "");
+                apply_props(&mut wid, node);
+                (wid, TypeId::of::<$t>())
+            }};
+        }
+        macro_rules! valuator {
+            ($t:ty) => {{
+                let (mut wid, id) = widget!($t);
+                apply_valuator_props(&mut wid, node);
+                self.add_widget(&wid, id, node);
+            }};
+        }
+        macro_rules! input {
+            ($t:ty) => {{
+                let (mut wid, id) = widget!($t);
+                apply_input_props(&mut wid, node);
+                self.add_widget(&wid, id, node);
+            }};
+        }
+        macro_rules! button {
+            ($t:ty) => {{
+                let (mut wid, id) = widget!($t);
+                apply_button_props(&mut wid, node);
+                self.add_widget(&wid, id, node);
+            }};
+        }
+        macro_rules! menu {
+            ($t:ty) => {{
+                let (mut wid, id) = widget!($t);
+                add_menu_items(&mut wid, "", &node.children);
+                self.add_widget(&wid, id, node);
+            }};
+        }
+        macro_rules! group {
+            ($t:ty) => {{
+                let (wid, id) = widget!($t);
+                self.add_widget(&wid, id, node);
+                self.build_children(&wid, node);
+            }};
+        }
+        match class {
+            "Fl_Window" | "Fl_Double_Window" => {
+                let double = class == "Fl_Double_Window" || node.prop("type") == Some("Double");
+                let win = if double {
+                    let (wid, id) = widget!(DoubleWindow);
+                    self.add_widget(&wid, id, node);
+                    self.build_children(&wid, node);
+                    unsafe { Window::from_widget_ptr(wid.as_widget_ptr()) }
+                } else {
+                    let (wid, id) = widget!(SingleWindow);
+                    self.add_widget(&wid, id, node);
+                    self.build_children(&wid, node);
+                    unsafe { Window::from_widget_ptr(wid.as_widget_ptr()) }
+                };
+                if top_level {
+                    self.windows.push(win);
+                }
+            }
+            "Fl_Group" => group!(Group),
+            "Fl_Pack" => group!(Pack),
+            "Fl_Tabs" => group!(Tabs),
+            "Fl_Scroll" => group!(Scroll),
+            "Fl_Tile" => group!(Tile),
+            "Fl_Wizard" => group!(Wizard),
+            "Fl_Box" => {
+                let (wid, id) = widget!(Frame);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Button" => match node.prop("type") {
+                Some("Toggle") => button!(ToggleButton),
+                Some("Radio") => button!(RadioButton),
+                _ => button!(Button),
+            },
+            "Fl_Return_Button" => button!(ReturnButton),
+            "Fl_Repeat_Button" => button!(RepeatButton),
+            "Fl_Light_Button" => button!(LightButton),
+            "Fl_Check_Button" => button!(CheckButton),
+            "Fl_Round_Button" => button!(RoundButton),
+            "Fl_Input" => match node.prop("type") {
+                Some("Int") => input!(IntInput),
+                Some("Float") => input!(FloatInput),
+                Some("Secret") => input!(SecretInput),
+                Some("Multiline") => input!(MultilineInput),
+                _ => input!(Input),
+            },
+            "Fl_Int_Input" => input!(IntInput),
+            "Fl_Float_Input" => input!(FloatInput),
+            "Fl_Secret_Input" => input!(SecretInput),
+            "Fl_Multiline_Input" => input!(MultilineInput),
+            "Fl_File_Input" => input!(FileInput),
+            "Fl_Output" => match node.prop("type") {
+                Some("Multiline") => input!(MultilineOutput),
+                _ => input!(Output),
+            },
+            "Fl_Multiline_Output" => input!(MultilineOutput),
+            "Fl_Text_Display" | "Fl_Text_Editor" => {
+                let (mut wid, id) = if class == "Fl_Text_Editor" {
+                    let (wid, id) = widget!(TextEditor);
+                    (
+                        unsafe { TextDisplay::from_widget_ptr(wid.as_widget_ptr()) },
+                        id,
+                    )
+                } else {
+                    widget!(TextDisplay)
+                };
+                wid.set_buffer(TextBuffer::default());
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Slider" => match node.prop("type") {
+                Some("Horizontal") | Some("Horz Knob") => valuator!(HorSlider),
+                Some("Vert Fill") => valuator!(FillSlider),
+                Some("Horz Fill") => valuator!(HorFillSlider),
+                _ => valuator!(Slider),
+            },
+            "Fl_Value_Slider" => match node.prop("type") {
+                Some("Horizontal") | Some("Horz Knob") => valuator!(HorValueSlider),
+                _ => valuator!(ValueSlider),
+            },
+            "Fl_Dial" => valuator!(Dial),
+            "Fl_Roller" => valuator!(Roller),
+            "Fl_Counter" => valuator!(Counter),
+            "Fl_Adjuster" => valuator!(Adjuster),
+            "Fl_Scrollbar" => valuator!(Scrollbar),
+            "Fl_Value_Input" => valuator!(ValueInput),
+            "Fl_Value_Output" => valuator!(ValueOutput),
+            "Fl_Spinner" => {
+                let (wid, id) = widget!(Spinner);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Progress" => {
+                let (wid, id) = widget!(Progress);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Clock" => {
+                let (wid, id) = widget!(Clock);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Chart" => {
+                let (wid, id) = widget!(Chart);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Menu_Bar" => menu!(MenuBar),
+            "Fl_Menu_Button" => menu!(MenuButton),
+            "Fl_Choice" => menu!(Choice),
+            "Fl_Browser" => match node.prop("type") {
+                Some("Hold") => {
+                    let (wid, id) = widget!(HoldBrowser);
+                    self.add_widget(&wid, id, node);
+                }
+                Some("Multi") => {
+                    let (wid, id) = widget!(MultiBrowser);
+                    self.add_widget(&wid, id, node);
+                }
+                _ => {
+                    let (wid, id) = widget!(Browser);
+                    self.add_widget(&wid, id, node);
+                }
+            },
+            "Fl_Hold_Browser" => {
+                let (wid, id) = widget!(HoldBrowser);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Multi_Browser" => {
+                let (wid, id) = widget!(MultiBrowser);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_File_Browser" => {
+                let (wid, id) = widget!(FileBrowser);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Check_Browser" => {
+                let (wid, id) = widget!(CheckBrowser);
+                self.add_widget(&wid, id, node);
+            }
+            "Fl_Tree" => {
+                let (wid, id) = widget!(Tree);
+                self.add_widget(&wid, id, node);
+            }
+            // unsupported widgets are replaced by a frame keeping their place
+            _ => {
+                let (wid, id) = widget!(Frame);
+                self.add_widget(&wid, id, node);
+            }
+        }
+    }
+
+    fn build_children<G: GroupExt>(&mut self, grp: &G, node: &FluidNode) {
+        grp.begin();
+        let mut resizable = None;
+        for child in &node.children {
+            if child.class.starts_with("Fl_") {
+                self.build_widget(child, false);
+                if child.has("resizable") {
+                    resizable = grp.child(grp.children() - 1);
+                }
+            }
+        }
+        grp.end();
+        if let Some(r) = resizable {
+            let r = unsafe { Widget::from_widget_ptr(r.as_widget_ptr()) };
+            grp.resizable(&r);
+        }
+    }
+
+    fn add_widget<W: WidgetExt>(&mut self, wid: &W, id: TypeId, node: &FluidNode) {
+        let wid = unsafe { Widget::from_widget_ptr(wid.as_widget_ptr()) };
+        if let Some(cb) = node.prop("callback") {
+            self.callbacks.push((wid.clone(), cb.to_string()));
+        }
+        if !node.name.is_empty() {
+            self.widgets.insert(node.name.clone(), (wid, id));
+        }
+    }
+
+    /// Returns the top-level windows, in the order of the file
+    pub fn windows(&self) -> Vec<Window> {
+        self.windows.clone()
+    }
+
+    /// Returns the names of the named widgets
+    pub fn names(&self) -> Vec<String> {
+        self.widgets.keys().cloned().collect()
+    }
+
+    /// Returns a named widget as a base widget
+    pub fn widget(&self, name: &str) -> Option<Widget> {
+        self.widgets.get(name).map(|(w, _)| w.clone())
+    }
+
+    /// Returns a named widget, None if it's missing or isn't of type W.
+    /// Widgets of FLUID types which have no equivalent are frames
+    pub fn get<W: WidgetBase + 'static>(&self, name: &str) -> Option<W> {
+        let (w, id) = self.widgets.get(name)?;
+        if *id == TypeId::of::<W>() && !w.was_deleted() {
+            Some(unsafe { W::from_widget_ptr(w.as_widget_ptr()) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of a named widget, for the input, valuator, button and text widgets
+    pub(crate) fn widget_state(&self, name: &str) -> Option<WidgetState> {
+        use crate::{button::*, input::*, output::*, text::*, valuator::*};
+        let (_, id) = self.widgets.get(name)?;
+        macro_rules! get_state {
+            ($variant:ident, [$($t:ty),+], |$w:ident| $get:expr) => {
+                $(
+                    if *id == TypeId::of::<$t>() {
+                        let $w: $t = self.get(name)?;
+                        return Some(WidgetState::$variant($get));
+                    }
+                )+
+            };
+        }
+        get_state!(
+            Text,
+            [
+                Input,
+                IntInput,
+                FloatInput,
+                SecretInput,
+                MultilineInput,
+                FileInput,
+                Output,
+                MultilineOutput
+            ],
+            |w| w.value()
+        );
+        get_state!(
+            Number,
+            [
+                Slider,
+                HorSlider,
+                FillSlider,
+                HorFillSlider,
+                ValueSlider,
+                HorValueSlider,
+                Dial,
+                Roller,
+                Counter,
+                Adjuster,
+                Scrollbar,
+                ValueInput,
+                ValueOutput
+            ],
+            |w| w.value()
+        );
+        get_state!(
+            Flag,
+            [
+                Button,
+                ToggleButton,
+                RadioButton,
+                LightButton,
+                CheckButton,
+                RoundButton
+            ],
+            |w| w.value()
+        );
+        get_state!(Text, [TextDisplay, TextEditor], |w| w
+            .buffer()
+            .map(|b| b.text())
+            .unwrap_or_default());
+        None
+    }
+
+    /// Sets the value of a named widget, if it's of the same kind as the value
+    #[allow(unused_mut)]
+    pub(crate) fn set_widget_state(&self, name: &str, state: &WidgetState) {
+        use crate::{button::*, input::*, output::*, text::*, valuator::*};
+        let id = match self.widgets.get(name) {
+            Some((_, id)) => *id,
+            None => return,
+        };
+        macro_rules! set_state {
+            ($variant:ident, [$($t:ty),+], |$w:ident, $v:ident| $set:expr) => {
+                if let WidgetState::$variant($v) = state {
+                    $(
+                        if id == TypeId::of::<$t>() {
+                            if let Some(mut $w) = self.get::<$t>(name) {
+                                $set;
+                            }
+                            return;
+                        }
+                    )+
+                }
+            };
+        }
+        set_state!(
+            Text,
+            [
+                Input,
+                IntInput,
+                FloatInput,
+                SecretInput,
+                MultilineInput,
+                FileInput,
+                Output,
+                MultilineOutput
+            ],
+            |w, v| w.set_value(v)
+        );
+        set_state!(
+            Number,
+            [
+                Slider,
+                HorSlider,
+                FillSlider,
+                HorFillSlider,
+                ValueSlider,
+                HorValueSlider,
+                Dial,
+                Roller,
+                Counter,
+                Adjuster,
+                Scrollbar,
+                ValueInput,
+                ValueOutput
+            ],
+            |w, v| w.set_value(*v)
+        );
+        set_state!(
+            Flag,
+            [
+                Button,
+                ToggleButton,
+                RadioButton,
+                LightButton,
+                CheckButton,
+                RoundButton
+            ],
+            |w, v| w.set_value(*v)
+        );
+        set_state!(Text, [TextDisplay, TextEditor], |w, v| {
+            if let Some(mut b) = w.buffer() {
+                b.set_text(v);
+            }
+        });
+    }
+
+    /// Sets a callback on the widgets whose FLUID callback is the given code or name,
+    /// and returns the number of widgets it was set on
+    pub fn bind_callback<F: FnMut() + Clone + 'static>(&mut self, callback: &str, cb: F) -> usize {
+        let mut count = 0;
+        for (w, c) in self.callbacks.iter_mut() {
+            if c.trim() == callback.trim() && !w.was_deleted() {
+                w.set_callback(cb.clone());
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod fluid {
+    use super::*;
+    #[test]
+    fn fluid_parsing() {
+        let src = r#"# data file for the Fltk User Interface Designer (fluid)
+version 1.0400
+header_name {.h}
+code_name {.cxx}
+Function {make_window()} {open
+} {
+  Fl_Window main_win {
+    label {Hello \{world\}} open
+    xywh {100 100 400 300} type Double visible
+  } {
+    Fl_Button btn {
+      label Click
+      callback {quit_cb();}
+      xywh {10 10 80 30} box FLAT_BOX
+    }
+  }
+}
+"#;
+        let items = parse(src).unwrap();
+        assert_eq!(items.len(), 1);
+        let win = &items[0].children[0];
+        assert_eq!(win.class, "Fl_Window");
+        assert_eq!(win.name, "main_win");
+        assert_eq!(win.prop("label"), Some("Hello {world}"));
+        assert_eq!(win.prop("type"), Some("Double"));
+        assert!(win.has("visible"));
+        let btn = &win.children[0];
+        assert_eq!(btn.prop("callback"), Some("quit_cb();"));
+        assert_eq!(fluid_xywh(btn), (10, 10, 80, 30));
+        assert_eq!(fluid_box("FLAT_BOX"), Some(FrameType::FlatBox));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::app;
+use crate::button::{Button, CheckButton, ReturnButton};
+use crate::input::Input;
+use crate::menu::Choice;
+use crate::prelude::*;
+use crate::valuator::{Counter, CounterType, HorValueSlider, ValueInput};
+use crate::widget::Widget;
+use crate::window::Window;
+use std::{cell::Cell, rc::Rc};
+
+/// Derives Form for a struct with named fields, each field becoming a row of the form.
+/// The fields' types implement FormValue, and the `form` attribute customizes them:
+/// `label = "..."`, `widget = "input" | "check" | "spinner" | "slider" | "value_input" | "choice"`,
+/// `range = "min..max"`, `step = ...`, `choices = "a|b|c"` and `skip`
+/// ```no_run
+/// use fltk::*;
+/// #[derive(Default, form::Form)]
+/// struct Settings {
+///     #[form(label = "User name")]
+///     name: String,
+///     #[form(widget = "spinner", range = "1..10")]
+///     threads: u32,
+///     #[form(widget = "slider", range = "0..1", step = 0.1)]
+///     volume: f64,
+///     #[form(widget = "choice", choices = "Light|Dark")]
+///     theme: String,
+///     autosave: bool,
+/// }
+/// let app = app::App::default();
+/// let mut settings = Settings::default();
+/// if form::edit(&mut settings, "Settings") {
+///     println!("{} threads", settings.threads);
+/// }
+/// ```
+pub use fltk_derive::Form;
+
+/// The height of the rows of a form
+const ROW_HEIGHT: i32 = 30;
+/// The vertical space between the rows of a form
+const ROW_SPACING: i32 = 5;
+
+/// The value of a form field
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    /// The text of an input or a choice
+    Text(String),
+    /// The value of a spinner, slider or value input
+    Number(f64),
+    /// The state of a check button
+    Flag(bool),
+}
+
+/// The widget of a form field
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FieldKind {
+    /// A text input
+    Input,
+    /// A check button
+    Check,
+    /// A counter with arrows
+    Spinner,
+    /// A horizontal slider showing its value
+    Slider,
+    /// A numeric input
+    ValueInput,
+    /// A drop-down choice of texts
+    Choice,
+}
+
+/// Describes a form field, as generated by the Form derive
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDesc {
+    /// The name of the struct field
+    pub name: &'static str,
+    /// The label shown next to the widget
+    pub label: String,
+    /// The widget kind
+    pub kind: FieldKind,
+    /// The range of numeric fields
+    pub range: Option<(f64, f64)>,
+    /// The step of numeric fields
+    pub step: Option<f64>,
+    /// The choices of choice fields
+    pub choices: Vec<String>,
+}
+
+/// The types which can be edited by form fields
+pub trait FormValue: Sized {
+    /// The widget used when the field doesn't set one
+    fn default_kind() -> FieldKind;
+    /// The step used when the field doesn't set one
+    fn default_step() -> Option<f64> {
+        None
+    }
+    /// Converts to a field value
+    fn to_field(&self) -> FieldValue;
+    /// Converts from a field value, None meaning the field's value is kept
+    fn from_field(val: &FieldValue) -> Option<Self>;
+}
+
+impl FormValue for String {
+    fn default_kind() -> FieldKind {
+        FieldKind::Input
+    }
+
+    fn to_field(&self) -> FieldValue {
+        FieldValue::Text(self.clone())
+    }
+
+    fn from_field(val: &FieldValue) -> Option<Self> {
+        match val {
+            FieldValue::Text(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl FormValue for bool {
+    fn default_kind() -> FieldKind {
+        FieldKind::Check
+    }
+
+    fn to_field(&self) -> FieldValue {
+        FieldValue::Flag(*self)
+    }
+
+    fn from_field(val: &FieldValue) -> Option<Self> {
+        match val {
+            FieldValue::Flag(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_int_form_value {
+    ($($t:ty),+) => {
+        $(
+            impl FormValue for $t {
+                fn default_kind() -> FieldKind {
+                    FieldKind::Spinner
+                }
+
+                fn default_step() -> Option<f64> {
+                    Some(1.)
+                }
+
+                fn to_field(&self) -> FieldValue {
+                    FieldValue::Number(*self as f64)
+                }
+
+                fn from_field(val: &FieldValue) -> Option<Self> {
+                    match val {
+                        FieldValue::Number(n) => {
+                            let n = n.round();
+                            if n >= <$t>::MIN as f64 && n <= <$t>::MAX as f64 {
+                                Some(n as $t)
+                            } else {
+                                None
+                            }
+                        }
+                        FieldValue::Text(s) => s.trim().parse().ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_int_form_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_float_form_value {
+    ($($t:ty),+) => {
+        $(
+            impl FormValue for $t {
+                fn default_kind() -> FieldKind {
+                    FieldKind::ValueInput
+                }
+
+                fn to_field(&self) -> FieldValue {
+                    FieldValue::Number(*self as f64)
+                }
+
+                fn from_field(val: &FieldValue) -> Option<Self> {
+                    match val {
+                        FieldValue::Number(n) => Some(*n as $t),
+                        FieldValue::Text(s) => s.trim().parse().ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_float_form_value!(f32, f64);
+
+#[derive(Debug, Clone)]
+enum FieldWidget {
+    Input(Input),
+    Check(CheckButton),
+    Spinner(Counter),
+    Slider(HorValueSlider),
+    ValueInput(ValueInput),
+    Choice(Choice),
+}
+
+/// The widgets of a form, one per field
+#[derive(Debug, Clone)]
+pub struct FormWidgets {
+    fields: Vec<(FieldDesc, FieldWidget)>,
+    height: i32,
+}
+
+impl FormWidgets {
+    /// Builds the rows of the fields in the current group, the labels taking the left third of the width
+    pub fn build(fields: Vec<FieldDesc>, x: i32, y: i32, w: i32) -> FormWidgets {
+        let lw = w / 3;
+        let (wx, ww) = (x + lw, w - lw);
+        let mut rows = vec![];
+        let mut wy = y;
+        for desc in fields {
+            let label = desc.label.as_str();
+            let widget = match desc.kind {
+                FieldKind::Input => FieldWidget::Input(Input::new(wx, wy, ww, ROW_HEIGHT, label)),
+                FieldKind::Check => {
+                    FieldWidget::Check(CheckButton::new(wx, wy, ww, ROW_HEIGHT, label))
+                }
+                FieldKind::Spinner => {
+                    let mut c = Counter::new(wx, wy, ww, ROW_HEIGHT, label);
+                    c.set_type(CounterType::Simple);
+                    c.set_align(Align::Left);
+                    FieldWidget::Spinner(c)
+                }
+                FieldKind::Slider => {
+                    let mut s = HorValueSlider::new(wx, wy, ww, ROW_HEIGHT, label);
+                    s.set_align(Align::Left);
+                    FieldWidget::Slider(s)
+                }
+                FieldKind::ValueInput => {
+                    FieldWidget::ValueInput(ValueInput::new(wx, wy, ww, ROW_HEIGHT, label))
+                }
+                FieldKind::Choice => {
+                    let mut c = Choice::new(wx, wy, ww, ROW_HEIGHT, label);
+                    for choice in &desc.choices {
+                        c.add_choice(choice);
+                    }
+                    FieldWidget::Choice(c)
+                }
+            };
+            let numeric = match &widget {
+                FieldWidget::Spinner(c) => Some(Box::new(c.clone()) as Box<dyn ValuatorExt>),
+                FieldWidget::Slider(s) => Some(Box::new(s.clone()) as Box<dyn ValuatorExt>),
+                FieldWidget::ValueInput(v) => Some(Box::new(v.clone()) as Box<dyn ValuatorExt>),
+                _ => None,
+            };
+            if let Some(mut v) = numeric {
+                if let Some((min, max)) = desc.range {
+                    v.set_range(min, max);
+                } else {
+                    v.set_range(f64::MIN, f64::MAX);
+                }
+                if let Some(step) = desc.step {
+                    v.set_step(step, 1);
+                }
+            }
+            rows.push((desc, widget));
+            wy += ROW_HEIGHT + ROW_SPACING;
+        }
+        FormWidgets {
+            fields: rows,
+            height: (wy - y - ROW_SPACING).max(0),
+        }
+    }
+
+    /// Returns the height taken by the rows
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Returns the value of a field, None for a choice without a chosen option,
+    /// so that storing the form keeps a struct value matching none of the options
+    pub fn value(&self, name: &str) -> Option<FieldValue> {
+        let (_, w) = self.fields.iter().find(|(d, _)| d.name == name)?;
+        Some(match w {
+            FieldWidget::Input(i) => FieldValue::Text(i.value()),
+            FieldWidget::Check(c) => FieldValue::Flag(c.is_checked()),
+            FieldWidget::Spinner(c) => FieldValue::Number(c.value()),
+            FieldWidget::Slider(s) => FieldValue::Number(s.value()),
+            FieldWidget::ValueInput(v) => FieldValue::Number(v.value()),
+            FieldWidget::Choice(c) => FieldValue::Text(c.choice()?),
+        })
+    }
+
+    /// Sets the value of a field, ignoring values of another kind
+    pub fn set_value(&mut self, name: &str, val: &FieldValue) {
+        let (_, w) = match self.fields.iter_mut().find(|(d, _)| d.name == name) {
+            Some(f) => f,
+            None => return,
+        };
+        match (w, val) {
+            (FieldWidget::Input(i), FieldValue::Text(s)) => i.set_value(s),
+            (FieldWidget::Input(i), FieldValue::Number(n)) => i.set_value(&n.to_string()),
+            (FieldWidget::Check(c), FieldValue::Flag(b)) => c.set_checked(*b),
+            (FieldWidget::Spinner(c), FieldValue::Number(n)) => c.set_value(*n),
+            (FieldWidget::Slider(s), FieldValue::Number(n)) => s.set_value(*n),
+            (FieldWidget::ValueInput(v), FieldValue::Number(n)) => v.set_value(*n),
+            (FieldWidget::Choice(c), FieldValue::Text(s)) => {
+                let idx = (0..c.size()).find(|i| c.text(*i).as_deref() == Some(s.as_str()));
+                if let Some(idx) = idx {
+                    c.set_value(idx as i32);
+                }
+            }
+            (FieldWidget::Choice(c), FieldValue::Number(n)) => {
+                c.set_value(*n as i32);
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the widget of a field
+    pub fn widget(&self, name: &str) -> Option<Widget> {
+        let (_, w) = self.fields.iter().find(|(d, _)| d.name == name)?;
+        unsafe {
+            let ptr = match w {
+                FieldWidget::Input(i) => i.as_widget_ptr(),
+                FieldWidget::Check(c) => c.as_widget_ptr(),
+                FieldWidget::Spinner(c) => c.as_widget_ptr(),
+                FieldWidget::Slider(s) => s.as_widget_ptr(),
+                FieldWidget::ValueInput(v) => v.as_widget_ptr(),
+                FieldWidget::Choice(c) => c.as_widget_ptr(),
+            };
+            Some(Widget::from_widget_ptr(ptr))
+        }
+    }
+}
+
+/// A struct editable using a generated form, usually derived
+pub trait Form {
+    /// Describes the fields of the form
+    fn form_fields() -> Vec<FieldDesc>;
+    /// Sets the widgets of the form to the values of the struct
+    fn load_form(&self, form: &mut FormWidgets);
+    /// Sets the values of the struct to the widgets of the form
+    fn store_form(&mut self, form: &FormWidgets);
+    /// Builds the form in the current group and loads the values of the struct
+    fn build_form(&self, x: i32, y: i32, w: i32) -> FormWidgets
+    where
+        Self: Sized,
+    {
+        let mut form = FormWidgets::build(Self::form_fields(), x, y, w);
+        self.load_form(&mut form);
+        form
+    }
+}
+
+/// Shows a modal dialog editing a struct, whose values are stored when Ok is clicked, returns whether they were
+pub fn edit<F: Form>(value: &mut F, title: &str) -> bool {
+    let (w, pad) = (400, 10);
+    let rows = F::form_fields().len() as i32;
+    let h = pad * 3 + rows * (ROW_HEIGHT + ROW_SPACING) + ROW_HEIGHT;
+    let mut win = Window::new(100, 100, w, h, title);
+    let form = value.build_form(pad, pad, w - 2 * pad);
+    let mut ok = ReturnButton::new(
+        w - 2 * (80 + pad),
+        h - pad - ROW_HEIGHT,
+        80,
+        ROW_HEIGHT,
+        "Ok",
+    );
+    let mut cancel = Button::new(w - 80 - pad, h - pad - ROW_HEIGHT, 80, ROW_HEIGHT, "Cancel");
+    win.end();
+    win.make_modal(true);
+    win.show();
+    let accepted = Rc::new(Cell::new(false));
+    ok.set_callback({
+        let accepted = accepted.clone();
+        let mut win = win.clone();
+        move || {
+            accepted.set(true);
+            win.hide();
+        }
+    });
+    cancel.set_callback({
+        let mut win = win.clone();
+        move || win.hide()
+    });
+    while win.shown() {
+        app::wait();
+    }
+    if accepted.get() {
+        value.store_form(&form);
+    }
+    Window::delete(win);
+    accepted.get()
+}
+
+#[cfg(test)]
+mod form {
+    use super::*;
+    #[test]
+    fn field_values() {
+        assert_eq!(u8::from_field(&FieldValue::Number(3.4)), Some(3));
+        assert_eq!(u8::from_field(&FieldValue::Number(-1.)), None);
+        assert_eq!(
+            i32::from_field(&FieldValue::Text(" 42".to_string())),
+            Some(42)
+        );
+        assert_eq!(f32::from_field(&FieldValue::Number(0.5)), Some(0.5));
+        assert_eq!(
+            bool::from_field(&FieldValue::Text("true".to_string())),
+            None
+        );
+        assert_eq!(String::default_kind(), FieldKind::Input);
+        assert_eq!(u32::default_step(), Some(1.));
+    }
+}
@@ -1,10 +1,14 @@
+use crate::app;
+use crate::draw;
 use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::frame::*;
 use std::{
+    cell::{Cell, RefCell},
     ffi::{CStr, CString},
     mem,
     os::raw,
+    rc::Rc,
 };
 
 /// Creates a new frame, an equivalent of Fl_Box
@@ -13,3 +17,317 @@ pub struct Frame {
     _inner: *mut Fl_Box,
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
+
+#[derive(Debug, Default, Copy, Clone)]
+struct FrameSelection {
+    selectable: bool,
+    anchor: usize,
+    cursor: usize,
+}
+
+thread_local! {
+    /// The text selections of the selectable frames, shared with their draw and handle methods
+    static FRAME_SELECTIONS: RefCell<crate::utils::WidgetMap<Rc<Cell<FrameSelection>>>> = RefCell::new(Default::default());
+}
+
+/// Splits text into lines as byte ranges, breaking at newlines and, if a width is given, between words
+pub(crate) fn wrap_lines<F: Fn(&str) -> f64>(
+    text: &str,
+    max_w: Option<f64>,
+    measure: F,
+) -> Vec<(usize, usize)> {
+    let mut lines = vec![];
+    let mut start = 0;
+    for part in text.split('\n') {
+        let end = start + part.len();
+        let mut line_start = start;
+        if let Some(max_w) = max_w {
+            let mut last_break = None;
+            for (i, c) in part.char_indices() {
+                let i = start + i;
+                if c == ' ' {
+                    last_break = Some(i);
+                }
+                if i > line_start && measure(&text[line_start..i + c.len_utf8()]) > max_w {
+                    if let Some(b) = last_break.filter(|b| *b > line_start) {
+                        lines.push((line_start, b));
+                        line_start = b + 1;
+                    } else if c != ' ' {
+                        lines.push((line_start, i));
+                        line_start = i;
+                    }
+                    last_break = None;
+                }
+            }
+        }
+        lines.push((line_start, end));
+        start = end + 1;
+    }
+    lines
+}
+
+/// Returns the byte range of the word around a position
+fn word_bounds(text: &str, pos: usize) -> (usize, usize) {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let start = text[..pos]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word(*c))
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(pos);
+    let end = text[pos..]
+        .char_indices()
+        .find(|(_, c)| !is_word(*c))
+        .map(|(i, _)| pos + i)
+        .unwrap_or_else(|| text.len());
+    (start, end)
+}
+
+impl Frame {
+    fn selection_state(&self) -> Option<Rc<Cell<FrameSelection>>> {
+        FRAME_SELECTIONS.with(|s| s.borrow_mut().get(self).cloned())
+    }
+
+    fn text_selection(&self) -> FrameSelection {
+        self.selection_state()
+            .map(|sel| sel.get())
+            .unwrap_or_default()
+    }
+
+    /// Returns the text lines of the label and their origin
+    fn text_layout(&self) -> (Vec<(usize, usize)>, i32, i32) {
+        let label = self.label();
+        let align = self.align();
+        draw::set_font(self.label_font(), self.label_size() as u32);
+        let (x, y, w, h) = (
+            self.x() + 3,
+            self.y() + 3,
+            self.width() - 6,
+            self.height() - 6,
+        );
+        let max_w = if align.contains(Align::Wrap) {
+            Some(w as f64)
+        } else {
+            None
+        };
+        let lines = wrap_lines(&label, max_w, draw::width);
+        let text_h = lines.len() as i32 * draw::height();
+        let ty = if align.contains(Align::Top) {
+            y
+        } else if align.contains(Align::Bottom) {
+            y + h - text_h
+        } else {
+            y + (h - text_h) / 2
+        };
+        (lines, x, ty)
+    }
+
+    fn line_x(&self, line: &str, x: i32) -> i32 {
+        let align = self.align();
+        let w = self.width() - 6;
+        let lw = draw::width(line) as i32;
+        if align.contains(Align::Left) {
+            x
+        } else if align.contains(Align::Right) {
+            x + w - lw
+        } else {
+            x + (w - lw) / 2
+        }
+    }
+
+    /// Returns the byte position in the label nearest to a point
+    fn text_position(&self, px: i32, py: i32) -> usize {
+        let label = self.label();
+        let (lines, x, y) = self.text_layout();
+        let line_h = draw::height().max(1);
+        let idx = ((py - y) / line_h).clamp(0, lines.len() as i32 - 1) as usize;
+        let (start, end) = lines[idx];
+        let line = &label[start..end];
+        let lx = self.line_x(line, x);
+        let mut pos = end;
+        let mut prev = 0.;
+        for (i, c) in line.char_indices() {
+            let next = draw::width(&line[..i + c.len_utf8()]);
+            if (px - lx) as f64 <= (prev + next) / 2. {
+                pos = start + i;
+                break;
+            }
+            prev = next;
+        }
+        pos
+    }
+
+    fn draw_text_frame(f: &mut Frame, state: &Cell<FrameSelection>) {
+        let label = f.label();
+        let sel = state.get();
+        let (s0, s1) = (sel.anchor.min(sel.cursor), sel.anchor.max(sel.cursor));
+        draw::draw_box(f.frame(), f.x(), f.y(), f.width(), f.height(), f.color());
+        let (lines, x, mut y) = f.text_layout();
+        let line_h = draw::height();
+        let sel_color = f.selection_color();
+        draw::push_clip(f.x(), f.y(), f.width(), f.height());
+        for (start, end) in lines {
+            let line = &label[start..end];
+            let lx = f.line_x(line, x);
+            if sel.selectable && s0 < s1 && s0 <= end && s1 >= start {
+                let a = s0.max(start) - start;
+                let b = s1.min(end) - start;
+                let ax = lx + draw::width(&line[..a]) as i32;
+                let bx = lx + draw::width(&line[..b]) as i32;
+                draw::draw_rect_fill(ax, y, bx - ax, line_h, sel_color);
+            }
+            draw::set_draw_color(f.label_color());
+            draw::draw_text(line, lx, y + line_h - draw::descent());
+            y += line_h;
+        }
+        draw::pop_clip();
+    }
+
+    fn handle_text_frame(f: &mut Frame, state: &Cell<FrameSelection>, ev: Event) -> bool {
+        let mut sel = state.get();
+        if !sel.selectable {
+            return false;
+        }
+        match ev {
+            Event::Enter => {
+                draw::set_cursor(Cursor::Insert);
+                true
+            }
+            Event::Leave => {
+                draw::set_cursor(Cursor::Default);
+                true
+            }
+            Event::Focus | Event::Unfocus => true,
+            Event::Push => {
+                let _ = f.take_focus();
+                let (ex, ey) = app::event_coords();
+                let pos = f.text_position(ex, ey);
+                if app::event_clicks() {
+                    let (a, b) = word_bounds(&f.label(), pos);
+                    sel.anchor = a;
+                    sel.cursor = b;
+                } else {
+                    sel.anchor = pos;
+                    sel.cursor = pos;
+                }
+                state.set(sel);
+                f.redraw();
+                true
+            }
+            Event::Drag => {
+                let (ex, ey) = app::event_coords();
+                sel.cursor = f.text_position(ex, ey);
+                state.set(sel);
+                f.redraw();
+                true
+            }
+            Event::Released => {
+                let txt = f.selected_text();
+                if !txt.is_empty() {
+                    app::copy2(&txt);
+                }
+                true
+            }
+            Event::KeyDown if app::event_state().contains(Shortcut::Ctrl) => {
+                let key = app::event_key();
+                if key == Key::from_char('c') {
+                    let txt = f.selected_text();
+                    if !txt.is_empty() {
+                        app::copy(&txt);
+                    }
+                    true
+                } else if key == Key::from_char('a') {
+                    sel.anchor = 0;
+                    sel.cursor = f.label().len();
+                    state.set(sel);
+                    f.redraw();
+                    true
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Sets whether the label wraps within the bounds of the frame
+    pub fn set_wrap(&mut self, flag: bool) {
+        assert!(!self.was_deleted());
+        let align = if flag {
+            self.align() | Align::Wrap | Align::Inside
+        } else {
+            self.align() & !Align::Wrap
+        };
+        self.set_align(align);
+        self.redraw();
+    }
+
+    /// Returns whether the label wraps within the bounds of the frame
+    pub fn wrap(&self) -> bool {
+        assert!(!self.was_deleted());
+        self.align().contains(Align::Wrap)
+    }
+
+    /// Sets whether the text of the label can be selected using the mouse and copied using Ctrl-C.
+    /// Double-clicking selects a word and Ctrl-A selects the whole text.
+    /// The label is then drawn by the frame as plain text, symbols and images of the label aren't shown.
+    /// This sets a custom draw method and handler, which replace any set using draw/handle
+    pub fn set_selectable(&mut self, flag: bool) {
+        assert!(!self.was_deleted());
+        let sel = FrameSelection {
+            selectable: flag,
+            ..Default::default()
+        };
+        match self.selection_state() {
+            Some(state) => state.set(sel),
+            None if flag => {
+                let state = Rc::new(Cell::new(sel));
+                FRAME_SELECTIONS.with(|s| s.borrow_mut().insert(self, state.clone()));
+                let st = state.clone();
+                self.draw2(move |f| Frame::draw_text_frame(f, &st));
+                self.handle2(move |f, ev| Frame::handle_text_frame(f, &state, ev));
+            }
+            None => (),
+        }
+        self.redraw();
+    }
+
+    /// Returns whether the text of the label can be selected
+    pub fn selectable(&self) -> bool {
+        assert!(!self.was_deleted());
+        self.text_selection().selectable
+    }
+
+    /// Returns the selected text of the label
+    pub fn selected_text(&self) -> String {
+        assert!(!self.was_deleted());
+        let sel = self.text_selection();
+        let label = self.label();
+        let (a, b) = (sel.anchor.min(sel.cursor), sel.anchor.max(sel.cursor));
+        if !sel.selectable || b > label.len() {
+            return String::new();
+        }
+        label[a..b].to_string()
+    }
+}
+
+#[cfg(test)]
+mod frame {
+    use super::*;
+    #[test]
+    fn text_wrapping() {
+        let measure = |s: &str| s.chars().count() as f64;
+        assert_eq!(
+            wrap_lines("hello big world", Some(9.), measure),
+            vec![(0, 9), (10, 15)]
+        );
+        assert_eq!(wrap_lines("ab\ncd", None, measure), vec![(0, 2), (3, 5)]);
+        assert_eq!(
+            wrap_lines("abcdef", Some(4.), measure),
+            vec![(0, 4), (4, 6)]
+        );
+        assert_eq!(word_bounds("say hello there", 6), (4, 9));
+        assert_eq!(word_bounds("a b", 1), (0, 1));
+    }
+}
@@ -1,12 +1,16 @@
+use crate::app;
+use crate::draw;
 use crate::image::Image;
 pub use crate::prelude::*;
 use crate::widget::*;
 use fltk_sys::group::*;
 use std::{
+    cell::RefCell,
     ffi::{CStr, CString},
     mem,
     ops::{Deref, DerefMut},
     os::raw,
+    rc::Rc,
 };
 
 /// Creates a widget group
@@ -227,6 +231,266 @@ impl Tabs {
         assert!(!self.was_deleted());
         unsafe { mem::transmute(Fl_Tabs_tab_align(self._inner)) }
     }
+
+    /// Returns the tab group whose tab is at the given position, if any
+    pub fn which(&mut self, x: i32, y: i32) -> Option<Group> {
+        assert!(!self.was_deleted());
+        unsafe {
+            let ptr = Fl_Tabs_which(self._inner, x, y);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Group::from_widget_ptr(
+                    ptr as *mut fltk_sys::widget::Fl_Widget,
+                ))
+            }
+        }
+    }
+
+    /// Shows a close button on every tab, clicking it calls the callback with the tab's group.
+    /// The callback decides whether to actually close the tab, for example by removing the group.
+    /// Since the tab width follows its label, labels should end with a few spaces to leave room for the button.
+    /// This installs custom handle and draw methods, replacing any set previously
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut tabs = group::Tabs::new(10, 10, 380, 280, "");
+    /// let grp = group::Group::new(10, 35, 380, 255, "Document 1    ");
+    /// grp.end();
+    /// tabs.end();
+    /// tabs.set_close_callback(|tabs, grp| {
+    ///     tabs.remove(&grp);
+    ///     tabs.redraw();
+    /// });
+    /// ```
+    pub fn set_close_callback<F: FnMut(&mut Tabs, Group) + 'static>(&mut self, cb: F) {
+        assert!(!self.was_deleted());
+        let state = self.tab_state();
+        state.borrow_mut().close_cb = Some(Box::new(cb));
+        self.redraw();
+    }
+
+    /// Returns whether the tabs can be reordered by dragging them
+    pub fn reorderable(&self) -> bool {
+        assert!(!self.was_deleted());
+        TABS_STATES.with(|m| {
+            m.borrow_mut()
+                .get(self)
+                .map_or(false, |st| st.borrow().reorderable)
+        })
+    }
+
+    /// Sets whether the tabs can be reordered by dragging them.
+    /// This installs custom handle and draw methods, replacing any set previously, but keeps close buttons working.
+    /// Disabling it on tabs without close buttons restores the default handle and draw methods
+    pub fn set_reorderable(&mut self, flag: bool) {
+        assert!(!self.was_deleted());
+        if flag {
+            self.tab_state().borrow_mut().reorderable = true;
+            return;
+        }
+        let state = TABS_STATES.with(|m| m.borrow_mut().get(self).cloned());
+        if let Some(state) = state {
+            state.borrow_mut().reorderable = false;
+            if state.borrow().close_cb.is_none() {
+                TABS_STATES.with(|m| m.borrow_mut().remove(self));
+                unsafe {
+                    let _ = self.handle_data();
+                    let _ = self.draw_data();
+                }
+                self.redraw();
+            }
+        }
+    }
+
+    /// Returns the state of the close buttons and reordering, installing their handle and draw methods the first time
+    fn tab_state(&mut self) -> Rc<RefCell<TabsState>> {
+        if let Some(state) = TABS_STATES.with(|m| m.borrow_mut().get(self).cloned()) {
+            return state;
+        }
+        let state = Rc::new(RefCell::new(TabsState::default()));
+        TABS_STATES.with(|m| m.borrow_mut().insert(self, state.clone()));
+        let st = state.clone();
+        self.draw2(move |t| {
+            if st.borrow().close_cb.is_none() {
+                return;
+            }
+            let (top, bottom) = t.tab_bar();
+            let mid = (top + bottom) / 2;
+            draw::set_draw_color(t.label_color());
+            for (_, right) in t.tab_bounds() {
+                let cx = right - 10;
+                draw::draw_line(cx - 3, mid - 3, cx + 3, mid + 3);
+                draw::draw_line(cx - 3, mid + 3, cx + 3, mid - 3);
+            }
+        });
+        let st = state.clone();
+        self.handle2(move |t, ev| match ev {
+            Event::Push => {
+                let (ex, ey) = (app::event_x(), app::event_y());
+                let (top, bottom) = t.tab_bar();
+                if ey < top || ey >= bottom {
+                    return false;
+                }
+                let bounds = t.tab_bounds();
+                let idx = match bounds.iter().position(|(l, r)| ex >= *l && ex < *r) {
+                    Some(idx) => idx,
+                    None => return false,
+                };
+                let right = bounds[idx].1;
+                let cb = if ex >= right - 16 && ex <= right - 4 {
+                    st.borrow_mut().close_cb.take()
+                } else {
+                    None
+                };
+                let reorderable = st.borrow().reorderable;
+                if cb.is_none() && !reorderable {
+                    return false;
+                }
+                let grp = unsafe {
+                    let c = Fl_Group_child(t._inner as *mut Fl_Group, idx as i32);
+                    Group::from_widget_ptr(c as *mut fltk_sys::widget::Fl_Widget)
+                };
+                if let Some(mut cb) = cb {
+                    cb(t, grp);
+                    // the callback might have set another one
+                    let mut state = st.borrow_mut();
+                    if state.close_cb.is_none() {
+                        state.close_cb = Some(cb);
+                    }
+                    return true;
+                }
+                st.borrow_mut().dragged = Some(grp);
+                false
+            }
+            Event::Drag => {
+                let dragged = match &st.borrow().dragged {
+                    Some(grp) => grp.clone(),
+                    None => return false,
+                };
+                let (ex, ey) = (app::event_x(), app::event_y());
+                let (top, bottom) = t.tab_bar();
+                if ey >= top && ey < bottom {
+                    let to = t.tab_bounds().iter().position(|(l, r)| ex >= *l && ex < *r);
+                    let from = t.find(&dragged);
+                    if let Some(to) = to.map(|to| to as u32) {
+                        if from != to {
+                            // insert shifts the index down when moving a child forward
+                            t.insert(&dragged, if from < to { to + 1 } else { to });
+                            t.redraw();
+                        }
+                    }
+                }
+                true
+            }
+            Event::Released => {
+                st.borrow_mut().dragged = None;
+                false
+            }
+            _ => false,
+        });
+        state
+    }
+
+    /// Returns the vertical extent of the tab bar
+    fn tab_bar(&mut self) -> (i32, i32) {
+        let (_, cy, _, ch) = self.client_area();
+        if cy > self.y() {
+            (self.y(), cy)
+        } else {
+            (cy + ch, self.y() + self.height())
+        }
+    }
+
+    /// Returns the horizontal extent of each tab, measured from the labels of the children like Fl_Tabs does
+    fn tab_bounds(&self) -> Vec<(i32, i32)> {
+        let n = self.children();
+        let mut widths = vec![];
+        let mut selected = 0;
+        unsafe {
+            let grp = self._inner as *mut Fl_Group;
+            let value = Fl_Tabs_value(self._inner);
+            for i in 0..n {
+                let c = Fl_Group_child(grp, i as i32);
+                if c == value {
+                    selected = i as usize;
+                }
+                let (mut w, mut h) = (0, 0);
+                fltk_sys::widget::Fl_Widget_measure_label(c as *const _, &mut w, &mut h);
+                widths.push(w);
+            }
+        }
+        let x = self.x();
+        tab_positions(&widths, self.width(), selected, TABS_BOX_DX)
+            .into_iter()
+            .map(|(l, w)| (x + l, x + l + w))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct TabsState {
+    close_cb: Option<Box<dyn FnMut(&mut Tabs, Group)>>,
+    reorderable: bool,
+    dragged: Option<Group>,
+}
+
+thread_local! {
+    /// The close buttons and reordering state of the tabs, shared with their handle and draw methods
+    static TABS_STATES: RefCell<crate::utils::WidgetMap<Rc<RefCell<TabsState>>>> = RefCell::new(Default::default());
+}
+
+/// The left frame width of the default box of the tabs
+const TABS_BOX_DX: i32 = 1;
+
+/// Returns the offset and width of each tab, given the widths of their labels,
+/// following Fl_Tabs::tab_positions() which squeezes the tabs when they don't fit
+fn tab_positions(label_widths: &[i32], w: i32, selected: usize, box_dx: i32) -> Vec<(i32, i32)> {
+    const BORDER: i32 = 2;
+    const EXTRASPACE: i32 = 10;
+    const EXTRAGAP: i32 = 2;
+    let n = label_widths.len();
+    let mut width: Vec<i32> = label_widths.iter().map(|w| w + EXTRASPACE).collect();
+    let mut pos = vec![box_dx; n + 1];
+    for i in 0..n {
+        pos[i + 1] = pos[i] + width[i] + BORDER;
+    }
+    if pos[n] > w {
+        // pack them against the right edge
+        let mut r = w;
+        pos[n] = r;
+        for i in (0..n).rev() {
+            let l = std::cmp::min(r - width[i], pos[i + 1]);
+            if pos[i] <= l {
+                break;
+            }
+            pos[i] = l;
+            r -= EXTRAGAP;
+        }
+        // pack them against the left edge and truncate them if they still don't fit
+        for i in 0..n {
+            let gap = i as i32 * EXTRAGAP;
+            if pos[i] >= gap {
+                break;
+            }
+            pos[i] = gap;
+            let max = w - 1 - EXTRAGAP * (n - i) as i32 - pos[i];
+            width[i] = std::cmp::min(width[i], max);
+        }
+        // the tabs after the selected one start where their left neighbour ends
+        for i in (selected + 1..=n).rev() {
+            pos[i] = pos[i - 1] + width[i - 1];
+        }
+    }
+    (0..n)
+        .map(|i| {
+            // the tabs after the selected one are drawn against their right edge when they overlap
+            if i > selected && pos[i + 1] < pos[i] + width[i] {
+                (pos[i + 1] - width[i], width[i])
+            } else {
+                (pos[i], width[i])
+            }
+        })
+        .collect()
 }
 
 /// Creates a tile which can contain widgets
@@ -321,9 +585,12 @@ impl Pack {
     }
 
     /// Layout the children of the pack automatically
-    /// Must be called on existing children
+    /// Must be called on existing children, and called again after children are added or removed
     pub fn auto_layout(&mut self) {
         let children = self.children() as i32;
+        if children == 0 {
+            return;
+        }
         let spacing = self.spacing() * (children - 1);
         let t = self.get_type::<PackType>();
         let w = (self.width() - spacing) / children;
@@ -339,6 +606,7 @@ impl Pack {
                 c.set_size(w, c_h);
             }
         }
+        self.redraw();
     }
 }
 
@@ -479,3 +747,1259 @@ impl DerefMut for HGrid {
         &mut self.hpack
     }
 }
+
+/// The state of a custom group whose children are laid out by code
+pub(crate) trait GroupLayout: 'static {
+    /// Returns the geometry of the group when its children were last laid out
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32);
+    /// Lays out the children of the group
+    fn layout(&mut self, grp: &mut Group);
+}
+
+/// Lays out the children of a custom group and redraws it
+pub(crate) fn relayout<S: GroupLayout>(grp: &mut Group, state: &mut S) {
+    assert!(!grp.was_deleted());
+    *state.last_geometry() = (grp.x(), grp.y(), grp.width(), grp.height());
+    state.layout(grp);
+    grp.redraw();
+}
+
+/// Lays out the children of a custom group again when it's drawn after being resized,
+/// so overriding the group's draw method disables that
+pub(crate) fn relayout_on_resize<S: GroupLayout>(grp: &mut Group, state: &Rc<RefCell<S>>) {
+    let st = state.clone();
+    grp.draw2(move |g| {
+        let geo = (g.x(), g.y(), g.width(), g.height());
+        let changed = *st.borrow_mut().last_geometry() != geo;
+        if changed {
+            relayout(g, &mut *st.borrow_mut());
+        }
+    });
+}
+
+/// Defines Flex types
+#[repr(i32)]
+#[derive(WidgetType, Debug, Copy, Clone, PartialEq)]
+pub enum FlexType {
+    /// Lays out the children vertically
+    Column = 0,
+    /// Lays out the children horizontally
+    Row = 1,
+}
+
+#[derive(Debug, Default)]
+struct FlexState {
+    fixed: crate::utils::WidgetMap<i32>,
+    margin: i32,
+    pad: i32,
+    last: (i32, i32, i32, i32),
+}
+
+/// Defines a Flex layout (custom widget), similar to Fl_Flex
+/// Children are laid out in a row or column, children with a fixed size set via set_size keep it,
+/// while the remaining space is evenly distributed among the other children.
+/// The layout is recalculated whenever the flex is resized, so overriding its draw method disables that
+/// ```no_run
+/// use fltk::*;
+/// let mut flex = group::Flex::new(0, 0, 400, 300, "");
+/// flex.set_type(group::FlexType::Row);
+/// let _left = frame::Frame::default().with_label("stretches");
+/// let btn = button::Button::default().with_label("fixed");
+/// flex.set_size(&btn, 100);
+/// flex.end();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Flex {
+    grp: Group,
+    state: Rc<RefCell<FlexState>>,
+}
+
+impl Flex {
+    /// Creates a new flex, which is a column by default
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Flex {
+        let mut grp = Group::new(x, y, w, h, label);
+        grp.set_type(FlexType::Column);
+        let state = Rc::new(RefCell::new(FlexState::default()));
+        relayout_on_resize(&mut grp, &state);
+        Flex { grp, state }
+    }
+
+    /// Ends the flex and lays out its children
+    pub fn end(&mut self) {
+        self.grp.end();
+        self.recalc();
+    }
+
+    /// Adds a widget to the flex, which will take a share of the free space
+    pub fn add<W: WidgetExt>(&mut self, w: &W) {
+        self.grp.add(w);
+        self.recalc();
+    }
+
+    /// Removes a widget from the flex, also clearing its fixed size
+    pub fn remove<W: WidgetExt>(&mut self, w: &W) {
+        self.state.borrow_mut().fixed.remove(w);
+        self.grp.remove(w);
+        self.recalc();
+    }
+
+    /// Fixes the size (the height in a column, the width in a row) of a child of the flex
+    pub fn set_size<W: WidgetExt>(&mut self, w: &W, size: i32) {
+        assert!(!w.was_deleted());
+        self.state.borrow_mut().fixed.insert(w, size);
+        self.recalc();
+    }
+
+    /// Returns the fixed size of a child, or None if it takes a share of the free space
+    pub fn fixed_size<W: WidgetExt>(&self, w: &W) -> Option<i32> {
+        self.state.borrow_mut().fixed.get(w).copied()
+    }
+
+    /// Lets a child with a fixed size take a share of the free space again
+    pub fn unset_size<W: WidgetExt>(&mut self, w: &W) {
+        self.state.borrow_mut().fixed.remove(w);
+        self.recalc();
+    }
+
+    /// Gets the margin around the children of the flex
+    pub fn margin(&self) -> i32 {
+        self.state.borrow().margin
+    }
+
+    /// Sets the margin around the children of the flex
+    pub fn set_margin(&mut self, margin: i32) {
+        self.state.borrow_mut().margin = margin;
+        self.recalc();
+    }
+
+    /// Gets the padding between the children of the flex
+    pub fn pad(&self) -> i32 {
+        self.state.borrow().pad
+    }
+
+    /// Sets the padding between the children of the flex
+    pub fn set_pad(&mut self, pad: i32) {
+        self.state.borrow_mut().pad = pad;
+        self.recalc();
+    }
+
+    /// Recalculates the layout of the flex's children
+    pub fn recalc(&mut self) {
+        relayout(&mut self.grp, &mut *self.state.borrow_mut());
+    }
+}
+
+impl GroupLayout for FlexState {
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32) {
+        &mut self.last
+    }
+
+    fn layout(&mut self, grp: &mut Group) {
+        let state = self;
+        let (x, y, w, h) = (grp.x(), grp.y(), grp.width(), grp.height());
+        let children = grp.children() as i32;
+        if children == 0 {
+            return;
+        }
+        let is_row = grp.get_type::<FlexType>() == FlexType::Row;
+        let margin = state.margin;
+        let total = if is_row { w } else { h } - 2 * margin - state.pad * (children - 1);
+        let mut fixed_total = 0;
+        let mut flexible = 0;
+        for i in 0..children {
+            let c = grp.child(i as u32).unwrap();
+            match state.fixed.get(&*c).copied() {
+                Some(s) => fixed_total += s,
+                None => flexible += 1,
+            }
+        }
+        let free = std::cmp::max(total - fixed_total, 0);
+        let share = if flexible > 0 { free / flexible } else { 0 };
+        let mut rem = if flexible > 0 { free % flexible } else { 0 };
+        let mut pos = if is_row { x } else { y } + margin;
+        for i in 0..children {
+            let mut c = grp.child(i as u32).unwrap();
+            let size = match state.fixed.get(&*c).copied() {
+                Some(s) => s,
+                None => {
+                    let extra = if rem > 0 { 1 } else { 0 };
+                    rem -= extra;
+                    share + extra
+                }
+            };
+            if is_row {
+                c.resize(pos, y + margin, size, h - 2 * margin);
+            } else {
+                c.resize(x + margin, pos, w - 2 * margin, size);
+            }
+            pos += size + state.pad;
+        }
+    }
+}
+
+impl Deref for Flex {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for Flex {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ToolKind {
+    Button,
+    Toggle,
+    Separator,
+}
+
+#[derive(Debug)]
+struct Tool {
+    wid: Widget,
+    /// None for separators
+    button: Option<crate::button::Button>,
+    kind: ToolKind,
+    label: String,
+}
+
+#[derive(Debug)]
+struct ToolbarState {
+    tools: Vec<Tool>,
+    hidden: Vec<usize>,
+    overflow: crate::menu::MenuButton,
+    last: (i32, i32, i32, i32),
+}
+
+/// Defines a Toolbar (custom widget), which lays out tools horizontally.
+/// Tools which don't fit in the toolbar's width are hidden and made available through an overflow menu button
+/// ```no_run
+/// use fltk::*;
+/// let mut toolbar = group::Toolbar::new(0, 0, 400, 30, "");
+/// let mut open = toolbar.add_tool("Open", None::<image::PngImage>);
+/// open.set_callback(|| println!("open"));
+/// toolbar.add_separator();
+/// let mut bold = toolbar.add_toggle_tool("Bold", None::<image::PngImage>);
+/// bold.set_callback2(|b| println!("bold: {}", b.is_toggled()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Toolbar {
+    grp: Group,
+    state: Rc<RefCell<ToolbarState>>,
+}
+
+impl Toolbar {
+    /// Creates a new toolbar
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Toolbar {
+        let mut grp = Group::new(x, y, w, h, label);
+        grp.set_frame(FrameType::ThinUpBox);
+        let mut overflow = crate::menu::MenuButton::new(0, 0, 0, 0, "@-2>>");
+        overflow.set_frame(FrameType::FlatBox);
+        overflow.hide();
+        grp.end();
+        let state = Rc::new(RefCell::new(ToolbarState {
+            tools: vec![],
+            hidden: vec![],
+            overflow,
+            last: (0, 0, 0, 0),
+        }));
+        relayout_on_resize(&mut grp, &state);
+        Toolbar { grp, state }
+    }
+
+    /// Adds a tool button showing the image, or the label if no image is passed.
+    /// The label is also used as the tooltip and as the text of the tool's overflow menu entry
+    pub fn add_tool<I: ImageExt>(&mut self, label: &str, image: Option<I>) -> crate::button::Button {
+        let mut b = crate::button::Button::default();
+        self.init_tool(&mut b, label, image, ToolKind::Button);
+        b
+    }
+
+    /// Adds a tool which stays down when clicked until clicked again
+    pub fn add_toggle_tool<I: ImageExt>(
+        &mut self,
+        label: &str,
+        image: Option<I>,
+    ) -> crate::button::ToggleButton {
+        let mut b = crate::button::ToggleButton::default();
+        self.init_tool(&mut b, label, image, ToolKind::Toggle);
+        b
+    }
+
+    /// Adds a separator after the last tool
+    pub fn add_separator(&mut self) {
+        let mut sep = crate::frame::Frame::default();
+        sep.set_frame(FrameType::ThinDownFrame);
+        self.grp.insert(&sep, self.grp.children() - 1);
+        let wid = unsafe { Widget::from_widget_ptr(sep.as_widget_ptr()) };
+        self.state.borrow_mut().tools.push(Tool {
+            wid,
+            button: None,
+            kind: ToolKind::Separator,
+            label: String::new(),
+        });
+        self.recalc();
+    }
+
+    fn init_tool<B: ButtonExt, I: ImageExt>(
+        &mut self,
+        b: &mut B,
+        label: &str,
+        image: Option<I>,
+        kind: ToolKind,
+    ) {
+        b.set_frame(FrameType::FlatBox);
+        b.set_down_frame(FrameType::ThinDownBox);
+        b.set_tooltip(label);
+        b.clear_visible_focus();
+        if image.is_some() {
+            b.set_image(image);
+        } else {
+            b.set_label(label);
+        }
+        // keep the overflow menu button last
+        self.grp.insert(b, self.grp.children() - 1);
+        let (wid, button) = unsafe {
+            (
+                Widget::from_widget_ptr(b.as_widget_ptr()),
+                crate::button::Button::from_widget_ptr(b.as_widget_ptr()),
+            )
+        };
+        self.state.borrow_mut().tools.push(Tool {
+            wid,
+            button: Some(button),
+            kind,
+            label: label.to_string(),
+        });
+        self.recalc();
+    }
+
+    /// Recalculates the layout of the tools
+    pub fn recalc(&mut self) {
+        relayout(&mut self.grp, &mut *self.state.borrow_mut());
+    }
+}
+
+impl GroupLayout for ToolbarState {
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32) {
+        &mut self.last
+    }
+
+    fn layout(&mut self, grp: &mut Group) {
+        let state = self;
+        let mut menu = state.overflow.clone();
+        let (x, y, w, h) = (grp.x(), grp.y(), grp.width(), grp.height());
+        let pad = 2;
+        let size = std::cmp::max(h - 2 * pad, 0);
+        let widths: Vec<i32> = state
+            .tools
+            .iter()
+            .map(|t| match t.kind {
+                ToolKind::Separator => 6,
+                _ if t.wid.label().is_empty() => size,
+                _ => std::cmp::max(size, t.wid.measure_label().0 + 12),
+            })
+            .collect();
+        let needed: i32 = widths.iter().map(|w| w + pad).sum::<i32>() + pad;
+        let limit = if needed > w {
+            x + w - 2 * pad - size
+        } else {
+            x + w
+        };
+        let mut pos = x + pad;
+        let mut hidden = vec![];
+        for (i, (t, tw)) in state.tools.iter_mut().zip(widths).enumerate() {
+            if pos + tw > limit {
+                t.wid.hide();
+                if t.kind != ToolKind::Separator {
+                    hidden.push(i);
+                }
+            } else {
+                t.wid.show();
+                if t.kind == ToolKind::Separator {
+                    t.wid.resize(pos + tw / 2 - 1, y + pad + 2, 2, size - 4);
+                } else {
+                    t.wid.resize(pos, y + pad, tw, size);
+                }
+            }
+            pos += tw + pad;
+        }
+        if hidden.is_empty() {
+            menu.hide();
+        } else {
+            menu.resize(x + w - pad - size, y + pad, size, size);
+            menu.show();
+        }
+        if hidden != state.hidden {
+            menu.clear();
+            for i in &hidden {
+                let t = &state.tools[*i];
+                let mut b = match &t.button {
+                    Some(b) => b.clone(),
+                    None => continue,
+                };
+                let is_toggle = t.kind == ToolKind::Toggle;
+                let flag = if is_toggle {
+                    crate::menu::MenuFlag::Toggle
+                } else {
+                    crate::menu::MenuFlag::Normal
+                };
+                menu.add(&t.label, Shortcut::None, flag, move || {
+                    if is_toggle {
+                        let val = b.is_set();
+                        b.set(!val);
+                    }
+                    b.do_callback();
+                });
+            }
+            state.hidden = hidden;
+        }
+        // sync the check marks of hidden toggle tools
+        for i in &state.hidden {
+            let t = &state.tools[*i];
+            if let (ToolKind::Toggle, Some(b)) = (t.kind, &t.button) {
+                if let Some(mut item) = menu.find_item(&t.label) {
+                    if b.is_set() {
+                        item.set();
+                    } else {
+                        item.clear();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Deref for Toolbar {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for Toolbar {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+#[derive(Debug, Default)]
+struct StatusBarState {
+    sections: Vec<(crate::frame::Frame, i32)>,
+    progress: Option<(crate::misc::Progress, i32)>,
+    progress_shown: bool,
+    last: (i32, i32, i32, i32),
+}
+
+/// Defines a StatusBar (custom widget), made of text sections laid out horizontally and an optional progress bar.
+/// Sections with a width of 0 share the space left by the sections with a fixed width.
+/// For the status bar to keep its height when the window is resized, the window's resizable should be set to the content above it
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let content = group::Group::new(0, 0, 400, 276, "");
+/// content.end();
+/// let mut status = group::StatusBar::pinned_to(&win, 24);
+/// let msg = status.add_section("Ready", 0);
+/// let pos = status.add_section("Ln 1, Col 1", 100);
+/// win.end();
+/// win.resizable(&content);
+/// status.set_text(msg, "Saving...").unwrap();
+/// status.show_progress(120);
+/// status.set_progress(50.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatusBar {
+    grp: Group,
+    state: Rc<RefCell<StatusBarState>>,
+}
+
+impl StatusBar {
+    /// Creates a new status bar
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> StatusBar {
+        let mut grp = Group::new(x, y, w, h, label);
+        grp.set_frame(FrameType::FlatBox);
+        grp.end();
+        let state = Rc::new(RefCell::new(StatusBarState::default()));
+        relayout_on_resize(&mut grp, &state);
+        StatusBar { grp, state }
+    }
+
+    /// Creates a status bar spanning the bottom of a window, or of any group
+    pub fn pinned_to<G: GroupExt>(parent: &G, height: i32) -> StatusBar {
+        assert!(!parent.was_deleted());
+        let (x, y) = if parent.as_window().is_some() {
+            (0, 0)
+        } else {
+            (parent.x(), parent.y())
+        };
+        let (w, h) = (parent.width(), parent.height());
+        let mut bar = StatusBar::new(x, y + h - height, w, height, "");
+        let mut p = unsafe { Group::from_widget_ptr(parent.as_widget_ptr()) };
+        p.add(&*bar);
+        bar.recalc();
+        bar
+    }
+
+    /// Adds a text section, returning its index.
+    /// A width of 0 makes the section share the free space
+    pub fn add_section(&mut self, text: &str, width: i32) -> usize {
+        let mut f = crate::frame::Frame::default().with_label(text);
+        f.set_frame(FrameType::ThinDownFrame);
+        f.set_align(Align::Left | Align::Inside | Align::Clip);
+        f.set_label_size(self.grp.label_size());
+        self.grp.add(&f);
+        let idx = {
+            let mut st = self.state.borrow_mut();
+            st.sections.push((f, width));
+            st.sections.len() - 1
+        };
+        self.recalc();
+        idx
+    }
+
+    /// Gets the text of a section
+    pub fn text(&self, idx: usize) -> Option<String> {
+        self.state.borrow().sections.get(idx).map(|(f, _)| f.label())
+    }
+
+    /// Sets the text of a section
+    pub fn set_text(&mut self, idx: usize, text: &str) -> Result<(), FltkError> {
+        match self.state.borrow_mut().sections.get_mut(idx) {
+            Some((f, _)) => {
+                f.set_label(text);
+                f.redraw();
+                Ok(())
+            }
+            None => Err(FltkError::Internal(FltkErrorKind::FailedOperation)),
+        }
+    }
+
+    /// Sets the width of a section, 0 makes it share the free space
+    pub fn set_section_width(&mut self, idx: usize, width: i32) -> Result<(), FltkError> {
+        match self.state.borrow_mut().sections.get_mut(idx) {
+            Some((_, w)) => *w = width,
+            None => return Err(FltkError::Internal(FltkErrorKind::FailedOperation)),
+        }
+        self.recalc();
+        Ok(())
+    }
+
+    /// Shows a progress bar with the given width at the right end of the status bar
+    pub fn show_progress(&mut self, width: i32) {
+        let has_progress = self.state.borrow().progress.is_some();
+        if !has_progress {
+            let mut p = crate::misc::Progress::default();
+            p.set_selection_color(Color::Selection);
+            self.grp.add(&p);
+            self.state.borrow_mut().progress = Some((p, width));
+        } else if let Some((p, w)) = self.state.borrow_mut().progress.as_mut() {
+            *w = width;
+            p.show();
+        }
+        self.state.borrow_mut().progress_shown = true;
+        self.recalc();
+    }
+
+    /// Hides the progress bar
+    pub fn hide_progress(&mut self) {
+        if let Some((p, _)) = self.state.borrow_mut().progress.as_mut() {
+            p.hide();
+        }
+        self.state.borrow_mut().progress_shown = false;
+        self.recalc();
+    }
+
+    /// Returns the progress bar, if shown once
+    pub fn progress(&self) -> Option<crate::misc::Progress> {
+        self.state.borrow().progress.as_ref().map(|(p, _)| p.clone())
+    }
+
+    /// Sets the value of the progress bar, which ranges from 0 to 100 by default
+    pub fn set_progress(&mut self, val: f64) {
+        if let Some((p, _)) = self.state.borrow_mut().progress.as_mut() {
+            p.set_value(val);
+        }
+    }
+
+    /// Recalculates the layout of the sections
+    pub fn recalc(&mut self) {
+        relayout(&mut self.grp, &mut *self.state.borrow_mut());
+    }
+}
+
+impl GroupLayout for StatusBarState {
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32) {
+        &mut self.last
+    }
+
+    fn layout(&mut self, grp: &mut Group) {
+        let state = self;
+        let (x, y, w, h) = (grp.x(), grp.y(), grp.width(), grp.height());
+        let pad = 2;
+        let mut right = x + w - pad;
+        if let Some((p, pw)) = state.progress.as_mut() {
+            if state.progress_shown {
+                right -= *pw;
+                p.resize(right, y + pad, *pw, h - 2 * pad);
+                right -= pad;
+            }
+        }
+        let count = state.sections.len() as i32;
+        if count == 0 {
+            return;
+        }
+        let total = right - x - pad - pad * (count - 1);
+        let fixed: i32 = state.sections.iter().map(|(_, sw)| *sw).sum();
+        let flexible = state.sections.iter().filter(|(_, sw)| *sw == 0).count() as i32;
+        let share = if flexible > 0 {
+            std::cmp::max(total - fixed, 0) / flexible
+        } else {
+            0
+        };
+        let mut pos = x + pad;
+        for (f, sw) in state.sections.iter_mut() {
+            let sw = if *sw == 0 { share } else { *sw };
+            f.resize(pos, y + pad, sw, h - 2 * pad);
+            pos += sw + pad;
+        }
+    }
+}
+
+impl Deref for StatusBar {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for StatusBar {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+/// Defines the window edge a dock panel is docked to
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DockSide {
+    /// Left edge
+    Left = 0,
+    /// Right edge
+    Right,
+    /// Top edge
+    Top,
+    /// Bottom edge
+    Bottom,
+}
+
+impl DockSide {
+    fn from_str(s: &str) -> Option<DockSide> {
+        match s {
+            "left" => Some(DockSide::Left),
+            "right" => Some(DockSide::Right),
+            "top" => Some(DockSide::Top),
+            "bottom" => Some(DockSide::Bottom),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DockSide::Left => "left",
+            DockSide::Right => "right",
+            DockSide::Top => "top",
+            DockSide::Bottom => "bottom",
+        }
+    }
+}
+
+const DOCK_BAR: i32 = 20;
+const DOCK_MIN: i32 = 40;
+
+#[derive(Debug)]
+struct DockPanel {
+    title: String,
+    side: DockSide,
+    size: i32,
+    collapsed: bool,
+    outer: Group,
+    floating: Option<crate::window::Window>,
+}
+
+#[derive(Debug, Default)]
+struct DockState {
+    panels: Vec<DockPanel>,
+    resizing: Option<usize>,
+    last: (i32, i32, i32, i32),
+}
+
+/// Defines a DockArea (custom widget), which surrounds a central group with panels docked to its edges.
+/// Panels are docked in the order they're added, each taking its size from the space left by the previous ones.
+/// Every panel has a title bar with buttons to collapse it and to float it into its own window,
+/// closing the floating window docks the panel back. Docked panels are resized by dragging their inner edge
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 800, 600, "IDE");
+/// let mut dock = group::DockArea::new(0, 0, 800, 600, "");
+/// let mut files = dock.add_panel("Files", group::DockSide::Left, 200);
+/// files.begin();
+/// let _tree = tree::Tree::new(0, 0, 200, 580, "");
+/// files.end();
+/// let _log = dock.add_panel("Output", group::DockSide::Bottom, 150);
+/// let mut center = dock.center();
+/// center.begin();
+/// let _editor = text::TextEditor::new(0, 0, 400, 400, "");
+/// center.end();
+/// win.end();
+/// win.make_resizable(true);
+/// let layout = dock.save_layout();
+/// dock.restore_layout(&layout).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DockArea {
+    grp: Group,
+    center: Group,
+    state: Rc<RefCell<DockState>>,
+}
+
+impl DockArea {
+    /// Creates a new dock area
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> DockArea {
+        let mut grp = Group::new(x, y, w, h, label);
+        let center = Group::new(x, y, w, h, "");
+        center.end();
+        grp.end();
+        let state = Rc::new(RefCell::new(DockState::default()));
+        relayout_on_resize(&mut grp, &state);
+        let st = state.clone();
+        grp.handle2(move |g, ev| {
+            let (ex, ey) = (app::event_x(), app::event_y());
+            match ev {
+                Event::Push => {
+                    let hit = DockArea::edge_at(&st.borrow(), ex, ey);
+                    st.borrow_mut().resizing = hit;
+                    hit.is_some()
+                }
+                Event::Drag => {
+                    let idx = match st.borrow().resizing {
+                        Some(idx) => idx,
+                        None => return false,
+                    };
+                    {
+                        let mut state = st.borrow_mut();
+                        let p = &mut state.panels[idx];
+                        let o = &p.outer;
+                        let size = match p.side {
+                            DockSide::Left => ex - o.x(),
+                            DockSide::Right => o.x() + o.width() - ex,
+                            DockSide::Top => ey - o.y(),
+                            DockSide::Bottom => o.y() + o.height() - ey,
+                        };
+                        p.size = std::cmp::max(size, DOCK_MIN);
+                        relayout(g, &mut *state);
+                    }
+                    true
+                }
+                Event::Released => st.borrow_mut().resizing.take().is_some(),
+                _ => false,
+            }
+        });
+        DockArea { grp, center, state }
+    }
+
+    /// Returns the central group, which takes the space left by the docked panels
+    pub fn center(&self) -> Group {
+        self.center.clone()
+    }
+
+    /// Adds a panel docked to a side with the given size, returning its content group.
+    /// Panel titles identify panels and should be unique
+    pub fn add_panel(&mut self, title: &str, side: DockSide, size: i32) -> Group {
+        assert!(
+            self.panel_index(title).is_none(),
+            "A panel with the same title already exists!"
+        );
+        self.grp.begin();
+        let mut outer = Group::new(0, 0, DOCK_MIN, DOCK_MIN, "");
+        outer.set_frame(FrameType::ThinUpBox);
+        let mut bar = crate::frame::Frame::new(0, 0, 0, 0, "").with_label(title);
+        bar.set_frame(FrameType::ThinUpBox);
+        bar.set_align(Align::Left | Align::Inside | Align::Clip);
+        let mut collapse = crate::button::Button::new(0, 0, 0, 0, "-");
+        collapse.set_tooltip("Collapse");
+        let mut float = crate::button::Button::new(0, 0, 0, 0, "^");
+        float.set_tooltip("Float");
+        let content = Group::new(0, 0, 0, 0, "");
+        content.end();
+        outer.end();
+        self.grp.end();
+        outer.resizable(&content);
+
+        let title = title.to_string();
+        {
+            let (st, mut g, t) = (self.state.clone(), self.grp.clone(), title.clone());
+            collapse.set_callback(move || {
+                let collapsed = DockArea::with_panel(&st, &t, |p| p.collapsed).unwrap_or(false);
+                DockArea::set_collapsed_(&mut g, &st, &t, !collapsed);
+            });
+        }
+        {
+            let (st, mut g, t) = (self.state.clone(), self.grp.clone(), title.clone());
+            float.set_callback(move || {
+                if DockArea::with_panel(&st, &t, |p| p.floating.is_some()).unwrap_or(false) {
+                    DockArea::dock_(&mut g, &st, &t);
+                } else {
+                    DockArea::float_(&mut g, &st, &t);
+                }
+            });
+        }
+        {
+            let (st, t) = (self.state.clone(), title.clone());
+            outer.draw2(move |o| {
+                let collapsed = DockArea::with_panel(&st, &t, |p| p.collapsed).unwrap_or(false);
+                if DockArea::layout_panel(o, collapsed) {
+                    o.redraw();
+                }
+            });
+        }
+        self.state.borrow_mut().panels.push(DockPanel {
+            title,
+            side,
+            size,
+            collapsed: false,
+            outer,
+            floating: None,
+        });
+        self.recalc();
+        content
+    }
+
+    /// Returns the content group of a panel
+    pub fn panel(&self, title: &str) -> Option<Group> {
+        DockArea::with_panel(&self.state, title, |p| p.outer.child(3))
+            .flatten()
+            .map(|c| unsafe { Group::from_widget_ptr(c.as_widget_ptr()) })
+    }
+
+    /// Moves a panel to another side
+    pub fn set_panel_side(&mut self, title: &str, side: DockSide) -> Result<(), FltkError> {
+        DockArea::with_panel(&self.state, title, |p| p.side = side)
+            .ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+        self.recalc();
+        Ok(())
+    }
+
+    /// Returns whether a panel is collapsed to its title bar
+    pub fn is_collapsed(&self, title: &str) -> bool {
+        DockArea::with_panel(&self.state, title, |p| p.collapsed).unwrap_or(false)
+    }
+
+    /// Collapses a panel to its title bar, or expands it
+    pub fn set_collapsed(&mut self, title: &str, flag: bool) {
+        DockArea::set_collapsed_(&mut self.grp, &self.state, title, flag);
+    }
+
+    /// Returns whether a panel is floating in its own window
+    pub fn is_floating(&self, title: &str) -> bool {
+        DockArea::with_panel(&self.state, title, |p| p.floating.is_some()).unwrap_or(false)
+    }
+
+    /// Floats a panel into its own window
+    pub fn float_panel(&mut self, title: &str) {
+        DockArea::float_(&mut self.grp, &self.state, title);
+    }
+
+    /// Docks a floating panel back
+    pub fn dock_panel(&mut self, title: &str) {
+        DockArea::dock_(&mut self.grp, &self.state, title);
+    }
+
+    /// Saves the side, size, collapsed and floating state of the panels, one panel per line
+    pub fn save_layout(&self) -> String {
+        let mut out = String::new();
+        for p in &self.state.borrow().panels {
+            let geo = match &p.floating {
+                Some(w) => format!("{},{},{},{}", w.x(), w.y(), w.width(), w.height()),
+                None => String::from("-"),
+            };
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                p.title.replace('\t', " "),
+                p.side.as_str(),
+                p.size,
+                p.collapsed as i32,
+                geo
+            ));
+        }
+        out
+    }
+
+    /// Restores a layout returned by save_layout, lines of unknown panels are ignored
+    pub fn restore_layout(&mut self, layout: &str) -> Result<(), FltkError> {
+        let err = || FltkError::Unknown(String::from("Invalid dock layout"));
+        for line in layout.lines().filter(|l| !l.is_empty()) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 5 {
+                return Err(err());
+            }
+            let title = fields[0];
+            let side = DockSide::from_str(fields[1]).ok_or_else(err)?;
+            let size: i32 = fields[2].parse().map_err(|_| err())?;
+            let collapsed = fields[3] == "1";
+            let geo: Option<Vec<i32>> = if fields[4] == "-" {
+                None
+            } else {
+                let v = fields[4]
+                    .split(',')
+                    .map(|v| v.parse::<i32>())
+                    .collect::<Result<Vec<i32>, _>>()
+                    .map_err(|_| err())?;
+                if v.len() != 4 {
+                    return Err(err());
+                }
+                Some(v)
+            };
+            if self.panel_index(title).is_none() {
+                continue;
+            }
+            DockArea::with_panel(&self.state, title, |p| {
+                p.side = side;
+                p.size = size;
+            });
+            DockArea::set_collapsed_(&mut self.grp, &self.state, title, collapsed);
+            match geo {
+                Some(v) => {
+                    DockArea::float_(&mut self.grp, &self.state, title);
+                    let win = DockArea::with_panel(&self.state, title, |p| p.floating.clone());
+                    if let Some(mut w) = win.flatten() {
+                        w.resize(v[0], v[1], v[2], v[3]);
+                    }
+                }
+                None => DockArea::dock_(&mut self.grp, &self.state, title),
+            }
+        }
+        self.recalc();
+        Ok(())
+    }
+
+    /// Recalculates the layout of the panels
+    pub fn recalc(&mut self) {
+        relayout(&mut self.grp, &mut *self.state.borrow_mut());
+    }
+
+    fn panel_index(&self, title: &str) -> Option<usize> {
+        self.state.borrow().panels.iter().position(|p| p.title == title)
+    }
+
+    fn with_panel<T, F: FnOnce(&mut DockPanel) -> T>(
+        state: &Rc<RefCell<DockState>>,
+        title: &str,
+        f: F,
+    ) -> Option<T> {
+        state
+            .borrow_mut()
+            .panels
+            .iter_mut()
+            .find(|p| p.title == title)
+            .map(f)
+    }
+
+    fn set_collapsed_(grp: &mut Group, state: &Rc<RefCell<DockState>>, title: &str, flag: bool) {
+        DockArea::with_panel(state, title, |p| {
+            p.collapsed = flag;
+            if let Some(mut b) = p.outer.child(1) {
+                b.set_label(if flag { "+" } else { "-" });
+            }
+        });
+        relayout(grp, &mut *state.borrow_mut());
+    }
+
+    fn float_(grp: &mut Group, state: &Rc<RefCell<DockState>>, title: &str) {
+        let mut outer = match DockArea::with_panel(state, title, |p| {
+            if p.floating.is_none() {
+                Some(p.outer.clone())
+            } else {
+                None
+            }
+        })
+        .flatten()
+        {
+            Some(outer) => outer,
+            None => return,
+        };
+        let (x, y) = (outer.x_root(), outer.y_root());
+        let (w, h) = (
+            std::cmp::max(outer.width(), 150),
+            std::cmp::max(outer.height(), 100),
+        );
+        let mut win = unsafe {
+            let current = Fl_Group_current();
+            Fl_Group_set_current(std::ptr::null_mut());
+            let win = crate::window::Window::new(x, y, w, h, title);
+            win.end();
+            Fl_Group_set_current(current);
+            win
+        };
+        grp.remove(&outer);
+        win.add(&outer);
+        outer.resize(0, 0, w, h);
+        win.resizable(&outer);
+        if let Some(mut b) = outer.child(2) {
+            b.set_label("v");
+            b.set_tooltip("Dock");
+        }
+        {
+            let (st, mut g, t) = (state.clone(), grp.clone(), title.to_string());
+            win.set_callback(move || DockArea::dock_(&mut g, &st, &t));
+        }
+        win.show();
+        DockArea::with_panel(state, title, |p| p.floating = Some(win));
+        relayout(grp, &mut *state.borrow_mut());
+    }
+
+    fn dock_(grp: &mut Group, state: &Rc<RefCell<DockState>>, title: &str) {
+        let (mut outer, mut win) = match DockArea::with_panel(state, title, |p| {
+            p.floating.take().map(|w| (p.outer.clone(), w))
+        })
+        .flatten()
+        {
+            Some(v) => v,
+            None => return,
+        };
+        win.remove(&outer);
+        win.hide();
+        app::delete_widget(win);
+        grp.add(&outer);
+        if let Some(mut b) = outer.child(2) {
+            b.set_label("^");
+            b.set_tooltip("Float");
+        }
+        outer.show();
+        relayout(grp, &mut *state.borrow_mut());
+    }
+
+    /// Returns the index of the docked panel whose inner edge is under the position
+    fn edge_at(state: &DockState, x: i32, y: i32) -> Option<usize> {
+        state.panels.iter().position(|p| {
+            if p.floating.is_some() || p.collapsed {
+                return false;
+            }
+            let o = &p.outer;
+            let (ox, oy, ow, oh) = (o.x(), o.y(), o.width(), o.height());
+            let in_x = x >= ox && x < ox + ow;
+            let in_y = y >= oy && y < oy + oh;
+            match p.side {
+                DockSide::Left => in_y && (x - (ox + ow)).abs() <= 3,
+                DockSide::Right => in_y && (x - ox).abs() <= 3,
+                DockSide::Top => in_x && (y - (oy + oh)).abs() <= 3,
+                DockSide::Bottom => in_x && (y - oy).abs() <= 3,
+            }
+        })
+    }
+
+    /// Lays out the title bar, buttons and content of a panel, returns whether anything moved
+    fn layout_panel(outer: &mut Group, collapsed: bool) -> bool {
+        let (x, y, w, h) = (outer.x(), outer.y(), outer.width(), outer.height());
+        let rects = [
+            (x, y, std::cmp::max(w - 2 * DOCK_BAR, 0), DOCK_BAR),
+            (x + w - 2 * DOCK_BAR, y, DOCK_BAR, DOCK_BAR),
+            (x + w - DOCK_BAR, y, DOCK_BAR, DOCK_BAR),
+            (x, y + DOCK_BAR, w, std::cmp::max(h - DOCK_BAR, 0)),
+        ];
+        let mut changed = false;
+        for (i, r) in rects.iter().enumerate() {
+            if let Some(mut c) = outer.child(i as u32) {
+                if (c.x(), c.y(), c.width(), c.height()) != *r {
+                    c.resize(r.0, r.1, r.2, r.3);
+                    changed = true;
+                }
+                if i == 3 {
+                    if collapsed {
+                        c.hide();
+                    } else {
+                        c.show();
+                    }
+                }
+            }
+        }
+        changed
+    }
+}
+
+impl GroupLayout for DockState {
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32) {
+        &mut self.last
+    }
+
+    fn layout(&mut self, grp: &mut Group) {
+        let state = self;
+        let (gx, gy, gw, gh) = (grp.x(), grp.y(), grp.width(), grp.height());
+        let (mut x, mut y, mut w, mut h) = (gx, gy, gw, gh);
+        for p in state.panels.iter_mut().filter(|p| p.floating.is_none()) {
+            let avail = match p.side {
+                DockSide::Left | DockSide::Right => w - DOCK_MIN,
+                DockSide::Top | DockSide::Bottom => h - DOCK_MIN,
+            };
+            let size = if p.collapsed { DOCK_BAR } else { p.size };
+            let size = std::cmp::max(std::cmp::min(size, avail), 0);
+            let o = &mut p.outer;
+            match p.side {
+                DockSide::Left => {
+                    o.resize(x, y, size, h);
+                    x += size;
+                    w -= size;
+                }
+                DockSide::Right => {
+                    o.resize(x + w - size, y, size, h);
+                    w -= size;
+                }
+                DockSide::Top => {
+                    o.resize(x, y, w, size);
+                    y += size;
+                    h -= size;
+                }
+                DockSide::Bottom => {
+                    o.resize(x, y + h - size, w, size);
+                    h -= size;
+                }
+            }
+            DockArea::layout_panel(o, p.collapsed);
+        }
+        if let Some(mut c) = grp.child(0) {
+            c.resize(x, y, w, h);
+        }
+    }
+}
+
+impl Deref for DockArea {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for DockArea {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+thread_local! {
+    /// The focus orders of the groups, shared with their handle methods
+    static FOCUS_ORDERS: RefCell<crate::utils::WidgetMap<Rc<RefCell<Vec<Widget>>>>> = RefCell::new(Default::default());
+}
+
+/// Returns the index of the next focusable entry, wrapping around
+fn next_focus_index(focusable: &[bool], current: usize, backward: bool) -> Option<usize> {
+    let n = focusable.len();
+    (1..n)
+        .map(|step| {
+            if backward {
+                (current + n - step) % n
+            } else {
+                (current + step) % n
+            }
+        })
+        .find(|&i| focusable[i])
+}
+
+fn handle_focus_order(order: &RefCell<Vec<Widget>>) -> bool {
+    if app::event_key() != Key::Tab || app::is_event_ctrl() || app::is_event_alt() {
+        return false;
+    }
+    // taking the focus can run callbacks setting the focus order again
+    let order = order.borrow().clone();
+    let focus = match app::focus() {
+        Some(focus) => focus,
+        None => return false,
+    };
+    let current = match order
+        .iter()
+        .position(|w| unsafe { w.as_widget_ptr() == focus.as_widget_ptr() } || focus.inside(w))
+    {
+        Some(current) => current,
+        None => return false,
+    };
+    let focusable: Vec<bool> = order
+        .iter()
+        .map(|w| !w.was_deleted() && w.takes_events())
+        .collect();
+    let backward = app::is_event_shift();
+    let mut idx = current;
+    while let Some(next) = next_focus_index(&focusable, idx, backward) {
+        if next == current {
+            break;
+        }
+        if order[next].clone().take_focus().is_ok() {
+            return true;
+        }
+        idx = next;
+    }
+    false
+}
+
+pub(crate) fn set_focus_order<G: GroupExt + WidgetBase>(grp: &mut G, order: &[&dyn WidgetExt]) {
+    if order.is_empty() {
+        if FOCUS_ORDERS.with(|f| f.borrow_mut().remove(grp)).is_some() {
+            // removes the handler installed below
+            unsafe {
+                let _ = grp.handle_data();
+            }
+        }
+        return;
+    }
+    let order: Vec<Widget> = order
+        .iter()
+        .map(|w| unsafe { Widget::from_widget_ptr(w.as_widget_ptr()) })
+        .collect();
+    let current = FOCUS_ORDERS.with(|f| f.borrow_mut().get(grp).cloned());
+    match current {
+        Some(current) => *current.borrow_mut() = order,
+        None => {
+            let order = Rc::new(RefCell::new(order));
+            FOCUS_ORDERS.with(|f| f.borrow_mut().insert(grp, order.clone()));
+            grp.handle(move |ev| ev == Event::KeyDown && handle_focus_order(&order));
+        }
+    }
+}
+
+pub(crate) fn focus_order<G: GroupExt>(grp: &G) -> Vec<Widget> {
+    FOCUS_ORDERS
+        .with(|f| f.borrow_mut().get(grp).map(|o| o.borrow().clone()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod group {
+    use super::*;
+    #[test]
+    fn focus_indices() {
+        let focusable = [true, false, true, true];
+        assert_eq!(next_focus_index(&focusable, 0, false), Some(2));
+        assert_eq!(next_focus_index(&focusable, 3, false), Some(0));
+        assert_eq!(next_focus_index(&focusable, 0, true), Some(3));
+        assert_eq!(next_focus_index(&focusable, 2, true), Some(0));
+        assert_eq!(next_focus_index(&[true], 0, false), None);
+    }
+    #[test]
+    fn tab_extents() {
+        assert_eq!(tab_positions(&[40, 60], 300, 0, 1), vec![(1, 50), (53, 70)]);
+        assert_eq!(
+            tab_positions(&[90, 90, 90], 200, 0, 1),
+            vec![(1, 100), (98, 100), (100, 100)]
+        );
+        assert_eq!(
+            tab_positions(&[90, 90, 90], 200, 2, 1),
+            vec![(1, 100), (98, 100), (100, 100)]
+        );
+        assert!(tab_positions(&[], 200, 0, 1).is_empty());
+    }
+}
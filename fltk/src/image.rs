@@ -1,8 +1,11 @@
 pub use crate::prelude::*;
 use fltk_sys::image::*;
 use std::{
-    ffi::CString,
-    mem,
+    cell::RefCell,
+    ffi::{CStr, CString},
+    fmt, mem,
+    os::raw,
+    rc::Rc,
     sync::atomic::{AtomicUsize, Ordering},
 };
 
@@ -11,6 +14,121 @@ const TMP: &str = "TEMP";
 #[cfg(not(target_os = "windows"))]
 const TMP: &str = "TMPDIR";
 
+/// Defines the formats images can be written in
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ImageFormat {
+    /// PNG
+    Png,
+    /// JPEG
+    Jpeg,
+    /// BMP
+    Bmp,
+}
+
+impl ImageFormat {
+    /// Returns the format matching the extension of a path
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Option<ImageFormat> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "png" => Some(ImageFormat::Png),
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+            "bmp" => Some(ImageFormat::Bmp),
+            _ => None,
+        }
+    }
+
+    /// Returns the usual file extension of the format
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Bmp => "bmp",
+        }
+    }
+}
+
+/// Returns whether an image holds a single buffer of 1 to 4 channel pixels, which can be converted to RGB.
+/// Vector images such as SVGs hold no pixels until they're drawn, and pixmaps and bitmaps hold other data
+fn has_pixels<I: ImageExt>(image: &I) -> bool {
+    let (w, depth, ld) = (image.data_w(), image.depth(), image.ld());
+    if image.count() != 1 || depth == 0 || depth > 4 || (ld != 0 && ld != w * depth) {
+        return false;
+    }
+    let data = image.to_raw_data();
+    unsafe { !data.is_null() && !(*data).is_null() }
+}
+
+/// Encodes RGB pixels as a 24-bit BMP
+fn encode_bmp(rgb: &[u8], w: u32, h: u32) -> Vec<u8> {
+    let stride = (w * 3 + 3) & !3;
+    let size = 54 + stride * h;
+    let mut v = Vec::with_capacity(size as usize);
+    v.extend_from_slice(b"BM");
+    for field in &[size, 0, 54, 40, w, h] {
+        v.extend_from_slice(&field.to_le_bytes());
+    }
+    v.extend_from_slice(&1u16.to_le_bytes());
+    v.extend_from_slice(&24u16.to_le_bytes());
+    for field in &[0, stride * h, 2835, 2835, 0, 0] {
+        v.extend_from_slice(&field.to_le_bytes());
+    }
+    for row in rgb.chunks_exact((w * 3) as usize).rev() {
+        for p in row.chunks_exact(3) {
+            v.extend_from_slice(&[p[2], p[1], p[0]]);
+        }
+        v.resize(v.len() + (stride - w * 3) as usize, 0);
+    }
+    v
+}
+
+/// Encodes an image in a format in memory, converting it to RGB first.
+/// Images without pixels, like SVG images which weren't drawn yet, return an error
+pub(crate) fn encode_image<I: ImageExt>(
+    image: &I,
+    format: ImageFormat,
+) -> Result<Vec<u8>, FltkError> {
+    assert!(!image.was_deleted());
+    if !has_pixels(image) {
+        return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+    }
+    let (w, h) = (image.data_w(), image.data_h());
+    let data = crate::app::rgb_pixels(&image.to_rgb_data(), image.depth());
+    if w == 0 || h == 0 || data.len() < (w * h * 3) as usize {
+        return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+    }
+    let data = &data[..(w * h * 3) as usize];
+    let mut out: *mut u8 = std::ptr::null_mut();
+    let mut len = 0;
+    let ret = unsafe {
+        match format {
+            ImageFormat::Png => {
+                Fl_encode_png(data.as_ptr(), w as i32, h as i32, &mut out, &mut len)
+            }
+            ImageFormat::Jpeg => {
+                Fl_encode_jpeg(data.as_ptr(), w as i32, h as i32, 90, &mut out, &mut len)
+            }
+            ImageFormat::Bmp => return Ok(encode_bmp(data, w, h)),
+        }
+    };
+    if ret != 0 || out.is_null() {
+        return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+    }
+    unsafe {
+        let v = std::slice::from_raw_parts(out, len as usize).to_vec();
+        Fl_free_encoded(out);
+        Ok(v)
+    }
+}
+
+/// Writes an image to a file in a format, see encode_image
+pub(crate) fn write_image<I: ImageExt>(
+    image: &I,
+    path: &std::path::Path,
+    format: ImageFormat,
+) -> Result<(), FltkError> {
+    std::fs::write(path, encode_image(image, format)?).map_err(FltkError::IoError)
+}
+
 /// Wrapper around Fl_Image, used to wrap other image types
 #[derive(ImageExt, Debug)]
 pub struct Image {
@@ -26,12 +144,71 @@ pub struct SharedImage {
 }
 
 impl SharedImage {
-    /// Loads a SharedImage from a path
+    /// Loads a SharedImage from a path.
+    /// Shared images are cached and reference-counted by FLTK, so loading the same path again returns the cached image
+    /// ```no_run
+    /// use fltk::*;
+    /// let icon = image::SharedImage::load("icon.png").unwrap();
+    /// for i in 0..10 {
+    ///     let mut btn = button::Button::new(0, i * 30, 30, 30, "");
+    ///     btn.set_image(Some(image::SharedImage::load("icon.png").unwrap()));
+    /// }
+    /// ```
     pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<SharedImage, FltkError> {
-        Self::load_(path.as_ref())
+        Self::load_(path.as_ref(), 0, 0)
+    }
+
+    /// Loads a SharedImage from a path, scaled to width and height. The scaled copy is also cached
+    pub fn load_scaled<P: AsRef<std::path::Path>>(
+        path: P,
+        width: i32,
+        height: i32,
+    ) -> Result<SharedImage, FltkError> {
+        Self::load_(path.as_ref(), width, height)
     }
 
-    fn load_(path: &std::path::Path) -> Result<SharedImage, FltkError> {
+    /// Finds an already loaded SharedImage in the cache, without loading it from disk
+    pub fn find<P: AsRef<std::path::Path>>(path: P) -> Option<SharedImage> {
+        let temp = CString::safe_new(path.as_ref().to_str()?);
+        unsafe {
+            let x = Fl_Shared_Image_find(temp.as_ptr(), 0, 0);
+            if x.is_null() {
+                None
+            } else {
+                Some(SharedImage {
+                    _inner: x,
+                    _refcount: AtomicUsize::new(1),
+                })
+            }
+        }
+    }
+
+    /// Returns the path the image was loaded from
+    pub fn name(&self) -> String {
+        assert!(!self.was_deleted());
+        unsafe {
+            let ptr = Fl_Shared_Image_name(self._inner);
+            if ptr.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(ptr).to_string_lossy().to_string()
+            }
+        }
+    }
+
+    /// Returns the number of references FLTK holds to the cached image
+    pub fn refcount(&self) -> u32 {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Shared_Image_refcount(self._inner) as u32 }
+    }
+
+    /// Reloads the image from disk
+    pub fn reload(&mut self) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Shared_Image_reload(self._inner) }
+    }
+
+    fn load_(path: &std::path::Path, width: i32, height: i32) -> Result<SharedImage, FltkError> {
         if !path.exists() {
             return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound));
         }
@@ -40,7 +217,7 @@ impl SharedImage {
                 FltkError::Unknown(String::from("Failed to convert path to string"))
             })?;
             let temp = CString::new(temp)?;
-            let x = Fl_Shared_Image_get(temp.as_ptr(), 0, 0);
+            let x = Fl_Shared_Image_get(temp.as_ptr(), width, height);
             if x.is_null() {
                 Err(FltkError::Internal(FltkErrorKind::ResourceNotFound))
             } else {
@@ -132,15 +309,6 @@ impl JpegImage {
             }
         }
     }
-
-    /// Writes the JpegImage to a jpg file
-    pub fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FltkError> {
-        self.write_to_file_(path.as_ref())
-    }
-
-    fn write_to_file_(&self, path: &std::path::Path) -> Result<(), FltkError> {
-        crate::draw::write_to_jpg_file(self, path)
-    }
 }
 
 /// Creates a struct holding a PNG image
@@ -201,15 +369,6 @@ impl PngImage {
             }
         }
     }
-
-    /// Writes the PngImage to a png file
-    pub fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FltkError> {
-        self.write_to_file_(path.as_ref())
-    }
-
-    fn write_to_file_(&self, path: &std::path::Path) -> Result<(), FltkError> {
-        crate::draw::write_to_png_file(self, path)
-    }
 }
 
 /// Creates a struct holding an SVG image
@@ -331,15 +490,6 @@ impl BmpImage {
             }
         }
     }
-
-    /// Writes the BmpImage to a bmp file
-    pub fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FltkError> {
-        self.write_to_file_(path.as_ref())
-    }
-
-    fn write_to_file_(&self, path: &std::path::Path) -> Result<(), FltkError> {
-        crate::draw::write_to_bmp_file(self, path)
-    }
 }
 
 /// Creates a struct holding a GIF image
@@ -402,6 +552,235 @@ impl GifImage {
     }
 }
 
+struct GifPlayback {
+    current: usize,
+    playing: bool,
+    looping: bool,
+    generation: usize,
+    widget: Option<crate::widget::Widget>,
+}
+
+/// Holds the frames of an animated GIF, which can be played inside any widget showing an image.
+/// Clones share the same playback
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut frame = frame::Frame::new(0, 0, 400, 300, "");
+/// win.end();
+/// win.show();
+/// let mut gif = image::AnimatedGifImage::load("spinner.gif").unwrap();
+/// gif.play_in(&mut frame);
+/// app.run().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct AnimatedGifImage {
+    frames: Rc<Vec<(RgbImage, f64)>>,
+    state: Rc<RefCell<GifPlayback>>,
+}
+
+impl fmt::Debug for AnimatedGifImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let st = self.state.borrow();
+        f.debug_struct("AnimatedGifImage")
+            .field("frames", &self.frames.len())
+            .field("current", &st.current)
+            .field("playing", &st.playing)
+            .field("looping", &st.looping)
+            .finish()
+    }
+}
+
+struct GifTick {
+    gif: AnimatedGifImage,
+    generation: usize,
+}
+
+/// Shows the next frame, ticks scheduled before the playback was paused or moved are ignored
+unsafe extern "C" fn gif_tick(data: *mut raw::c_void) {
+    let tick = Box::from_raw(data as *mut GifTick);
+    tick.gif.advance(tick.generation);
+}
+
+impl AnimatedGifImage {
+    /// Loads and decodes the frames of a GIF from a filesystem path
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<AnimatedGifImage, FltkError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+        }
+        Self::from_data(&std::fs::read(path)?)
+    }
+
+    /// Decodes the frames of a GIF from data/memory
+    pub fn from_data(data: &[u8]) -> Result<AnimatedGifImage, FltkError> {
+        let (w, h, decoded) =
+            decode_gif(data).ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))?;
+        let mut frames = Vec::with_capacity(decoded.len());
+        for (pixels, delay) in decoded {
+            frames.push((RgbImage::new(&pixels, w, h, 4)?, delay));
+        }
+        Ok(AnimatedGifImage {
+            frames: Rc::new(frames),
+            state: Rc::new(RefCell::new(GifPlayback {
+                current: 0,
+                playing: false,
+                looping: true,
+                generation: 0,
+                widget: None,
+            })),
+        })
+    }
+
+    /// Returns the width of the frames
+    pub fn width(&self) -> i32 {
+        self.frames[0].0.width()
+    }
+
+    /// Returns the height of the frames
+    pub fn height(&self) -> i32 {
+        self.frames[0].0.height()
+    }
+
+    /// Returns the number of frames
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns a frame, composed with the previous ones like it's shown
+    pub fn frame(&self, idx: usize) -> Option<RgbImage> {
+        self.frames.get(idx).map(|(img, _)| img.clone())
+    }
+
+    /// Returns how long a frame is shown, in seconds
+    pub fn frame_delay(&self, idx: usize) -> Option<f64> {
+        self.frames.get(idx).map(|(_, delay)| *delay)
+    }
+
+    /// Sets the image of a widget to the current frame and starts playing inside it.
+    /// Playback stops when the widget is deleted
+    pub fn play_in<W: WidgetExt>(&mut self, wid: &mut W) {
+        assert!(!wid.was_deleted());
+        let wid = unsafe { crate::widget::Widget::from_widget_ptr(wid.as_widget_ptr()) };
+        self.state.borrow_mut().widget = Some(wid);
+        self.show_frame();
+        self.play();
+    }
+
+    /// Plays the frames from the current one, restarting from the first if the last one was reached
+    pub fn play(&mut self) {
+        {
+            let mut st = self.state.borrow_mut();
+            if st.playing || st.widget.is_none() || self.frames.len() < 2 {
+                return;
+            }
+            if st.current + 1 == self.frames.len() && !st.looping {
+                st.current = 0;
+            }
+            st.playing = true;
+            st.generation += 1;
+        }
+        self.show_frame();
+        self.schedule();
+    }
+
+    /// Pauses at the current frame
+    pub fn pause(&mut self) {
+        let mut st = self.state.borrow_mut();
+        st.playing = false;
+        st.generation += 1;
+    }
+
+    /// Returns whether the frames are playing
+    pub fn is_playing(&self) -> bool {
+        self.state.borrow().playing
+    }
+
+    /// Sets whether playback restarts from the first frame after the last one, true by default
+    pub fn set_looping(&mut self, flag: bool) {
+        self.state.borrow_mut().looping = flag;
+    }
+
+    /// Returns whether playback loops
+    pub fn is_looping(&self) -> bool {
+        self.state.borrow().looping
+    }
+
+    /// Returns the index of the current frame
+    pub fn current_frame(&self) -> usize {
+        self.state.borrow().current
+    }
+
+    /// Jumps to a frame, playback continues from it if playing
+    pub fn set_frame(&mut self, idx: usize) {
+        if idx >= self.frames.len() {
+            return;
+        }
+        let playing = {
+            let mut st = self.state.borrow_mut();
+            st.current = idx;
+            st.generation += 1;
+            st.playing
+        };
+        self.show_frame();
+        if playing {
+            self.schedule();
+        }
+    }
+
+    fn schedule(&self) {
+        let (generation, delay) = {
+            let st = self.state.borrow();
+            (st.generation, self.frames[st.current].1)
+        };
+        let tick = Box::into_raw(Box::new(GifTick {
+            gif: self.clone(),
+            generation,
+        }));
+        unsafe { fltk_sys::fl::Fl_add_timeout(delay, Some(gif_tick), tick as *mut raw::c_void) }
+    }
+
+    fn advance(&self, generation: usize) {
+        {
+            let mut st = self.state.borrow_mut();
+            if st.generation != generation || !st.playing {
+                return;
+            }
+            if st.widget.as_ref().map_or(true, |w| w.was_deleted()) {
+                st.playing = false;
+                st.widget = None;
+                return;
+            }
+            if st.current + 1 < self.frames.len() {
+                st.current += 1;
+            } else if st.looping {
+                st.current = 0;
+            } else {
+                st.playing = false;
+                return;
+            }
+        }
+        self.show_frame();
+        self.schedule();
+    }
+
+    fn show_frame(&self) {
+        let (current, wid) = {
+            let st = self.state.borrow();
+            (st.current, st.widget.clone())
+        };
+        if let Some(mut wid) = wid {
+            if !wid.was_deleted() {
+                wid.set_image(Some(self.frames[current].0.clone()));
+                // transparent frames are drawn over the parent
+                if !crate::utils::redraw_parent(unsafe { wid.as_widget_ptr() }) {
+                    wid.redraw();
+                }
+            }
+        }
+    }
+}
+
 /// Creates a struct holding a XPM image
 #[derive(ImageExt, Debug)]
 pub struct XpmImage {
@@ -524,7 +903,8 @@ pub struct TiledImage {
 }
 
 impl TiledImage {
-    /// Loads the image from a filesystem path, doesn't check for the validity of the data
+    /// Creates a tiled image from an image, which is repeated to fill w and h.
+    /// A w and h of 0 fill the whole window the image is drawn in
     pub fn new<Img: ImageExt>(img: Img, w: i32, h: i32) -> TiledImage {
         unsafe {
             let ptr = Fl_Tiled_Image_new(img.as_image_ptr(), w, h);
@@ -558,6 +938,148 @@ impl Pixmap {
             }
         }
     }
+
+    /// Creates a new Pixmap image from inline XPM data, the data is copied
+    /// ```no_run
+    /// use fltk::*;
+    /// const PXM: &[&str] = &[
+    ///     "4 2 2 1",
+    ///     "  c None",
+    ///     "# c #FF0000",
+    ///     "#  #",
+    ///     " ## ",
+    /// ];
+    /// let pxm = image::Pixmap::from_xpm(PXM).unwrap();
+    /// ```
+    pub fn from_xpm(data: &[&str]) -> Result<Pixmap, FltkError> {
+        let header: Vec<i32> = data
+            .first()
+            .ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))?
+            .split_whitespace()
+            .map(|v| v.parse::<i32>())
+            .collect::<Result<Vec<i32>, _>>()
+            .map_err(|_| FltkError::Internal(FltkErrorKind::ImageFormatError))?;
+        if header.len() < 4 || header[1] < 0 || header[2] < 0 {
+            return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+        }
+        let lines = header[1]
+            .checked_add(header[2])
+            .and_then(|n| n.checked_add(1))
+            .ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))?;
+        if data.len() < lines as usize {
+            return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+        }
+        let data: Vec<CString> = data.iter().map(|s| CString::safe_new(s)).collect();
+        let ptrs: Vec<*const raw::c_char> = data.iter().map(|s| s.as_ptr()).collect();
+        unsafe {
+            let ptr = Fl_Pixmap_new(ptrs.as_ptr() as *const *const raw::c_uchar);
+            if ptr.is_null() {
+                return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+            }
+            if Fl_Pixmap_fail(ptr) < 0 {
+                Fl_Pixmap_delete(ptr);
+                return Err(FltkError::Internal(FltkErrorKind::ImageFormatError));
+            }
+            // The pixmap doesn't own the data it's created from, its copy does
+            let copy = Fl_Pixmap_copy(ptr);
+            Fl_Pixmap_delete(ptr);
+            assert!(!copy.is_null());
+            Ok(Pixmap {
+                _inner: copy,
+                _refcount: AtomicUsize::new(1),
+            })
+        }
+    }
+}
+
+/// Creates a struct holding a Windows ICO image, decoded from one of the sizes stored in the file
+#[derive(ImageExt, Debug)]
+pub struct IcoImage {
+    _inner: *mut Fl_RGB_Image,
+    _refcount: AtomicUsize,
+}
+
+impl IcoImage {
+    /// Loads the largest image of an ICO file from a filesystem path
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<IcoImage, FltkError> {
+        Self::load_sized(path, None)
+    }
+
+    /// Loads the image of an ICO file best fitting a size, the smallest one at least that large, or the largest one
+    pub fn load_sized<P: AsRef<std::path::Path>>(
+        path: P,
+        size: Option<u32>,
+    ) -> Result<IcoImage, FltkError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+        }
+        Self::from_data_sized(&std::fs::read(path)?, size)
+    }
+
+    /// Loads the largest image of an ICO file from data/memory
+    pub fn from_data(data: &[u8]) -> Result<IcoImage, FltkError> {
+        Self::from_data_sized(data, None)
+    }
+
+    /// Loads the image of an ICO file best fitting a size from data/memory
+    pub fn from_data_sized(data: &[u8], size: Option<u32>) -> Result<IcoImage, FltkError> {
+        let entries =
+            ico_entries(data).ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))?;
+        let sizes: Vec<u32> = entries.iter().map(|(s, _)| *s).collect();
+        let entry = entries[ico_best_fit(&sizes, size)].1;
+        let img = if entry.starts_with(b"\x89PNG") {
+            PngImage::from_data(entry)?.to_rgb()?
+        } else {
+            let (w, h, pixels) = ico_dib_decode(entry)
+                .ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))?;
+            RgbImage::new(&pixels, w, h, 4)?
+        };
+        Ok(IcoImage {
+            _inner: rgb_image_ptr(img),
+            _refcount: AtomicUsize::new(1),
+        })
+    }
+}
+
+/// Creates a struct holding a WebP image, decoded using the image crate
+#[cfg(feature = "webp")]
+#[derive(ImageExt, Debug)]
+pub struct WebpImage {
+    _inner: *mut Fl_RGB_Image,
+    _refcount: AtomicUsize,
+}
+
+#[cfg(feature = "webp")]
+impl WebpImage {
+    /// Loads the image from a filesystem path
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<WebpImage, FltkError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+        }
+        Self::from_data(&std::fs::read(path)?)
+    }
+
+    /// Loads the image from data/memory
+    pub fn from_data(data: &[u8]) -> Result<WebpImage, FltkError> {
+        let img = ::image::load_from_memory_with_format(data, ::image::ImageFormat::WebP)
+            .map_err(|_| FltkError::Internal(FltkErrorKind::ImageFormatError))?;
+        let rgba = img.to_rgba8();
+        let (w, h) = rgba.dimensions();
+        let img = RgbImage::new(&rgba.into_raw(), w, h, 4)?;
+        Ok(WebpImage {
+            _inner: rgb_image_ptr(img),
+            _refcount: AtomicUsize::new(1),
+        })
+    }
+}
+
+/// Takes the pointer of an RgbImage to wrap it in another image type
+fn rgb_image_ptr(img: RgbImage) -> *mut Fl_RGB_Image {
+    let ptr = img._inner;
+    mem::forget(img);
+    ptr
 }
 
 /// Creates a struct holding a raw RGB image
@@ -631,6 +1153,40 @@ impl RgbImage {
         }
     }
 
+    /// Creates an RgbImage from an image crate DynamicImage, the data is converted to RGBA
+    /// ```no_run
+    /// use fltk::*;
+    /// let dyn_img = ::image::open("screenshot.png").unwrap();
+    /// let img = image::RgbImage::from_dynamic_image(&dyn_img).unwrap();
+    /// ```
+    #[cfg(feature = "use-image")]
+    pub fn from_dynamic_image(img: &::image::DynamicImage) -> Result<RgbImage, FltkError> {
+        let rgba = img.to_rgba8();
+        let (w, h) = rgba.dimensions();
+        RgbImage::new(&rgba.into_raw(), w, h, 4)
+    }
+
+    /// Converts the RgbImage to an image crate RgbaImage
+    #[cfg(feature = "use-image")]
+    pub fn to_rgba8(&self) -> Result<::image::RgbaImage, FltkError> {
+        assert!(!self.was_deleted());
+        let (w, h, d) = (self.data_w(), self.data_h(), self.depth());
+        let data = self.to_rgb_data();
+        let d = if d == 0 { 1 } else { d as usize };
+        let mut buf = Vec::with_capacity((w * h * 4) as usize);
+        for px in data.chunks_exact(d).take((w * h) as usize) {
+            match px {
+                [l] => buf.extend_from_slice(&[*l, *l, *l, 255]),
+                [l, a] => buf.extend_from_slice(&[*l, *l, *l, *a]),
+                [r, g, b] => buf.extend_from_slice(&[*r, *g, *b, 255]),
+                [r, g, b, a] => buf.extend_from_slice(&[*r, *g, *b, *a]),
+                _ => return Err(FltkError::Internal(FltkErrorKind::ImageFormatError)),
+            }
+        }
+        ::image::RgbaImage::from_raw(w, h, buf)
+            .ok_or(FltkError::Internal(FltkErrorKind::ImageFormatError))
+    }
+
     /// Deconstructs a raw RgbImage into parts
     /// # Safety
     /// Destructures the image into its raw elements
@@ -640,3 +1196,421 @@ impl RgbImage {
         (self.to_rgb_data(), w, h)
     }
 }
+
+/// Returns how long a GIF frame is shown in seconds, from its delay in hundredths of seconds.
+/// Like browsers, shorter delays than 2 are shown for a tenth of a second
+fn gif_delay(delay: u16) -> f64 {
+    if delay < 2 {
+        0.1
+    } else {
+        delay as f64 / 100.
+    }
+}
+
+/// Returns the rows of an interlaced GIF frame in the order they're stored
+fn gif_interlaced_rows(h: usize) -> Vec<usize> {
+    [(0, 8), (4, 8), (2, 4), (1, 2)]
+        .iter()
+        .flat_map(|&(start, step)| (start..h).step_by(step))
+        .collect()
+}
+
+/// Reads the data sub-blocks starting at pos, returns the position after them.
+/// Blocks cut by the end of the data are read up to it
+fn gif_sub_blocks(data: &[u8], mut pos: usize, mut out: Option<&mut Vec<u8>>) -> usize {
+    while let Some(&len) = data.get(pos) {
+        pos += 1;
+        if len == 0 {
+            return pos;
+        }
+        let end = (pos + len as usize).min(data.len());
+        if let Some(out) = out.as_mut() {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    pos
+}
+
+/// Decodes the LZW compressed color indices of a GIF frame
+fn gif_lzw_decode(data: &[u8], min_code_size: u8) -> Option<Vec<u8>> {
+    if !(1..=11).contains(&min_code_size) {
+        return None;
+    }
+    let clear = 1usize << min_code_size;
+    let end = clear + 1;
+    let mut prefix = [0u16; 4096];
+    let mut suffix = [0u8; 4096];
+    let mut first = [0u8; 4096];
+    for i in 0..clear {
+        suffix[i] = i as u8;
+        first[i] = i as u8;
+    }
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next = end + 1;
+    let mut prev: Option<usize> = None;
+    let (mut bits, mut nbits) = (0u32, 0u32);
+    let mut out = vec![];
+    let mut stack = vec![];
+    for &byte in data {
+        bits |= (byte as u32) << nbits;
+        nbits += 8;
+        while nbits >= code_size {
+            let code = (bits & ((1 << code_size) - 1)) as usize;
+            bits >>= code_size;
+            nbits -= code_size;
+            if code == clear {
+                code_size = min_code_size as u32 + 1;
+                next = end + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return Some(out);
+            }
+            let p = match prev {
+                Some(p) => p,
+                None => {
+                    if code >= clear {
+                        return None;
+                    }
+                    out.push(code as u8);
+                    prev = Some(code);
+                    continue;
+                }
+            };
+            // a code not in the table yet is the previous string followed by its first index
+            let k = if code < next && code != clear && code != end {
+                first[code]
+            } else if code == next {
+                first[p]
+            } else {
+                return None;
+            };
+            let mut c = if code == next {
+                stack.push(k);
+                p
+            } else {
+                code
+            };
+            while c > end {
+                stack.push(suffix[c]);
+                c = prefix[c] as usize;
+            }
+            stack.push(c as u8);
+            out.extend(stack.drain(..).rev());
+            if next < 4096 {
+                prefix[next] = p as u16;
+                suffix[next] = k;
+                first[next] = first[p];
+                next += 1;
+                if next == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            prev = Some(code);
+        }
+    }
+    Some(out)
+}
+
+/// Decodes the frames of a GIF, returning its size and the RGBA pixels of the frames
+/// composed on the whole canvas with their delays in seconds
+#[allow(clippy::type_complexity)]
+fn decode_gif(data: &[u8]) -> Option<(u32, u32, Vec<(Vec<u8>, f64)>)> {
+    if !(data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a")) {
+        return None;
+    }
+    let u16_at = |i: usize| {
+        data.get(i..i + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+    };
+    let (w, h) = (u16_at(6)?, u16_at(8)?);
+    let flags = *data.get(10)?;
+    if w == 0 || h == 0 {
+        return None;
+    }
+    let mut pos = 13;
+    let mut global: &[u8] = &[];
+    if flags & 0x80 != 0 {
+        let len = 3 * (2 << (flags & 7));
+        global = data.get(pos..pos + len)?;
+        pos += len;
+    }
+    let mut canvas = vec![0u8; w * h * 4];
+    let mut frames = vec![];
+    let (mut delay, mut transparent, mut disposal) = (0, None, 0);
+    loop {
+        match data.get(pos) {
+            Some(0x21) => {
+                let label = *data.get(pos + 1)?;
+                pos += 2;
+                if label == 0xF9 {
+                    // graphic control extension: size, flags, delay and transparent index
+                    let b = data.get(pos..pos + 5)?;
+                    disposal = (b[1] >> 2) & 7;
+                    delay = u16::from_le_bytes([b[2], b[3]]);
+                    transparent = if b[1] & 1 != 0 { Some(b[4]) } else { None };
+                }
+                pos = gif_sub_blocks(data, pos, None);
+            }
+            Some(0x2C) => {
+                let (fx, fy) = (u16_at(pos + 1)?, u16_at(pos + 3)?);
+                let (fw, fh) = (u16_at(pos + 5)?, u16_at(pos + 7)?);
+                let fflags = *data.get(pos + 9)?;
+                pos += 10;
+                let mut palette = global;
+                if fflags & 0x80 != 0 {
+                    let len = 3 * (2 << (fflags & 7));
+                    palette = data.get(pos..pos + len)?;
+                    pos += len;
+                }
+                let min_code_size = *data.get(pos)?;
+                let mut lzw = vec![];
+                pos = gif_sub_blocks(data, pos + 1, Some(&mut lzw));
+                let indices = gif_lzw_decode(&lzw, min_code_size)?;
+                let rows: Vec<usize> = if fflags & 0x40 != 0 {
+                    gif_interlaced_rows(fh)
+                } else {
+                    (0..fh).collect()
+                };
+                let previous = if disposal == 3 {
+                    Some(canvas.clone())
+                } else {
+                    None
+                };
+                for (i, &idx) in indices.iter().take(fw * fh).enumerate() {
+                    let (x, y) = (fx + i % fw, fy + rows[i / fw]);
+                    if Some(idx) == transparent || x >= w || y >= h {
+                        continue;
+                    }
+                    let idx = idx as usize * 3;
+                    if let Some(c) = palette.get(idx..idx + 3) {
+                        let o = (y * w + x) * 4;
+                        canvas[o..o + 3].copy_from_slice(c);
+                        canvas[o + 3] = 255;
+                    }
+                }
+                frames.push((canvas.clone(), gif_delay(delay)));
+                match disposal {
+                    2 => {
+                        for y in fy..(fy + fh).min(h) {
+                            for x in fx..(fx + fw).min(w) {
+                                let o = (y * w + x) * 4;
+                                canvas[o..o + 4].copy_from_slice(&[0, 0, 0, 0]);
+                            }
+                        }
+                    }
+                    3 => {
+                        if let Some(previous) = previous {
+                            canvas = previous;
+                        }
+                    }
+                    _ => (),
+                }
+                delay = 0;
+                transparent = None;
+                disposal = 0;
+            }
+            // a truncated file keeps the frames decoded so far
+            Some(0x3B) | None => break,
+            Some(_) => return None,
+        }
+    }
+    if frames.is_empty() {
+        None
+    } else {
+        Some((w as u32, h as u32, frames))
+    }
+}
+
+/// Returns the sizes and data of the images of an ICO or CUR file, a size of 0 being 256
+fn ico_entries(data: &[u8]) -> Option<Vec<(u32, &[u8])>> {
+    let u16_at = |i: usize| data.get(i..i + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_at = |i: usize| {
+        data.get(i..i + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    };
+    if u16_at(0)? != 0 || !matches!(u16_at(2)?, 1 | 2) {
+        return None;
+    }
+    let mut entries = vec![];
+    for i in 0..u16_at(4)? as usize {
+        let e = 6 + i * 16;
+        let (w, h) = (*data.get(e)?, *data.get(e + 1)?);
+        let (len, offset) = (u32_at(e + 8)?, u32_at(e + 12)?);
+        let size = match w.max(h) {
+            0 => 256,
+            s => s as u32,
+        };
+        entries.push((size, data.get(offset..offset.checked_add(len)?)?));
+    }
+    if entries.is_empty() {
+        None
+    } else {
+        Some(entries)
+    }
+}
+
+/// Returns the index of the smallest size at least as large as the wanted one, or of the largest size
+fn ico_best_fit(sizes: &[u32], size: Option<u32>) -> usize {
+    let largest = (0..sizes.len()).max_by_key(|&i| sizes[i]).unwrap_or(0);
+    match size {
+        Some(size) => (0..sizes.len())
+            .filter(|&i| sizes[i] >= size)
+            .min_by_key(|&i| sizes[i])
+            .unwrap_or(largest),
+        None => largest,
+    }
+}
+
+/// Decodes the bitmap of an ICO image into RGBA pixels. Its height counts both the color bitmap
+/// and the transparency mask following it, used unless the 32 bits colors have an alpha channel
+fn ico_dib_decode(data: &[u8]) -> Option<(u32, u32, Vec<u8>)> {
+    let u16_at = |i: usize| {
+        data.get(i..i + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+    };
+    let u32_at = |i: usize| {
+        data.get(i..i + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    };
+    let header = u32_at(0)?;
+    let (w, h) = (u32_at(4)?, u32_at(8)? / 2);
+    let bpp = u16_at(14)?;
+    // only uncompressed bitmaps are used in icons
+    if w == 0 || h == 0 || w > 1024 || h > 1024 || u32_at(16)? != 0 {
+        return None;
+    }
+    let palette_len = match bpp {
+        1 | 4 | 8 => match u32_at(32)? {
+            0 => 1 << bpp,
+            n => n,
+        },
+        24 | 32 => 0,
+        _ => return None,
+    };
+    let palette = data.get(header..header + palette_len * 4)?;
+    let stride = (w * bpp + 31) / 32 * 4;
+    let colors_start = header + palette.len();
+    let colors = data.get(colors_start..colors_start + stride * h)?;
+    let mask_stride = (w + 31) / 32 * 4;
+    let mask_start = colors_start + colors.len();
+    let mask = data.get(mask_start..mask_start + mask_stride * h);
+    let has_alpha = bpp == 32 && colors.chunks(4).any(|px| px[3] != 0);
+    let mut out = Vec::with_capacity(w * h * 4);
+    // the rows are stored bottom-up, in BGR order
+    for y in (0..h).rev() {
+        let row = &colors[y * stride..];
+        for x in 0..w {
+            let (b, g, r, a) = match bpp {
+                32 => (row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]),
+                24 => (row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 255),
+                _ => {
+                    let bit = x * bpp;
+                    let idx = (row[bit / 8] >> (8 - bpp - bit % 8)) as usize & ((1 << bpp) - 1);
+                    let c = palette.get(idx * 4..idx * 4 + 3)?;
+                    (c[0], c[1], c[2], 255)
+                }
+            };
+            let masked = mask.map_or(false, |m| {
+                (m[y * mask_stride + x / 8] >> (7 - x % 8)) & 1 != 0
+            });
+            let a = if has_alpha {
+                a
+            } else if masked {
+                0
+            } else {
+                255
+            };
+            out.extend_from_slice(&[r, g, b, a]);
+        }
+    }
+    Some((w as u32, h as u32, out))
+}
+
+#[cfg(test)]
+mod image {
+    use super::*;
+    #[test]
+    fn gif_decoding() {
+        // clear, 0, 6 (0 0, not in the table yet), end
+        assert_eq!(gif_lzw_decode(&[0x84, 0x0B], 2), Some(vec![0, 0, 0]));
+        assert_eq!(gif_interlaced_rows(5), vec![0, 4, 2, 1, 3]);
+        let data = [
+            b'G', b'I', b'F', b'8', b'9', b'a', 2, 0, 1, 0, 0x80, 0, 0, // header
+            0xFF, 0, 0, 0, 0, 0xFF, // red and blue
+            0x21, 0xF9, 4, 0, 10, 0, 0, 0, // 10/100s
+            0x2C, 0, 0, 0, 0, 2, 0, 1, 0, 0, 2, 2, 0x44, 0x0A, 0, // red, blue
+            0x21, 0xF9, 4, 0, 50, 0, 0, 0, // 50/100s
+            0x2C, 1, 0, 0, 0, 1, 0, 1, 0, 0, 2, 2, 0x44, 0x01, 0, // red at x = 1
+            0x3B,
+        ];
+        let (w, h, frames) = decode_gif(&data).unwrap();
+        assert_eq!((w, h, frames.len()), (2, 1, 2));
+        assert_eq!(frames[0].0, vec![0xFF, 0, 0, 0xFF, 0, 0, 0xFF, 0xFF]);
+        assert_eq!(frames[1].0, vec![0xFF, 0, 0, 0xFF, 0xFF, 0, 0, 0xFF]);
+        assert!((frames[0].1 - 0.1).abs() < 1e-9);
+        assert!((frames[1].1 - 0.5).abs() < 1e-9);
+        assert!(decode_gif(b"GIF89a").is_none());
+    }
+    #[test]
+    fn xpm_headers() {
+        assert!(Pixmap::from_xpm(&[]).is_err());
+        assert!(Pixmap::from_xpm(&["4 2 2"]).is_err());
+        assert!(Pixmap::from_xpm(&["4 x 2 1"]).is_err());
+        assert!(Pixmap::from_xpm(&["4 2147483647 1 1", "  c None"]).is_err());
+        assert!(Pixmap::from_xpm(&["4 -2 2 1", "  c None"]).is_err());
+    }
+
+    #[test]
+    fn bmp_encoding() {
+        // red and green on top of blue and (1, 2, 3)
+        let bmp = encode_bmp(&[0xFF, 0, 0, 0, 0xFF, 0, 0, 0, 0xFF, 1, 2, 3], 2, 2);
+        assert_eq!(bmp.len(), 70);
+        assert_eq!(&bmp[..6], &[b'B', b'M', 70, 0, 0, 0]);
+        assert_eq!(&bmp[18..30], &[2, 0, 0, 0, 2, 0, 0, 0, 1, 0, 24, 0]);
+        assert_eq!(&bmp[54..62], &[0xFF, 0, 0, 3, 2, 1, 0, 0]);
+        assert_eq!(&bmp[62..], &[0, 0, 0xFF, 0, 0xFF, 0, 0, 0]);
+    }
+    #[test]
+    fn ico_decoding() {
+        let mut data = vec![0, 0, 1, 0, 1, 0]; // an icon with 1 image
+        data.extend_from_slice(&[2, 2, 0, 0, 1, 0, 24, 0, 64, 0, 0, 0, 22, 0, 0, 0]);
+        data.extend_from_slice(&[40, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 1, 0, 24, 0]);
+        data.extend_from_slice(&[0; 24]);
+        // bottom row blue and green, top row red and white
+        data.extend_from_slice(&[0xFF, 0, 0, 0, 0xFF, 0, 0, 0]);
+        data.extend_from_slice(&[0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0]);
+        // the top right pixel is transparent
+        data.extend_from_slice(&[0, 0, 0, 0, 0x40, 0, 0, 0]);
+        let entries = ico_entries(&data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, 2);
+        let (w, h, pixels) = ico_dib_decode(entries[0].1).unwrap();
+        assert_eq!((w, h), (2, 2));
+        assert_eq!(
+            pixels,
+            vec![
+                0xFF, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0, //
+                0, 0, 0xFF, 0xFF, 0, 0xFF, 0, 0xFF,
+            ]
+        );
+        assert_eq!(ico_best_fit(&[16, 256, 32, 48], None), 1);
+        assert_eq!(ico_best_fit(&[16, 256, 32, 48], Some(24)), 2);
+        assert_eq!(ico_best_fit(&[16, 32], Some(64)), 1);
+        assert!(ico_entries(&[0, 0, 1, 0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn image_formats() {
+        assert_eq!(ImageFormat::from_path("a.PNG"), Some(ImageFormat::Png));
+        assert_eq!(
+            ImageFormat::from_path("dir/a.jpeg"),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(ImageFormat::from_path("a.bmp"), Some(ImageFormat::Bmp));
+        assert_eq!(ImageFormat::from_path("a.gif"), None);
+        assert_eq!(ImageFormat::from_path("png"), None);
+    }
+}
@@ -2,11 +2,354 @@ use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::input::*;
 use std::{
+    cell::RefCell,
     ffi::{CStr, CString},
-    mem,
+    fmt, fs, mem,
+    ops::{Deref, DerefMut},
     os::raw,
+    path,
+    rc::Rc,
 };
 
+/// Path validation and completion settings of a FileInput
+#[derive(Debug, Default, Copy, Clone)]
+struct PathAssist {
+    /// The normal and invalid text colors
+    validation: Option<(Color, Color)>,
+    completion: bool,
+}
+
+/// The content, insertion position and mark of an input
+type Snapshot = (String, u32, u32);
+
+/// Validation, undo history and path assistance state of an input, see InputExt::set_validator,
+/// InputExt::set_undo_limit and FileInput::set_path_validation.
+/// All of them are handled by a single handler installed using handle2, see handle_edit
+#[derive(Default)]
+struct EditState {
+    validator: Option<Box<dyn FnMut(&str) -> bool>>,
+    limit: usize,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// The nesting level of undo groups, and the content before the outermost one
+    group: Option<(u32, Snapshot)>,
+    path: PathAssist,
+    /// Whether handle_edit is forwarding an event to FLTK's handler
+    forwarding: bool,
+}
+
+thread_local! {
+    /// The edit states of the inputs, shared with their handle methods
+    static EDIT_STATES: RefCell<crate::utils::WidgetMap<Rc<RefCell<EditState>>>> = RefCell::new(Default::default());
+}
+
+fn snapshot<I: InputExt>(inp: &I) -> Snapshot {
+    (inp.value(), inp.position(), inp.mark())
+}
+
+fn restore<I: InputExt>(inp: &mut I, s: &Snapshot) {
+    inp.set_value(&s.0);
+    let _ = inp.set_position(s.1);
+    let _ = inp.set_mark(s.2);
+}
+
+fn edit_state<I: InputExt>(inp: &I) -> Option<Rc<RefCell<EditState>>> {
+    EDIT_STATES.with(|m| m.borrow_mut().get(inp).cloned())
+}
+
+/// Returns the edit state of an input, creating it and installing its handler if needed
+fn edit_state_init<I: InputExt + WidgetBase>(inp: &mut I) -> Rc<RefCell<EditState>> {
+    if let Some(state) = edit_state(inp) {
+        return state;
+    }
+    let state = Rc::new(RefCell::new(EditState::default()));
+    EDIT_STATES.with(|m| m.borrow_mut().insert(inp, state.clone()));
+    let st = state.clone();
+    inp.handle2(move |inp, ev| handle_edit(&st, inp, ev));
+    state
+}
+
+/// Handles the undo and redo shortcuts and path completion, and checks the edits typed, pasted or dropped.
+/// The handler runs before FLTK processes the event, so edits are checked by forwarding the event to FLTK
+/// from here, with the Changed trigger masked so that the callback only sees accepted edits
+fn handle_edit<I: InputExt>(state: &RefCell<EditState>, inp: &mut I, ev: Event) -> bool {
+    let (forwarding, history, path) = {
+        let st = state.borrow();
+        (st.forwarding, st.limit > 0, st.path)
+    };
+    if forwarding {
+        return false;
+    }
+    match ev {
+        Event::KeyDown if history && crate::app::is_event_command() => {
+            let k = crate::app::event_key();
+            let shift = crate::app::is_event_shift();
+            let undo = k == Key::from_char('z') && !shift;
+            let redo = (k == Key::from_char('z') && shift) || k == Key::from_char('y');
+            if undo || redo {
+                let _ = step_history(state, inp, undo);
+                validate_path(state, inp);
+                true
+            } else {
+                forward_edit(state, inp, ev)
+            }
+        }
+        Event::KeyDown if path.completion && crate::app::event_key() == Key::Tab => {
+            let before = snapshot(inp);
+            complete_path(inp);
+            let current = snapshot(inp);
+            if current.0 != before.0 && !accept_edit(state, before.clone(), &current) {
+                restore(inp, &before);
+            }
+            validate_path(state, inp);
+            true
+        }
+        Event::KeyDown | Event::Paste => forward_edit(state, inp, ev),
+        Event::Unfocus => {
+            validate_path(state, inp);
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Lets FLTK handle an event which may edit the input, then rejects or records the edit.
+/// A rejected edit is undone before the input is redrawn or its callback is triggered
+fn forward_edit<I: InputExt>(state: &RefCell<EditState>, inp: &mut I, ev: Event) -> bool {
+    let before = snapshot(inp);
+    let was_changed = inp.changed();
+    let trigger = inp.trigger();
+    inp.set_trigger(trigger - CallbackTrigger::Changed);
+    state.borrow_mut().forwarding = true;
+    let handled = crate::app::send_event(inp, ev);
+    state.borrow_mut().forwarding = false;
+    if inp.was_deleted() {
+        return true;
+    }
+    inp.set_trigger(trigger);
+    let current = snapshot(inp);
+    if current.0 != before.0 {
+        if accept_edit(state, before.clone(), &current) {
+            if trigger.contains(CallbackTrigger::Changed) {
+                inp.do_callback();
+            }
+        } else {
+            restore(inp, &before);
+            if !was_changed {
+                inp.clear_changed();
+            }
+        }
+    }
+    validate_path(state, inp);
+    // an event FLTK didn't use is handed to it again once this returns, it doesn't edit the input either
+    handled
+}
+
+/// Validates an edit from ```before``` to ```current```, recording it in the undo history if it's accepted
+fn accept_edit(state: &RefCell<EditState>, before: Snapshot, current: &Snapshot) -> bool {
+    let validator = state.borrow_mut().validator.take();
+    let valid = match validator {
+        Some(mut cb) => {
+            let ret = cb(&current.0);
+            state.borrow_mut().validator = Some(cb);
+            ret
+        }
+        None => true,
+    };
+    if valid {
+        let mut st = state.borrow_mut();
+        if st.limit > 0 && st.group.is_none() {
+            st.undo.push(before);
+            let excess = st.undo.len().saturating_sub(st.limit);
+            st.undo.drain(..excess);
+            st.redo.clear();
+        }
+    }
+    valid
+}
+
+/// Moves one step back in the undo history if ```undo```, otherwise one step forward
+fn step_history<I: InputExt>(
+    state: &RefCell<EditState>,
+    inp: &mut I,
+    undo: bool,
+) -> Result<(), FltkError> {
+    let current = snapshot(inp);
+    let target = {
+        let mut st = state.borrow_mut();
+        let st = &mut *st;
+        let (from, to) = if undo {
+            (&mut st.undo, &mut st.redo)
+        } else {
+            (&mut st.redo, &mut st.undo)
+        };
+        let target = from
+            .pop()
+            .ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+        to.push(current);
+        target
+    };
+    restore(inp, &target);
+    Ok(())
+}
+
+pub(crate) fn set_validator<I: InputExt + WidgetBase>(
+    inp: &mut I,
+    cb: Box<dyn FnMut(&str) -> bool>,
+) {
+    edit_state_init(inp).borrow_mut().validator = Some(cb);
+}
+
+pub(crate) fn set_undo_limit<I: InputExt + WidgetBase>(inp: &mut I, limit: usize) {
+    let state = edit_state_init(inp);
+    let mut st = state.borrow_mut();
+    st.limit = limit;
+    let excess = st.undo.len().saturating_sub(limit);
+    st.undo.drain(..excess);
+    st.redo.truncate(limit);
+}
+
+pub(crate) fn has_undo_history<I: InputExt>(inp: &I) -> bool {
+    edit_state(inp).map_or(false, |st| st.borrow().limit > 0)
+}
+
+pub(crate) fn undo_depth<I: InputExt>(inp: &I) -> (usize, usize) {
+    edit_state(inp).map_or((0, 0), |st| {
+        let st = st.borrow();
+        (st.undo.len(), st.redo.len())
+    })
+}
+
+pub(crate) fn edit_undo<I: InputExt>(inp: &mut I) -> Result<(), FltkError> {
+    let state = edit_state(inp).ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+    step_history(&state, inp, true)
+}
+
+pub(crate) fn edit_redo<I: InputExt>(inp: &mut I) -> Result<(), FltkError> {
+    let state = edit_state(inp).ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+    step_history(&state, inp, false)
+}
+
+pub(crate) fn begin_undo_group<I: InputExt>(inp: &I) {
+    if let Some(state) = edit_state(inp) {
+        let current = snapshot(inp);
+        let mut st = state.borrow_mut();
+        st.group = match st.group.take() {
+            Some((level, start)) => Some((level + 1, start)),
+            None => Some((1, current)),
+        };
+    }
+}
+
+pub(crate) fn end_undo_group<I: InputExt>(inp: &I) {
+    if let Some(state) = edit_state(inp) {
+        let current = snapshot(inp);
+        let mut st = state.borrow_mut();
+        st.group = match st.group.take() {
+            Some((1, start)) => {
+                if start.0 != current.0 && st.limit > 0 {
+                    st.undo.push(start);
+                    let excess = st.undo.len().saturating_sub(st.limit);
+                    st.undo.drain(..excess);
+                    st.redo.clear();
+                }
+                None
+            }
+            Some((level, start)) => Some((level - 1, start)),
+            None => None,
+        };
+    }
+}
+
+/// Colors the text of an input with path validation enabled depending on whether the typed path exists
+fn validate_path<I: InputExt>(state: &RefCell<EditState>, inp: &mut I) {
+    let validation = state.borrow().path.validation;
+    if let Some((normal, invalid)) = validation {
+        let value = inp.value();
+        let color = if value.is_empty() || path::Path::new(&value).exists() {
+            normal
+        } else {
+            invalid
+        };
+        if inp.text_color() != color {
+            inp.set_text_color(color);
+            inp.redraw();
+        }
+    }
+}
+
+/// Returns the existing paths starting with ```value```, sorted
+fn path_completions(value: &str) -> Vec<String> {
+    let (dir, prefix) = match value.rfind(|c| c == '/' || c == path::MAIN_SEPARATOR) {
+        Some(i) => value.split_at(i + 1),
+        None => ("", value),
+    };
+    let read_dir = if dir.is_empty() { "." } else { dir };
+    let mut v: Vec<String> = match fs::read_dir(read_dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|n| n.starts_with(prefix))
+            .map(|n| format!("{}{}", dir, n))
+            .collect(),
+        Err(_) => vec![],
+    };
+    v.sort();
+    v
+}
+
+fn set_completed_path<I: InputExt>(inp: &mut I, p: &str) {
+    let mut p = p.to_string();
+    let has_sep = p.ends_with('/') || p.ends_with(path::MAIN_SEPARATOR);
+    if !has_sep && path::Path::new(&p).is_dir() {
+        p.push(path::MAIN_SEPARATOR);
+    }
+    inp.set_value(&p);
+    let _ = inp.set_position(p.len() as u32);
+    let _ = inp.set_mark(p.len() as u32);
+}
+
+fn complete_path<I: InputExt>(inp: &mut I) {
+    let completions = path_completions(&inp.value());
+    match completions.len() {
+        0 => (),
+        1 => set_completed_path(inp, &completions[0]),
+        _ => {
+            let first = &completions[0];
+            let common = completions
+                .iter()
+                .skip(1)
+                .fold(first.chars().count(), |len, c| {
+                    first
+                        .chars()
+                        .zip(c.chars())
+                        .take(len)
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                });
+            let common: String = first.chars().take(common).collect();
+            if common.len() > inp.value().len() {
+                inp.set_value(&common);
+                let _ = inp.set_position(common.len() as u32);
+                let _ = inp.set_mark(common.len() as u32);
+                return;
+            }
+            let dir_len = common
+                .rfind(|c| c == '/' || c == path::MAIN_SEPARATOR)
+                .map(|i| i + 1)
+                .unwrap_or(0);
+            let names: Vec<&str> = completions.iter().map(|c| &c[dir_len..]).collect();
+            let mut menu = crate::menu::MenuItem::new(&names);
+            if let Some(item) = menu.popup(inp.x(), inp.y() + inp.height()) {
+                if let Some(name) = item.label() {
+                    let p = format!("{}{}", &common[..dir_len], name);
+                    set_completed_path(inp, &p);
+                }
+            }
+        }
+    }
+}
+
 /// Creates an input widget
 #[derive(WidgetBase, WidgetExt, InputExt, Debug)]
 pub struct Input {
@@ -76,6 +419,48 @@ impl FileInput {
         assert!(!self.was_deleted());
         unsafe { mem::transmute(Fl_File_Input_down_box(self._inner)) }
     }
+
+    /// Colors the text with invalid_color while the typed path doesn't exist, None disables validation.
+    /// Shares its handler with set_validator() and set_undo_limit(), and replaces any handler set using handle() or handle2()
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut inp = input::FileInput::new(10, 10, 300, 35, "");
+    /// inp.set_path_validation(Some(Color::Red));
+    /// inp.set_path_completion(true);
+    /// ```
+    pub fn set_path_validation(&mut self, invalid_color: Option<Color>) {
+        assert!(!self.was_deleted());
+        let normal = self.text_color();
+        let state = edit_state_init(self);
+        {
+            let mut st = state.borrow_mut();
+            // keep the original color if validation was already enabled
+            let normal = st.path.validation.map(|(n, _)| n).unwrap_or(normal);
+            st.path.validation = invalid_color.map(|c| (normal, c));
+        }
+        validate_path(&state, self);
+    }
+
+    /// Enables completing the typed path using the Tab key.
+    /// A unique match is completed directly, otherwise the common part is completed
+    /// and the matching entries are shown in a popup menu.
+    /// Completions are checked by the validator, if any, and recorded in the undo history
+    pub fn set_path_completion(&mut self, flag: bool) {
+        assert!(!self.was_deleted());
+        edit_state_init(self).borrow_mut().path.completion = flag;
+    }
+
+    /// Returns whether the typed path exists
+    pub fn path_exists(&self) -> bool {
+        assert!(!self.was_deleted());
+        path::Path::new(&self.value()).exists()
+    }
+
+    /// Returns the existing paths starting with the typed path, sorted
+    pub fn path_completions(&self) -> Vec<String> {
+        assert!(!self.was_deleted());
+        path_completions(&self.value())
+    }
 }
 
 /// Creates a secret input widget
@@ -84,3 +469,318 @@ pub struct SecretInput {
     _inner: *mut Fl_Secret_Input,
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
+
+/// Returns a validator for InputExt::set_validator accepting integers between min and max inclusive,
+/// along with the incomplete input which can still become one
+pub fn int_range_validator(min: i64, max: i64) -> impl FnMut(&str) -> bool {
+    move |s| {
+        if s.is_empty() || (s == "-" && min < 0) {
+            return true;
+        }
+        let negative = s.starts_with('-');
+        let digits = if negative { &s[1..] } else { s };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        let n: i64 = match digits.parse() {
+            Ok(n) => n,
+            Err(_) => return false,
+        };
+        // check whether appending digits can reach the range
+        let mut scale: i64 = 1;
+        for _ in 0..19 {
+            let next = n.checked_add(1).and_then(|n| n.checked_mul(scale));
+            let (lo, hi) = match (n.checked_mul(scale), next) {
+                (Some(lo), Some(hi)) => (lo, hi - 1),
+                _ => break,
+            };
+            let (lo, hi) = if negative { (-hi, -lo) } else { (lo, hi) };
+            if lo <= max && hi >= min {
+                return true;
+            }
+            if n == 0 {
+                break;
+            }
+            scale = match scale.checked_mul(10) {
+                Some(scale) => scale,
+                None => break,
+            };
+        }
+        false
+    }
+}
+
+/// Returns a validator for InputExt::set_validator accepting IPv4 addresses like 192.168.0.1,
+/// along with the incomplete input which can still become one
+pub fn ipv4_validator() -> impl FnMut(&str) -> bool {
+    |s| {
+        let parts: Vec<&str> = s.split('.').collect();
+        parts.len() <= 4
+            && parts.iter().all(|p| {
+                p.len() <= 3
+                    && p.chars().all(|c| c.is_ascii_digit())
+                    && p.parse::<u32>().map(|v| v <= 255).unwrap_or(true)
+            })
+    }
+}
+
+/// Returns a validator for InputExt::set_validator accepting dates in the YYYY-MM-DD format,
+/// along with the incomplete input which can still become one
+pub fn date_validator() -> impl FnMut(&str) -> bool {
+    |s| {
+        if s.len() > 10 {
+            return false;
+        }
+        let template_ok = s.chars().enumerate().all(|(i, c)| match i {
+            4 | 7 => c == '-',
+            _ => c.is_ascii_digit(),
+        });
+        if !template_ok {
+            return false;
+        }
+        let num = |r: std::ops::Range<usize>| s.get(r).and_then(|v| v.parse::<u32>().ok());
+        let month = num(5..7);
+        if let Some(m) = month {
+            if m == 0 || m > 12 {
+                return false;
+            }
+        } else if s.len() == 6 && &s[5..6] > "1" {
+            return false;
+        }
+        if let (Some(y), Some(m), Some(d)) = (num(0..4), month, num(8..10)) {
+            return (1..=crate::misc::days_in_month(y as i32, m)).contains(&d);
+        }
+        !(s.len() == 9 && &s[8..9] > "3")
+    }
+}
+
+/// Returns a rough estimate of a password's strength from 0 to 1, based on its length and the kinds of characters it mixes.
+/// Can be used as the strength callback of a PasswordInput
+pub fn password_strength(pass: &str) -> f64 {
+    let kinds = [
+        pass.chars().any(|c| c.is_lowercase()),
+        pass.chars().any(|c| c.is_uppercase()),
+        pass.chars().any(|c| c.is_numeric()),
+        pass.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .iter()
+    .filter(|k| **k)
+    .count();
+    let len = pass.chars().count() as f64;
+    ((len / 16.).min(1.) * 0.6 + kinds as f64 / 4. * 0.4).min(1.)
+}
+
+type StrengthCallback = Box<dyn FnMut(&str) -> f64>;
+
+#[derive(Default)]
+struct PasswordState {
+    strength: Option<StrengthCallback>,
+    revealed: bool,
+}
+
+impl fmt::Debug for PasswordState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PasswordState")
+            .field("revealed", &self.revealed)
+            .finish()
+    }
+}
+
+/// Defines a password input (custom widget), a secret input with a toggle button revealing its text.
+/// When a strength callback is set, a bar under the input shows the strength it returns for the text,
+/// from red to green. The inner input's callback is used to update the bar
+/// ```no_run
+/// use fltk::*;
+/// let mut pass = input::PasswordInput::new(80, 5, 200, 30, "Password");
+/// pass.set_strength_callback(input::password_strength);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PasswordInput {
+    grp: crate::group::Group,
+    inp: SecretInput,
+    toggle: crate::button::ToggleButton,
+    bar: crate::frame::Frame,
+    state: Rc<RefCell<PasswordState>>,
+}
+
+impl PasswordInput {
+    /// Creates a new password input, without a strength bar
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> PasswordInput {
+        let mut grp = crate::group::Group::new(x, y, w, h, label);
+        grp.set_align(Align::Left);
+        let mut inp = SecretInput::new(x, y, w - h, h, "");
+        let mut toggle = crate::button::ToggleButton::new(x + w - h, y, h, h, "");
+        let mut bar = crate::frame::Frame::new(x, y + h - 4, w - h, 4, "");
+        grp.end();
+        grp.resizable(&inp);
+        bar.hide();
+        toggle.set_tooltip("Show the password");
+        toggle.clear_visible_focus();
+        inp.set_trigger(CallbackTrigger::Changed);
+        let state = Rc::new(RefCell::new(PasswordState::default()));
+
+        toggle.draw2(|b| {
+            let (cx, cy) = (b.x() + b.width() / 2, b.y() + b.height() / 2);
+            let r = (b.width().min(b.height()) / 3).max(3);
+            crate::draw::set_draw_color(Color::ForeGround);
+            crate::draw::draw_arc(cx - r, cy - r / 2, 2 * r, r, 0., 360.);
+            crate::draw::draw_pie(cx - r / 3, cy - r / 3, 2 * (r / 3), 2 * (r / 3), 0., 360.);
+            if !b.is_toggled() {
+                crate::draw::draw_line(cx - r, cy + r * 2 / 3, cx + r, cy - r * 2 / 3);
+            }
+        });
+
+        let st = state.clone();
+        let i = inp.clone();
+        bar.draw2(move |f| {
+            let val = {
+                let cb = st.borrow_mut().strength.take();
+                match cb {
+                    Some(mut cb) => {
+                        let val = cb(&i.value());
+                        st.borrow_mut().strength = Some(cb);
+                        val.clamp(0., 1.)
+                    }
+                    None => 0.,
+                }
+            };
+            let color = if val < 0.34 {
+                Color::Red
+            } else if val < 0.67 {
+                Color::from_rgb(230, 160, 0)
+            } else {
+                Color::from_rgb(0, 170, 0)
+            };
+            crate::draw::draw_rect_fill(f.x(), f.y(), f.width(), f.height(), Color::Light2);
+            let filled = (f.width() as f64 * val) as i32;
+            crate::draw::draw_rect_fill(f.x(), f.y(), filled, f.height(), color);
+        });
+
+        let mut b = bar.clone();
+        inp.set_callback(move || b.redraw());
+
+        let st = state.clone();
+        let mut i = inp.clone();
+        toggle.set_callback2(move |t| {
+            let revealed = t.is_toggled();
+            st.borrow_mut().revealed = revealed;
+            i.set_type(if revealed {
+                InputType::Normal
+            } else {
+                InputType::Secret
+            });
+            t.set_tooltip(if revealed {
+                "Hide the password"
+            } else {
+                "Show the password"
+            });
+            i.redraw();
+        });
+
+        PasswordInput {
+            grp,
+            inp,
+            toggle,
+            bar,
+            state,
+        }
+    }
+
+    /// Returns the inner secret input
+    pub fn input(&self) -> SecretInput {
+        self.inp.clone()
+    }
+
+    /// Returns the password
+    pub fn value(&self) -> String {
+        self.inp.value()
+    }
+
+    /// Sets the password, also updating the strength bar
+    pub fn set_value(&mut self, val: &str) {
+        self.inp.set_value(val);
+        self.bar.redraw();
+    }
+
+    /// Returns whether the password is shown in clear text
+    pub fn revealed(&self) -> bool {
+        self.state.borrow().revealed
+    }
+
+    /// Shows or hides the password's text, as the toggle button does
+    pub fn set_revealed(&mut self, flag: bool) {
+        self.toggle.toggle(flag);
+        self.toggle.do_callback();
+    }
+
+    /// Sets the callback returning the strength of the password from 0 to 1, and shows the strength bar
+    pub fn set_strength_callback<F: FnMut(&str) -> f64 + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().strength = Some(Box::new(cb));
+        let (x, y, w, h) = (
+            self.grp.x(),
+            self.grp.y(),
+            self.grp.width(),
+            self.grp.height(),
+        );
+        self.inp.resize(x, y, w - h, h - 6);
+        self.bar.resize(x, y + h - 4, w - h, 4);
+        self.bar.show();
+        self.grp.redraw();
+    }
+
+    /// Removes the strength callback and hides the strength bar
+    pub fn clear_strength_callback(&mut self) {
+        self.state.borrow_mut().strength = None;
+        let (x, y, w, h) = (
+            self.grp.x(),
+            self.grp.y(),
+            self.grp.width(),
+            self.grp.height(),
+        );
+        self.inp.resize(x, y, w - h, h);
+        self.bar.hide();
+        self.grp.redraw();
+    }
+}
+
+impl Deref for PasswordInput {
+    type Target = crate::group::Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for PasswordInput {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+#[cfg(test)]
+mod input {
+    use super::*;
+    #[test]
+    fn validators() {
+        let mut port = int_range_validator(10, 500);
+        assert!(port("") && port("5") && port("50") && port("500"));
+        assert!(!port("501") && !port("-") && !port("5a") && !port("0"));
+        let mut temp = int_range_validator(-40, 60);
+        assert!(temp("-") && temp("-4") && temp("-40") && temp("6"));
+        assert!(!temp("-41") && !temp("70"));
+        let mut ip = ipv4_validator();
+        assert!(ip("") && ip("192.") && ip("192.168.0.1"));
+        assert!(!ip("256.0.0.1") && !ip("1.2.3.4.5") && !ip("1.2.3.4444"));
+        let mut date = date_validator();
+        assert!(date("") && date("2024-0") && date("2024-02-29") && date("2024-1"));
+        assert!(!date("2023-02-29") && !date("2024-13") && !date("2024-2") && !date("2024/"));
+        assert!(!date("2024-01-4") && !date("2024-01-311"));
+    }
+
+    #[test]
+    fn strength() {
+        assert_eq!(password_strength(""), 0.);
+        assert!(password_strength("password") < password_strength("Passw0rd!"));
+        assert_eq!(password_strength("Correct-Horse-Battery-9"), 1.);
+    }
+}
@@ -208,6 +208,10 @@
 #![allow(non_upper_case_globals)]
 #![warn(missing_docs)]
 
+/// Actions shared by menus, buttons and shortcuts
+pub mod action;
+/// Animations of widget properties with easing curves
+pub mod animation;
 /// Application related methods and functions
 pub mod app;
 /// Browser widgets
@@ -216,12 +220,18 @@ pub mod browser;
 pub mod button;
 /// Dialog widgets
 pub mod dialog;
+/// Drag and drop of app-defined values between widgets
+pub mod dnd;
 /// Drawing primitives
 pub mod draw;
 /// Fltk defined enums: Color, Font, CallbackTrigger etc
 pub mod enums;
+/// Settings forms generated from structs
+pub mod form;
 /// Basic fltk box/frame widget
 pub mod frame;
+/// Loading of FLUID user interface files
+pub mod fluid;
 /// Group widgets
 pub mod group;
 /// Image types supported by fltk
@@ -232,10 +242,16 @@ pub mod input;
 pub mod menu;
 /// Miscellaneous widgets not fitting a certain group
 pub mod misc;
+/// Observable values which widgets can be bound to
+pub mod observe;
 /// Output widgets
 pub mod output;
 /// All fltk widget traits and flt error types
 pub mod prelude;
+/// Persistent application preferences
+pub mod prefs;
+/// Event recording and playback
+pub mod recorder;
 /// Widget surface to image functions
 pub mod surface;
 /// Table widgets
@@ -244,6 +260,8 @@ pub mod table;
 pub mod text;
 /// Tree widgets
 pub mod tree;
+/// User interfaces built from descriptions
+pub mod ui;
 /// General utility functions
 pub mod utils;
 /// Valuator widgets
@@ -257,6 +275,10 @@ pub mod window;
 #[cfg(not(target_os = "android"))]
 pub mod printer;
 
+/// System tray icon
+#[cfg(feature = "tray")]
+pub mod tray;
+
 pub use enums::*;
 pub use prelude::*;
 
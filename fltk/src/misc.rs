@@ -1,12 +1,23 @@
+use crate::app;
+use crate::button::Button;
+use crate::draw::{self, LineStyle, Offscreen};
+use crate::frame::Frame;
+use crate::group::Group;
 use crate::image::Image;
+use crate::input::IntInput;
 pub use crate::prelude::*;
 use crate::widget::Widget;
 use crate::window::Window;
 use fltk_sys::misc::*;
 use std::{
+    cell::RefCell,
+    collections::VecDeque,
     ffi::{CStr, CString},
-    mem,
+    fmt, mem,
+    ops::{Deref, DerefMut},
     os::raw,
+    rc::Rc,
+    time::Duration,
 };
 
 /// Defines the chart types supported by fltk
@@ -160,18 +171,111 @@ pub struct Chart {
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
 
+#[derive(Default)]
+struct ChartValues {
+    values: VecDeque<f64>,
+    autoscale: bool,
+}
+
+thread_local! {
+    /// The values of the charts' entries, kept for autoscaling
+    static CHART_VALUES: RefCell<crate::utils::WidgetMap<ChartValues>> = RefCell::new(Default::default());
+}
+
+/// Returns bounds enclosing the values with a 5% margin, extended to zero if needed
+fn autoscale_bounds<'a, I: Iterator<Item = &'a f64>>(
+    values: I,
+    include_zero: bool,
+) -> Option<(f64, f64)> {
+    let (mut min, mut max) =
+        values
+            .filter(|v| v.is_finite())
+            .fold(None, |acc: Option<(f64, f64)>, v| match acc {
+                Some((min, max)) => Some((min.min(*v), max.max(*v))),
+                None => Some((*v, *v)),
+            })?;
+    if include_zero {
+        min = min.min(0.0);
+        max = max.max(0.0);
+    }
+    let margin = if max > min {
+        (max - min) * 0.05
+    } else if min != 0.0 {
+        min.abs() * 0.05
+    } else {
+        1.0
+    };
+    if !include_zero || min < 0.0 {
+        min -= margin;
+    }
+    if !include_zero || max > 0.0 {
+        max += margin;
+    }
+    Some((min, max))
+}
+
 impl Chart {
+    /// Runs f on the chart's values, creating them if needed
+    fn with_values<T, F: FnOnce(&mut ChartValues) -> T>(&self, f: F) -> T {
+        CHART_VALUES.with(|m| {
+            let mut m = m.borrow_mut();
+            if m.get(self).is_none() {
+                m.insert(self, ChartValues::default());
+            }
+            f(m.get(self).unwrap())
+        })
+    }
+
+    /// Runs f on the chart's values, if any
+    fn read_values<T, F: FnOnce(&ChartValues) -> T>(&self, f: F) -> Option<T> {
+        CHART_VALUES.with(|m| m.borrow_mut().get(self).map(|c| f(c)))
+    }
+
+    fn update_values<F: FnOnce(&mut VecDeque<f64>, usize)>(&mut self, f: F) {
+        let max = self.maximum_size() as usize;
+        let autoscale = self.with_values(|c| {
+            f(&mut c.values, max);
+            c.autoscale
+        });
+        if autoscale {
+            self.rescale();
+        }
+    }
+
+    fn rescale(&mut self) {
+        let include_zero = !matches!(
+            self.get_type::<ChartType>(),
+            ChartType::Line | ChartType::Fill | ChartType::Spike
+        );
+        let bounds = self
+            .read_values(|c| autoscale_bounds(c.values.iter(), include_zero))
+            .flatten();
+        if let Some((min, max)) = bounds {
+            unsafe { Fl_Chart_set_bounds(self._inner, min, max) }
+        }
+        self.redraw();
+    }
+
     /// Clears the chart
     pub fn clear(&mut self) {
         assert!(!self.was_deleted());
         unsafe { Fl_Chart_clear(self._inner) }
+        self.update_values(|v, _| v.clear());
     }
 
-    /// Adds an entry
+    /// Adds an entry.
+    /// When the chart already holds maximum_size() entries, the oldest one is dropped,
+    /// so a chart with a maximum size acts as a ring of the latest values
     pub fn add(&mut self, val: f64, txt: &str, col: Color) {
         assert!(!self.was_deleted());
         let txt = CString::safe_new(txt);
         unsafe { Fl_Chart_add(self._inner, val, txt.as_ptr(), col.bits() as u32) }
+        self.update_values(|v, max| {
+            if max > 0 && v.len() >= max {
+                v.pop_front();
+            }
+            v.push_back(val);
+        });
     }
 
     /// Inserts an entry at an index
@@ -191,6 +295,15 @@ impl Chart {
                 col.bits() as u32,
             )
         }
+        self.update_values(|v, max| {
+            let idx = idx as usize;
+            if idx >= 1 && idx <= v.len() + 1 {
+                v.insert(idx - 1, val);
+                if max > 0 {
+                    v.truncate(max);
+                }
+            }
+        });
     }
 
     /// Replaces an entry at an index
@@ -210,14 +323,64 @@ impl Chart {
                 col.bits() as u32,
             )
         }
+        self.update_values(|v, _| {
+            if let Some(old) = (idx as usize).checked_sub(1).and_then(|i| v.get_mut(i)) {
+                *old = val;
+            }
+        });
     }
 
-    /// Sets the bounds of the chart
+    /// Sets the bounds of the chart, this disables autoscaling
     pub fn set_bounds(&mut self, a: f64, b: f64) {
         assert!(!self.was_deleted());
+        CHART_VALUES.with(|m| {
+            if let Some(c) = m.borrow_mut().get(self) {
+                c.autoscale = false;
+            }
+        });
         unsafe { Fl_Chart_set_bounds(self._inner, a, b) }
     }
 
+    /// Sets whether the bounds follow the entries' values, with a small margin.
+    /// Bar and pie charts also keep zero within the bounds
+    pub fn set_autoscale(&mut self, flag: bool) {
+        assert!(!self.was_deleted());
+        self.with_values(|c| c.autoscale = flag);
+        if flag {
+            self.rescale();
+        }
+    }
+
+    /// Returns whether the bounds follow the entries' values
+    pub fn autoscale(&self) -> bool {
+        assert!(!self.was_deleted());
+        self.read_values(|c| c.autoscale).unwrap_or(false)
+    }
+
+    /// Returns the values of the entries
+    pub fn values(&self) -> Vec<f64> {
+        assert!(!self.was_deleted());
+        self.read_values(|c| c.values.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Switches the chart type, keeping the entries
+    pub fn set_chart_type(&mut self, typ: ChartType) {
+        assert!(!self.was_deleted());
+        self.set_type(typ);
+        if self.autoscale() {
+            self.rescale();
+        } else {
+            self.redraw();
+        }
+    }
+
+    /// Returns the chart type
+    pub fn chart_type(&self) -> ChartType {
+        assert!(!self.was_deleted());
+        self.get_type()
+    }
+
     /// Returns the size of the chart
     pub fn size(&self) -> u32 {
         assert!(!self.was_deleted());
@@ -252,6 +415,11 @@ impl Chart {
         );
         assert!(!self.was_deleted());
         unsafe { Fl_Chart_set_maxsize(self._inner, s as i32) }
+        self.update_values(|v, max| {
+            if max > 0 {
+                v.truncate(max);
+            }
+        });
     }
 
     /// Gets the text font
@@ -849,3 +1017,1874 @@ impl HelpView {
         }
     }
 }
+
+/// Defines how the points of a plot series are drawn
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlotStyle {
+    /// Points joined by lines
+    Line,
+    /// Points drawn as markers
+    Points,
+    /// Points drawn as markers joined by lines
+    LinePoints,
+}
+
+#[derive(Debug, Clone)]
+struct PlotSeries {
+    name: String,
+    color: Color,
+    style: PlotStyle,
+    points: Vec<(f64, f64)>,
+}
+
+type PlotDrag = (i32, i32, (f64, f64), (f64, f64));
+
+#[derive(Debug)]
+struct PlotState {
+    series: Vec<PlotSeries>,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    auto_fit: bool,
+    grid: bool,
+    x_label: String,
+    y_label: String,
+    cache: Option<Offscreen>,
+    cache_size: (i32, i32),
+    dirty: bool,
+    drag: Option<PlotDrag>,
+    hover: Option<(usize, usize)>,
+}
+
+impl Default for PlotState {
+    fn default() -> PlotState {
+        PlotState {
+            series: vec![],
+            x_range: (0.0, 1.0),
+            y_range: (0.0, 1.0),
+            auto_fit: true,
+            grid: true,
+            x_label: String::new(),
+            y_label: String::new(),
+            cache: None,
+            cache_size: (0, 0),
+            dirty: true,
+            drag: None,
+            hover: None,
+        }
+    }
+}
+
+impl PlotState {
+    fn fit(&mut self) {
+        let xs = self
+            .series
+            .iter()
+            .flat_map(|s| s.points.iter().map(|p| p.0));
+        if let Some(r) = autoscale_bounds(xs.collect::<Vec<_>>().iter(), false) {
+            self.x_range = r;
+        }
+        let ys = self
+            .series
+            .iter()
+            .flat_map(|s| s.points.iter().map(|p| p.1));
+        if let Some(r) = autoscale_bounds(ys.collect::<Vec<_>>().iter(), false) {
+            self.y_range = r;
+        }
+        self.auto_fit = true;
+        self.dirty = true;
+    }
+
+    fn changed(&mut self) {
+        if self.auto_fit {
+            self.fit();
+        }
+        self.hover = None;
+        self.dirty = true;
+    }
+
+    /// Maps data coordinates to pixels relative to the widget
+    fn to_px(&self, w: i32, h: i32, x: f64, y: f64) -> (i32, i32) {
+        let (ax, ay, aw, ah) = plot_area(w, h);
+        let (x0, x1) = self.x_range;
+        let (y0, y1) = self.y_range;
+        let px = ax as f64 + (x - x0) / (x1 - x0) * aw as f64;
+        let py = (ay + ah) as f64 - (y - y0) / (y1 - y0) * ah as f64;
+        (px.clamp(-1e6, 1e6) as i32, py.clamp(-1e6, 1e6) as i32)
+    }
+
+    /// Maps pixels relative to the widget to data coordinates
+    fn to_data(&self, w: i32, h: i32, px: i32, py: i32) -> (f64, f64) {
+        let (ax, ay, aw, ah) = plot_area(w, h);
+        let (x0, x1) = self.x_range;
+        let (y0, y1) = self.y_range;
+        (
+            x0 + (px - ax) as f64 / aw.max(1) as f64 * (x1 - x0),
+            y0 + (ay + ah - py) as f64 / ah.max(1) as f64 * (y1 - y0),
+        )
+    }
+
+    fn nearest(&self, w: i32, h: i32, px: i32, py: i32) -> Option<(usize, usize)> {
+        let mut best = None;
+        let mut best_dist = 8 * 8 + 1;
+        for (i, s) in self.series.iter().enumerate() {
+            for (j, p) in s.points.iter().enumerate() {
+                let (x, y) = self.to_px(w, h, p.0, p.1);
+                let dist = (x - px).pow(2) + (y - py).pow(2);
+                if dist < best_dist {
+                    best_dist = dist;
+                    best = Some((i, j));
+                }
+            }
+        }
+        best
+    }
+}
+
+/// Returns the plotting area relative to the widget, leaving room for the tick labels
+fn plot_area(w: i32, h: i32) -> (i32, i32, i32, i32) {
+    (60, 10, w - 70, h - 50)
+}
+
+/// Returns evenly spaced round values within the range, about count of them
+pub(crate) fn nice_ticks(min: f64, max: f64, count: usize) -> (Vec<f64>, f64) {
+    if !min.is_finite() || !max.is_finite() || max <= min || count == 0 {
+        return (vec![], 0.0);
+    }
+    let raw = (max - min) / count as f64;
+    let mag = 10f64.powf(raw.log10().floor());
+    let step = mag
+        * match raw / mag {
+            f if f < 1.5 => 1.0,
+            f if f < 3.0 => 2.0,
+            f if f < 7.0 => 5.0,
+            _ => 10.0,
+        };
+    let mut ticks = vec![];
+    let mut v = (min / step).ceil() * step;
+    while v <= max + step * 1e-9 && ticks.len() < 1000 {
+        ticks.push(if v.abs() < step * 1e-9 { 0.0 } else { v });
+        v += step;
+    }
+    (ticks, step)
+}
+
+pub(crate) fn tick_label(v: f64, step: f64) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    format!("{:.*}", decimals, v)
+}
+
+/// Defines a 2D plot (custom widget) drawing series of points over axes and an optional grid.
+/// Dragging with the mouse pans the plot, the mouse wheel zooms around the pointer
+/// (only horizontally with Shift, only vertically with Ctrl) and a double-click fits the data again.
+/// Hovering over a point shows its series and coordinates.
+/// The plot is rendered to an offscreen buffer which is only redrawn when the data or view change
+/// ```no_run
+/// use fltk::*;
+/// let mut plot = misc::Plot::new(5, 5, 400, 300, "");
+/// let sine = plot.add_series("sin", Color::Red);
+/// plot.set_data(sine, &(0..100).map(|i| (i as f64 / 10., (i as f64 / 10.).sin())).collect::<Vec<_>>());
+/// plot.set_x_label("t");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Plot {
+    frame: Frame,
+    state: Rc<RefCell<PlotState>>,
+}
+
+impl Plot {
+    /// Creates a new empty plot
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Plot {
+        let mut frame = Frame::new(x, y, w, h, label);
+        frame.set_frame(FrameType::FlatBox);
+        frame.set_align(Align::Top);
+        let state = Rc::new(RefCell::new(PlotState::default()));
+
+        let st = state.clone();
+        frame.draw2(move |f| {
+            let (x, y, w, h) = (f.x(), f.y(), f.width(), f.height());
+            if w <= 0 || h <= 0 {
+                return;
+            }
+            let mut st = st.borrow_mut();
+            if st.cache.is_none() || st.cache_size != (w, h) {
+                st.cache = Offscreen::new(w, h);
+                st.cache_size = (w, h);
+                st.dirty = true;
+            }
+            match &st.cache {
+                Some(cache) => {
+                    if st.dirty {
+                        cache.begin();
+                        Plot::render(&st, 0, 0, w, h);
+                        cache.end();
+                    }
+                    cache.copy(x, y, w, h, 0, 0);
+                }
+                None => Plot::render(&st, x, y, w, h),
+            }
+            st.dirty = false;
+            if let Some((i, j)) = st.hover {
+                Plot::draw_tip(&st, x, y, w, h, i, j);
+            }
+        });
+
+        let st = state.clone();
+        frame.handle2(move |f, ev| {
+            let (w, h) = (f.width(), f.height());
+            let (ex, ey) = (app::event_x() - f.x(), app::event_y() - f.y());
+            match ev {
+                Event::Enter => true,
+                Event::Push if app::event_button() == 1 => {
+                    if app::event_clicks() {
+                        st.borrow_mut().fit();
+                        st.borrow_mut().drag = None;
+                    } else {
+                        let mut st = st.borrow_mut();
+                        let ranges = (st.x_range, st.y_range);
+                        st.drag = Some((ex, ey, ranges.0, ranges.1));
+                    }
+                    f.redraw();
+                    true
+                }
+                Event::Drag => {
+                    let mut st = st.borrow_mut();
+                    if let Some((sx, sy, xr, yr)) = st.drag {
+                        let (_, _, aw, ah) = plot_area(w, h);
+                        let dx = (ex - sx) as f64 / aw.max(1) as f64 * (xr.1 - xr.0);
+                        let dy = (ey - sy) as f64 / ah.max(1) as f64 * (yr.1 - yr.0);
+                        st.x_range = (xr.0 - dx, xr.1 - dx);
+                        st.y_range = (yr.0 + dy, yr.1 + dy);
+                        st.auto_fit = false;
+                        st.hover = None;
+                        st.dirty = true;
+                        f.redraw();
+                    }
+                    true
+                }
+                Event::Released => {
+                    st.borrow_mut().drag = None;
+                    true
+                }
+                Event::MouseWheel => {
+                    let factor = if app::event_dy() > 0 { 1.25 } else { 0.8 };
+                    let state = app::event_state();
+                    let mut st = st.borrow_mut();
+                    let (px, py) = st.to_data(w, h, ex, ey);
+                    if !state.contains(Shortcut::Ctrl) {
+                        let (x0, x1) = st.x_range;
+                        st.x_range = (px + (x0 - px) * factor, px + (x1 - px) * factor);
+                    }
+                    if !state.contains(Shortcut::Shift) {
+                        let (y0, y1) = st.y_range;
+                        st.y_range = (py + (y0 - py) * factor, py + (y1 - py) * factor);
+                    }
+                    st.auto_fit = false;
+                    st.hover = None;
+                    st.dirty = true;
+                    f.redraw();
+                    true
+                }
+                Event::Move => {
+                    let hover = st.borrow().nearest(w, h, ex, ey);
+                    if hover != st.borrow().hover {
+                        st.borrow_mut().hover = hover;
+                        f.redraw();
+                    }
+                    true
+                }
+                Event::Leave => {
+                    if st.borrow_mut().hover.take().is_some() {
+                        f.redraw();
+                    }
+                    true
+                }
+                _ => false,
+            }
+        });
+
+        Plot { frame, state }
+    }
+
+    fn render(st: &PlotState, ox: i32, oy: i32, w: i32, h: i32) {
+        draw::draw_rect_fill(ox, oy, w, h, Color::BackGround2);
+        let (ax, ay, aw, ah) = plot_area(w, h);
+        if aw <= 0 || ah <= 0 {
+            return;
+        }
+        let (ax, ay) = (ox + ax, oy + ay);
+        draw::set_font(Font::Helvetica, 12);
+        let (xticks, xstep) = nice_ticks(st.x_range.0, st.x_range.1, (aw / 80).max(2) as usize);
+        for v in xticks {
+            let px = ox + st.to_px(w, h, v, 0.0).0;
+            if st.grid {
+                draw::set_draw_color(Color::Light2);
+                draw::draw_yxline(px, ay, ay + ah);
+            }
+            draw::set_draw_color(Color::ForeGround);
+            draw::draw_yxline(px, ay + ah, ay + ah + 4);
+            draw::draw_text2(
+                &tick_label(v, xstep),
+                px - 40,
+                ay + ah + 5,
+                80,
+                14,
+                Align::Center,
+            );
+        }
+        let (yticks, ystep) = nice_ticks(st.y_range.0, st.y_range.1, (ah / 40).max(2) as usize);
+        for v in yticks {
+            let py = oy + st.to_px(w, h, 0.0, v).1;
+            if st.grid {
+                draw::set_draw_color(Color::Light2);
+                draw::draw_xyline(ax, py, ax + aw);
+            }
+            draw::set_draw_color(Color::ForeGround);
+            draw::draw_xyline(ax - 4, py, ax);
+            draw::draw_text2(
+                &tick_label(v, ystep),
+                ox,
+                py - 7,
+                ax - ox - 6,
+                14,
+                Align::Right,
+            );
+        }
+        draw::set_draw_color(Color::ForeGround);
+        draw::draw_rect(ax, ay, aw, ah);
+        if !st.x_label.is_empty() {
+            draw::draw_text2(&st.x_label, ax, oy + h - 18, aw, 16, Align::Center);
+        }
+        if !st.y_label.is_empty() {
+            let tw = draw::width(&st.y_label) as i32;
+            draw::draw_text_angled(90, &st.y_label, ox + 12, ay + ah / 2 + tw / 2);
+        }
+
+        draw::push_clip(ax, ay, aw, ah);
+        for s in &st.series {
+            draw::set_draw_color(s.color);
+            draw::set_line_style(LineStyle::Solid, 2);
+            let pts: Vec<(i32, i32)> = s
+                .points
+                .iter()
+                .filter(|p| p.0.is_finite() && p.1.is_finite())
+                .map(|p| {
+                    let (x, y) = st.to_px(w, h, p.0, p.1);
+                    (ox + x, oy + y)
+                })
+                .collect();
+            if s.style != PlotStyle::Points {
+                for seg in pts.windows(2) {
+                    draw::draw_line(seg[0].0, seg[0].1, seg[1].0, seg[1].1);
+                }
+            }
+            draw::set_line_style(LineStyle::Solid, 0);
+            if s.style != PlotStyle::Line {
+                for (x, y) in &pts {
+                    draw::draw_rectf(x - 2, y - 2, 5, 5);
+                }
+            }
+        }
+        let mut ly = ay + 6;
+        for s in st.series.iter().filter(|s| !s.name.is_empty()) {
+            let tw = draw::width(&s.name) as i32;
+            let lx = ax + aw - tw - 36;
+            draw::set_draw_color(s.color);
+            draw::draw_rectf(lx, ly + 6, 20, 3);
+            draw::set_draw_color(Color::ForeGround);
+            draw::draw_text2(&s.name, lx + 26, ly, tw + 4, 14, Align::Left);
+            ly += 16;
+        }
+        draw::pop_clip();
+    }
+
+    fn draw_tip(st: &PlotState, x: i32, y: i32, w: i32, h: i32, series: usize, idx: usize) {
+        let s = match st.series.get(series) {
+            Some(s) => s,
+            None => return,
+        };
+        let p = match s.points.get(idx) {
+            Some(p) => *p,
+            None => return,
+        };
+        let (px, py) = st.to_px(w, h, p.0, p.1);
+        let (px, py) = (x + px, y + py);
+        let txt = if s.name.is_empty() {
+            format!("{}, {}", p.0, p.1)
+        } else {
+            format!("{}: {}, {}", s.name, p.0, p.1)
+        };
+        draw::set_font(Tooltip::font(), Tooltip::font_size());
+        let tw = draw::width(&txt) as i32 + 8;
+        let th = draw::height() + 4;
+        let tx = if px + 8 + tw > x + w {
+            px - 8 - tw
+        } else {
+            px + 8
+        };
+        let ty = if py - 8 - th < y { py + 8 } else { py - 8 - th };
+        draw::set_draw_color(s.color);
+        draw::draw_rect(px - 4, py - 4, 9, 9);
+        draw::draw_box(FrameType::BorderBox, tx, ty, tw, th, Tooltip::color());
+        draw::set_draw_color(Tooltip::text_color());
+        draw::draw_text2(&txt, tx, ty, tw, th, Align::Center);
+    }
+
+    fn update<T, F: FnOnce(&mut PlotState) -> T>(&mut self, f: F) -> T {
+        let ret = f(&mut self.state.borrow_mut());
+        self.frame.redraw();
+        ret
+    }
+
+    /// Adds an empty series drawn with lines, returning its index
+    pub fn add_series(&mut self, name: &str, color: Color) -> usize {
+        self.update(|st| {
+            st.series.push(PlotSeries {
+                name: name.to_string(),
+                color,
+                style: PlotStyle::Line,
+                points: vec![],
+            });
+            st.dirty = true;
+            st.series.len() - 1
+        })
+    }
+
+    /// Removes a series, shifting the index of the later ones
+    pub fn remove_series(&mut self, series: usize) {
+        self.update(|st| {
+            if series < st.series.len() {
+                st.series.remove(series);
+                st.changed();
+            }
+        })
+    }
+
+    /// Removes all series
+    pub fn clear(&mut self) {
+        self.update(|st| {
+            st.series.clear();
+            st.changed();
+        })
+    }
+
+    /// Returns the number of series
+    pub fn series_count(&self) -> usize {
+        self.state.borrow().series.len()
+    }
+
+    /// Sets how the points of a series are drawn
+    pub fn set_series_style(&mut self, series: usize, style: PlotStyle) {
+        self.update(|st| {
+            if let Some(s) = st.series.get_mut(series) {
+                s.style = style;
+                st.dirty = true;
+            }
+        })
+    }
+
+    /// Replaces the points of a series
+    pub fn set_data(&mut self, series: usize, points: &[(f64, f64)]) {
+        self.update(|st| {
+            if let Some(s) = st.series.get_mut(series) {
+                s.points = points.to_vec();
+                st.changed();
+            }
+        })
+    }
+
+    /// Returns the points of a series
+    pub fn data(&self, series: usize) -> Vec<(f64, f64)> {
+        self.state
+            .borrow()
+            .series
+            .get(series)
+            .map(|s| s.points.clone())
+            .unwrap_or_default()
+    }
+
+    /// Appends a point to a series
+    pub fn add_point(&mut self, series: usize, x: f64, y: f64) {
+        self.update(|st| {
+            if let Some(s) = st.series.get_mut(series) {
+                s.points.push((x, y));
+                st.changed();
+            }
+        })
+    }
+
+    /// Sets the visible range of the x axis, this stops fitting the view to the data
+    pub fn set_x_range(&mut self, min: f64, max: f64) {
+        assert!(max > min);
+        self.update(|st| {
+            st.x_range = (min, max);
+            st.auto_fit = false;
+            st.dirty = true;
+        })
+    }
+
+    /// Returns the visible range of the x axis
+    pub fn x_range(&self) -> (f64, f64) {
+        self.state.borrow().x_range
+    }
+
+    /// Sets the visible range of the y axis, this stops fitting the view to the data
+    pub fn set_y_range(&mut self, min: f64, max: f64) {
+        assert!(max > min);
+        self.update(|st| {
+            st.y_range = (min, max);
+            st.auto_fit = false;
+            st.dirty = true;
+        })
+    }
+
+    /// Returns the visible range of the y axis
+    pub fn y_range(&self) -> (f64, f64) {
+        self.state.borrow().y_range
+    }
+
+    /// Fits the view to the data, and keeps fitting it as the data changes until the view is zoomed or panned
+    pub fn fit(&mut self) {
+        self.update(|st| st.fit())
+    }
+
+    /// Returns whether grid lines are drawn at the ticks
+    pub fn grid(&self) -> bool {
+        self.state.borrow().grid
+    }
+
+    /// Sets whether grid lines are drawn at the ticks
+    pub fn set_grid(&mut self, flag: bool) {
+        self.update(|st| {
+            st.grid = flag;
+            st.dirty = true;
+        })
+    }
+
+    /// Sets the label of the x axis
+    pub fn set_x_label(&mut self, label: &str) {
+        self.update(|st| {
+            st.x_label = label.to_string();
+            st.dirty = true;
+        })
+    }
+
+    /// Sets the label of the y axis
+    pub fn set_y_label(&mut self, label: &str) {
+        self.update(|st| {
+            st.y_label = label.to_string();
+            st.dirty = true;
+        })
+    }
+}
+
+impl Deref for Plot {
+    type Target = Frame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl DerefMut for Plot {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frame
+    }
+}
+
+/// Defines a calendar date, as used by the calendar widget
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year
+    pub year: i32,
+    /// The month, from 1 to 12
+    pub month: u32,
+    /// The day of the month, from 1
+    pub day: u32,
+}
+
+/// Returns the number of days in a month
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    /// Creates a date, returns None if it doesn't exist
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        if (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day) {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current date in UTC
+    pub fn today() -> Date {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Date::from_days(secs.div_euclid(86400))
+    }
+
+    /// Returns the number of days since 1970-01-01
+    pub fn to_days(self) -> i64 {
+        let y = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = self.month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Returns the date a number of days after 1970-01-01
+    pub fn from_days(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        Date { year, month, day }
+    }
+
+    /// Returns the day of the week, from 0 for Sunday to 6 for Saturday
+    pub fn weekday(self) -> u32 {
+        (self.to_days() + 4).rem_euclid(7) as u32
+    }
+
+    /// Returns the date a number of days later, or earlier if negative
+    pub fn add_days(self, days: i64) -> Date {
+        Date::from_days(self.to_days() + days)
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const DAY_NAMES: [&str; 7] = ["Su", "Mo", "Tu", "We", "Th", "Fr", "Sa"];
+
+type DateCallback = Box<dyn FnMut(Date)>;
+
+struct CalendarState {
+    year: i32,
+    month: u32,
+    selected: Option<Date>,
+    first_weekday: u32,
+    on_select: Option<DateCallback>,
+}
+
+impl fmt::Debug for CalendarState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CalendarState")
+            .field("year", &self.year)
+            .field("month", &self.month)
+            .field("selected", &self.selected)
+            .field("first_weekday", &self.first_weekday)
+            .finish()
+    }
+}
+
+impl CalendarState {
+    /// Returns the grid index of the first day of the shown month
+    fn offset(&self) -> u32 {
+        let first = Date {
+            year: self.year,
+            month: self.month,
+            day: 1,
+        };
+        (first.weekday() + 7 - self.first_weekday) % 7
+    }
+
+    fn step_month(&mut self, delta: i32) {
+        let m = self.year * 12 + self.month as i32 - 1 + delta;
+        self.year = m.div_euclid(12);
+        self.month = m.rem_euclid(12) as u32 + 1;
+    }
+}
+
+/// Defines a calendar (custom widget) showing a month as a grid of days, with buttons to change the month.
+/// A day is selected by clicking it or with the arrow keys, while Page Up and Page Down change the month
+/// ```no_run
+/// use fltk::*;
+/// let mut cal = misc::Calendar::new(5, 5, 250, 200, "");
+/// cal.set_value(misc::Date::new(2021, 3, 14));
+/// cal.set_selection_callback(|date| println!("{}", date));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    grp: Group,
+    title: Frame,
+    grid: Frame,
+    state: Rc<RefCell<CalendarState>>,
+}
+
+impl Calendar {
+    /// Creates a new calendar showing the current month, with weeks starting on Sunday
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Calendar {
+        let today = Date::today();
+        let state = Rc::new(RefCell::new(CalendarState {
+            year: today.year,
+            month: today.month,
+            selected: None,
+            first_weekday: 0,
+            on_select: None,
+        }));
+        let mut grp = Group::new(x, y, w, h, label);
+        let mut prev = Button::new(x, y, 24, 24, "@<");
+        let title = Frame::new(x + 24, y, w - 48, 24, "");
+        let mut next = Button::new(x + w - 24, y, 24, 24, "@>");
+        let mut grid = Frame::new(x, y + 24, w, h - 24, "");
+        grp.end();
+        grp.resizable(&grid);
+        prev.clear_visible_focus();
+        next.clear_visible_focus();
+        grid.set_frame(FrameType::DownBox);
+        grid.set_color(Color::BackGround2);
+
+        let mut cal = Calendar {
+            grp,
+            title,
+            grid,
+            state,
+        };
+
+        let mut c = cal.clone();
+        prev.set_callback(move || c.change_month(-1));
+        let mut c = cal.clone();
+        next.set_callback(move || c.change_month(1));
+
+        let st = cal.state.clone();
+        cal.grid
+            .draw2(move |g| Calendar::draw_grid(g, &st.borrow()));
+
+        let mut c = cal.clone();
+        cal.grid.handle2(move |g, ev| match ev {
+            Event::Focus | Event::Unfocus => {
+                g.redraw();
+                true
+            }
+            Event::Push => {
+                let _ = g.take_focus();
+                let day = {
+                    let (cw, ch) = ((g.width() / 7).max(1), (g.height() / 7).max(1));
+                    let col = (app::event_x() - g.x()) / cw;
+                    let row = (app::event_y() - g.y()) / ch - 1;
+                    if !(0..7).contains(&col) || !(0..6).contains(&row) {
+                        return true;
+                    }
+                    let st = c.state.borrow();
+                    let idx = (row * 7 + col) as i64 - st.offset() as i64;
+                    Date::new(st.year, st.month, (idx + 1).max(0) as u32)
+                };
+                if let Some(day) = day {
+                    c.select(day);
+                }
+                true
+            }
+            Event::KeyDown => {
+                let key = app::event_key();
+                let current = c.state.borrow().selected;
+                let current = current.unwrap_or_else(|| {
+                    let st = c.state.borrow();
+                    Date::new(st.year, st.month, 1).unwrap()
+                });
+                let delta = if key == Key::Left {
+                    -1
+                } else if key == Key::Right {
+                    1
+                } else if key == Key::Up {
+                    -7
+                } else if key == Key::Down {
+                    7
+                } else if key == Key::PageUp || key == Key::PageDown {
+                    c.change_month(if key == Key::PageUp { -1 } else { 1 });
+                    return true;
+                } else {
+                    return false;
+                };
+                c.select(current.add_days(delta));
+                true
+            }
+            _ => false,
+        });
+
+        cal.update_title();
+        cal
+    }
+
+    fn draw_grid(g: &Frame, st: &CalendarState) {
+        let (cw, ch) = (g.width() / 7, g.height() / 7);
+        if cw <= 0 || ch <= 0 {
+            return;
+        }
+        let (x, y) = (g.x(), g.y());
+        draw::set_font(Font::Helvetica, (ch * 2 / 5).max(8).min(16) as u32);
+        for col in 0..7 {
+            let name = DAY_NAMES[((col as u32 + st.first_weekday) % 7) as usize];
+            draw::set_draw_color(Color::Dark3);
+            draw::draw_text2(name, x + col * cw, y, cw, ch, Align::Center);
+        }
+        draw::set_draw_color(Color::Light2);
+        draw::draw_xyline(x + 2, y + ch - 1, x + 7 * cw - 2);
+        let today = Date::today();
+        let offset = st.offset() as i32;
+        for day in 1..=days_in_month(st.year, st.month) {
+            let idx = offset + day as i32 - 1;
+            let (cx, cy) = (x + idx % 7 * cw, y + (idx / 7 + 1) * ch);
+            let date = Date {
+                year: st.year,
+                month: st.month,
+                day,
+            };
+            let selected = st.selected == Some(date);
+            if selected {
+                draw::draw_rect_fill(cx + 1, cy + 1, cw - 2, ch - 2, g.selection_color());
+                if app::focus().map(|f| unsafe { f.as_widget_ptr() == g.as_widget_ptr() })
+                    == Some(true)
+                {
+                    draw::draw_focus_rect(cx + 1, cy + 1, cw - 2, ch - 2);
+                }
+            }
+            if date == today {
+                draw::set_draw_color(Color::Red);
+                draw::draw_rect(cx + 1, cy + 1, cw - 2, ch - 2);
+            }
+            draw::set_draw_color(if selected {
+                Color::BackGround2
+            } else {
+                Color::ForeGround
+            });
+            draw::draw_text2(&day.to_string(), cx, cy, cw, ch, Align::Center);
+        }
+    }
+
+    fn update_title(&mut self) {
+        let (year, month) = {
+            let st = self.state.borrow();
+            (st.year, st.month)
+        };
+        self.title
+            .set_label(&format!("{} {}", MONTH_NAMES[month as usize - 1], year));
+        self.grid.redraw();
+    }
+
+    fn change_month(&mut self, delta: i32) {
+        self.state.borrow_mut().step_month(delta);
+        self.update_title();
+    }
+
+    fn select(&mut self, date: Date) {
+        self.set_value(Some(date));
+        let cb = self.state.borrow_mut().on_select.take();
+        if let Some(mut cb) = cb {
+            cb(date);
+            self.state.borrow_mut().on_select = Some(cb);
+        }
+    }
+
+    /// Returns the selected date
+    pub fn value(&self) -> Option<Date> {
+        self.state.borrow().selected
+    }
+
+    /// Selects a date, also showing its month, without calling the selection callback
+    pub fn set_value(&mut self, date: Option<Date>) {
+        {
+            let mut st = self.state.borrow_mut();
+            st.selected = date;
+            if let Some(date) = date {
+                st.year = date.year;
+                st.month = date.month;
+            }
+        }
+        self.update_title();
+    }
+
+    /// Returns the shown year and month
+    pub fn shown_month(&self) -> (i32, u32) {
+        let st = self.state.borrow();
+        (st.year, st.month)
+    }
+
+    /// Shows a month, from 1 to 12
+    pub fn show_month(&mut self, year: i32, month: u32) {
+        assert!((1..=12).contains(&month));
+        {
+            let mut st = self.state.borrow_mut();
+            st.year = year;
+            st.month = month;
+        }
+        self.update_title();
+    }
+
+    /// Sets the day weeks start on, from 0 for Sunday to 6 for Saturday
+    pub fn set_first_weekday(&mut self, day: u32) {
+        assert!(day < 7);
+        self.state.borrow_mut().first_weekday = day;
+        self.grid.redraw();
+    }
+
+    /// Sets the callback called with the date selected by a click or the keyboard
+    pub fn set_selection_callback<F: FnMut(Date) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_select = Some(Box::new(cb));
+    }
+}
+
+impl Deref for Calendar {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for Calendar {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+type DurationCallback = Box<dyn FnMut(Duration)>;
+
+struct TimeInputState {
+    max_hours: u32,
+    last: Duration,
+    on_change: Option<DurationCallback>,
+}
+
+impl fmt::Debug for TimeInputState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimeInputState")
+            .field("max_hours", &self.max_hours)
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+/// Defines a time input (custom widget) with hours, minutes and seconds fields and step arrows.
+/// The arrows, and the Up and Down keys, step the focused field (the seconds by default), carrying over to the others
+/// ```no_run
+/// use fltk::*;
+/// let mut time = misc::TimeInput::new(80, 5, 120, 25, "Timeout");
+/// time.set_value(std::time::Duration::from_secs(90));
+/// time.set_change_callback(|d| println!("{:?}", d));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeInput {
+    grp: Group,
+    fields: [IntInput; 3],
+    state: Rc<RefCell<TimeInputState>>,
+}
+
+impl TimeInput {
+    /// Creates a new time input, set to zero and accepting up to 23:59:59
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> TimeInput {
+        let mut grp = Group::new(x, y, w, h, label);
+        grp.set_align(Align::Left);
+        let fw = (w - 16 - 16) / 3;
+        let mut fields = [
+            IntInput::new(x, y, fw, h, ""),
+            IntInput::new(x + fw + 8, y, fw, h, ""),
+            IntInput::new(x + 2 * (fw + 8), y, fw, h, ""),
+        ];
+        Frame::new(x + fw, y, 8, h, ":");
+        Frame::new(x + 2 * fw + 8, y, 8, h, ":");
+        let ax = x + 3 * fw + 16;
+        let mut up = Button::new(ax, y, w - (ax - x), h / 2, "@-28>");
+        let mut down = Button::new(ax, y + h / 2, w - (ax - x), h - h / 2, "@-22>");
+        grp.end();
+        for b in [&mut up, &mut down].iter_mut() {
+            b.clear_visible_focus();
+            b.set_label_size(8);
+        }
+        let state = Rc::new(RefCell::new(TimeInputState {
+            max_hours: 23,
+            last: Duration::from_secs(0),
+            on_change: None,
+        }));
+        for f in fields.iter_mut() {
+            f.set_value("00");
+            f.set_maximum_size(2);
+            f.set_trigger(CallbackTrigger::Changed | CallbackTrigger::Release);
+        }
+
+        let mut t = TimeInput { grp, fields, state };
+        t.set_max_hours(23);
+        for i in 0..3 {
+            let mut t2 = t.clone();
+            t.fields[i].set_callback2(move |f| {
+                // restores the field's last valid part if out of range
+                let limit = if i == 0 { t2.max_hours() as u64 } else { 59 };
+                let v = f.value();
+                if !v.is_empty() && v.parse::<u64>().map_or(true, |n| n > limit) {
+                    let last = t2.state.borrow().last.as_secs();
+                    let part = [last / 3600, last / 60 % 60, last % 60][i];
+                    f.set_value(&format!("{:02}", part));
+                }
+                // pads the field once it lost the focus
+                let focused =
+                    app::focus().map(|w| unsafe { w.as_widget_ptr() == f.as_widget_ptr() });
+                if focused != Some(true) {
+                    let v = f.value().parse::<u32>().unwrap_or(0);
+                    f.set_value(&format!("{:02}", v));
+                }
+                t2.notify();
+            });
+        }
+        let mut t2 = t.clone();
+        up.set_callback(move || t2.step(1));
+        let mut t2 = t.clone();
+        down.set_callback(move || t2.step(-1));
+        let mut t2 = t.clone();
+        t.grp.handle2(move |_, ev| {
+            if ev != Event::Shortcut && ev != Event::KeyDown {
+                return false;
+            }
+            match app::event_key() {
+                k if k == Key::Up && t2.focused_field().is_some() => t2.step(1),
+                k if k == Key::Down && t2.focused_field().is_some() => t2.step(-1),
+                _ => return false,
+            }
+            true
+        });
+        t
+    }
+
+    fn focused_field(&self) -> Option<usize> {
+        let focus = unsafe { app::focus()?.as_widget_ptr() };
+        self.fields
+            .iter()
+            .position(|f| unsafe { f.as_widget_ptr() } == focus)
+    }
+
+    fn step(&mut self, dir: i64) {
+        let unit = match self.focused_field() {
+            Some(0) => 3600,
+            Some(1) => 60,
+            _ => 1,
+        };
+        let secs = self.value().as_secs() as i64 + dir * unit;
+        self.set_value(Duration::from_secs(secs.max(0) as u64));
+        self.notify();
+    }
+
+    fn notify(&mut self) {
+        let val = self.value();
+        let cb = {
+            let mut st = self.state.borrow_mut();
+            if st.last == val {
+                return;
+            }
+            st.last = val;
+            st.on_change.take()
+        };
+        if let Some(mut cb) = cb {
+            cb(val);
+            self.state.borrow_mut().on_change = Some(cb);
+        }
+    }
+
+    /// Returns the entered duration, empty fields counting as zero
+    pub fn value(&self) -> Duration {
+        let part = |i: usize| self.fields[i].value().parse::<u64>().unwrap_or(0);
+        Duration::from_secs(part(0) * 3600 + part(1) * 60 + part(2))
+    }
+
+    /// Sets the duration, clamped to the maximum number of hours and truncated to whole seconds
+    pub fn set_value(&mut self, val: Duration) {
+        let max = self.state.borrow().max_hours as u64 * 3600 + 3599;
+        let secs = val.as_secs().min(max);
+        self.fields[0].set_value(&format!("{:02}", secs / 3600));
+        self.fields[1].set_value(&format!("{:02}", secs / 60 % 60));
+        self.fields[2].set_value(&format!("{:02}", secs % 60));
+        self.state.borrow_mut().last = Duration::from_secs(secs);
+    }
+
+    /// Returns the maximum number of hours
+    pub fn max_hours(&self) -> u32 {
+        self.state.borrow().max_hours
+    }
+
+    /// Sets the maximum number of hours, which also sets the width of the hours field
+    pub fn set_max_hours(&mut self, max: u32) {
+        self.state.borrow_mut().max_hours = max;
+        let digits = max.to_string().len().max(2);
+        self.fields[0].set_maximum_size(digits as u32);
+        let val = self.value();
+        self.set_value(val);
+    }
+
+    /// Sets the callback called with the new duration whenever it changes
+    pub fn set_change_callback<F: FnMut(Duration) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_change = Some(Box::new(cb));
+    }
+}
+
+impl Deref for TimeInput {
+    type Target = Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl DerefMut for TimeInput {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+struct RichStyle {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    color: Option<Color>,
+    size: Option<u32>,
+}
+
+fn markup_color(val: &str) -> Option<Color> {
+    if let Some(hex) = val.strip_prefix('#') {
+        return u32::from_str_radix(hex, 16).ok().map(Color::from_u32);
+    }
+    Some(match val.to_lowercase().as_str() {
+        "black" => Color::Black,
+        "white" => Color::White,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "blue" => Color::Blue,
+        "yellow" => Color::Yellow,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Dark2,
+        _ => return None,
+    })
+}
+
+/// Splits markup into runs of text sharing a style, leaving unknown tags as text
+fn parse_markup(markup: &str) -> Vec<(String, RichStyle)> {
+    let mut spans: Vec<(String, RichStyle)> = vec![];
+    let mut stack: Vec<(&str, RichStyle)> = vec![];
+    let mut style = RichStyle::default();
+    let mut push = |txt: &str, style: RichStyle| match spans.last_mut() {
+        Some((last, s)) if *s == style => last.push_str(txt),
+        _ if txt.is_empty() => (),
+        _ => spans.push((txt.to_string(), style)),
+    };
+    let mut rest = markup;
+    while !rest.is_empty() {
+        let next = rest
+            .find(|c: char| c == '<' || c == '&')
+            .unwrap_or(rest.len());
+        push(&rest[..next], style);
+        rest = &rest[next..];
+        if rest.starts_with('&') {
+            let (txt, len) = match rest {
+                r if r.starts_with("&lt;") => ("<", 4),
+                r if r.starts_with("&gt;") => (">", 4),
+                r if r.starts_with("&amp;") => ("&", 5),
+                _ => ("&", 1),
+            };
+            push(txt, style);
+            rest = &rest[len..];
+            continue;
+        }
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => {
+                push(rest, style);
+                break;
+            }
+        };
+        let tag = &rest[1..end];
+        let (name, val) = match tag.find('=') {
+            Some(i) => (&tag[..i], Some(&tag[i + 1..])),
+            None => (tag, None),
+        };
+        let mut next_style = style;
+        let known = match (name, val) {
+            ("b", None) => {
+                next_style.bold = true;
+                true
+            }
+            ("i", None) => {
+                next_style.italic = true;
+                true
+            }
+            ("u", None) => {
+                next_style.underline = true;
+                true
+            }
+            ("color", Some(v)) => match markup_color(v) {
+                Some(c) => {
+                    next_style.color = Some(c);
+                    true
+                }
+                None => false,
+            },
+            ("size", Some(v)) => match v.parse::<u32>() {
+                Ok(s) if s > 0 => {
+                    next_style.size = Some(s);
+                    true
+                }
+                _ => false,
+            },
+            ("br", None) => {
+                push("\n", style);
+                rest = &rest[end + 1..];
+                continue;
+            }
+            _ => false,
+        };
+        if known {
+            stack.push((name, style));
+            style = next_style;
+        } else if let Some(closing) = name.strip_prefix('/') {
+            match stack.iter().rposition(|(n, _)| *n == closing) {
+                Some(i) if val.is_none() => {
+                    style = stack[i].1;
+                    stack.truncate(i);
+                }
+                _ => push(&rest[..=end], style),
+            }
+        } else {
+            push(&rest[..=end], style);
+        }
+        rest = &rest[end + 1..];
+    }
+    spans
+}
+
+/// Splits text into words, each keeping its trailing spaces
+fn split_words(s: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut start = 0;
+    let mut in_space = false;
+    for (i, c) in s.char_indices() {
+        if c == ' ' {
+            in_space = true;
+        } else if in_space {
+            words.push(&s[start..i]);
+            start = i;
+            in_space = false;
+        }
+    }
+    if start < s.len() {
+        words.push(&s[start..]);
+    }
+    words
+}
+
+#[derive(Debug, Clone)]
+struct RichLabelState {
+    spans: Vec<(String, RichStyle)>,
+    markup: String,
+    wrap: bool,
+}
+
+/// Defines a label (custom widget) rendering a small markup, with several styles in the same text.
+/// Supported tags are `<b>`, `<i>`, `<u>`, `<color=red>` or `<color=#ff0000>`, `<size=18>`, each closed like `</b>`,
+/// and `<br>` for a line break. `&lt;`, `&gt;` and `&amp;` escape the special characters.
+/// The text is drawn using the label font, size and color of the widget, and wraps within its width by default
+/// ```no_run
+/// use fltk::*;
+/// let mut lbl = misc::RichLabel::new(5, 5, 300, 60, "");
+/// lbl.set_markup("Status: <b><color=green>online</color></b>, <size=10>last seen <i>now</i></size>");
+/// ```
+#[derive(Debug, Clone)]
+pub struct RichLabel {
+    frame: Frame,
+    state: Rc<RefCell<RichLabelState>>,
+}
+
+impl RichLabel {
+    /// Creates a new rich label showing a markup
+    pub fn new(x: i32, y: i32, w: i32, h: i32, markup: &str) -> RichLabel {
+        let mut frame = Frame::new(x, y, w, h, "");
+        let state = Rc::new(RefCell::new(RichLabelState {
+            spans: parse_markup(markup),
+            markup: markup.to_string(),
+            wrap: true,
+        }));
+        let st = state.clone();
+        frame.draw2(move |f| RichLabel::render(f, &st.borrow()));
+        RichLabel { frame, state }
+    }
+
+    fn render(f: &Frame, st: &RichLabelState) {
+        let (x, y, w, h) = (f.x() + 4, f.y() + 2, f.width() - 8, f.height() - 4);
+        let base = f.label_font();
+        let base_size = f.label_size().max(1) as u32;
+        let base_color = f.label_color();
+        let font_for = |s: &RichStyle| {
+            if base.bits() < 12 && base.bits() % 4 == 0 {
+                Font::from_bits_truncate(
+                    base.bits() + if s.bold { 1 } else { 0 } + if s.italic { 2 } else { 0 },
+                )
+            } else {
+                base
+            }
+        };
+        // each line holds its pieces as (x, text, style) along with its height and descent
+        let mut lines: Vec<(Vec<(i32, &str, RichStyle)>, i32, i32)> = vec![(vec![], 0, 0)];
+        let mut cx = 0;
+        for (text, style) in &st.spans {
+            draw::set_font(font_for(style), style.size.unwrap_or(base_size));
+            let (lh, ld) = (draw::height(), draw::descent());
+            for (i, part) in text.split('\n').enumerate() {
+                if i > 0 {
+                    let line = lines.last_mut().unwrap();
+                    if line.1 == 0 {
+                        line.1 = lh;
+                        line.2 = ld;
+                    }
+                    lines.push((vec![], 0, 0));
+                    cx = 0;
+                }
+                for word in split_words(part) {
+                    let mut word = word;
+                    if st.wrap && cx > 0 && cx + draw::width(word.trim_end()) as i32 > w {
+                        lines.push((vec![], 0, 0));
+                        cx = 0;
+                        word = word.trim_start();
+                    }
+                    let line = lines.last_mut().unwrap();
+                    line.0.push((cx, word, *style));
+                    line.1 = line.1.max(lh);
+                    line.2 = line.2.max(ld);
+                    cx += draw::width(word) as i32;
+                }
+            }
+        }
+        draw::push_clip(f.x(), f.y(), f.width(), f.height());
+        let mut ly = y;
+        for (pieces, lh, ld) in lines {
+            if ly > y + h {
+                break;
+            }
+            let baseline = ly + lh - ld;
+            for (px, txt, style) in pieces {
+                draw::set_font(font_for(&style), style.size.unwrap_or(base_size));
+                draw::set_draw_color(style.color.unwrap_or(base_color));
+                draw::draw_text(txt, x + px, baseline);
+                if style.underline {
+                    let tw = draw::width(txt.trim_end()) as i32;
+                    draw::draw_xyline(x + px, baseline + 1, x + px + tw);
+                }
+            }
+            ly += lh;
+        }
+        draw::pop_clip();
+    }
+
+    /// Returns the markup
+    pub fn markup(&self) -> String {
+        self.state.borrow().markup.clone()
+    }
+
+    /// Sets the markup
+    pub fn set_markup(&mut self, markup: &str) {
+        {
+            let mut st = self.state.borrow_mut();
+            st.spans = parse_markup(markup);
+            st.markup = markup.to_string();
+        }
+        self.frame.redraw();
+    }
+
+    /// Returns the text of the markup without its tags
+    pub fn plain_text(&self) -> String {
+        self.state
+            .borrow()
+            .spans
+            .iter()
+            .map(|(t, _)| t.as_str())
+            .collect()
+    }
+
+    /// Returns whether the text wraps within the width of the label
+    pub fn wrap(&self) -> bool {
+        self.state.borrow().wrap
+    }
+
+    /// Sets whether the text wraps within the width of the label
+    pub fn set_wrap(&mut self, flag: bool) {
+        self.state.borrow_mut().wrap = flag;
+        self.frame.redraw();
+    }
+}
+
+impl Deref for RichLabel {
+    type Target = Frame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl DerefMut for RichLabel {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frame
+    }
+}
+
+#[derive(Debug)]
+struct BadgeState {
+    count: Option<u32>,
+    target: Widget,
+}
+
+thread_local! {
+    static BADGES: RefCell<Vec<(Frame, Rc<RefCell<BadgeState>>)>> = RefCell::new(Vec::new());
+    static BADGE_CHECK_ADDED: RefCell<bool> = RefCell::new(false);
+}
+
+fn badge_text(count: u32) -> String {
+    if count > 99 {
+        "99+".to_string()
+    } else {
+        count.to_string()
+    }
+}
+
+/// Keeps the badges over their targets, redrawing them whenever their target was redrawn
+fn update_badges() {
+    let badges = BADGES.with(|b| b.borrow().clone());
+    let mut gone = vec![];
+    for (mut frame, st) in badges {
+        if frame.was_deleted() || st.borrow().target.was_deleted() {
+            gone.push(st);
+            continue;
+        }
+        let (damaged, moved) = {
+            let st = st.borrow();
+            let (x, y, w, h) = Badge::geometry(&st.target, st.count);
+            let moved = (x, y, w, h) != (frame.x(), frame.y(), frame.width(), frame.height());
+            if moved {
+                frame.resize(x, y, w, h);
+            }
+            (st.target.damage(), moved)
+        };
+        if moved {
+            // clears the previous location of the badge
+            if let Some(mut p) = frame.parent() {
+                p.redraw();
+            }
+        }
+        if damaged || moved {
+            frame.redraw();
+        }
+    }
+    if !gone.is_empty() {
+        BADGES.with(|b| {
+            b.borrow_mut()
+                .retain(|(_, s)| !gone.iter().any(|g| Rc::ptr_eq(g, s)))
+        });
+    }
+}
+
+/// Defines a badge (custom widget) showing a small count over the top-right corner of another widget,
+/// like the number of unread messages over a toolbar button.
+/// The badge is added to the parent of the widget, after it, so it's drawn on top of it,
+/// and it follows the widget when it moves or gets redrawn. It doesn't take any event.
+/// Counts above 99 are shown as `99+`
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let btn = button::Button::new(10, 10, 40, 40, "@mail");
+/// win.end();
+/// let mut badge = misc::Badge::new(&btn);
+/// badge.set_badge(Some(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Badge {
+    frame: Frame,
+    state: Rc<RefCell<BadgeState>>,
+}
+
+impl Badge {
+    /// Creates a badge decorating a widget, hidden until a count is set.
+    /// # Panics
+    /// Panics if the widget has no parent
+    pub fn new<W: WidgetExt>(target: &W) -> Badge {
+        assert!(!target.was_deleted());
+        let parent = target
+            .parent()
+            .expect("The decorated widget must have a parent");
+        let target = unsafe { Widget::from_widget_ptr(target.as_widget_ptr()) };
+        let mut frame = Frame::new(0, 0, 0, 0, "");
+        let mut parent = unsafe { Group::from_widget_ptr(parent.as_widget_ptr()) };
+        parent.add(&frame);
+        frame.set_color(Color::Red);
+        frame.set_label_color(Color::White);
+        frame.set_label_font(Font::HelveticaBold);
+        frame.set_label_size(11);
+        frame.hide();
+        let state = Rc::new(RefCell::new(BadgeState {
+            count: None,
+            target,
+        }));
+        let st = state.clone();
+        frame.draw2(move |f| {
+            if let Some(count) = st.borrow().count {
+                draw::draw_box(
+                    FrameType::RFlatBox,
+                    f.x(),
+                    f.y(),
+                    f.width(),
+                    f.height(),
+                    f.color(),
+                );
+                draw::set_draw_color(f.label_color());
+                draw::set_font(f.label_font(), f.label_size() as u32);
+                draw::draw_text2(
+                    &badge_text(count),
+                    f.x(),
+                    f.y(),
+                    f.width(),
+                    f.height(),
+                    Align::Center,
+                );
+            }
+        });
+        BADGES.with(|b| b.borrow_mut().push((frame.clone(), state.clone())));
+        let added = BADGE_CHECK_ADDED.with(|a| mem::replace(&mut *a.borrow_mut(), true));
+        if !added {
+            unsafe extern "C" fn shim(_data: *mut raw::c_void) {
+                update_badges();
+            }
+            unsafe { fltk_sys::fl::Fl_add_check(Some(shim), std::ptr::null_mut()) }
+        }
+        Badge { frame, state }
+    }
+
+    fn geometry(target: &Widget, count: Option<u32>) -> (i32, i32, i32, i32) {
+        let h = 16;
+        let w = match count {
+            Some(c) => (badge_text(c).len() as i32 * 7 + 6).max(h),
+            None => h,
+        };
+        (
+            target.x() + target.width() - w * 2 / 3,
+            target.y() - h / 3,
+            w,
+            h,
+        )
+    }
+
+    /// Returns the count shown by the badge
+    pub fn badge(&self) -> Option<u32> {
+        self.state.borrow().count
+    }
+
+    /// Sets the count shown by the badge, `None` hides the badge
+    pub fn set_badge(&mut self, count: Option<u32>) {
+        assert!(!self.frame.was_deleted());
+        let (x, y, w, h) = {
+            let mut st = self.state.borrow_mut();
+            st.count = count;
+            Badge::geometry(&st.target, count)
+        };
+        if let Some(mut p) = self.frame.parent() {
+            p.redraw();
+        }
+        self.frame.resize(x, y, w, h);
+        if count.is_some() {
+            self.frame.show();
+        } else {
+            self.frame.hide();
+        }
+        self.frame.redraw();
+    }
+
+    /// Sets the background color of the badge, red by default
+    pub fn set_badge_color(&mut self, color: Color) {
+        self.frame.set_color(color);
+        self.frame.redraw();
+    }
+}
+
+impl Deref for Badge {
+    type Target = Frame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl DerefMut for Badge {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frame
+    }
+}
+
+const BUSY_DOTS: usize = 12;
+const BUSY_INTERVAL: f64 = 1. / 12.;
+
+#[derive(Debug, Clone)]
+struct BusyIndicatorState {
+    running: bool,
+    phase: usize,
+    size: Option<i32>,
+    color: Color,
+}
+
+thread_local! {
+    static BUSY_INDICATORS: RefCell<Vec<(Frame, Rc<RefCell<BusyIndicatorState>>)>> = RefCell::new(Vec::new());
+    static BUSY_TIMER_ADDED: RefCell<bool> = RefCell::new(false);
+}
+
+/// Advances the running busy indicators, the timer stops once none is left running
+unsafe extern "C" fn busy_tick(_data: *mut raw::c_void) {
+    let running = BUSY_INDICATORS.with(|b| {
+        let mut b = b.borrow_mut();
+        b.retain(|(f, st)| !f.was_deleted() && st.borrow().running);
+        b.clone()
+    });
+    for (mut f, st) in running.iter().cloned() {
+        {
+            let mut st = st.borrow_mut();
+            st.phase = (st.phase + 1) % BUSY_DOTS;
+        }
+        f.redraw();
+    }
+    if running.is_empty() {
+        BUSY_TIMER_ADDED.with(|a| *a.borrow_mut() = false);
+    } else {
+        fltk_sys::fl::Fl_repeat_timeout(BUSY_INTERVAL, Some(busy_tick), std::ptr::null_mut());
+    }
+}
+
+/// Defines an indeterminate busy indicator (custom widget), a circle of dots spinning while some work is running.
+/// The indicator is drawn centered, or at the left of the label if the widget has one,
+/// and nothing is drawn for it while stopped
+/// ```no_run
+/// use fltk::*;
+/// let mut busy = misc::BusyIndicator::new(5, 5, 150, 24, "Loading...");
+/// busy.start();
+/// ```
+#[derive(Debug, Clone)]
+pub struct BusyIndicator {
+    frame: Frame,
+    state: Rc<RefCell<BusyIndicatorState>>,
+}
+
+impl BusyIndicator {
+    /// Creates a new busy indicator, stopped
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> BusyIndicator {
+        let mut frame = Frame::new(x, y, w, h, label);
+        let state = Rc::new(RefCell::new(BusyIndicatorState {
+            running: false,
+            phase: 0,
+            size: None,
+            color: Color::Selection,
+        }));
+        let st = state.clone();
+        frame.draw2(move |f| BusyIndicator::draw_indicator(f, &st.borrow()));
+        BusyIndicator { frame, state }
+    }
+
+    fn draw_indicator(f: &Frame, st: &BusyIndicatorState) {
+        let (x, y, w, h) = (f.x(), f.y(), f.width(), f.height());
+        draw::draw_box(f.frame(), x, y, w, h, f.color());
+        let label = f.label();
+        let size = st.size.unwrap_or_else(|| w.min(h) - 4).max(8);
+        let (cx, cy) = if label.is_empty() {
+            (x + w / 2, y + h / 2)
+        } else {
+            (x + 2 + size / 2, y + h / 2)
+        };
+        if st.running {
+            draw::set_draw_color(st.color);
+            let ring = size as f64 / 2. * 0.75;
+            let dot = (size as f64 / 8.).max(1.5);
+            for i in 0..BUSY_DOTS {
+                // the dots trail behind the current one, shrinking
+                let age = (st.phase + BUSY_DOTS - i) % BUSY_DOTS;
+                let r = dot * (1. - age as f64 / BUSY_DOTS as f64 * 0.75);
+                let a = (90. - i as f64 * 360. / BUSY_DOTS as f64).to_radians();
+                let (dx, dy) = (cx as f64 + ring * a.cos(), cy as f64 - ring * a.sin());
+                let d = (2. * r).round().max(1.) as i32;
+                draw::draw_pie(
+                    (dx - r).round() as i32,
+                    (dy - r).round() as i32,
+                    d,
+                    d,
+                    0.,
+                    360.,
+                );
+            }
+        }
+        if !label.is_empty() {
+            draw::set_font(f.label_font(), f.label_size() as u32);
+            draw::set_draw_color(f.label_color());
+            let lx = cx + size / 2 + 6;
+            draw::draw_text2(&label, lx, y, x + w - lx, h, Align::Left | Align::Inside);
+        }
+    }
+
+    /// Starts spinning
+    pub fn start(&mut self) {
+        assert!(!self.frame.was_deleted());
+        self.state.borrow_mut().running = true;
+        BUSY_INDICATORS.with(|b| {
+            let mut b = b.borrow_mut();
+            if !b.iter().any(|(_, st)| Rc::ptr_eq(st, &self.state)) {
+                b.push((self.frame.clone(), self.state.clone()));
+            }
+        });
+        let added = BUSY_TIMER_ADDED.with(|a| mem::replace(&mut *a.borrow_mut(), true));
+        if !added {
+            unsafe {
+                fltk_sys::fl::Fl_add_timeout(BUSY_INTERVAL, Some(busy_tick), std::ptr::null_mut())
+            }
+        }
+        self.frame.redraw();
+    }
+
+    /// Stops spinning
+    pub fn stop(&mut self) {
+        assert!(!self.frame.was_deleted());
+        self.state.borrow_mut().running = false;
+        self.frame.redraw();
+    }
+
+    /// Returns whether the indicator is spinning
+    pub fn is_running(&self) -> bool {
+        self.state.borrow().running
+    }
+
+    /// Sets the diameter of the spinning circle, `None` fits it in the widget
+    pub fn set_indicator_size(&mut self, size: Option<i32>) {
+        self.state.borrow_mut().size = size;
+        self.frame.redraw();
+    }
+
+    /// Returns the diameter set for the spinning circle
+    pub fn indicator_size(&self) -> Option<i32> {
+        self.state.borrow().size
+    }
+
+    /// Sets the color of the dots, the selection color by default
+    pub fn set_indicator_color(&mut self, color: Color) {
+        self.state.borrow_mut().color = color;
+        self.frame.redraw();
+    }
+
+    /// Returns the color of the dots
+    pub fn indicator_color(&self) -> Color {
+        self.state.borrow().color
+    }
+}
+
+impl Deref for BusyIndicator {
+    type Target = Frame;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frame
+    }
+}
+
+impl DerefMut for BusyIndicator {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frame
+    }
+}
+
+#[cfg(test)]
+mod misc {
+    use super::*;
+    #[test]
+    fn chart_autoscale() {
+        let vals = [10.0, 20.0];
+        assert_eq!(autoscale_bounds(vals.iter(), false), Some((9.5, 20.5)));
+        assert_eq!(autoscale_bounds(vals.iter(), true), Some((0.0, 21.0)));
+        assert_eq!(autoscale_bounds([].iter(), true), None);
+        assert_eq!(autoscale_bounds([0.0].iter(), false), Some((-1.0, 1.0)));
+    }
+
+    #[test]
+    fn plot_ticks() {
+        assert_eq!(
+            nice_ticks(0.0, 10.0, 5),
+            (vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0], 2.0)
+        );
+        assert_eq!(nice_ticks(1.0, 1.0, 5).0, vec![]);
+        assert_eq!(tick_label(0.25, 0.05), "0.25");
+        assert_eq!(tick_label(20.0, 5.0), "20");
+    }
+
+    #[test]
+    fn calendar_dates() {
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(Date::new(2021, 2, 29), None);
+        let d = Date::new(2000, 1, 1).unwrap();
+        assert_eq!(d.to_days(), 10957);
+        assert_eq!(Date::from_days(10957), d);
+        assert_eq!(d.weekday(), 6);
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!(d.add_days(-1), Date::new(1999, 12, 31).unwrap());
+    }
+
+    #[test]
+    fn badge_counts() {
+        assert_eq!(badge_text(7), "7");
+        assert_eq!(badge_text(99), "99");
+        assert_eq!(badge_text(100), "99+");
+    }
+
+    #[test]
+    fn rich_label_markup() {
+        let bold = RichStyle {
+            bold: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_markup("a <b>b</b> c"),
+            vec![
+                ("a ".to_string(), RichStyle::default()),
+                ("b".to_string(), bold),
+                (" c".to_string(), RichStyle::default())
+            ]
+        );
+        assert_eq!(
+            parse_markup("<x>&lt;y&gt;</b>")[0].0,
+            "<x><y></b>".to_string()
+        );
+        let spans = parse_markup("<size=20><color=#ff0000>big</color></size>");
+        assert_eq!(spans[0].1.size, Some(20));
+        assert_eq!(
+            split_words("one  two three"),
+            vec!["one  ", "two ", "three"]
+        );
+    }
+}
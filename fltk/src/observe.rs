@@ -0,0 +1,230 @@
+use crate::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::Rc,
+};
+
+type Observer<T> = Rc<RefCell<dyn FnMut(&T) -> bool>>;
+
+struct ValueInner<T> {
+    value: T,
+    observers: Vec<(usize, Observer<T>)>,
+    next_id: usize,
+}
+
+/// An observable value, shared by its clones, which notifies its observers when it changes.
+/// Widgets bound to it are updated when it changes and update it when they're changed by the user
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// let mut name = input::Input::new(100, 10, 200, 30, "Name");
+/// let mut greeting = frame::Frame::new(100, 50, 200, 30, "");
+/// let mut volume = valuator::HorSlider::new(100, 90, 200, 30, "Volume");
+/// let mut mute = button::CheckButton::new(100, 130, 200, 30, "Mute");
+/// win.end();
+/// win.show();
+/// let name_val = observe::Value::new(String::from("World"));
+/// name_val.bind_input(&mut name);
+/// name_val.subscribe(move |n| greeting.set_label(&format!("Hello {}", n)));
+/// let volume_val = observe::Value::new(0.5);
+/// volume_val.bind_valuator(&mut volume);
+/// let mute_val = observe::Value::new(false);
+/// mute_val.bind_button(&mut mute);
+/// mute_val.subscribe(move |m| if *m { volume.deactivate() } else { volume.activate() });
+/// app.run().unwrap();
+/// ```
+pub struct Value<T> {
+    inner: Rc<RefCell<ValueInner<T>>>,
+}
+
+impl<T> Clone for Value<T> {
+    fn clone(&self) -> Self {
+        Value {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Value<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.borrow();
+        f.debug_struct("Value")
+            .field("value", &inner.value)
+            .field("observers", &inner.observers.len())
+            .finish()
+    }
+}
+
+impl<T: Default + Clone + PartialEq + 'static> Default for Value<T> {
+    fn default() -> Self {
+        Value::new(T::default())
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> Value<T> {
+    /// Creates an observable value
+    pub fn new(value: T) -> Value<T> {
+        Value {
+            inner: Rc::new(RefCell::new(ValueInner {
+                value,
+                observers: vec![],
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Returns a copy of the value
+    pub fn get(&self) -> T {
+        self.inner.borrow().value.clone()
+    }
+
+    /// Sets the value, notifying the observers if it changed
+    pub fn set(&self, value: T) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            if inner.value == value {
+                return;
+            }
+            inner.value = value.clone();
+        }
+        self.notify(&value);
+    }
+
+    /// Modifies the value in place, notifying the observers if it changed
+    pub fn update<F: FnOnce(&mut T)>(&self, f: F) {
+        let mut value = self.get();
+        f(&mut value);
+        self.set(value);
+    }
+
+    /// Adds an observer called with the new value on every change, returns an id for unsubscribe()
+    pub fn subscribe<F: FnMut(&T) + 'static>(&self, mut f: F) -> usize {
+        self.add_observer(move |v| {
+            f(v);
+            true
+        })
+    }
+
+    /// Removes an observer
+    pub fn unsubscribe(&self, id: usize) {
+        self.inner.borrow_mut().observers.retain(|(i, _)| *i != id);
+    }
+
+    /// Adds an observer which is removed once it returns false
+    fn add_observer<F: FnMut(&T) -> bool + 'static>(&self, f: F) -> usize {
+        let mut inner = self.inner.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.observers.push((id, Rc::new(RefCell::new(f))));
+        id
+    }
+
+    fn notify(&self, value: &T) {
+        // the observers can set the value or subscribe, so they're called without borrowing the value
+        let observers = self.inner.borrow().observers.clone();
+        let mut dropped = vec![];
+        for (id, obs) in observers {
+            // an observer setting the value isn't notified of its own change
+            let keep = match obs.try_borrow_mut() {
+                Ok(mut obs) => (*obs)(value),
+                Err(_) => true,
+            };
+            if !keep {
+                dropped.push(id);
+            }
+        }
+        if !dropped.is_empty() {
+            self.inner
+                .borrow_mut()
+                .observers
+                .retain(|(i, _)| !dropped.contains(i));
+        }
+    }
+
+    /// Binds a widget to the value: the widget is set to the value and updated when it changes,
+    /// and its callback sets the value using get_widget. The binding ends when the widget is deleted.
+    /// Replaces any callback set using set_callback() or set_callback2()
+    pub fn bind<W, G, S>(&self, wid: &mut W, mut get_widget: G, mut set_widget: S)
+    where
+        W: WidgetExt + Clone + 'static,
+        G: FnMut(&W) -> T + 'static,
+        S: FnMut(&mut W, &T) + 'static,
+    {
+        set_widget(wid, &self.get());
+        wid.redraw();
+        // the widget already shows the values it sets, setting them back would reset an input's cursor
+        let setting = Rc::new(Cell::new(false));
+        let mut w = wid.clone();
+        let s = setting.clone();
+        self.add_observer(move |v| {
+            if w.was_deleted() {
+                return false;
+            }
+            if !s.get() {
+                set_widget(&mut w, v);
+                w.redraw();
+            }
+            true
+        });
+        let val = self.clone();
+        let w = wid.clone();
+        wid.set_callback(move || {
+            setting.set(true);
+            val.set(get_widget(&w));
+            setting.set(false);
+        });
+    }
+}
+
+impl Value<String> {
+    /// Binds the text of an input widget, updating the value as the user types
+    pub fn bind_input<I: InputExt + Clone + 'static>(&self, inp: &mut I) {
+        inp.set_trigger(CallbackTrigger::Changed);
+        self.bind(inp, |i| i.value(), |i, v| i.set_value(v));
+    }
+}
+
+impl Value<f64> {
+    /// Binds the value of a valuator widget, like a slider or a counter
+    pub fn bind_valuator<V: ValuatorExt + Clone + 'static>(&self, val: &mut V) {
+        self.bind(val, |v| v.value(), |v, x| v.set_value(*x));
+    }
+}
+
+impl Value<bool> {
+    /// Binds the state of a button, like a check button or a toggle button
+    pub fn bind_button<B: ButtonExt + Clone + 'static>(&self, btn: &mut B) {
+        self.bind(btn, |b| b.is_set(), |b, x| b.set(*x));
+    }
+}
+
+#[cfg(test)]
+mod observe {
+    use super::*;
+    #[test]
+    fn notifications() {
+        let val = Value::new(1);
+        let seen = Rc::new(RefCell::new(vec![]));
+        let s = seen.clone();
+        let id = val.subscribe(move |v| s.borrow_mut().push(*v));
+        val.set(2);
+        val.set(2);
+        val.update(|v| *v += 1);
+        assert_eq!(*seen.borrow(), vec![2, 3]);
+        // an observer clamping the value
+        let v = val.clone();
+        val.subscribe(move |x| {
+            if *x > 10 {
+                v.set(10)
+            }
+        });
+        val.set(20);
+        assert_eq!(val.get(), 10);
+        assert_eq!(*seen.borrow(), vec![2, 3, 20, 10]);
+        val.unsubscribe(id);
+        val.set(4);
+        assert_eq!(seen.borrow().len(), 4);
+    }
+}
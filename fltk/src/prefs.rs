@@ -0,0 +1,245 @@
+use crate::app;
+use crate::prelude::*;
+use crate::utils::{escape, unescape};
+use std::{collections::BTreeMap, env, fmt, fs, path, str::FromStr};
+
+/// Persistent key/value preferences of an application, stored in a text file.
+/// This is a store of its own rather than a wrapper of Fl_Preferences, and doesn't read files written by it
+/// ```no_run
+/// use fltk::*;
+/// let mut prefs = prefs::Preferences::open("mycompany", "myapp").unwrap();
+/// let runs: u32 = prefs.get("runs").unwrap_or(0);
+/// prefs.set("runs", runs + 1);
+/// prefs.save().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Preferences {
+    path: Option<path::PathBuf>,
+    entries: BTreeMap<String, String>,
+}
+
+impl Preferences {
+    /// Creates empty preferences which aren't associated with a file
+    pub fn new() -> Preferences {
+        Preferences::default()
+    }
+
+    /// Opens the preferences of an application, from the user's configuration directory:
+    /// `%APPDATA%` on Windows, `~/Library/Preferences` on MacOS and `$XDG_CONFIG_HOME` or `~/.config` otherwise.
+    /// The preferences are empty if the file doesn't exist yet
+    pub fn open(vendor: &str, application: &str) -> Result<Preferences, FltkError> {
+        let dir = if cfg!(target_os = "windows") {
+            env::var_os("APPDATA").map(path::PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            env::var_os("HOME").map(|h| path::Path::new(&h).join("Library/Preferences"))
+        } else {
+            env::var_os("XDG_CONFIG_HOME")
+                .map(path::PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|h| path::Path::new(&h).join(".config")))
+        }
+        .ok_or(FltkError::Internal(FltkErrorKind::ResourceNotFound))?;
+        let path = dir.join(vendor).join(format!("{}.prefs", application));
+        if path.exists() {
+            Preferences::load(path)
+        } else {
+            Ok(Preferences {
+                path: Some(path),
+                entries: BTreeMap::new(),
+            })
+        }
+    }
+
+    /// Loads preferences from a file, which they're saved back to
+    pub fn load<P: AsRef<path::Path>>(path: P) -> Result<Preferences, FltkError> {
+        let mut prefs: Preferences = fs::read_to_string(&path)?.parse()?;
+        prefs.path = Some(path.as_ref().to_path_buf());
+        Ok(prefs)
+    }
+
+    /// Saves the preferences to their file, creating its directory if needed
+    pub fn save(&self) -> Result<(), FltkError> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or(FltkError::Internal(FltkErrorKind::ResourceNotFound))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Returns the file of the preferences
+    pub fn path(&self) -> Option<&path::Path> {
+        self.path.as_deref()
+    }
+
+    /// Sets the file of the preferences
+    pub fn set_path<P: AsRef<path::Path>>(&mut self, path: P) {
+        self.path = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Sets a value
+    pub fn set<T: ToString>(&mut self, key: &str, val: T) {
+        self.entries.insert(key.to_string(), val.to_string());
+    }
+
+    /// Returns a value, None if it's missing or can't be parsed as a T
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.entries.get(key).and_then(|v| v.parse().ok())
+    }
+
+    /// Returns whether a value is set
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Removes a value
+    pub fn remove(&mut self, key: &str) {
+        self.entries.remove(key);
+    }
+
+    /// Returns the keys, in alphabetical order
+    pub fn keys(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect()
+    }
+}
+
+/// Each entry is written on its own line as a tab-separated key and value
+impl fmt::Display for Preferences {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (k, v) in &self.entries {
+            writeln!(f, "{}\t{}", escape(k), escape(v))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Preferences {
+    type Err = FltkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = BTreeMap::new();
+        for (n, line) in s.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+            let mut fields = line.splitn(2, '\t');
+            match (fields.next(), fields.next()) {
+                (Some(k), Some(v)) => {
+                    entries.insert(unescape(k), unescape(v));
+                }
+                _ => {
+                    return Err(FltkError::Unknown(format!(
+                        "Invalid preferences line {}",
+                        n + 1
+                    )))
+                }
+            }
+        }
+        Ok(Preferences {
+            path: None,
+            entries,
+        })
+    }
+}
+
+/// Moves and shrinks a window's geometry to fit in a work area.
+/// The saved screen's work area is used unless the geometry overlaps another one
+fn fit_geometry(
+    geom: (i32, i32, i32, i32),
+    screen: usize,
+    work_areas: &[(i32, i32, i32, i32)],
+) -> (i32, i32, i32, i32) {
+    let (x, y, w, h) = geom;
+    let overlaps =
+        |a: &&(i32, i32, i32, i32)| x < a.0 + a.2 && x + w > a.0 && y < a.1 + a.3 && y + h > a.1;
+    let area = work_areas
+        .get(screen)
+        .filter(overlaps)
+        .or_else(|| work_areas.iter().find(overlaps))
+        .or_else(|| work_areas.get(screen))
+        .or_else(|| work_areas.first());
+    let (ax, ay, aw, ah) = match area {
+        Some(a) => *a,
+        None => return geom,
+    };
+    let (w, h) = (w.min(aw), h.min(ah));
+    (x.clamp(ax, ax + aw - w), y.clamp(ay, ay + ah - h), w, h)
+}
+
+pub(crate) fn save_window_geometry<W: WindowExt>(win: &W, prefs: &mut Preferences, key: &str) {
+    let (x, y, w, h) = (win.x(), win.y(), win.width(), win.height());
+    let screen = app::screen_num(x + w / 2, y + h / 2);
+    let (_, _, aw, ah) = app::screen_work_area(screen);
+    let fullscreen = win.fullscreen_active();
+    // FLTK can't query the maximized state, a window filling the work area is taken as maximized
+    let maximized = !fullscreen && win.decorated_w() >= aw && win.decorated_h() >= ah;
+    prefs.set(&format!("{}.x", key), x);
+    prefs.set(&format!("{}.y", key), y);
+    prefs.set(&format!("{}.w", key), w);
+    prefs.set(&format!("{}.h", key), h);
+    prefs.set(&format!("{}.screen", key), screen);
+    prefs.set(&format!("{}.maximized", key), maximized);
+    prefs.set(&format!("{}.fullscreen", key), fullscreen);
+}
+
+pub(crate) fn restore_window_geometry<W: WindowExt>(
+    win: &mut W,
+    prefs: &Preferences,
+    key: &str,
+) -> bool {
+    let get = |field: &str| prefs.get::<i32>(&format!("{}.{}", key, field));
+    let geom = match (get("x"), get("y"), get("w"), get("h")) {
+        (Some(x), Some(y), Some(w), Some(h)) if w > 0 && h > 0 => (x, y, w, h),
+        _ => return false,
+    };
+    let screen = get("screen").unwrap_or(0).max(0) as usize;
+    let work_areas: Vec<_> = (0..app::screen_count())
+        .map(app::screen_work_area)
+        .collect();
+    let (x, y, w, h) = fit_geometry(geom, screen, &work_areas);
+    let flag = |field: &str| prefs.get::<bool>(&format!("{}.{}", key, field)) == Some(true);
+    if flag("maximized") {
+        let n = work_areas.len().saturating_sub(1).min(screen);
+        if let Some((ax, ay, aw, ah)) = work_areas.get(n) {
+            win.resize(*ax, *ay, *aw, *ah);
+        }
+    } else {
+        win.resize(x, y, w, h);
+    }
+    if flag("fullscreen") {
+        win.fullscreen(true);
+    }
+    true
+}
+
+#[cfg(test)]
+mod prefs {
+    use super::*;
+    #[test]
+    fn preferences_text() {
+        let mut p = Preferences::new();
+        p.set("win.w", 400);
+        p.set("name", "a\tb");
+        let p2: Preferences = p.to_string().parse().unwrap();
+        assert_eq!(p2.get::<i32>("win.w"), Some(400));
+        assert_eq!(p2.get::<String>("name").unwrap(), "a\tb");
+        assert_eq!(p2.get::<i32>("name"), None);
+    }
+
+    #[test]
+    fn geometry_fitting() {
+        let areas = [(0, 0, 1920, 1040), (1920, 0, 1280, 1000)];
+        assert_eq!(
+            fit_geometry((2000, 100, 400, 300), 1, &areas),
+            (2000, 100, 400, 300)
+        );
+        // the second screen was disconnected
+        assert_eq!(
+            fit_geometry((2000, 100, 400, 300), 1, &areas[..1]),
+            (1520, 100, 400, 300)
+        );
+        assert_eq!(
+            fit_geometry((-50, -20, 3000, 300), 0, &areas),
+            (0, 0, 1920, 300)
+        );
+    }
+}
@@ -89,6 +89,7 @@ pub unsafe trait WidgetExt {
     /// Set to dimensions width and height
     fn set_size(&mut self, width: i32, height: i32);
     /// Sets the widget's label
+    /// For windows, this sets the title, which is updated in the title bar of a shown window
     fn set_label(&mut self, title: &str);
     /// Redraws a widget, necessary for resizing and changing positions
     fn redraw(&mut self);
@@ -106,6 +107,10 @@ pub unsafe trait WidgetExt {
     fn height(&self) -> i32;
     /// Returns the label of the widget
     fn label(&self) -> String;
+    /// Returns the x coordinate of the widget relative to the screen
+    fn x_root(&self) -> i32;
+    /// Returns the y coordinate of the widget relative to the screen
+    fn y_root(&self) -> i32;
     /// Measures the label's width and height
     fn measure_label(&self) -> (i32, i32);
     /// transforms a widget to a base Fl_Widget, for internal use
@@ -126,6 +131,32 @@ pub unsafe trait WidgetExt {
         Self: Sized;
     /// Sets the initial alignment of the widget, (should only be called on initialization)
     fn with_align(self, align: Align) -> Self
+    where
+        Self: Sized;
+    /// Sets the initial type of the widget, (should only be called on initialization)
+    fn with_type<T: WidgetType>(self, typ: T) -> Self
+    where
+        Self: Sized;
+    /// Sets the initial color of the widget, (should only be called on initialization)
+    fn with_color(self, color: Color) -> Self
+    where
+        Self: Sized;
+    /// Sets the initial label color of the widget, (should only be called on initialization)
+    fn with_label_color(self, color: Color) -> Self
+    where
+        Self: Sized;
+    /// Sets the initial label size of the widget, (should only be called on initialization)
+    fn with_label_size(self, sz: i32) -> Self
+    where
+        Self: Sized;
+    /// Sets the initial callback of the widget, takes the widget as a closure argument, (should only be called on initialization)
+    /// ```no_run
+    /// use fltk::*;
+    /// let _btn = button::Button::new(0, 0, 80, 30, "Click")
+    ///     .with_align(Align::Inside | Align::Left)
+    ///     .with_callback(|b| b.set_label("Clicked"));
+    /// ```
+    fn with_callback<F: FnMut(&mut Self) + 'static>(self, cb: F) -> Self
     where
         Self: Sized;
     /// Positions the widget below w, the size of w should be known
@@ -150,6 +181,64 @@ pub unsafe trait WidgetExt {
         Self: Sized;
     /// Takes the size of w, the size of w should be known
     fn size_of<W: WidgetExt>(self, w: &W) -> Self
+    where
+        Self: Sized;
+    /// Positions the widget inside the top-left corner of w, the size of w should be known
+    fn top_left_of<W: WidgetExt>(self, w: &W, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Positions the widget inside the top-right corner of w, the size of w should be known
+    fn top_right_of<W: WidgetExt>(self, w: &W, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Positions the widget inside the bottom-left corner of w, the size of w should be known
+    fn bottom_left_of<W: WidgetExt>(self, w: &W, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Positions the widget inside the bottom-right corner of w, the size of w should be known
+    fn bottom_right_of<W: WidgetExt>(self, w: &W, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Anchors the widget to the top edge of its parent, keeping its x position
+    fn top_of_parent(self, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Anchors the widget to the bottom edge of its parent, keeping its x position
+    fn bottom_of_parent(self, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Anchors the widget to the left edge of its parent, keeping its y position
+    fn left_of_parent(self, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Anchors the widget to the right edge of its parent, keeping its y position
+    fn right_of_parent(self, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Positions the widget to the center of its parent
+    fn center_of_parent(self) -> Self
+    where
+        Self: Sized;
+    /// Stretches the widget horizontally to the edges of its parent, keeping its y position and height
+    fn stretch_width_to_parent(self, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Stretches the widget vertically to the edges of its parent, keeping its x position and width
+    fn stretch_height_to_parent(self, padding: i32) -> Self
+    where
+        Self: Sized;
+    /// Stretches the widget to fill its parent
+    /// The parent anchoring methods require the widget to already be inside a group
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(0, 0, 400, 300, "");
+    /// let _frame = frame::Frame::default().stretch_to_parent();
+    /// let _btn = button::Button::default()
+    ///     .with_size(80, 30)
+    ///     .bottom_right_of(&win, 10);
+    /// win.end();
+    /// ```
+    fn stretch_to_parent(self) -> Self
     where
         Self: Sized;
     /// Checks whether the self widget is inside another widget
@@ -190,9 +279,43 @@ pub unsafe trait WidgetExt {
     where
         Self: Sized;
     /// Emits a message on callback using a sender
+    /// ```no_run
+    /// use fltk::*;
+    /// #[derive(Debug, Copy, Clone)]
+    /// enum Message {
+    ///     Save,
+    /// }
+    /// let app = app::App::default();
+    /// let (s, r) = app::channel::<Message>();
+    /// let mut btn = button::Button::new(0, 0, 80, 30, "Save");
+    /// btn.emit(s, Message::Save);
+    /// while app.wait() {
+    ///     if let Some(Message::Save) = r.recv() {
+    ///         println!("Saving");
+    ///     }
+    /// }
+    /// ```
     fn emit<T: 'static + Clone + Send + Sync>(&mut self, sender: crate::app::Sender<T>, msg: T)
     where
         Self: Sized;
+    /// Emits the message returned by the closure on callback using a sender, no message is sent if it returns None.
+    /// The closure takes the widget as an argument, so messages can carry the widget's state
+    /// ```no_run
+    /// use fltk::*;
+    /// #[derive(Debug, Copy, Clone)]
+    /// enum Message {
+    ///     Volume(f64),
+    /// }
+    /// let (s, _r) = app::channel::<Message>();
+    /// let mut slider = valuator::Slider::new(0, 0, 200, 30, "");
+    /// slider.emit_with(s, |sl| Some(Message::Volume(sl.value())));
+    /// ```
+    fn emit_with<T: 'static + Send + Sync, F: FnMut(&mut Self) -> Option<T> + 'static>(
+        &mut self,
+        sender: crate::app::Sender<T>,
+        f: F,
+    ) where
+        Self: Sized;
     /// Activates the widget
     fn activate(&mut self);
     /// Deactivates the widget
@@ -261,6 +384,33 @@ pub unsafe trait WidgetExt {
     fn clear_visible_focus(&mut self);
     /// Set the visible focus using a flag
     fn visible_focus(&mut self, v: bool);
+    /// Sets the name of the widget for assistive technologies, which defaults to its label.
+    /// FLTK doesn't expose widgets to the platform accessibility APIs, the name being retrievable using accessible_name(),
+    /// for tests or a bridge to a screen reader
+    fn set_accessible_name(&mut self, name: &str);
+    /// Returns the name of the widget for assistive technologies, or its label if it has none
+    fn accessible_name(&self) -> Option<String>;
+    /// Sets the description of the widget for assistive technologies
+    fn set_accessible_description(&mut self, desc: &str);
+    /// Returns the description of the widget for assistive technologies
+    fn accessible_description(&self) -> Option<String>;
+    /// Sets a callback run when the widget gains the keyboard focus, like highlighting a form field
+    fn on_focus_gained<F: FnMut(&mut Self) + 'static>(&mut self, cb: F)
+    where
+        Self: Sized;
+    /// Sets a callback run when the widget loses the keyboard focus, like validating a form field
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut inp = input::IntInput::new(100, 100, 200, 30, "Age");
+    /// inp.on_focus_lost(|i| {
+    ///     let color = if i.value().parse::<u8>().is_ok() { enums::Color::BackGround2 } else { enums::Color::Red };
+    ///     i.set_color(color);
+    ///     i.redraw();
+    /// });
+    /// ```
+    fn on_focus_lost<F: FnMut(&mut Self) + 'static>(&mut self, cb: F)
+    where
+        Self: Sized;
     /// Return whether the widget has visible focus
     fn has_visible_focus(&mut self) -> bool;
     /// Check if a widget was deleted
@@ -271,14 +421,32 @@ pub unsafe trait WidgetExt {
     fn set_damage(&mut self, flag: bool);
     /// Clear the damaged flag
     fn clear_damage(&mut self);
-    /// Sets the default callback trigger for a widget
+    /// Sets the default callback trigger for a widget, triggers can be combined
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut inp = input::Input::new(0, 0, 200, 30, "");
+    /// inp.set_trigger(CallbackTrigger::Changed | CallbackTrigger::EnterKey);
+    /// assert!(inp.trigger().contains(CallbackTrigger::EnterKey));
+    /// ```
     fn set_trigger(&mut self, trigger: CallbackTrigger);
-    /// Return the callback trigger
+    /// Return the callback trigger, which can be queried for individual triggers using contains()
     fn trigger(&self) -> CallbackTrigger;
     /// Return the widget as a window if it's a window
-    fn as_window(&self) -> Option<Box<dyn WindowExt>>;
-    /// Return the widget as a group widget if it's a group widget
-    fn as_group(&self) -> Option<Box<dyn GroupExt>>;
+    fn as_window(&self) -> Option<crate::window::Window>;
+    /// Return the widget as a group widget if it's a group widget,
+    /// which allows generic code to recurse into containers
+    /// ```no_run
+    /// use fltk::*;
+    /// fn print_tree(wid: &dyn WidgetExt, depth: usize) {
+    ///     println!("{}{}", " ".repeat(depth * 2), wid.label());
+    ///     if let Some(grp) = wid.as_group() {
+    ///         for i in 0..grp.children() {
+    ///             print_tree(&*grp.child(i).unwrap(), depth + 1);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn as_group(&self) -> Option<crate::group::Group>;
     /// INTERNAL: Retakes ownership of the user callback data
     /// # Safety
     /// Can return multiple mutable references to the user_data
@@ -302,6 +470,8 @@ pub unsafe trait WidgetBase: WidgetExt {
     /// * `title` - The title or label of the widget
     fn new(x: i32, y: i32, width: i32, height: i32, title: &str) -> Self;
     /// Deletes widgets and their children.
+    /// The widget is removed from its parent group, hidden and its deletion is scheduled for the next event loop cycle.
+    /// Clones of the widget can check WidgetExt::was_deleted() to detect the deletion.
     fn delete(wid: Self)
     where
         Self: Sized;
@@ -327,6 +497,20 @@ pub unsafe trait WidgetBase: WidgetExt {
     /// takes the widget as a closure argument
     /// MacOS requires that WidgetBase::draw actually calls drawing functions
     fn draw2<F: FnMut(&mut Self) + 'static>(&mut self, cb: F);
+    /// Accepts files dropped onto the widget, calling the callback with their paths.
+    /// Replaces any handler set using handle() or handle2(), app::event_paths() can be used in custom handlers instead
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "Drop files here");
+    /// win.on_file_drop(|w, paths| {
+    ///     if let Some(path) = paths.first() {
+    ///         w.set_label(&path.to_string_lossy());
+    ///     }
+    /// });
+    /// ```
+    fn on_file_drop<F: FnMut(&mut Self, Vec<std::path::PathBuf>) + 'static>(&mut self, cb: F)
+    where
+        Self: Sized;
     /// INTERNAL: Retrieve the draw data
     /// # Safety
     /// Can return multiple mutable references to the draw_data
@@ -352,6 +536,12 @@ pub unsafe trait ButtonExt: WidgetExt {
     /// Sets whether a button is set or not
     /// Useful for round, radio, light, toggle and check buttons
     fn set(&mut self, flag: bool);
+    /// Returns the value of the button, same as is_set()
+    fn value(&self) -> bool;
+    /// Sets the value of the button, same as set()
+    fn set_value(&mut self, flag: bool);
+    /// Sets the value of a radio button and clears the other radio buttons of its group
+    fn setonly(&mut self);
     /// Set the down_box of the widget
     fn set_down_frame(&mut self, f: FrameType);
     /// Get the down frame type of the widget
@@ -364,12 +554,22 @@ pub unsafe trait GroupExt: WidgetExt {
     fn begin(&self);
     /// Ends a group, used for widgets implementing the group trait
     fn end(&self);
-    /// Clear a group from all widgets
+    /// Clear a group from all widgets, the children are deleted.
+    /// The deletion happens in the next event loop iteration, so it's safe to call it from a child's callback
     fn clear(&mut self);
     /// Return the number of children in a group
     fn children(&self) -> u32;
     /// Return child widget by index
     fn child(&self, idx: u32) -> Option<Box<dyn WidgetExt>>;
+    /// Recursively searches the group's descendants for the first widget with the given label
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// let _b = button::Button::new(160, 200, 80, 40, "Ok");
+    /// win.end();
+    /// assert!(win.find_by_label("Ok").is_some());
+    /// ```
+    fn find_by_label(&self, label: &str) -> Option<Box<dyn WidgetExt>>;
     /// Find a widget within a group and return its index
     fn find<W: WidgetExt>(&self, widget: &W) -> u32
     where
@@ -392,6 +592,35 @@ pub unsafe trait GroupExt: WidgetExt {
         Self: Sized;
     /// Make the window resizable, should be called before ```show```
     fn make_resizable(&mut self, val: bool);
+    /// Sets the resizable widget of the group, passing None clears it
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut grp = group::Group::new(0, 0, 400, 300, "");
+    /// grp.set_resizable::<widget::Widget>(None);
+    /// ```
+    fn set_resizable<W: WidgetExt>(&mut self, widget: Option<&W>)
+    where
+        Self: Sized;
+    /// Resets the internal array of widget sizes and positions.
+    /// Should be called after manually resizing or moving children, so that later group resizes use the new positions
+    fn init_sizes(&mut self);
+    /// Sets the order in which Tab and Shift+Tab move the focus between the widgets, which can be nested in subgroups.
+    /// The focus skips the widgets which don't take events, and the default navigation is used when the focus is outside the list.
+    /// An empty list restores the default order and removes the handler.
+    /// Replaces any handler set using handle() or handle2()
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// let first = input::Input::new(100, 100, 200, 30, "First");
+    /// let last = input::Input::new(100, 50, 200, 30, "Last");
+    /// win.end();
+    /// win.set_focus_order(&[&first, &last]);
+    /// ```
+    fn set_focus_order(&mut self, order: &[&dyn WidgetExt])
+    where
+        Self: Sized;
+    /// Returns the widgets of the focus order set using set_focus_order()
+    fn focus_order(&self) -> Vec<crate::widget::Widget>;
 }
 
 /// Defines the methods implemented by all window widgets
@@ -404,7 +633,7 @@ pub unsafe trait WindowExt: GroupExt {
     fn make_modal(&mut self, val: bool);
     /// Makes a window fullscreen
     fn fullscreen(&mut self, val: bool);
-    /// Makes the window current
+    /// Makes the window current, the window must be shown
     fn make_current(&mut self);
     /// Returns the icon of the window
     fn icon(&self) -> Option<Box<dyn ImageExt>>;
@@ -444,12 +673,89 @@ pub unsafe trait WindowExt: GroupExt {
     fn decorated_w(&self) -> i32;
     /// Returns the decorated height
     fn decorated_h(&self) -> i32;
-    /// Set the window's minimum width, minimum height, max width and max height
+    /// Set the window's minimum width, minimum height, max width and max height, which constrain user resizing.
+    /// A max of 0 means the dimension is unbounded, and a window with different min and max sizes becomes resizable
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// win.size_range(300, 200, 800, 600);
+    /// ```
     fn size_range(&mut self, min_w: i32, min_h: i32, max_w: i32, max_h: i32);
     /// Set the hotspot widget of the window
     fn hotspot<W: WidgetExt>(&mut self, w: &W)
     where
         Self: Sized;
+    /// Sets an image which is tiled over the window's background, the window's children are drawn over it.
+    /// The ImageBackdrop flag is added to the window's alignment, its other alignment flags are kept
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// let texture = image::PngImage::load("texture.png").unwrap();
+    /// win.set_background_image(&texture);
+    /// ```
+    fn set_background_image<I: ImageExt + Clone>(&mut self, image: &I)
+    where
+        Self: Sized;
+    /// Asks the window manager to draw the user's attention to the window,
+    /// by flashing its taskbar entry on Windows, bouncing the dock icon on MacOS
+    /// or setting the urgency hint on X11.
+    /// Taskbar progress isn't exposed since FLTK's backends have no support for it
+    fn request_attention(&mut self);
+    /// Makes btn the default button of the window, which is triggered by the Enter key
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut dlg = window::Window::new(100, 100, 300, 100, "Confirm");
+    /// let mut ok = button::Button::new(110, 60, 80, 30, "Ok");
+    /// let mut cancel = button::Button::new(200, 60, 80, 30, "Cancel");
+    /// dlg.end();
+    /// dlg.set_default_button(&mut ok);
+    /// dlg.set_cancel_button(&mut cancel);
+    /// dlg.hotspot(&ok);
+    /// ```
+    fn set_default_button<B: ButtonExt>(&mut self, btn: &mut B)
+    where
+        Self: Sized;
+    /// Makes btn the cancel button of the window, which is triggered by the Escape key instead of closing the window
+    fn set_cancel_button<B: ButtonExt>(&mut self, btn: &mut B)
+    where
+        Self: Sized;
+    /// Shows the window as a modal dialog and runs a nested event loop until it's hidden.
+    /// Returns the value passed to set_modal_result, or None if the window was closed otherwise
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut dlg = window::Window::new(100, 100, 300, 100, "Save changes?");
+    /// let mut yes = button::Button::new(20, 60, 80, 30, "Yes");
+    /// let mut no = button::Button::new(110, 60, 80, 30, "No");
+    /// dlg.end();
+    /// let mut d = dlg.clone();
+    /// yes.set_callback(move || d.set_modal_result(1));
+    /// let mut d = dlg.clone();
+    /// no.set_callback(move || d.set_modal_result(0));
+    /// match dlg.show_modal() {
+    ///     Some(1) => println!("Saving"),
+    ///     Some(_) => println!("Discarding"),
+    ///     None => println!("Cancelled"),
+    /// }
+    /// ```
+    fn show_modal(&mut self) -> Option<i32>;
+    /// Sets the result returned by show_modal and hides the window, the result is ignored if the window isn't shown by show_modal
+    fn set_modal_result(&mut self, val: i32);
+    /// Saves the position, size, maximized and fullscreen states and the screen of the window in preferences, under a key
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// win.end();
+    /// let mut prefs = prefs::Preferences::open("mycompany", "myapp").unwrap();
+    /// win.restore_geometry(&prefs, "main");
+    /// win.show();
+    /// // before exiting
+    /// win.save_geometry(&mut prefs, "main");
+    /// prefs.save().unwrap();
+    /// ```
+    fn save_geometry(&self, prefs: &mut crate::prefs::Preferences, key: &str);
+    /// Restores the geometry saved under a key, moving and shrinking the window to fit in the current screens.
+    /// Returns false if no geometry was saved under the key
+    fn restore_geometry(&mut self, prefs: &crate::prefs::Preferences, key: &str) -> bool;
 }
 
 /// Defines the methods implemented by all input and output widgets
@@ -478,7 +784,7 @@ pub unsafe trait InputExt: WidgetExt {
     fn append(&mut self, txt: &str) -> Result<(), FltkError>;
     /// Copy the value within the widget
     fn copy(&mut self) -> Result<(), FltkError>;
-    /// Undo changes
+    /// Undo changes, using the undo history if enabled by set_undo_limit()
     fn undo(&mut self) -> Result<(), FltkError>;
     /// Cut the value within the widget
     fn cut(&mut self) -> Result<(), FltkError>;
@@ -502,6 +808,59 @@ pub unsafe trait InputExt: WidgetExt {
     fn wrap(&self) -> bool;
     /// Set whether text is wrapped inside an input/output widget
     fn set_wrap(&mut self, val: bool);
+    /// Aligns a byte index to the closest utf8 character boundary at or before it
+    fn utf8_align(&self, pos: u32) -> u32;
+    /// Returns the character index of the insertion position
+    fn char_position(&self) -> u32;
+    /// Sets the insertion position using a character index rather than a byte index
+    fn set_char_position(&mut self, idx: u32) -> Result<(), FltkError>;
+    /// Returns the character at the character index ```idx```
+    fn char_at(&self, idx: u32) -> Option<char>;
+    /// Inserts a &str at the character index ```idx```
+    fn insert_at_char(&mut self, idx: u32, txt: &str) -> Result<(), FltkError>;
+    /// Sets a validator which rejects typed, pasted or dropped text that would make the content invalid.
+    /// Since the content is checked after every keystroke, the validator should accept incomplete input.
+    /// Rejected edits are undone before the input is redrawn, and a callback triggered by CallbackTrigger::Changed only sees accepted edits.
+    /// Shares its handler with set_undo_limit(), and replaces any handler set using handle() or handle2()
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut port = input::Input::new(10, 10, 100, 30, "Port");
+    /// port.set_validator(input::int_range_validator(1, 65535));
+    /// let mut hex = input::Input::new(10, 50, 100, 30, "Color");
+    /// hex.set_validator(|s| s.len() <= 6 && s.chars().all(|c| c.is_ascii_hexdigit()));
+    /// ```
+    fn set_validator<F: FnMut(&str) -> bool + 'static>(&mut self, cb: F)
+    where
+        Self: Sized;
+    /// Enables a multi-level undo history keeping up to ```limit``` steps, used by undo(), redo() and the
+    /// Ctrl/Cmd+Z, Ctrl/Cmd+Shift+Z and Ctrl/Cmd+Y shortcuts. A limit of 0 restores FLTK's single-level undo.
+    /// Typed, pasted and dropped edits are recorded once accepted by the validator set using set_validator(), if any,
+    /// while programmatic edits are only recorded within an undo group. Undoing and redoing don't trigger the callback.
+    /// Shares its handler with set_validator(), and replaces any handler set using handle() or handle2()
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut inp = input::Input::new(10, 10, 200, 30, "");
+    /// inp.set_undo_limit(100);
+    /// inp.begin_undo_group();
+    /// inp.set_value("Hello");
+    /// inp.append(" world").unwrap();
+    /// inp.end_undo_group();
+    /// assert_eq!(inp.undo_depth(), 1);
+    /// inp.undo().unwrap();
+    /// inp.redo().unwrap();
+    /// ```
+    fn set_undo_limit(&mut self, limit: usize);
+    /// Redoes the last undone edit, requires set_undo_limit
+    fn redo(&mut self) -> Result<(), FltkError>;
+    /// Returns the number of edits which can be undone using the undo history
+    fn undo_depth(&self) -> usize;
+    /// Returns the number of edits which can be redone using the undo history
+    fn redo_depth(&self) -> usize;
+    /// Starts grouping edits, so that edits until the matching end_undo_group() are undone as one step.
+    /// Groups can be nested, requires set_undo_limit
+    fn begin_undo_group(&mut self);
+    /// Ends grouping edits started with begin_undo_group()
+    fn end_undo_group(&mut self);
 }
 
 /// Defines the methods implemented by all menu widgets
@@ -631,6 +990,16 @@ pub unsafe trait MenuExt: WidgetExt {
     fn mode(&self, idx: u32) -> crate::menu::MenuFlag;
     /// Get the mode of a menu item
     fn set_mode(&mut self, idx: u32, flag: crate::menu::MenuFlag);
+    /// Changes the shortcut of the menu item found by its path, e.g. "File/Open", failure returns FltkErrorKind::FailedOperation
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut menu = menu::MenuBar::new(0, 0, 400, 30, "");
+    /// menu.add("File/Open", Shortcut::Ctrl | 'o', menu::MenuFlag::Normal, || ());
+    /// menu.set_item_shortcut("File/Open", Shortcut::Ctrl | Shortcut::Shift | 'o').unwrap();
+    /// ```
+    fn set_item_shortcut(&mut self, path: &str, shortcut: Shortcut) -> Result<(), FltkError>;
+    /// Changes the flag (mode) of the menu item found by its path, e.g. "View/Wrap", failure returns FltkErrorKind::FailedOperation
+    fn set_item_flag(&mut self, path: &str, flag: crate::menu::MenuFlag) -> Result<(), FltkError>;
     /// End the menu
     fn end(&mut self);
     /// Set the down_box of the widget
@@ -792,6 +1161,31 @@ pub unsafe trait DisplayExt: WidgetExt {
     fn wrapped_column(&self, row: i32, column: i32) -> i32;
     /// Correct a row number from an unconstrained position
     fn wrapped_row(&self, row: i32) -> i32;
+    /// Returns the insert position closest to the widget coordinates x and y
+    fn xy_to_position(&self, x: i32, y: i32) -> u32;
+    /// Sets a callback run when the line number gutter is clicked, receiving the clicked line starting from 1.
+    /// Replaces any handler set using handle() or handle2()
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut ed = text::TextEditor::new(0, 0, 400, 300, "");
+    /// ed.set_buffer(Some(text::TextBuffer::default()));
+    /// ed.set_linenumber_width(40);
+    /// ed.set_linenumber_callback(|ed, line| {
+    ///     let has_breakpoint = ed.linenumber_markers().iter().any(|m| m.0 == line);
+    ///     let marker = if has_breakpoint { None } else { Some(('●', Color::Red)) };
+    ///     ed.set_linenumber_marker(line, marker);
+    /// });
+    /// ```
+    fn set_linenumber_callback<F: FnMut(&mut Self, u32) + 'static>(&mut self, cb: F)
+    where
+        Self: Sized;
+    /// Draws a marker glyph with the given color in the gutter of a line starting from 1, None removes it.
+    /// Markers stay on their line numbers while the text is edited, and are drawn using draw2()
+    fn set_linenumber_marker(&mut self, line: u32, marker: Option<(char, Color)>)
+    where
+        Self: Sized;
+    /// Returns the line number markers as (line, glyph, color)
+    fn linenumber_markers(&self) -> Vec<(u32, char, Color)>;
 }
 
 /// Defines the methods implemented by all browser types
@@ -896,6 +1290,54 @@ pub unsafe trait BrowserExt: WidgetExt {
     fn hscrollbar(&self) -> Box<dyn ValuatorExt>;
     /// Returns the selected line, returns 0 if no line is selected
     fn value(&self) -> u32;
+    /// Hides a line, which keeps its index
+    /// Lines start at 1
+    fn hide_line(&mut self, line: u32);
+    /// Shows a line hidden by hide_line() or a filter
+    /// Lines start at 1
+    fn show_line(&mut self, line: u32);
+    /// Returns whether a line isn't hidden
+    /// Lines start at 1
+    fn line_visible(&self, line: u32) -> bool;
+    /// Enables selecting items by typing the start (or any part) of their text, or disables it with `None`.
+    /// Keys typed in quick succession extend the searched text, and repeating a single key cycles through the matches.
+    /// Type-ahead is handled by a handler installed using handle2(), so enabling it replaces any handler set using handle() or handle2(),
+    /// and setting a handler afterwards disables it until set_type_ahead() is called again
+    fn set_type_ahead(&mut self, mode: Option<crate::browser::TypeAheadMode>);
+    /// Returns the type-ahead mode
+    fn type_ahead(&self) -> Option<crate::browser::TypeAheadMode>;
+    /// Hides the items for which the filter, called with their text stripped of format codes, returns false.
+    /// The filter is also applied to items added afterwards.
+    fn set_item_filter<F: FnMut(&str) -> bool + 'static>(&mut self, filter: F)
+    where
+        Self: Sized;
+    /// Removes the item filter and shows all items
+    fn clear_item_filter(&mut self);
+    /// Replaces the items with the records of CSV data, their fields separated by the delimiter
+    /// becoming columns separated by the column char. Line breaks and column chars within fields are replaced by spaces
+    fn load_csv<R: std::io::Read>(&mut self, reader: R, delimiter: char) -> Result<(), FltkError>
+    where
+        Self: Sized;
+    /// Replaces the items with the records of a CSV file, see load_csv()
+    fn load_csv_file<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+        delimiter: char,
+    ) -> Result<(), FltkError>
+    where
+        Self: Sized;
+    /// Writes the items as CSV data, their columns stripped of format codes becoming fields separated by the delimiter
+    fn save_csv<W: std::io::Write>(&self, writer: W, delimiter: char) -> Result<(), FltkError>
+    where
+        Self: Sized;
+    /// Writes the items to a CSV file, see save_csv()
+    fn save_csv_file<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        delimiter: char,
+    ) -> Result<(), FltkError>
+    where
+        Self: Sized;
 }
 
 /// Defines the methods implemented by table types
@@ -1005,8 +1447,6 @@ pub unsafe trait TableExt: GroupExt {
     ) -> Result<(), FltkError>;
     /// Moves the cursor
     fn move_cursor(&mut self, r: i32, c: i32) -> Result<(), FltkError>;
-    /// Resets the internal array of widget sizes and positions.
-    fn init_sizes(&mut self);
     /// Returns the scrollbar size
     fn scrollbar_size(&self) -> u32;
     /// Sets the scrollbar size
@@ -1040,6 +1480,14 @@ pub unsafe trait TableExt: GroupExt {
     fn callback_row(&self) -> i32;
     /// Get the callback context, should be called from within a callback
     fn callback_context(&self) -> crate::table::TableContext;
+    /// Returns the (x, y, width, height) of a cell or header in the given context,
+    /// or None if the row or column is out of range
+    fn find_cell(
+        &self,
+        ctx: crate::table::TableContext,
+        row: i32,
+        col: i32,
+    ) -> Option<(i32, i32, i32, i32)>;
 }
 
 /// Defines the methods implemented by all image types
@@ -1084,6 +1532,16 @@ pub unsafe trait ImageExt {
     fn ld(&self) -> u32;
     /// Greys the image
     fn inactive(&mut self);
+    /// Converts the image to grayscale
+    fn desaturate(&mut self);
+    /// Blends the image with a color, the weight is the fraction (0 to 1) of the original image that's kept
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut img = image::PngImage::load("icon.png").unwrap();
+    /// img.scale(24, 24, true, true);
+    /// img.color_average(Color::Red, 0.5);
+    /// ```
+    fn color_average(&mut self, color: Color, weight: f32);
     /// Transforms an image to a PngImage
     fn into_png(self) -> Result<crate::image::PngImage, FltkError>
     where
@@ -1094,6 +1552,25 @@ pub unsafe trait ImageExt {
         Self: Sized;
     /// Transforms an image to a BmpImage
     fn into_bmp(self) -> Result<crate::image::BmpImage, FltkError>
+    where
+        Self: Sized;
+    /// Writes the image to a file, in the format matching its extension: png, jpg, jpeg or bmp.
+    /// PNG, JPEG and BMP images are written in their own format when the extension is missing or unknown,
+    /// other images return an error. Images without pixels, such as SVG images which weren't drawn yet, pixmaps and bitmaps, return an error.
+    /// The transparent pixels are composited over white
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::Window::new(100, 100, 400, 300, "");
+    /// win.show();
+    /// let img = draw::capture_window(&mut win).unwrap();
+    /// img.write_to_file("screenshot.jpg").unwrap();
+    /// ```
+    fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), FltkError>
+    where
+        Self: Sized;
+    /// Encodes the image in a format in memory, the transparent pixels are composited over white.
+    /// Images without pixels, such as SVG images which weren't drawn yet, pixmaps and bitmaps, return an error
+    fn encode(&self, format: crate::image::ImageFormat) -> Result<Vec<u8>, FltkError>
     where
         Self: Sized;
     /// Deletes the image
@@ -0,0 +1,270 @@
+use crate::app;
+pub use crate::enums::*;
+use crate::prelude::*;
+use crate::utils::{escape, unescape};
+use crate::window::*;
+use fltk_sys::fl::*;
+use std::{
+    ffi::{CStr, CString},
+    fmt, fs,
+    os::raw,
+    panic, path,
+    str::FromStr,
+    sync::Mutex,
+    time::Instant,
+};
+
+lazy_static! {
+    /// The start time and events of the current recording
+    static ref RECORDING: Mutex<Option<(Instant, Vec<RecordedEvent>)>> = Mutex::new(None);
+}
+
+/// A single event captured by the recorder, along with the event state needed to replay it
+#[derive(Debug, Clone)]
+pub struct RecordedEvent {
+    /// Seconds elapsed since the recording started
+    pub time: f64,
+    /// The event type
+    pub event: Event,
+    /// The label of the window which received the event
+    pub window: String,
+    /// The x coordinate relative to the window
+    pub x: i32,
+    /// The y coordinate relative to the window
+    pub y: i32,
+    /// The x coordinate relative to the screen
+    pub x_root: i32,
+    /// The y coordinate relative to the screen
+    pub y_root: i32,
+    /// The horizontal scroll amount
+    pub dx: i32,
+    /// The vertical scroll amount
+    pub dy: i32,
+    /// The mouse button
+    pub button: i32,
+    /// The key
+    pub key: Key,
+    /// The modifier and mouse button state
+    pub state: Shortcut,
+    /// The number of clicks minus one
+    pub clicks: i32,
+    /// The text of keyboard events
+    pub text: String,
+}
+
+/// A recorded sequence of events which can be replayed, saved to and loaded from a file
+#[derive(Debug, Clone, Default)]
+pub struct EventScript {
+    /// The recorded events, ordered by time
+    pub events: Vec<RecordedEvent>,
+}
+
+fn recordable_event(ev: i32) -> Option<Event> {
+    [
+        Event::Push,
+        Event::Released,
+        Event::Drag,
+        Event::Move,
+        Event::MouseWheel,
+        Event::KeyDown,
+        Event::KeyUp,
+    ]
+    .iter()
+    .copied()
+    .find(|e| *e as i32 == ev)
+}
+
+unsafe extern "C" fn record_dispatch(ev: raw::c_int, win: *mut raw::c_void) -> raw::c_int {
+    let _ = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let event = match recordable_event(ev) {
+            Some(event) if !win.is_null() => event,
+            _ => return,
+        };
+        if let Some((start, events)) = &mut *RECORDING.lock().unwrap() {
+            // the window isn't wrapped, since that would allocate a tracker for every event
+            let label = fltk_sys::widget::Fl_Widget_label(win as *mut fltk_sys::widget::Fl_Widget);
+            let window = if label.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(label).to_string_lossy().to_string()
+            };
+            events.push(RecordedEvent {
+                time: start.elapsed().as_secs_f64(),
+                event,
+                window,
+                x: app::event_x(),
+                y: app::event_y(),
+                x_root: app::event_x_root(),
+                y_root: app::event_y_root(),
+                dx: app::event_dx(),
+                dy: app::event_dy(),
+                button: app::event_button(),
+                key: app::event_key(),
+                state: app::event_state(),
+                clicks: app::event_clicks_num(),
+                text: app::event_text(),
+            });
+        }
+    }));
+    Fl_handle_(ev, win)
+}
+
+/// Starts recording the mouse and keyboard events delivered to all windows,
+/// discarding any previous unfinished recording
+/// ```no_run
+/// use fltk::*;
+/// recorder::start();
+/// // interact with the app
+/// let script = recorder::stop();
+/// script.save("session.txt").unwrap();
+/// ```
+pub fn start() {
+    *RECORDING.lock().unwrap() = Some((Instant::now(), vec![]));
+    unsafe { Fl_event_dispatch(Some(record_dispatch)) }
+}
+
+/// Stops recording and returns the recorded events
+pub fn stop() -> EventScript {
+    unsafe { Fl_event_dispatch(None) }
+    let events = RECORDING
+        .lock()
+        .unwrap()
+        .take()
+        .map(|(_, events)| events)
+        .unwrap_or_default();
+    EventScript { events }
+}
+
+/// Returns whether events are currently being recorded
+pub fn is_recording() -> bool {
+    RECORDING.lock().unwrap().is_some()
+}
+
+impl RecordedEvent {
+    /// Sets the event state and sends the event to its window through app::handle.
+    /// The event is sent to the first window if no shown window has the recorded label.
+    /// Returns whether the event was handled
+    pub fn replay(&self) -> bool {
+        let win = app::windows_iter()
+            .find(|w| w.label() == self.window)
+            .or_else(|| app::windows_iter().next());
+        let win = match win {
+            Some(win) => win,
+            None => return false,
+        };
+        let text = CString::new(self.text.as_str()).unwrap_or_default();
+        unsafe {
+            Fl_set_event_x(self.x);
+            Fl_set_event_y(self.y);
+            Fl_set_event_x_root(self.x_root);
+            Fl_set_event_y_root(self.y_root);
+            Fl_set_event_dx(self.dx);
+            Fl_set_event_dy(self.dy);
+            Fl_set_event_button(self.button);
+            Fl_set_event_key(self.key.bits());
+            Fl_set_event_state(self.state.bits());
+            Fl_set_event_clicks(self.clicks);
+            Fl_set_event_text(text.as_ptr());
+            let ret = app::handle(self.event, &win);
+            Fl_set_event_text(b"\0".as_ptr() as _);
+            ret
+        }
+    }
+}
+
+impl EventScript {
+    /// Replays the events through the event loop, respecting the recorded timing
+    pub fn play(&self) {
+        let start = Instant::now();
+        for ev in &self.events {
+            loop {
+                let elapsed = start.elapsed().as_secs_f64();
+                if elapsed >= ev.time {
+                    break;
+                }
+                let _ = app::wait_for(ev.time - elapsed);
+            }
+            ev.replay();
+        }
+    }
+
+    /// Replays the events back-to-back, ignoring the recorded timing
+    pub fn play_immediate(&self) {
+        for ev in &self.events {
+            ev.replay();
+            let _ = app::wait_for(0.0);
+        }
+    }
+
+    /// Saves the script to a file
+    pub fn save<P: AsRef<path::Path>>(&self, path: P) -> Result<(), FltkError> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    /// Loads a script from a file
+    pub fn load<P: AsRef<path::Path>>(path: P) -> Result<EventScript, FltkError> {
+        fs::read_to_string(path)?.parse()
+    }
+}
+
+/// Each event is written on its own line as tab-separated fields
+impl fmt::Display for EventScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ev in &self.events {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                ev.time,
+                ev.event as i32,
+                escape(&ev.window),
+                ev.x,
+                ev.y,
+                ev.x_root,
+                ev.y_root,
+                ev.dx,
+                ev.dy,
+                ev.button,
+                ev.key.bits(),
+                ev.state.bits(),
+                ev.clicks,
+                escape(&ev.text),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EventScript {
+    type Err = FltkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut events = vec![];
+        for (n, line) in s.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+            let err = || FltkError::Unknown(format!("Invalid event script line {}", n + 1));
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != 14 {
+                return Err(err());
+            }
+            let int = |i: usize| fields[i].parse::<i32>().map_err(|_| err());
+            let event = recordable_event(int(1)?).ok_or_else(err)?;
+            events.push(RecordedEvent {
+                time: fields[0].parse().map_err(|_| err())?,
+                event,
+                window: unescape(fields[2]),
+                x: int(3)?,
+                y: int(4)?,
+                x_root: int(5)?,
+                y_root: int(6)?,
+                dx: int(7)?,
+                dy: int(8)?,
+                button: int(9)?,
+                key: Key::from_i32(int(10)?),
+                state: Shortcut::from_bits_truncate(int(11)?),
+                clicks: int(12)?,
+                text: unescape(fields[13]),
+            });
+        }
+        Ok(EventScript { events })
+    }
+}
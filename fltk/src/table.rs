@@ -1,11 +1,25 @@
+use crate::app;
+use crate::button::CheckButton;
+use crate::draw;
 use crate::image::Image;
+use crate::input::Input;
+use crate::menu::Choice;
 pub use crate::prelude::*;
+use crate::valuator::{Counter, CounterType};
 use crate::widget::Widget;
 use fltk_sys::table::*;
 use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::HashMap,
     ffi::{CStr, CString},
+    fmt, fs,
+    io::{Read, Write},
     mem,
+    ops::{Deref, DerefMut},
     os::raw,
+    path::Path,
+    rc::Rc,
 };
 
 /// Creates a table
@@ -112,3 +126,1048 @@ impl TableRow {
         unsafe { Fl_Table_Row_select_all_rows(self._inner, selection_flag as i32) }
     }
 }
+
+type SelectionCallback = Box<dyn FnMut(i32, i32)>;
+type SortCallback = Box<dyn FnMut(i32, bool)>;
+type EditCallback = Box<dyn FnMut(i32, i32, &str)>;
+
+/// Defines the in-place editor of a SmartTable column
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellEditor {
+    /// A text input, the default
+    Text,
+    /// A choice between options, the cell holding the chosen option
+    Choice(Vec<String>),
+    /// A check button, the cell holding "true" or "false"
+    Check,
+    /// A spinner with a minimum, a maximum and a step, the cell holding the number
+    Spinner(f64, f64, f64),
+}
+
+impl Default for CellEditor {
+    fn default() -> Self {
+        CellEditor::Text
+    }
+}
+
+/// Returns whether a cell of a check column is checked
+fn cell_checked(val: &str) -> bool {
+    matches!(
+        val.trim().to_ascii_lowercase().as_str(),
+        "true" | "1" | "yes" | "x"
+    )
+}
+
+/// Formats the value of a spinner, without decimals for integral steps
+fn spinner_text(val: f64, step: f64) -> String {
+    if step.fract() == 0.0 {
+        format!("{}", val.round() as i64)
+    } else {
+        let decimals = step.to_string().split('.').nth(1).map_or(0, |d| d.len());
+        format!("{:.*}", decimals, val)
+    }
+}
+
+/// The hidden widgets placed over the edited cell
+#[derive(Debug, Clone)]
+struct CellEditors {
+    inp: Input,
+    choice: Choice,
+    check: CheckButton,
+    spinner: Counter,
+}
+
+impl CellEditors {
+    fn new() -> CellEditors {
+        let mut inp = Input::default();
+        inp.set_trigger(CallbackTrigger::EnterKeyAlways);
+        let choice = Choice::default();
+        let mut check = CheckButton::default();
+        check.set_frame(FrameType::FlatBox);
+        check.set_color(Color::BackGround2);
+        let mut spinner = Counter::default();
+        spinner.set_type(CounterType::Simple);
+        let mut eds = CellEditors {
+            inp,
+            choice,
+            check,
+            spinner,
+        };
+        eds.hide();
+        eds
+    }
+
+    fn widget(&mut self, editor: &CellEditor) -> &mut dyn WidgetExt {
+        match editor {
+            CellEditor::Text => &mut self.inp,
+            CellEditor::Choice(_) => &mut self.choice,
+            CellEditor::Check => &mut self.check,
+            CellEditor::Spinner(..) => &mut self.spinner,
+        }
+    }
+
+    fn load(&mut self, editor: &CellEditor, val: &str) {
+        match editor {
+            CellEditor::Text => self.inp.set_value(val),
+            CellEditor::Choice(options) => {
+                self.choice.clear();
+                for opt in options {
+                    self.choice.add_choice(opt);
+                }
+                let idx = options
+                    .iter()
+                    .position(|o| o == val)
+                    .map_or(-1, |i| i as i32);
+                self.choice.set_value(idx);
+            }
+            CellEditor::Check => self.check.set_checked(cell_checked(val)),
+            CellEditor::Spinner(min, max, step) => {
+                self.spinner.set_bounds(*min, *max);
+                self.spinner.set_step(*step, 1);
+                let v = val.trim().parse::<f64>().unwrap_or(*min);
+                self.spinner.set_value(v.max(*min).min(*max));
+            }
+        }
+    }
+
+    fn value(&self, editor: &CellEditor) -> Option<String> {
+        match editor {
+            CellEditor::Text => Some(self.inp.value()),
+            CellEditor::Choice(options) => options.get(self.choice.value() as usize).cloned(),
+            CellEditor::Check => Some(self.check.is_checked().to_string()),
+            CellEditor::Spinner(_, _, step) => Some(spinner_text(self.spinner.value(), *step)),
+        }
+    }
+
+    /// Returns whether one of the editors has the keyboard focus
+    fn has_focus(&self) -> bool {
+        match app::focus() {
+            Some(f) => unsafe {
+                [
+                    self.inp.as_widget_ptr(),
+                    self.choice.as_widget_ptr(),
+                    self.check.as_widget_ptr(),
+                    self.spinner.as_widget_ptr(),
+                ]
+                .contains(&f.as_widget_ptr())
+            },
+            None => false,
+        }
+    }
+
+    fn hide(&mut self) {
+        self.inp.hide();
+        self.choice.hide();
+        self.check.hide();
+        self.spinner.hide();
+    }
+}
+
+#[derive(Default)]
+struct SmartTableState {
+    data: Vec<Vec<String>>,
+    row_headers: Vec<String>,
+    col_headers: Vec<String>,
+    editable: bool,
+    editors: HashMap<i32, CellEditor>,
+    current: Option<(i32, i32)>,
+    editing: Option<(i32, i32)>,
+    sort: Option<(i32, bool)>,
+    on_select: Option<SelectionCallback>,
+    on_sort: Option<SortCallback>,
+    on_edit: Option<EditCallback>,
+}
+
+impl fmt::Debug for SmartTableState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SmartTableState")
+            .field("data", &self.data)
+            .field("row_headers", &self.row_headers)
+            .field("col_headers", &self.col_headers)
+            .field("editable", &self.editable)
+            .field("editors", &self.editors)
+            .field("current", &self.current)
+            .field("editing", &self.editing)
+            .field("sort", &self.sort)
+            .finish()
+    }
+}
+
+/// Compares cells numerically when both parse as numbers, otherwise as strings
+fn cmp_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+/// Defines a table (custom widget) which stores its cells as strings and draws them itself.
+/// Clicking a column header sorts the rows by that column, toggling the order on subsequent clicks.
+/// Double-clicking a cell of an editable table, or pressing F2, opens the column's editor over it,
+/// committed with the Enter key or by leaving it and cancelled with the Escape key
+/// ```no_run
+/// use fltk::*;
+/// let mut table = table::SmartTable::new(5, 5, 400, 300, "");
+/// table.set_data(vec![
+///     vec!["Bob".to_string(), "32".to_string()],
+///     vec!["Alice".to_string(), "27".to_string()],
+/// ]);
+/// table.set_col_header_value(0, "Name");
+/// table.set_col_header_value(1, "Age");
+/// table.set_editable(true);
+/// table.set_col_editor(1, table::CellEditor::Spinner(0., 150., 1.));
+/// table.set_edit_callback(|row, col, val| println!("{} {} {}", row, col, val));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SmartTable {
+    table: TableRow,
+    editors: CellEditors,
+    state: Rc<RefCell<SmartTableState>>,
+}
+
+impl SmartTable {
+    /// Creates a new smart table with row and column headers and single row selection
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> SmartTable {
+        let mut table = TableRow::new(x, y, w, h, label);
+        let mut editors = CellEditors::new();
+        table.end();
+        table.set_type(TableRowSelectMode::Single);
+        table.set_row_header(true);
+        table.set_col_header(true);
+        table.set_col_resize(true);
+        let state = Rc::new(RefCell::new(SmartTableState::default()));
+
+        let st = state.clone();
+        table.draw_cell2(move |t, ctx, row, col, x, y, w, h| match ctx {
+            TableContext::StartPage => draw::set_font(Font::Helvetica, 14),
+            TableContext::ColHeader => {
+                let st = st.borrow();
+                let txt = match st.col_headers.get(col as usize) {
+                    Some(txt) if !txt.is_empty() => txt.clone(),
+                    _ if col < 26 => ((b'A' + col as u8) as char).to_string(),
+                    _ => (col + 1).to_string(),
+                };
+                SmartTable::draw_header(&txt, x, y, w, h);
+                if let Some((sorted, ascending)) = st.sort {
+                    if sorted == col {
+                        let (cx, cy) = (x + w - 10, y + h / 2);
+                        draw::set_draw_color(Color::Dark3);
+                        if ascending {
+                            draw::draw_polygon(cx - 4, cy + 2, cx + 4, cy + 2, cx, cy - 3);
+                        } else {
+                            draw::draw_polygon(cx - 4, cy - 2, cx + 4, cy - 2, cx, cy + 3);
+                        }
+                    }
+                }
+            }
+            TableContext::RowHeader => {
+                let st = st.borrow();
+                let txt = match st.row_headers.get(row as usize) {
+                    Some(txt) if !txt.is_empty() => txt.clone(),
+                    _ => (row + 1).to_string(),
+                };
+                SmartTable::draw_header(&txt, x, y, w, h);
+            }
+            TableContext::Cell => {
+                let st = st.borrow();
+                let txt = st
+                    .data
+                    .get(row as usize)
+                    .and_then(|r| r.get(col as usize))
+                    .map(|c| c.as_str())
+                    .unwrap_or("");
+                let selected = t.row_selected(row);
+                draw::push_clip(x, y, w, h);
+                draw::set_draw_color(if selected {
+                    t.selection_color()
+                } else {
+                    Color::BackGround2
+                });
+                draw::draw_rectf(x, y, w, h);
+                if st.editors.get(&col) == Some(&CellEditor::Check) {
+                    let (bx, by) = (x + 4, y + (h - 14) / 2);
+                    draw::draw_box(FrameType::DownBox, bx, by, 14, 14, Color::BackGround2);
+                    if cell_checked(txt) {
+                        draw::set_draw_color(Color::ForeGround);
+                        draw::set_line_style(draw::LineStyle::Solid, 2);
+                        draw::draw_line(bx + 3, by + 7, bx + 6, by + 10);
+                        draw::draw_line(bx + 6, by + 10, bx + 11, by + 4);
+                        draw::set_line_style(draw::LineStyle::Solid, 0);
+                    }
+                } else {
+                    draw::set_draw_color(if selected {
+                        Color::BackGround2
+                    } else {
+                        Color::ForeGround
+                    });
+                    draw::draw_text2(txt, x + 4, y, w - 8, h, Align::Left);
+                }
+                draw::set_draw_color(Color::Light2);
+                draw::draw_rect(x, y, w, h);
+                draw::pop_clip();
+            }
+            _ => (),
+        });
+
+        let st = state.clone();
+        let mut eds = editors.clone();
+        table.set_callback2(move |t| {
+            let (ctx, row, col) = (t.callback_context(), t.callback_row(), t.callback_col());
+            match (ctx, app::event()) {
+                (TableContext::ColHeader, Event::Released) if app::event_button() == 1 => {
+                    SmartTable::commit_edit(t, &mut eds, &st);
+                    let ascending = match st.borrow().sort {
+                        Some((c, asc)) if c == col => !asc,
+                        _ => true,
+                    };
+                    SmartTable::sort_rows(t, &st, col, ascending);
+                }
+                (TableContext::Cell, Event::Push) => {
+                    SmartTable::commit_edit(t, &mut eds, &st);
+                    st.borrow_mut().current = Some((row, col));
+                    if app::event_clicks() && st.borrow().editable {
+                        SmartTable::start_edit(t, &mut eds, &st, row, col);
+                    }
+                    let cb = st.borrow_mut().on_select.take();
+                    if let Some(mut cb) = cb {
+                        cb(row, col);
+                        st.borrow_mut().on_select = Some(cb);
+                    }
+                }
+                _ => (),
+            }
+        });
+
+        let st = state.clone();
+        let mut eds = editors.clone();
+        table.handle2(move |t, ev| {
+            // the keyboard moves the cursor without a callback, so it is picked up once the
+            // table has handled the key
+            if ev == Event::KeyUp {
+                SmartTable::sync_current(t, &st);
+            }
+            if ev != Event::KeyDown || app::event_key() != Key::F2 || !st.borrow().editable {
+                return false;
+            }
+            SmartTable::sync_current(t, &st);
+            let current = st.borrow().current;
+            match current {
+                Some((row, col)) => {
+                    SmartTable::start_edit(t, &mut eds, &st, row, col);
+                    true
+                }
+                None => false,
+            }
+        });
+
+        // the input commits through its callback, the choice and the check button once changed,
+        // and the spinner with the Enter key
+        let (st, mut t, mut eds) = (state.clone(), table.clone(), editors.clone());
+        editors
+            .inp
+            .set_callback(move || SmartTable::commit_edit(&mut t, &mut eds, &st));
+        let (st, mut t, mut eds) = (state.clone(), table.clone(), editors.clone());
+        editors
+            .choice
+            .set_callback(move || SmartTable::commit_edit(&mut t, &mut eds, &st));
+        let (st, mut t, mut eds) = (state.clone(), table.clone(), editors.clone());
+        editors
+            .check
+            .set_callback(move || SmartTable::commit_edit(&mut t, &mut eds, &st));
+        SmartTable::handle_editor(&mut editors.inp, &table, &editors, &state, false);
+        SmartTable::handle_editor(&mut editors.choice, &table, &editors, &state, true);
+        SmartTable::handle_editor(&mut editors.check, &table, &editors, &state, true);
+        SmartTable::handle_editor(&mut editors.spinner, &table, &editors, &state, true);
+
+        SmartTable {
+            table,
+            editors,
+            state,
+        }
+    }
+
+    /// Sets the current cell to the table's cursor cell and runs the selection callback if it moved
+    fn sync_current(table: &TableRow, state: &Rc<RefCell<SmartTableState>>) {
+        let (mut top, mut left, mut bot, mut right) = (-1, -1, -1, -1);
+        table.get_selection(&mut top, &mut left, &mut bot, &mut right);
+        if top < 0 || left < 0 || top != bot || left != right {
+            return;
+        }
+        if state.borrow().current == Some((top, left)) {
+            return;
+        }
+        state.borrow_mut().current = Some((top, left));
+        let cb = state.borrow_mut().on_select.take();
+        if let Some(mut cb) = cb {
+            cb(top, left);
+            state.borrow_mut().on_select = Some(cb);
+        }
+    }
+
+    /// Makes an editor commit when it loses the focus and cancel with the Escape key
+    fn handle_editor<W: WidgetBase>(
+        wid: &mut W,
+        table: &TableRow,
+        editors: &CellEditors,
+        state: &Rc<RefCell<SmartTableState>>,
+        enter_commits: bool,
+    ) {
+        let (st, mut t, mut eds) = (state.clone(), table.clone(), editors.clone());
+        wid.handle(move |ev| match ev {
+            Event::KeyDown if app::event_key() == Key::Escape => {
+                SmartTable::discard_edit(&mut t, &mut eds, &st);
+                true
+            }
+            Event::KeyDown
+                if enter_commits
+                    && (app::event_key() == Key::Enter || app::event_key() == Key::KPEnter) =>
+            {
+                SmartTable::commit_edit(&mut t, &mut eds, &st);
+                true
+            }
+            Event::Unfocus => {
+                SmartTable::commit_edit(&mut t, &mut eds, &st);
+                false
+            }
+            _ => false,
+        });
+    }
+
+    fn draw_header(txt: &str, x: i32, y: i32, w: i32, h: i32) {
+        draw::push_clip(x, y, w, h);
+        draw::draw_box(FrameType::ThinUpBox, x, y, w, h, Color::FrameDefault);
+        draw::set_draw_color(Color::ForeGround);
+        draw::draw_text2(txt, x, y, w, h, Align::Center);
+        draw::pop_clip();
+    }
+
+    fn start_edit(
+        table: &mut TableRow,
+        editors: &mut CellEditors,
+        state: &Rc<RefCell<SmartTableState>>,
+        row: i32,
+        col: i32,
+    ) {
+        if let Some((x, y, w, h)) = table.find_cell(TableContext::Cell, row, col) {
+            let (val, editor) = {
+                let st = state.borrow();
+                let val = st
+                    .data
+                    .get(row as usize)
+                    .and_then(|r| r.get(col as usize))
+                    .cloned()
+                    .unwrap_or_default();
+                (val, st.editors.get(&col).cloned().unwrap_or_default())
+            };
+            state.borrow_mut().editing = Some((row, col));
+            editors.load(&editor, &val);
+            let wid = editors.widget(&editor);
+            wid.resize(x, y, w, h);
+            wid.show();
+            let _ = wid.take_focus();
+            table.redraw();
+        }
+    }
+
+    fn commit_edit(
+        table: &mut TableRow,
+        editors: &mut CellEditors,
+        state: &Rc<RefCell<SmartTableState>>,
+    ) {
+        let editing = state.borrow_mut().editing.take();
+        if let Some((row, col)) = editing {
+            let editor = state
+                .borrow()
+                .editors
+                .get(&col)
+                .cloned()
+                .unwrap_or_default();
+            // taking the edited cell first makes the unfocus caused by hiding the editor a no-op,
+            // and the focus only goes back to the table if the editor wasn't left for another widget
+            let refocus = editors.has_focus();
+            editors.hide();
+            if let Some(val) = editors.value(&editor) {
+                if let Some(cell) = state
+                    .borrow_mut()
+                    .data
+                    .get_mut(row as usize)
+                    .and_then(|r| r.get_mut(col as usize))
+                {
+                    *cell = val.clone();
+                }
+                let cb = state.borrow_mut().on_edit.take();
+                if let Some(mut cb) = cb {
+                    cb(row, col, &val);
+                    state.borrow_mut().on_edit = Some(cb);
+                }
+            }
+            if refocus {
+                let _ = table.take_focus();
+            }
+            table.redraw();
+        }
+    }
+
+    fn discard_edit(
+        table: &mut TableRow,
+        editors: &mut CellEditors,
+        state: &Rc<RefCell<SmartTableState>>,
+    ) {
+        if state.borrow_mut().editing.take().is_some() {
+            let refocus = editors.has_focus();
+            editors.hide();
+            if refocus {
+                let _ = table.take_focus();
+            }
+            table.redraw();
+        }
+    }
+
+    fn sort_rows(
+        table: &mut TableRow,
+        state: &Rc<RefCell<SmartTableState>>,
+        col: i32,
+        ascending: bool,
+    ) {
+        {
+            let mut st = state.borrow_mut();
+            let headers = mem::take(&mut st.row_headers);
+            let mut rows: Vec<(Vec<String>, Option<String>)> = st
+                .data
+                .drain(..)
+                .enumerate()
+                .map(|(i, r)| (r, headers.get(i).cloned()))
+                .collect();
+            rows.sort_by(|(a, _), (b, _)| {
+                let a = a.get(col as usize).map(|c| c.as_str()).unwrap_or("");
+                let b = b.get(col as usize).map(|c| c.as_str()).unwrap_or("");
+                if ascending {
+                    cmp_cells(a, b)
+                } else {
+                    cmp_cells(b, a)
+                }
+            });
+            let has_headers = !headers.is_empty();
+            for (r, hdr) in rows {
+                st.data.push(r);
+                if has_headers {
+                    st.row_headers.push(hdr.unwrap_or_default());
+                }
+            }
+            st.sort = Some((col, ascending));
+        }
+        let cb = state.borrow_mut().on_sort.take();
+        if let Some(mut cb) = cb {
+            cb(col, ascending);
+            state.borrow_mut().on_sort = Some(cb);
+        }
+        table.redraw();
+    }
+
+    /// Replaces the table's cells, resizing the table to the number of rows and the longest row
+    pub fn set_data(&mut self, data: Vec<Vec<String>>) {
+        let cols = data.iter().map(|r| r.len()).max().unwrap_or(0);
+        let rows = data.len();
+        {
+            let mut st = self.state.borrow_mut();
+            st.data = data;
+            for r in st.data.iter_mut() {
+                r.resize(cols, String::new());
+            }
+            st.sort = None;
+        }
+        self.table.set_rows(rows as u32);
+        self.table.set_cols(cols as u32);
+        self.table.redraw();
+    }
+
+    /// Returns a copy of the table's cells
+    pub fn data(&self) -> Vec<Vec<String>> {
+        self.state.borrow().data.clone()
+    }
+
+    /// Sets the number of rows and columns, keeping the existing cells which still fit
+    pub fn set_size(&mut self, rows: u32, cols: u32) {
+        {
+            let mut st = self.state.borrow_mut();
+            st.data.resize(rows as usize, vec![]);
+            for r in st.data.iter_mut() {
+                r.resize(cols as usize, String::new());
+            }
+        }
+        self.table.set_rows(rows);
+        self.table.set_cols(cols);
+        self.table.redraw();
+    }
+
+    /// Gets the value of a cell
+    pub fn cell_value(&self, row: i32, col: i32) -> Option<String> {
+        self.state
+            .borrow()
+            .data
+            .get(row as usize)
+            .and_then(|r| r.get(col as usize))
+            .cloned()
+    }
+
+    /// Sets the value of a cell, failing if the cell is out of range
+    pub fn set_cell_value(&mut self, row: i32, col: i32, val: &str) -> Result<(), FltkError> {
+        match self
+            .state
+            .borrow_mut()
+            .data
+            .get_mut(row as usize)
+            .and_then(|r| r.get_mut(col as usize))
+        {
+            Some(cell) => *cell = val.to_string(),
+            None => return Err(FltkError::Internal(FltkErrorKind::TableError)),
+        }
+        self.table.redraw();
+        Ok(())
+    }
+
+    /// Appends a row, which is padded or truncated to the number of columns
+    pub fn append_row(&mut self, mut row: Vec<String>) {
+        let rows = {
+            let mut st = self.state.borrow_mut();
+            row.resize(self.table.cols() as usize, String::new());
+            st.data.push(row);
+            st.data.len()
+        };
+        self.table.set_rows(rows as u32);
+        self.table.redraw();
+    }
+
+    /// Removes a row, failing if the row is out of range
+    pub fn remove_row(&mut self, row: i32) -> Result<(), FltkError> {
+        let rows = {
+            let mut st = self.state.borrow_mut();
+            if row < 0 || row as usize >= st.data.len() {
+                return Err(FltkError::Internal(FltkErrorKind::TableError));
+            }
+            st.data.remove(row as usize);
+            if (row as usize) < st.row_headers.len() {
+                st.row_headers.remove(row as usize);
+            }
+            st.data.len()
+        };
+        self.table.set_rows(rows as u32);
+        self.table.redraw();
+        Ok(())
+    }
+
+    /// Replaces the table's cells with CSV data whose fields are separated by the delimiter,
+    /// the first record being the column headers if `headers` is true
+    pub fn load_csv<R: Read>(
+        &mut self,
+        mut reader: R,
+        delimiter: char,
+        headers: bool,
+    ) -> Result<(), FltkError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        SmartTable::commit_edit(&mut self.table, &mut self.editors, &self.state);
+        let mut records = crate::utils::parse_csv(&text, delimiter);
+        if headers && !records.is_empty() {
+            self.state.borrow_mut().col_headers = records.remove(0);
+        }
+        self.set_data(records);
+        Ok(())
+    }
+
+    /// Replaces the table's cells with the contents of a CSV file, see load_csv()
+    pub fn load_csv_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        delimiter: char,
+        headers: bool,
+    ) -> Result<(), FltkError> {
+        if !path.as_ref().exists() {
+            return Err(FltkError::Internal(FltkErrorKind::ResourceNotFound));
+        }
+        self.load_csv(fs::File::open(path)?, delimiter, headers)
+    }
+
+    /// Writes the table's cells as CSV data whose fields are separated by the delimiter,
+    /// preceded by the column headers if `headers` is true
+    pub fn save_csv<W: Write>(
+        &self,
+        mut writer: W,
+        delimiter: char,
+        headers: bool,
+    ) -> Result<(), FltkError> {
+        let st = self.state.borrow();
+        let mut text = String::new();
+        if headers {
+            let cols = self.table.cols() as usize;
+            let mut hdrs = st.col_headers.clone();
+            hdrs.resize(cols, String::new());
+            text.push_str(&crate::utils::write_csv(&[hdrs], delimiter));
+        }
+        text.push_str(&crate::utils::write_csv(&st.data, delimiter));
+        writer.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes the table's cells to a CSV file, see save_csv()
+    pub fn save_csv_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        delimiter: char,
+        headers: bool,
+    ) -> Result<(), FltkError> {
+        self.save_csv(fs::File::create(path)?, delimiter, headers)
+    }
+
+    /// Sets the label of a row header, by default rows are numbered
+    pub fn set_row_header_value(&mut self, row: i32, val: &str) {
+        assert!(row >= 0);
+        {
+            let mut st = self.state.borrow_mut();
+            if st.row_headers.len() <= row as usize {
+                st.row_headers.resize(row as usize + 1, String::new());
+            }
+            st.row_headers[row as usize] = val.to_string();
+        }
+        self.table.redraw();
+    }
+
+    /// Sets the label of a column header, by default columns are lettered
+    pub fn set_col_header_value(&mut self, col: i32, val: &str) {
+        assert!(col >= 0);
+        {
+            let mut st = self.state.borrow_mut();
+            if st.col_headers.len() <= col as usize {
+                st.col_headers.resize(col as usize + 1, String::new());
+            }
+            st.col_headers[col as usize] = val.to_string();
+        }
+        self.table.redraw();
+    }
+
+    /// Returns whether cells can be edited by double-clicking them or pressing F2
+    pub fn editable(&self) -> bool {
+        self.state.borrow().editable
+    }
+
+    /// Sets whether cells can be edited by double-clicking them or pressing F2
+    pub fn set_editable(&mut self, flag: bool) {
+        self.state.borrow_mut().editable = flag;
+    }
+
+    /// Opens the editor of the column over a cell, as a double-click would
+    pub fn edit_cell(&mut self, row: i32, col: i32) {
+        SmartTable::commit_edit(&mut self.table, &mut self.editors, &self.state);
+        SmartTable::start_edit(&mut self.table, &mut self.editors, &self.state, row, col);
+    }
+
+    /// Closes the open editor, if any, leaving its cell unchanged
+    pub fn cancel_edit(&mut self) {
+        SmartTable::discard_edit(&mut self.table, &mut self.editors, &self.state);
+    }
+
+    /// Returns the row and column of the cell being edited
+    pub fn editing_cell(&self) -> Option<(i32, i32)> {
+        self.state.borrow().editing
+    }
+
+    /// Sets the editor of a column's cells, a text input by default.
+    /// Options of a choice editor are menu labels, where '/', '&' and '_' are special characters
+    pub fn set_col_editor(&mut self, col: i32, editor: CellEditor) {
+        assert!(col >= 0);
+        SmartTable::commit_edit(&mut self.table, &mut self.editors, &self.state);
+        self.state.borrow_mut().editors.insert(col, editor);
+        self.table.redraw();
+    }
+
+    /// Gets the editor of a column's cells
+    pub fn col_editor(&self, col: i32) -> CellEditor {
+        self.state
+            .borrow()
+            .editors
+            .get(&col)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Sorts the rows by a column, also showing the sort indicator in the column header
+    pub fn sort_by_col(&mut self, col: i32, ascending: bool) {
+        SmartTable::commit_edit(&mut self.table, &mut self.editors, &self.state);
+        SmartTable::sort_rows(&mut self.table, &self.state, col, ascending);
+    }
+
+    /// Returns the column the rows are sorted by and whether the order is ascending
+    pub fn sort_col(&self) -> Option<(i32, bool)> {
+        self.state.borrow().sort
+    }
+
+    /// Sets the callback called with the row and column of a clicked cell
+    pub fn set_selection_callback<F: FnMut(i32, i32) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_select = Some(Box::new(cb));
+    }
+
+    /// Sets the callback called with the column and order after the rows are sorted
+    pub fn set_sort_callback<F: FnMut(i32, bool) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_sort = Some(Box::new(cb));
+    }
+
+    /// Sets the callback called with the row, column and new value after a cell is edited
+    pub fn set_edit_callback<F: FnMut(i32, i32, &str) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_edit = Some(Box::new(cb));
+    }
+}
+
+impl Deref for SmartTable {
+    type Target = TableRow;
+
+    fn deref(&self) -> &Self::Target {
+        &self.table
+    }
+}
+
+impl DerefMut for SmartTable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.table
+    }
+}
+
+type ItemTextCallback = Box<dyn FnMut(usize) -> String>;
+type ItemSelectCallback = Box<dyn FnMut(usize)>;
+
+#[derive(Default)]
+struct VirtualBrowserState {
+    item_text: Option<ItemTextCallback>,
+    on_select: Option<ItemSelectCallback>,
+    selected: Option<usize>,
+}
+
+impl fmt::Debug for VirtualBrowserState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VirtualBrowserState")
+            .field("selected", &self.selected)
+            .finish()
+    }
+}
+
+/// Defines a virtual browser (custom widget) which doesn't store its items.
+/// The text of an item is requested from the `item_text` callback only when its row is drawn,
+/// so listing millions of rows costs neither startup time nor memory for the items
+/// ```no_run
+/// use fltk::*;
+/// let mut vb = table::VirtualBrowser::new(5, 5, 400, 300, "");
+/// vb.set_item_text(|idx| format!("Item {}", idx));
+/// vb.set_size(1_000_000);
+/// vb.set_selection_callback(|idx| println!("{}", idx));
+/// ```
+#[derive(Debug, Clone)]
+pub struct VirtualBrowser {
+    table: TableRow,
+    state: Rc<RefCell<VirtualBrowserState>>,
+}
+
+impl VirtualBrowser {
+    /// Creates a new empty virtual browser
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> VirtualBrowser {
+        let mut table = TableRow::new(x, y, w, h, label);
+        table.end();
+        table.set_type(TableRowSelectMode::None);
+        table.set_row_header(false);
+        table.set_col_header(false);
+        table.set_cols(1);
+        table.set_row_height_all(20);
+        let state = Rc::new(RefCell::new(VirtualBrowserState::default()));
+
+        let st = state.clone();
+        table.draw_cell2(move |t, ctx, row, _col, x, y, w, h| match ctx {
+            TableContext::StartPage => draw::set_font(Font::Helvetica, 14),
+            TableContext::Cell => {
+                let (cb, selected) = {
+                    let mut st = st.borrow_mut();
+                    (st.item_text.take(), st.selected == Some(row as usize))
+                };
+                let txt = match cb {
+                    Some(mut cb) => {
+                        let txt = cb(row as usize);
+                        st.borrow_mut().item_text = Some(cb);
+                        txt
+                    }
+                    None => String::new(),
+                };
+                draw::push_clip(x, y, w, h);
+                draw::set_draw_color(if selected {
+                    t.selection_color()
+                } else {
+                    Color::BackGround2
+                });
+                draw::draw_rectf(x, y, w, h);
+                draw::set_draw_color(if selected {
+                    Color::BackGround2
+                } else {
+                    Color::ForeGround
+                });
+                draw::draw_text2(&txt, x + 4, y, w - 8, h, Align::Left);
+                draw::pop_clip();
+            }
+            _ => (),
+        });
+
+        // keeps the single column as wide as the visible area
+        table.draw2(|t| {
+            let mut inner = t.width() - 4;
+            if t.rows() as i64 * t.row_height(0) as i64 > t.height() as i64 - 4 {
+                inner -= match t.scrollbar_size() {
+                    0 => app::scrollbar_size() as i32,
+                    s => s as i32,
+                };
+            }
+            if inner > 0 && t.col_width(0) != inner {
+                t.set_col_width(0, inner);
+                t.redraw();
+            }
+        });
+
+        let st = state.clone();
+        table.set_callback2(move |t| {
+            if t.callback_context() == TableContext::Cell && app::event() == Event::Push {
+                VirtualBrowser::select_item(t, &st, Some(t.callback_row() as usize), true);
+            }
+        });
+
+        let st = state.clone();
+        table.handle2(move |t, ev| {
+            if ev != Event::KeyDown || t.rows() == 0 {
+                return false;
+            }
+            let last = t.rows() as usize - 1;
+            let page = (t.height() / t.row_height(0).max(1)).max(1) as usize;
+            let current = st.borrow().selected;
+            let next = match (app::event_key(), current) {
+                (Key::Up, Some(i)) => i.saturating_sub(1),
+                (Key::Down, Some(i)) => (i + 1).min(last),
+                (Key::Up, None) | (Key::Down, None) | (Key::Home, _) => 0,
+                (Key::End, _) => last,
+                (Key::PageUp, i) => i.unwrap_or(0).saturating_sub(page),
+                (Key::PageDown, i) => (i.unwrap_or(0) + page).min(last),
+                _ => return false,
+            };
+            VirtualBrowser::select_item(t, &st, Some(next), true);
+            VirtualBrowser::scroll_to(t, next);
+            true
+        });
+
+        VirtualBrowser { table, state }
+    }
+
+    fn select_item(
+        table: &mut TableRow,
+        state: &Rc<RefCell<VirtualBrowserState>>,
+        idx: Option<usize>,
+        notify: bool,
+    ) {
+        let idx = idx.filter(|i| *i < table.rows() as usize);
+        state.borrow_mut().selected = idx;
+        table.redraw();
+        if let (Some(idx), true) = (idx, notify) {
+            let cb = state.borrow_mut().on_select.take();
+            if let Some(mut cb) = cb {
+                cb(idx);
+                state.borrow_mut().on_select = Some(cb);
+            }
+        }
+    }
+
+    fn scroll_to(table: &mut TableRow, idx: usize) {
+        let (mut r1, mut r2, mut c1, mut c2) = (0, 0, 0, 0);
+        table.visible_cells(&mut r1, &mut r2, &mut c1, &mut c2);
+        let idx = idx as i32;
+        if idx < r1 {
+            table.set_row_position(idx);
+        } else if idx >= r2 {
+            table.set_row_position((idx - (r2 - r1) + 1).max(0));
+        }
+    }
+
+    /// Sets the callback returning the text of an item, called only for the rows being drawn
+    pub fn set_item_text<F: FnMut(usize) -> String + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().item_text = Some(Box::new(cb));
+        self.table.redraw();
+    }
+
+    /// Sets the number of items, clearing the selection if it's no longer in range
+    pub fn set_size(&mut self, count: usize) {
+        assert!(count <= i32::MAX as usize);
+        self.table.set_rows(count as u32);
+        let selected = self.state.borrow().selected;
+        if selected.map(|i| i >= count).unwrap_or(false) {
+            self.state.borrow_mut().selected = None;
+        }
+        self.table.redraw();
+    }
+
+    /// Returns the number of items
+    pub fn size(&self) -> usize {
+        self.table.rows() as usize
+    }
+
+    /// Returns the index of the selected item
+    pub fn value(&self) -> Option<usize> {
+        self.state.borrow().selected
+    }
+
+    /// Selects an item, or clears the selection with `None`, without calling the selection callback
+    pub fn set_value(&mut self, idx: Option<usize>) {
+        VirtualBrowser::select_item(&mut self.table, &self.state, idx, false);
+    }
+
+    /// Scrolls the browser, if needed, so that an item is visible
+    pub fn show_item(&mut self, idx: usize) {
+        if idx < self.size() {
+            VirtualBrowser::scroll_to(&mut self.table, idx);
+        }
+    }
+
+    /// Sets the height of the rows
+    pub fn set_item_height(&mut self, height: i32) {
+        self.table.set_row_height_all(height);
+        self.table.redraw();
+    }
+
+    /// Redraws the visible items, requesting their text again after the underlying data changed
+    pub fn refresh(&mut self) {
+        self.table.redraw();
+    }
+
+    /// Sets the callback called with the index of an item selected by a click or the keyboard
+    pub fn set_selection_callback<F: FnMut(usize) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_select = Some(Box::new(cb));
+    }
+}
+
+impl Deref for VirtualBrowser {
+    type Target = TableRow;
+
+    fn deref(&self) -> &Self::Target {
+        &self.table
+    }
+}
+
+impl DerefMut for VirtualBrowser {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.table
+    }
+}
+
+#[cfg(test)]
+mod table {
+    use super::*;
+    #[test]
+    fn editor_values() {
+        assert!(cell_checked("true"));
+        assert!(cell_checked(" Yes "));
+        assert!(!cell_checked("false"));
+        assert!(!cell_checked(""));
+        assert_eq!(spinner_text(3.0, 1.0), "3");
+        assert_eq!(spinner_text(2.5, 0.5), "2.5");
+        assert_eq!(spinner_text(0.1 + 0.2, 0.05), "0.30");
+    }
+}
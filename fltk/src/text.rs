@@ -2,12 +2,25 @@ use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::text::*;
 use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
     ffi::{CStr, CString},
     mem,
     os::raw,
+    rc::Rc,
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+thread_local! {
+    /// The batch depth and pending text of the buffers in a batch of appends, keyed by the buffer's address.
+    /// Entries are removed when their buffer is deleted
+    static BATCHES: RefCell<HashMap<usize, (u32, String)>> = RefCell::new(HashMap::new());
+}
+
+fn with_batches<T, F: FnOnce(&mut HashMap<usize, (u32, String)>) -> T>(f: F) -> T {
+    BATCHES.with(|b| f(&mut b.borrow_mut()))
+}
+
 /// Wraps a text buffer, Cloning a text buffer invalidates the underlying pointer, thus the no derive(Clone)
 #[derive(Debug)]
 pub struct TextBuffer {
@@ -32,6 +45,7 @@ impl TextBuffer {
     /// # Safety
     /// The buffer shouldn't be deleted while the Display widget still needs it
     pub unsafe fn delete(mut buf: Self) {
+        with_batches(|b| b.remove(&(buf._inner as usize)));
         Fl_Text_Buffer_delete(buf._inner);
         buf._inner = std::ptr::null_mut::<Fl_Text_Buffer>();
     }
@@ -40,6 +54,7 @@ impl TextBuffer {
     /// # Safety
     /// The buffer shouldn't be deleted while the Display widget still needs it
     pub unsafe fn delete_buffer(mut buf: TextBuffer) {
+        with_batches(|b| b.remove(&(buf._inner as usize)));
         Fl_Text_Buffer_delete(buf._inner);
         buf._inner = std::ptr::null_mut::<Fl_Text_Buffer>();
     }
@@ -71,28 +86,103 @@ impl TextBuffer {
         }
     }
 
-    /// Returns the text of the buffer
+    /// Returns the text of the buffer, including the text pending in a batch
     pub fn text(&self) -> String {
         assert!(!self._inner.is_null());
-        unsafe {
+        let mut text = unsafe {
             let text = Fl_Text_Buffer_text(self._inner);
             assert!(!text.is_null());
             CStr::from_ptr(text as *mut raw::c_char)
                 .to_string_lossy()
                 .to_string()
-        }
+        };
+        with_batches(|b| {
+            if let Some((_, pending)) = b.get(&(self._inner as usize)) {
+                text.push_str(pending);
+            }
+        });
+        text
     }
 
-    /// Appends to the buffer
+    /// Appends to the buffer, the text is kept pending while a batch is started
     pub fn append(&mut self, text: &str) {
         assert!(!self._inner.is_null());
+        let pending = with_batches(|b| match b.get_mut(&(self._inner as usize)) {
+            Some((_, pending)) => {
+                pending.push_str(text);
+                true
+            }
+            None => false,
+        });
+        if pending {
+            return;
+        }
         let text = CString::safe_new(text);
         unsafe { Fl_Text_Buffer_append(self._inner, text.as_ptr()) }
     }
 
-    /// Get the length of the buffer
+    /// Starts a batch of appends: appended text is kept pending until the matching end_batch(),
+    /// which appends it at once, so that displays remeasure and redraw once instead of for each append.
+    /// Only append() is batched, and only text() and length() see the pending text. Batches can be nested
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut buf = text::TextBuffer::default();
+    /// let mut disp = text::TextDisplay::new(0, 0, 600, 400, "");
+    /// disp.set_buffer(Some(buf.clone()));
+    /// let log = std::fs::read_to_string("huge.log").unwrap();
+    /// buf.begin_batch();
+    /// for line in log.lines() {
+    ///     buf.append(line);
+    ///     buf.append("\n");
+    /// }
+    /// buf.end_batch();
+    /// ```
+    pub fn begin_batch(&mut self) {
+        assert!(!self._inner.is_null());
+        with_batches(|b| {
+            b.entry(self._inner as usize)
+                .or_insert((0, String::new()))
+                .0 += 1
+        });
+    }
+
+    /// Ends a batch started with begin_batch(), appending the pending text when the outermost batch ends
+    pub fn end_batch(&mut self) {
+        assert!(!self._inner.is_null());
+        let key = self._inner as usize;
+        let pending = with_batches(|b| match b.get_mut(&key) {
+            Some((depth, _)) if *depth > 1 => {
+                *depth -= 1;
+                None
+            }
+            Some(_) => b.remove(&key).map(|(_, pending)| pending),
+            None => None,
+        });
+        if let Some(pending) = pending {
+            if !pending.is_empty() {
+                self.append(&pending);
+            }
+        }
+    }
+
+    /// Returns whether a batch of appends is started
+    pub fn in_batch(&self) -> bool {
+        assert!(!self._inner.is_null());
+        with_batches(|b| b.contains_key(&(self._inner as usize)))
+    }
+
+    /// Get the length of the buffer, including the text pending in a batch
     pub fn length(&self) -> u32 {
         assert!(!self._inner.is_null());
+        let pending = with_batches(|b| {
+            b.get(&(self._inner as usize))
+                .map_or(0, |(_, pending)| pending.len() as u32)
+        });
+        self.stored_length() + pending
+    }
+
+    /// Returns the length of the text in the buffer, excluding the text pending in a batch
+    fn stored_length(&self) -> u32 {
         unsafe { Fl_Text_Buffer_length(self._inner) as u32 }
     }
 
@@ -193,7 +283,7 @@ impl TextBuffer {
     pub fn copy(&self) -> TextBuffer {
         assert!(!self._inner.is_null());
         let mut temp = TextBuffer::default();
-        temp.copy_from(self, 0, 0, self.length());
+        temp.copy_from(self, 0, 0, self.stored_length());
         temp
     }
 
@@ -402,6 +492,43 @@ impl TextBuffer {
         }
     }
 
+    /// Aligns a byte position to the closest utf8 character boundary at or before it
+    pub fn utf8_align(&self, pos: u32) -> u32 {
+        assert!(!self._inner.is_null());
+        crate::utils::utf8_align(&self.text(), pos as usize) as u32
+    }
+
+    /// Returns the character starting at the byte position pos, the position is first aligned to a utf8 boundary
+    pub fn char_at(&self, pos: u32) -> Option<char> {
+        assert!(!self._inner.is_null());
+        let text = self.text();
+        text[crate::utils::utf8_align(&text, pos as usize)..]
+            .chars()
+            .next()
+    }
+
+    /// Converts a character index into a byte position within the buffer
+    pub fn char_to_byte(&self, idx: u32) -> Option<u32> {
+        assert!(!self._inner.is_null());
+        crate::utils::char_to_byte_index(&self.text(), idx as usize).map(|i| i as u32)
+    }
+
+    /// Converts a byte position within the buffer into a character index
+    pub fn byte_to_char(&self, pos: u32) -> u32 {
+        assert!(!self._inner.is_null());
+        crate::utils::byte_to_char_index(&self.text(), pos as usize) as u32
+    }
+
+    /// Inserts text at a character index, rather than a byte position
+    pub fn insert_at_char(&mut self, idx: u32, text: &str) -> Result<(), FltkError> {
+        assert!(!self._inner.is_null());
+        let pos = self
+            .char_to_byte(idx)
+            .ok_or(FltkError::Internal(FltkErrorKind::FailedOperation))?;
+        self.insert(pos, text);
+        Ok(())
+    }
+
     /// Returns the index of the line's start position at pos
     pub fn line_start(&self, pos: u32) -> u32 {
         assert!(!self._inner.is_null());
@@ -552,6 +679,8 @@ impl Drop for TextBuffer {
         assert!(!self._inner.is_null());
         let x = self._refcount.fetch_sub(1, Ordering::Relaxed);
         if x == 0 {
+            let key = self._inner as usize;
+            let _ = BATCHES.try_with(|b| b.borrow_mut().remove(&key));
             unsafe {
                 Fl_Text_Buffer_delete(self._inner);
             }
@@ -628,6 +757,133 @@ pub struct StyleTableEntry {
     pub size: u32,
 }
 
+type WordCallback = Box<dyn FnMut(&mut TextEditor, u32, u32, &str)>;
+
+/// The drawing overlay of a display: the word marks and word callback of an editor,
+/// search highlights and line number markers
+#[derive(Default)]
+struct Overlay {
+    marks: Vec<(u32, u32, Color)>,
+    /// Ranges drawn as boxes, used to highlight search matches
+    boxes: Vec<(u32, u32, Color)>,
+    cb: Option<WordCallback>,
+    markers: BTreeMap<u32, (char, Color)>,
+}
+
+thread_local! {
+    /// The overlays of the displays, shared with their draw methods
+    static OVERLAYS: RefCell<crate::utils::WidgetMap<Rc<RefCell<Overlay>>>> = RefCell::new(Default::default());
+}
+
+fn overlay<D: DisplayExt>(d: &D) -> Option<Rc<RefCell<Overlay>>> {
+    OVERLAYS.with(|m| m.borrow_mut().get(d).cloned())
+}
+
+/// Creates the overlay of a display and installs its draw method if not done already, then runs f on it
+fn with_overlay<D, T, F>(d: &mut D, f: F) -> T
+where
+    D: DisplayExt + WidgetBase,
+    F: FnOnce(&mut Overlay) -> T,
+{
+    let o = match overlay(d) {
+        Some(o) => o,
+        None => {
+            let o = Rc::new(RefCell::new(Overlay::default()));
+            OVERLAYS.with(|m| m.borrow_mut().insert(d, o.clone()));
+            let o2 = o.clone();
+            d.draw2(move |d| draw_overlay(&o2, d));
+            o
+        }
+    };
+    let mut o = o.borrow_mut();
+    f(&mut o)
+}
+
+fn draw_overlay<D: DisplayExt>(o: &RefCell<Overlay>, d: &mut D) {
+    let (marks, boxes, markers) = {
+        let o = o.borrow();
+        (o.marks.clone(), o.boxes.clone(), o.markers.clone())
+    };
+    let buf = match d.buffer() {
+        Some(buf) => buf,
+        None => return,
+    };
+    crate::draw::push_clip(d.x(), d.y(), d.width(), d.height());
+    crate::draw::set_font(d.linenumber_font(), d.linenumber_size());
+    let line_h = crate::draw::height();
+    let descent = crate::draw::descent();
+    for (line, (glyph, color)) in markers {
+        let pos = d.skip_lines(0, line.saturating_sub(1), true);
+        // lines past the end of the buffer and out of view aren't drawn
+        if buf.count_lines(0, pos) + 1 != line {
+            continue;
+        }
+        let y = d.position_to_xy(pos).1 as i32;
+        if y == 0 {
+            continue;
+        }
+        crate::draw::set_draw_color(color);
+        crate::draw::draw_text(&glyph.to_string(), d.x() + 4, y + line_h - descent);
+    }
+    crate::draw::set_font(d.text_font(), d.text_size());
+    let line_h = crate::draw::height();
+    // positions out of view aren't mapped, and only single line ranges are drawn
+    let span = |start: u32, end: u32| {
+        let (x0, y0) = d.position_to_xy(start);
+        let (x1, y1) = d.position_to_xy(end);
+        if y0 == 0 || y0 != y1 || x1 <= x0 {
+            None
+        } else {
+            Some((x0 as i32, x1 as i32, y0 as i32))
+        }
+    };
+    for (start, end, color) in boxes {
+        if let Some((x0, x1, y)) = span(start, end) {
+            crate::draw::set_draw_color(color);
+            crate::draw::draw_rect(x0, y, x1 - x0, line_h);
+        }
+    }
+    for (start, end, color) in marks {
+        let (x0, x1, y) = match span(start, end) {
+            Some((x0, x1, y)) => (x0, x1, y + line_h - 1),
+            None => continue,
+        };
+        crate::draw::set_draw_color(color);
+        let mut x = x0;
+        while x < x1 {
+            let up = (x - x0) / 2 % 2 == 0;
+            let (ya, yb) = if up { (y, y - 2) } else { (y - 2, y) };
+            crate::draw::draw_line(x, ya, std::cmp::min(x + 2, x1), yb);
+            x += 2;
+        }
+    }
+    crate::draw::pop_clip();
+}
+
+pub(crate) fn set_linenumber_marker<D: DisplayExt + WidgetBase>(
+    d: &mut D,
+    line: u32,
+    marker: Option<(char, Color)>,
+) {
+    with_overlay(d, |o| match marker {
+        Some(marker) => o.markers.insert(line, marker),
+        None => o.markers.remove(&line),
+    });
+    d.redraw();
+}
+
+pub(crate) fn linenumber_markers<D: DisplayExt>(d: &D) -> Vec<(u32, char, Color)> {
+    overlay(d)
+        .map(|o| {
+            o.borrow()
+                .markers
+                .iter()
+                .map(|(l, m)| (*l, m.0, m.1))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 impl TextEditor {
     /// Set to insert mode
     pub fn set_insert_mode(&mut self, b: bool) {
@@ -881,6 +1137,146 @@ impl TextEditor {
             Fl_Text_Editor_kf_select_all(self._inner);
         }
     }
+
+    /// Sets a callback receiving the words of the lines touched by each edit as (editor, start, end, word),
+    /// for instance to check their spelling and mark the misspelled ones using mark_range().
+    /// The marks of those lines are cleared before the callback runs, and marks elsewhere follow the edits.
+    /// The editor must have its buffer set. Marks are drawn using draw2(), which replaces any custom draw method
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut ed = text::TextEditor::new(0, 0, 400, 300, "");
+    /// ed.set_buffer(Some(text::TextBuffer::default()));
+    /// let dictionary = vec!["hello", "world"];
+    /// ed.set_word_callback(move |ed, start, end, word| {
+    ///     if !dictionary.contains(&word.to_lowercase().as_str()) {
+    ///         ed.mark_range(start, end, Color::Red);
+    ///     }
+    /// });
+    /// ```
+    pub fn set_word_callback<F: FnMut(&mut TextEditor, u32, u32, &str) + 'static>(
+        &mut self,
+        cb: F,
+    ) {
+        assert!(!self.was_deleted());
+        let mut buf = self.buffer().expect("The editor requires a buffer!");
+        let had_cb = with_overlay(self, |m| m.cb.replace(Box::new(cb)).is_some());
+        if !had_cb {
+            let mut ed = self.clone();
+            let o = overlay(self).unwrap();
+            buf.add_modify_callback(move |pos, inserted, deleted, _, _| {
+                if ed.was_deleted() || (inserted == 0 && deleted == 0) {
+                    return;
+                }
+                {
+                    let mut m = o.borrow_mut();
+                    m.marks.retain(|r| r.1 <= pos || r.0 >= pos + deleted);
+                    for r in m.marks.iter_mut().filter(|r| r.0 >= pos + deleted) {
+                        r.0 = r.0 + inserted - deleted;
+                        r.1 = r.1 + inserted - deleted;
+                    }
+                }
+                ed.check_words(pos, pos + inserted);
+            });
+        }
+        let len = buf.stored_length();
+        self.check_words(0, len);
+    }
+
+    /// Runs the word callback over the whole buffer again, for instance after the dictionary changed
+    pub fn recheck_words(&mut self) {
+        assert!(!self.was_deleted());
+        if let Some(buf) = self.buffer() {
+            self.check_words(0, buf.stored_length());
+        }
+    }
+
+    /// Marks a range with a squiggly underline of the given color
+    pub fn mark_range(&mut self, start: u32, end: u32, color: Color) {
+        assert!(!self.was_deleted());
+        with_overlay(self, |m| m.marks.push((start, end, color)));
+        self.redraw();
+    }
+
+    /// Removes the marks overlapping a range
+    pub fn unmark_range(&mut self, start: u32, end: u32) {
+        assert!(!self.was_deleted());
+        if let Some(o) = overlay(self) {
+            o.borrow_mut().marks.retain(|r| r.1 <= start || r.0 >= end);
+        }
+        self.redraw();
+    }
+
+    /// Removes all marks
+    pub fn clear_marks(&mut self) {
+        self.unmark_range(0, std::u32::MAX);
+    }
+
+    /// Returns the marked ranges as (start, end, color)
+    pub fn marks(&self) -> Vec<(u32, u32, Color)> {
+        assert!(!self.was_deleted());
+        overlay(self)
+            .map(|o| o.borrow().marks.clone())
+            .unwrap_or_default()
+    }
+
+    /// Sets the ranges drawn as boxes, used by SearchBar to highlight the matches
+    pub(crate) fn set_highlight_boxes(&mut self, ranges: &[(u32, u32)], color: Color) {
+        assert!(!self.was_deleted());
+        let boxes = ranges.iter().map(|r| (r.0, r.1, color)).collect();
+        with_overlay(self, |m| m.boxes = boxes);
+        self.redraw();
+    }
+
+    /// Clears the marks of the lines between start and end and passes their words to the word callback
+    fn check_words(&mut self, start: u32, end: u32) {
+        let buf = match self.buffer() {
+            Some(buf) => buf,
+            None => return,
+        };
+        let o = match overlay(self) {
+            Some(o) => o,
+            None => return,
+        };
+        let cb = o.borrow_mut().cb.take();
+        let mut cb = match cb {
+            Some(cb) => cb,
+            None => return,
+        };
+        let len = buf.stored_length();
+        let mut line_start = buf.line_start(std::cmp::min(start, len));
+        loop {
+            let line = buf.line_text(line_start);
+            let line_end = line_start + line.len() as u32;
+            o.borrow_mut()
+                .marks
+                .retain(|r| r.1 <= line_start || r.0 > line_end);
+            let mut word_start = None;
+            for (i, c) in line.char_indices().chain(std::iter::once((line.len(), ' '))) {
+                let in_word = c.is_alphanumeric() || (c == '\'' && word_start.is_some());
+                match (word_start, in_word) {
+                    (None, true) => word_start = Some(i),
+                    (Some(ws), false) => {
+                        let word = line[ws..i].trim_end_matches('\'');
+                        let pos = line_start + ws as u32;
+                        cb(self, pos, pos + word.len() as u32, word);
+                        word_start = None;
+                    }
+                    _ => (),
+                }
+            }
+            if line_end >= end || line_end >= len {
+                break;
+            }
+            line_start = line_end + 1;
+        }
+        {
+            let mut m = o.borrow_mut();
+            if m.cb.is_none() {
+                m.cb = Some(cb);
+            }
+        }
+        self.redraw();
+    }
 }
 
 impl SimpleTerminal {
@@ -982,8 +1378,704 @@ impl SimpleTerminal {
     }
 }
 
+/// Returns the byte ranges of the matches of pattern in text
+fn find_matches(
+    text: &str,
+    pattern: &str,
+    case_sensitive: bool,
+    use_regex: bool,
+) -> Result<Vec<(usize, usize)>, FltkError> {
+    if pattern.is_empty() {
+        return Ok(vec![]);
+    }
+    #[cfg(feature = "regex")]
+    {
+        if use_regex {
+            let re = regex::RegexBuilder::new(pattern)
+                .case_insensitive(!case_sensitive)
+                .multi_line(true)
+                .build()
+                .map_err(|e| FltkError::Unknown(e.to_string()))?;
+            return Ok(re
+                .find_iter(text)
+                .filter(|m| m.start() != m.end())
+                .map(|m| (m.start(), m.end()))
+                .collect());
+        }
+    }
+    #[cfg(not(feature = "regex"))]
+    {
+        if use_regex {
+            return Err(FltkError::Unknown(String::from(
+                "Regex search requires the regex feature",
+            )));
+        }
+    }
+    let pat: Vec<char> = pattern.chars().collect();
+    let eq = |a: char, b: char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a == b || a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+    let mut v = vec![];
+    let mut skip_until = 0;
+    for (i, _) in text.char_indices() {
+        if i < skip_until {
+            continue;
+        }
+        let mut chars = text[i..].char_indices();
+        let mut end = i;
+        let matched = pat.iter().all(|p| match chars.next() {
+            Some((j, c)) if eq(c, *p) => {
+                end = i + j + c.len_utf8();
+                true
+            }
+            _ => false,
+        });
+        if matched {
+            v.push((i, end));
+            skip_until = end;
+        }
+    }
+    Ok(v)
+}
+
+#[derive(Debug)]
+struct SearchState {
+    editor: TextEditor,
+    find: crate::input::Input,
+    replace: crate::input::Input,
+    buttons: Vec<crate::button::Button>,
+    toggles: Vec<crate::button::ToggleButton>,
+    labels: Vec<crate::frame::Frame>,
+    matches: Vec<(u32, u32)>,
+    /// Whether the bar is shown, matches are only highlighted then
+    active: bool,
+    highlight_color: Color,
+    last: (i32, i32, i32, i32),
+}
+
+impl crate::group::GroupLayout for SearchState {
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32) {
+        &mut self.last
+    }
+
+    fn layout(&mut self, grp: &mut crate::group::Group) {
+        let st = self;
+        let (x, y, w, h) = (grp.x(), grp.y(), grp.width(), grp.height());
+        let pad = 3;
+        let row_h = std::cmp::max((h - 3 * pad) / 2, 1);
+        let (y1, y2) = (y + pad, y + 2 * pad + row_h);
+        let label_w = 60;
+        let btn_w = 30;
+        // first row: label, input, prev, next, toggles, close
+        let right1 = 2 * btn_w + 3 * btn_w + 25 + 2 * pad;
+        let in_w = std::cmp::max(w - label_w - right1 - 3 * pad, 10);
+        st.labels[0].resize(x + pad, y1, label_w, row_h);
+        st.find.resize(x + pad + label_w, y1, in_w, row_h);
+        let mut bx = x + 2 * pad + label_w + in_w;
+        for b in st.buttons.iter_mut().take(2) {
+            b.resize(bx, y1, btn_w, row_h);
+            bx += btn_w;
+        }
+        bx += pad;
+        for t in st.toggles.iter_mut() {
+            t.resize(bx, y1, btn_w, row_h);
+            bx += btn_w;
+        }
+        st.buttons[4].resize(x + w - pad - 25, y1, 25, row_h);
+        // second row: label, input, replace, replace all
+        let right2 = 70 + 45;
+        let in_w = std::cmp::max(w - label_w - right2 - 3 * pad, 10);
+        st.labels[1].resize(x + pad, y2, label_w, row_h);
+        st.replace.resize(x + pad + label_w, y2, in_w, row_h);
+        let bx = x + 2 * pad + label_w + in_w;
+        st.buttons[2].resize(bx, y2, 70, row_h);
+        st.buttons[3].resize(bx + 70, y2, 45, row_h);
+    }
+}
+
+/// Defines a SearchBar (custom widget), a find & replace bar bound to a TextEditor.
+/// It searches as the text is typed, highlights all matches, and supports case sensitivity, wrapping around
+/// and regular expressions (which require the regex feature). Enter and Shift+Enter move to the next and previous match,
+/// and Escape or the close button hide the bar. The bar should be shown and hidden using show_bar() and hide_bar()
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 600, 400, "Editor");
+/// let mut ed = text::TextEditor::new(0, 0, 600, 340, "");
+/// ed.set_buffer(Some(text::TextBuffer::default()));
+/// let mut search = text::SearchBar::new(0, 340, 600, 60, &ed);
+/// win.end();
+/// search.hide_bar();
+/// win.show();
+/// // on Ctrl+F
+/// search.show_bar();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchBar {
+    grp: crate::group::Group,
+    state: Rc<RefCell<SearchState>>,
+}
+
+impl SearchBar {
+    /// Creates a new search bar for an editor, which must have its buffer set
+    pub fn new(x: i32, y: i32, w: i32, h: i32, editor: &TextEditor) -> SearchBar {
+        let mut buf = editor.buffer().expect("The editor requires a buffer!");
+        let mut grp = crate::group::Group::new(x, y, w, h, "");
+        grp.set_frame(FrameType::FlatBox);
+        let labels = vec![
+            crate::frame::Frame::default().with_label("Find:"),
+            crate::frame::Frame::default().with_label("Replace:"),
+        ];
+        let mut find = crate::input::Input::default();
+        let replace = crate::input::Input::default();
+        let mut buttons = vec![];
+        for (label, tip) in &[
+            ("@<", "Previous match"),
+            ("@>", "Next match"),
+            ("Replace", "Replace the current match"),
+            ("All", "Replace all matches"),
+            ("@1+", "Close"),
+        ] {
+            let mut b = crate::button::Button::default().with_label(label);
+            b.set_tooltip(tip);
+            buttons.push(b);
+        }
+        let mut toggles = vec![];
+        for (label, tip) in &[
+            ("Aa", "Match case"),
+            (".*", "Regular expression"),
+            ("@reload", "Wrap around"),
+        ] {
+            let mut t = crate::button::ToggleButton::default().with_label(label);
+            t.set_tooltip(tip);
+            toggles.push(t);
+        }
+        toggles[2].set(true);
+        #[cfg(not(feature = "regex"))]
+        toggles[1].deactivate();
+        grp.end();
+        let state = Rc::new(RefCell::new(SearchState {
+            editor: editor.clone(),
+            find: find.clone(),
+            replace,
+            buttons: buttons.clone(),
+            toggles: toggles.clone(),
+            labels,
+            matches: vec![],
+            active: true,
+            highlight_color: Color::Yellow,
+            last: (0, 0, 0, 0),
+        }));
+        let mut bar = SearchBar { grp, state };
+        crate::group::relayout_on_resize(&mut bar.grp, &bar.state);
+        find.set_trigger(CallbackTrigger::EnterKeyChanged);
+        {
+            let mut b = bar.clone();
+            find.set_callback(move || {
+                if crate::app::event_key() == Key::Enter {
+                    if crate::app::is_event_shift() {
+                        b.find_prev();
+                    } else {
+                        b.find_next();
+                    }
+                } else {
+                    b.update_matches();
+                }
+            });
+        }
+        let actions: Vec<fn(&mut SearchBar)> = vec![
+            |b| {
+                b.find_prev();
+            },
+            |b| {
+                b.find_next();
+            },
+            |b| {
+                b.replace();
+            },
+            |b| {
+                b.replace_all();
+            },
+            |b| b.hide_bar(),
+        ];
+        for (btn, action) in buttons.iter_mut().zip(actions) {
+            let mut b = bar.clone();
+            btn.set_callback(move || action(&mut b));
+        }
+        for t in toggles.iter_mut() {
+            let mut b = bar.clone();
+            t.set_callback(move || b.update_matches());
+        }
+        {
+            let b = bar.clone();
+            buf.add_modify_callback(move |_, inserted, deleted, _, _| {
+                let active = !b.grp.was_deleted() && b.state.borrow().active;
+                if active && (inserted > 0 || deleted > 0) {
+                    let mut b = b.clone();
+                    b.update_matches();
+                }
+            });
+        }
+        {
+            let mut b = bar.clone();
+            bar.grp.handle(move |ev| {
+                let key_ev = ev == Event::KeyDown || ev == Event::Shortcut;
+                if key_ev && crate::app::event_key() == Key::Escape {
+                    b.hide_bar();
+                    return true;
+                }
+                false
+            });
+        }
+        bar.recalc();
+        bar
+    }
+
+    /// Shows the bar, fills it with the editor's selection if any, and focuses the search field
+    pub fn show_bar(&mut self) {
+        assert!(!self.grp.was_deleted());
+        let (mut find, editor) = {
+            let st = self.state.borrow();
+            (st.find.clone(), st.editor.clone())
+        };
+        if let Some(mut buf) = editor.buffer() {
+            let sel = buf.selection_text();
+            if buf.selected() && !sel.contains('\n') {
+                find.set_value(&sel);
+            }
+        }
+        self.grp.show();
+        self.state.borrow_mut().active = true;
+        let _ = find.take_focus();
+        let len = find.value().len() as u32;
+        let _ = find.set_position(0);
+        let _ = find.set_mark(len);
+        self.update_matches();
+        if let Some(mut p) = self.grp.parent() {
+            p.redraw();
+        }
+    }
+
+    /// Hides the bar, clears the highlights and gives the focus back to the editor
+    pub fn hide_bar(&mut self) {
+        assert!(!self.grp.was_deleted());
+        self.grp.hide();
+        let (mut editor, color) = {
+            let mut st = self.state.borrow_mut();
+            st.matches.clear();
+            st.active = false;
+            (st.editor.clone(), st.highlight_color)
+        };
+        editor.set_highlight_boxes(&[], color);
+        let _ = editor.take_focus();
+        if let Some(mut p) = self.grp.parent() {
+            p.redraw();
+        }
+    }
+
+    /// Returns the searched text
+    pub fn search_text(&self) -> String {
+        self.state.borrow().find.value()
+    }
+
+    /// Sets the searched text
+    pub fn set_search_text(&mut self, text: &str) {
+        let find = self.state.borrow().find.clone();
+        find.set_value(text);
+        self.update_matches();
+    }
+
+    /// Returns the replacement text
+    pub fn replace_text(&self) -> String {
+        self.state.borrow().replace.value()
+    }
+
+    /// Sets the replacement text, which can refer to regex groups like $1 when searching with a regex
+    pub fn set_replace_text(&mut self, text: &str) {
+        self.state.borrow().replace.set_value(text);
+    }
+
+    /// Returns whether the search is case sensitive
+    pub fn case_sensitive(&self) -> bool {
+        self.state.borrow().toggles[0].is_set()
+    }
+
+    /// Sets whether the search is case sensitive
+    pub fn set_case_sensitive(&mut self, flag: bool) {
+        self.state.borrow_mut().toggles[0].set(flag);
+        self.update_matches();
+    }
+
+    /// Returns whether the searched text is a regular expression
+    pub fn use_regex(&self) -> bool {
+        self.state.borrow().toggles[1].is_set()
+    }
+
+    /// Sets whether the searched text is a regular expression, which requires the regex feature
+    #[cfg(feature = "regex")]
+    pub fn set_use_regex(&mut self, flag: bool) {
+        self.state.borrow_mut().toggles[1].set(flag);
+        self.update_matches();
+    }
+
+    /// Returns whether searching continues from the other end of the text
+    pub fn wrap_around(&self) -> bool {
+        self.state.borrow().toggles[2].is_set()
+    }
+
+    /// Sets whether searching continues from the other end of the text
+    pub fn set_wrap_around(&mut self, flag: bool) {
+        self.state.borrow_mut().toggles[2].set(flag);
+    }
+
+    /// Sets the color of the boxes highlighting the matches
+    pub fn set_highlight_color(&mut self, color: Color) {
+        self.state.borrow_mut().highlight_color = color;
+        self.update_matches();
+    }
+
+    /// Returns the matches as (start, end) byte positions
+    pub fn matches(&self) -> Vec<(u32, u32)> {
+        self.state.borrow().matches.clone()
+    }
+
+    /// Selects the next match after the insert position, returns whether a match was found
+    pub fn find_next(&mut self) -> bool {
+        self.update_matches();
+        let (editor, matches, wrap) = self.search_context();
+        let pos = editor.insert_position();
+        let next = matches
+            .iter()
+            .find(|m| m.0 >= pos)
+            .or_else(|| if wrap { matches.first() } else { None });
+        match next {
+            Some(m) => {
+                SearchBar::select_match(editor, *m);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Selects the previous match before the current selection, returns whether a match was found
+    pub fn find_prev(&mut self) -> bool {
+        self.update_matches();
+        let (editor, matches, wrap) = self.search_context();
+        let pos = match editor.buffer().and_then(|mut b| b.selection_position()) {
+            Some((start, _)) => start,
+            None => editor.insert_position(),
+        };
+        let prev = matches
+            .iter()
+            .rev()
+            .find(|m| m.1 < pos || (m.1 == pos && m.0 < pos))
+            .or_else(|| if wrap { matches.last() } else { None });
+        match prev {
+            Some(m) => {
+                SearchBar::select_match(editor, *m);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the selected match and selects the next one, returns whether a match was replaced
+    pub fn replace(&mut self) -> bool {
+        self.update_matches();
+        let (editor, matches, _) = self.search_context();
+        let mut buf = match editor.buffer() {
+            Some(buf) => buf,
+            None => return false,
+        };
+        let sel = match buf.selection_position() {
+            Some(sel) => sel,
+            None => {
+                self.find_next();
+                return false;
+            }
+        };
+        if !matches.contains(&sel) {
+            self.find_next();
+            return false;
+        }
+        let text = buf.text_range(sel.0, sel.1).unwrap_or_default();
+        let replacement = self.replacement_for(&text);
+        buf.replace(sel.0, sel.1, &replacement);
+        let mut editor = editor;
+        editor.set_insert_position(sel.0 + replacement.len() as u32);
+        self.find_next();
+        true
+    }
+
+    /// Replaces all matches, returns the number of replacements
+    pub fn replace_all(&mut self) -> usize {
+        self.update_matches();
+        let (editor, matches, _) = self.search_context();
+        let mut buf = match editor.buffer() {
+            Some(buf) => buf,
+            None => return 0,
+        };
+        for m in matches.iter().rev() {
+            let text = buf.text_range(m.0, m.1).unwrap_or_default();
+            let replacement = self.replacement_for(&text);
+            buf.replace(m.0, m.1, &replacement);
+        }
+        self.update_matches();
+        matches.len()
+    }
+
+    /// Recalculates the layout of the bar
+    pub fn recalc(&mut self) {
+        crate::group::relayout(&mut self.grp, &mut *self.state.borrow_mut());
+    }
+
+    fn search_context(&self) -> (TextEditor, Vec<(u32, u32)>, bool) {
+        let st = self.state.borrow();
+        (st.editor.clone(), st.matches.clone(), st.toggles[2].is_set())
+    }
+
+    fn replacement_for(&self, matched: &str) -> String {
+        let replacement = self.replace_text();
+        #[cfg(feature = "regex")]
+        {
+            if self.use_regex() {
+                let re = regex::RegexBuilder::new(&self.search_text())
+                    .case_insensitive(!self.case_sensitive())
+                    .multi_line(true)
+                    .build();
+                if let Ok(re) = re {
+                    return re.replace(matched, replacement.as_str()).to_string();
+                }
+            }
+        }
+        let _ = matched;
+        replacement
+    }
+
+    fn select_match(mut editor: TextEditor, m: (u32, u32)) {
+        if let Some(mut buf) = editor.buffer() {
+            buf.select(m.0, m.1);
+            editor.set_insert_position(m.1);
+            if editor.position_to_xy(m.0).1 == 0 {
+                let line = buf.count_lines(0, m.0);
+                editor.scroll(line.saturating_sub(3), 0);
+            }
+            editor.redraw();
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let (mut editor, pattern, case, regex, color) = {
+            let st = self.state.borrow();
+            (
+                st.editor.clone(),
+                st.find.value(),
+                st.toggles[0].is_set(),
+                st.toggles[1].is_set(),
+                st.highlight_color,
+            )
+        };
+        let text = match editor.buffer() {
+            Some(buf) => buf.text(),
+            None => return,
+        };
+        let (matches, ok) = match find_matches(&text, &pattern, case, regex) {
+            Ok(v) => (
+                v.iter().map(|m| (m.0 as u32, m.1 as u32)).collect(),
+                true,
+            ),
+            Err(_) => (vec![], false),
+        };
+        {
+            let mut st = self.state.borrow_mut();
+            st.find
+                .set_text_color(if ok { Color::ForeGround } else { Color::Red });
+            st.find.redraw();
+            st.matches = matches;
+        }
+        let matches = self.state.borrow().matches.clone();
+        editor.set_highlight_boxes(&matches, color);
+    }
+}
+
+impl std::ops::Deref for SearchBar {
+    type Target = crate::group::Group;
+
+    fn deref(&self) -> &Self::Target {
+        &self.grp
+    }
+}
+
+impl std::ops::DerefMut for SearchBar {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.grp
+    }
+}
+
+/// Defines a Highlighter, which maintains the style buffer of a display from regex rules, requires the regex feature.
+/// Each rule maps a regex to a style char, 'A' being the first StyleTableEntry.
+/// Rules are matched within single lines, and earlier rules take precedence over later ones for overlapping matches.
+/// After edits, only the modified lines are restyled
+/// ```no_run
+/// use fltk::*;
+/// let mut ed = text::TextEditor::new(0, 0, 400, 300, "");
+/// ed.set_buffer(Some(text::TextBuffer::default()));
+/// let styles = vec![
+///     text::StyleTableEntry { color: Color::Black, font: Font::Courier, size: 14 },
+///     text::StyleTableEntry { color: Color::Blue, font: Font::CourierBold, size: 14 },
+///     text::StyleTableEntry { color: Color::DarkGreen, font: Font::CourierItalic, size: 14 },
+/// ];
+/// let hl = text::Highlighter::from_rules(&[(r"//.*", 'C'), (r"\b(fn|let|mut|pub)\b", 'B')]).unwrap();
+/// hl.attach(&mut ed, styles);
+/// ```
+#[cfg(feature = "regex")]
+#[derive(Debug, Clone)]
+pub struct Highlighter {
+    rules: Vec<(regex::Regex, char)>,
+    default_style: char,
+}
+
+#[cfg(feature = "regex")]
+impl Highlighter {
+    /// Creates a highlighter from (regex, style char) rules
+    pub fn from_rules(rules: &[(&str, char)]) -> Result<Highlighter, FltkError> {
+        let mut v = vec![];
+        for (pattern, style) in rules {
+            assert!(style.is_ascii_uppercase(), "Style chars start from 'A'!");
+            let re = regex::Regex::new(pattern).map_err(|e| FltkError::Unknown(e.to_string()))?;
+            v.push((re, *style));
+        }
+        Ok(Highlighter {
+            rules: v,
+            default_style: 'A',
+        })
+    }
+
+    /// Sets the style of the text not matched by any rule, defaults to 'A'
+    pub fn set_default_style(&mut self, style: char) {
+        assert!(style.is_ascii_uppercase(), "Style chars start from 'A'!");
+        self.default_style = style;
+    }
+
+    /// Returns the styles of a text, one style char per byte as expected by style buffers
+    pub fn style_text(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                out.push(self.default_style);
+            }
+            let mut styles = vec![self.default_style as u8; line.len()];
+            let mut claimed = vec![false; line.len()];
+            for (re, style) in &self.rules {
+                for m in re.find_iter(line) {
+                    if claimed[m.start()..m.end()].iter().any(|c| *c) {
+                        continue;
+                    }
+                    let range = m.start()..m.end();
+                    for (s, c) in styles[range.clone()].iter_mut().zip(&mut claimed[range]) {
+                        *s = *style as u8;
+                        *c = true;
+                    }
+                }
+            }
+            out.push_str(&String::from_utf8_lossy(&styles));
+        }
+        out
+    }
+
+    /// Styles the display's buffer and keeps its style buffer up to date as the text changes.
+    /// The display must have its buffer set. Returns the style buffer
+    pub fn attach<D: DisplayExt>(
+        self,
+        display: &mut D,
+        entries: Vec<StyleTableEntry>,
+    ) -> TextBuffer {
+        let mut buf = display.buffer().expect("The display requires a buffer!");
+        let mut sbuf = TextBuffer::default();
+        sbuf.set_text(&self.style_text(&buf.text()));
+        display.set_highlight_data(sbuf.clone(), entries);
+        let mut styles = sbuf.clone();
+        let text = buf.clone();
+        buf.add_modify_callback(move |pos, inserted, deleted, _, _| {
+            if inserted == 0 && deleted == 0 {
+                return;
+            }
+            if deleted > 0 {
+                styles.remove(pos, pos + deleted);
+            }
+            if inserted > 0 {
+                let placeholder: String = std::iter::repeat(self.default_style)
+                    .take(inserted as usize)
+                    .collect();
+                styles.insert(pos, &placeholder);
+            }
+            let len = text.length();
+            let start = text.line_start(std::cmp::min(pos, len));
+            let last = text.line_start(std::cmp::min(pos + inserted, len));
+            let end = std::cmp::min(last + text.line_text(last).len() as u32, len);
+            let lines = text.text_range(start, end).unwrap_or_default();
+            styles.replace(start, end, &self.style_text(&lines));
+        });
+        sbuf
+    }
+}
+
 #[cfg(test)]
 mod editor {
+    use super::*;
     #[test]
     fn buffer() {}
+
+    #[test]
+    fn search() {
+        let text = "Foo foo fOO bär BÄR";
+        assert_eq!(find_matches(text, "foo", true, false).unwrap(), vec![(4, 7)]);
+        assert_eq!(find_matches(text, "foo", false, false).unwrap().len(), 3);
+        assert_eq!(find_matches(text, "bär", false, false).unwrap(), vec![(12, 16), (17, 21)]);
+        assert_eq!(find_matches("aaaa", "aa", true, false).unwrap(), vec![(0, 2), (2, 4)]);
+        assert!(find_matches(text, "", true, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn search_bar_toggles() {
+        let mut ed = TextEditor::new(0, 0, 0, 0, "");
+        let mut buf = TextBuffer::default();
+        buf.set_text("Foo foo f.o");
+        ed.set_buffer(Some(buf));
+        let mut bar = SearchBar::new(0, 0, 0, 0, &ed);
+        bar.set_search_text("foo");
+        assert_eq!(bar.matches().len(), 2);
+        bar.set_case_sensitive(true);
+        assert!(bar.case_sensitive());
+        assert_eq!(bar.matches(), vec![(4, 7)]);
+        bar.set_case_sensitive(false);
+        assert_eq!(bar.matches().len(), 2);
+        ed.set_insert_position(8);
+        bar.set_wrap_around(false);
+        assert!(!bar.wrap_around());
+        assert!(!bar.find_next());
+        bar.set_wrap_around(true);
+        assert!(bar.find_next());
+        assert_eq!(ed.insert_position(), 3);
+        #[cfg(feature = "regex")]
+        {
+            bar.set_search_text("f.o");
+            assert_eq!(bar.matches().len(), 1);
+            bar.set_use_regex(true);
+            assert!(bar.use_regex());
+            assert_eq!(bar.matches().len(), 3);
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn highlighter() {
+        let hl = Highlighter::from_rules(&[(r"//.*", 'C'), (r"\blet\b", 'B')]).unwrap();
+        assert_eq!(hl.style_text("let x; // let"), "BBBAAAACCCCCC");
+        assert_eq!(hl.style_text("a\nlet"), "AABBB");
+        assert_eq!(hl.style_text("é"), "AA");
+    }
 }
@@ -0,0 +1,255 @@
+pub use crate::prelude::*;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+static NEXT_TRAY_ACTION: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    static TRAY_ACTIONS: RefCell<HashMap<usize, Box<dyn FnMut()>>> = RefCell::new(HashMap::new());
+}
+
+fn add_tray_action(cb: Box<dyn FnMut()>) -> usize {
+    let id = NEXT_TRAY_ACTION.fetch_add(1, Ordering::Relaxed);
+    TRAY_ACTIONS.with(|a| a.borrow_mut().insert(id, cb));
+    id
+}
+
+/// Runs a tray action in the main thread, the tray service running in its own thread
+#[allow(dead_code)]
+fn run_tray_action(id: usize) {
+    crate::app::awake_callback_once(move || {
+        let cb = TRAY_ACTIONS.with(|a| a.borrow_mut().remove(&id));
+        if let Some(mut cb) = cb {
+            cb();
+            // the action might have been removed while it ran
+            TRAY_ACTIONS.with(|a| a.borrow_mut().entry(id).or_insert(cb));
+        }
+    });
+}
+
+/// Converts image data of any depth to ARGB32 pixels in network byte order
+fn argb_pixels(data: &[u8], depth: usize) -> Vec<u8> {
+    let depth = depth.max(1);
+    let mut buf = Vec::with_capacity(data.len() / depth * 4);
+    for px in data.chunks_exact(depth) {
+        match px {
+            [l] => buf.extend_from_slice(&[255, *l, *l, *l]),
+            [l, a] => buf.extend_from_slice(&[*a, *l, *l, *l]),
+            [r, g, b] => buf.extend_from_slice(&[255, *r, *g, *b]),
+            [r, g, b, a, ..] => buf.extend_from_slice(&[*a, *r, *g, *b]),
+            _ => (),
+        }
+    }
+    buf
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrayModel {
+    title: String,
+    tooltip: String,
+    icon: Option<(i32, i32, Vec<u8>)>,
+    // None for separators
+    menu: Vec<Option<(String, usize)>>,
+    activate: Option<usize>,
+}
+
+#[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+impl ksni::Tray for TrayModel {
+    fn id(&self) -> String {
+        self.title.clone()
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn icon_name(&self) -> String {
+        if self.icon.is_none() {
+            "application-x-executable".to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    fn icon_pixmap(&self) -> Vec<ksni::Icon> {
+        match &self.icon {
+            Some((width, height, data)) => vec![ksni::Icon {
+                width: *width,
+                height: *height,
+                data: data.clone(),
+            }],
+            None => vec![],
+        }
+    }
+
+    fn tool_tip(&self) -> ksni::ToolTip {
+        ksni::ToolTip {
+            title: self.tooltip.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        if let Some(id) = self.activate {
+            run_tray_action(id);
+        }
+    }
+
+    fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+        self.menu
+            .iter()
+            .map(|item| match item {
+                Some((label, id)) => {
+                    let id = *id;
+                    ksni::menu::StandardItem {
+                        label: label.clone(),
+                        activate: Box::new(move |_: &mut Self| run_tray_action(id)),
+                        ..Default::default()
+                    }
+                    .into()
+                }
+                None => ksni::MenuItem::Sepatator,
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+type TrayHandle = ksni::Handle<TrayModel>;
+
+#[cfg(not(all(unix, not(target_os = "macos"), not(target_os = "android"))))]
+type TrayHandle = ();
+
+/// Creates a system tray icon, with a context menu and a callback run when the icon is activated.
+/// It's currently supported on Linux and BSDs, with desktops implementing StatusNotifierItem (KDE, or Gnome with an extension).
+/// The callbacks are run in the main thread, so they can mutate widgets, for example to show a window minimized to the tray
+/// ```no_run
+/// use fltk::*;
+/// let mut win = window::Window::new(100, 100, 400, 300, "");
+/// win.end();
+/// let mut tray = tray::Tray::new("My app").unwrap();
+/// tray.set_tooltip("My app is running");
+/// let mut w = win.clone();
+/// tray.set_activate_callback(move || w.show());
+/// tray.add_menu_item("Quit", app::quit);
+/// ```
+pub struct Tray {
+    model: TrayModel,
+    handle: TrayHandle,
+}
+
+impl std::fmt::Debug for Tray {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tray").field("model", &self.model).finish()
+    }
+}
+
+impl Tray {
+    /// Creates and shows a tray icon.
+    /// # Errors
+    /// Errors on unsupported platforms
+    pub fn new(title: &str) -> Result<Tray, FltkError> {
+        let model = TrayModel {
+            title: title.to_string(),
+            tooltip: title.to_string(),
+            ..Default::default()
+        };
+        #[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+        {
+            let service = ksni::TrayService::new(model.clone());
+            let handle = service.handle();
+            service.spawn();
+            Ok(Tray { model, handle })
+        }
+        #[cfg(not(all(unix, not(target_os = "macos"), not(target_os = "android"))))]
+        {
+            let _ = model;
+            Err(FltkError::Internal(FltkErrorKind::FailedOperation))
+        }
+    }
+
+    fn update(&mut self) {
+        #[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+        {
+            let model = self.model.clone();
+            self.handle.update(move |m| *m = model);
+        }
+    }
+
+    /// Sets the tooltip of the tray icon
+    pub fn set_tooltip(&mut self, txt: &str) {
+        self.model.tooltip = txt.to_string();
+        self.update();
+    }
+
+    /// Sets the image of the tray icon, the default icon of the desktop being used otherwise
+    pub fn set_icon<I: ImageExt>(&mut self, image: &I) {
+        let data = argb_pixels(&image.to_rgb_data(), image.depth() as usize);
+        self.model.icon = Some((image.data_w() as i32, image.data_h() as i32, data));
+        self.update();
+    }
+
+    /// Adds an item to the context menu of the tray icon
+    pub fn add_menu_item<F: FnMut() + 'static>(&mut self, label: &str, cb: F) {
+        let id = add_tray_action(Box::new(cb));
+        self.model.menu.push(Some((label.to_string(), id)));
+        self.update();
+    }
+
+    /// Adds a separator to the context menu of the tray icon
+    pub fn add_separator(&mut self) {
+        self.model.menu.push(None);
+        self.update();
+    }
+
+    /// Removes all the items of the context menu
+    pub fn clear_menu(&mut self) {
+        for (_, id) in self.model.menu.drain(..).flatten() {
+            TRAY_ACTIONS.with(|a| a.borrow_mut().remove(&id));
+        }
+        self.update();
+    }
+
+    /// Sets the callback run when the tray icon is activated, usually by clicking it
+    pub fn set_activate_callback<F: FnMut() + 'static>(&mut self, cb: F) {
+        if let Some(id) = self.model.activate.take() {
+            TRAY_ACTIONS.with(|a| a.borrow_mut().remove(&id));
+        }
+        self.model.activate = Some(add_tray_action(Box::new(cb)));
+        self.update();
+    }
+}
+
+impl Drop for Tray {
+    fn drop(&mut self) {
+        let ids = self
+            .model
+            .menu
+            .iter()
+            .flatten()
+            .map(|(_, id)| *id)
+            .chain(self.model.activate);
+        TRAY_ACTIONS.with(|a| {
+            let mut a = a.borrow_mut();
+            for id in ids {
+                a.remove(&id);
+            }
+        });
+        #[cfg(all(unix, not(target_os = "macos"), not(target_os = "android")))]
+        self.handle.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tray {
+    use super::*;
+    #[test]
+    fn icon_pixels() {
+        assert_eq!(argb_pixels(&[1, 2, 3], 3), vec![255, 1, 2, 3]);
+        assert_eq!(argb_pixels(&[1, 2, 3, 4], 4), vec![4, 1, 2, 3]);
+        assert_eq!(argb_pixels(&[9, 128], 2), vec![128, 9, 9, 9]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::app;
 use crate::image::Image;
 pub use crate::prelude::*;
 use crate::widget::Widget;
@@ -1167,6 +1168,71 @@ impl Tree {
         assert!(!self.was_deleted());
         unsafe { mem::transmute(Fl_Tree_callback_reason(self._inner)) }
     }
+
+    /// Lets the user reorder items by dragging them.
+    /// Dropping an item on the upper or lower quarter of another item moves it above or below that item,
+    /// dropping it on the middle of an item moves it into that item as its last child.
+    /// The callback is called with the tree, the moved item and its new parent.
+    /// This installs a custom handle method, so it replaces any set using handle or handle2
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut tree = tree::Tree::new(5, 5, 300, 400, "");
+    /// tree.add("Playlist/Song 1");
+    /// tree.add("Playlist/Song 2");
+    /// tree.enable_drag_reorder(|_, item, parent| {
+    ///     println!("{:?} moved into {:?}", item.label(), parent.and_then(|p| p.label()));
+    /// });
+    /// ```
+    pub fn enable_drag_reorder<F: FnMut(&mut Tree, &TreeItem, Option<TreeItem>) + 'static>(
+        &mut self,
+        mut cb: F,
+    ) {
+        assert!(!self.was_deleted());
+        let mut dragged: Option<TreeItem> = None;
+        self.handle2(move |t, ev| match ev {
+            Event::Push => {
+                dragged = t.find_clicked(true).filter(|item| !item.is_root());
+                false
+            }
+            Event::Drag => dragged.is_some(),
+            Event::Released => {
+                let mut item = match dragged.take() {
+                    Some(item) => item,
+                    None => return false,
+                };
+                let target = match t.find_clicked(true) {
+                    Some(target) => target,
+                    None => return false,
+                };
+                if target._inner == item._inner || target.is_descendant_of(&item) {
+                    return false;
+                }
+                let (ty, th) = (target.y(), target.h());
+                let ey = app::event_y();
+                let moved = if target.is_root() {
+                    item.move_into(&target, target.children())
+                } else if ey < ty + th / 4 {
+                    item.move_above(target.clone())
+                } else if ey > ty + th * 3 / 4 {
+                    item.move_below(target.clone())
+                } else {
+                    let mut target = target;
+                    let pos = target.children();
+                    let ret = item.move_into(&target, pos);
+                    target.open();
+                    ret
+                };
+                if moved.is_err() {
+                    return false;
+                }
+                t.redraw();
+                let parent = item.parent();
+                cb(t, &item, parent);
+                true
+            }
+            _ => false,
+        });
+    }
 }
 
 impl IntoIterator for Tree {
@@ -1352,20 +1418,48 @@ impl TreeItem {
         unsafe { mem::transmute(Fl_Tree_Item_labelbgcolor(self._inner)) }
     }
 
-    /// Sets the item's associated widget
+    /// Sets the item's associated widget, which is drawn inline in the item's row.
+    /// The widget is added to the tree if it isn't already a child of it.
+    /// The tree's item draw mode determines whether the label is drawn next to the widget
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut tree = tree::Tree::new(5, 5, 300, 400, "");
+    /// tree.set_item_draw_mode(tree::TreeItemDrawMode::LabelAndWidget);
+    /// let mut item = tree.add("Tasks/Build").unwrap();
+    /// let check = button::CheckButton::new(0, 0, 20, 20, "");
+    /// item.set_widget(&check);
+    /// ```
     pub fn set_widget<W: WidgetExt>(&mut self, val: &W) {
         assert!(!val.was_deleted());
         assert!(!self.was_deleted());
-        unsafe { Fl_Tree_Item_set_widget(self._inner, val.as_widget_ptr() as *mut Fl_Widget) }
+        unsafe {
+            let tree = self._tree.as_widget_ptr();
+            let is_child = val
+                .parent()
+                .map(|p| p.as_widget_ptr() == tree)
+                .unwrap_or(false);
+            if !is_child {
+                fltk_sys::group::Fl_Group_add(
+                    tree as *mut fltk_sys::group::Fl_Group,
+                    val.as_widget_ptr() as _,
+                );
+            }
+            Fl_Tree_Item_set_widget(self._inner, val.as_widget_ptr() as *mut Fl_Widget)
+        }
     }
 
-    /// Gets the item's associated widget
-    pub fn widget(&self) -> Box<dyn WidgetExt> {
+    /// Gets the item's associated widget, if any
+    pub fn widget(&self) -> Option<Box<dyn WidgetExt>> {
         assert!(!self.was_deleted());
         unsafe {
-            Box::new(Widget::from_widget_ptr(
-                Fl_Tree_Item_widget(self._inner) as *mut fltk_sys::widget::Fl_Widget
-            ))
+            let ptr = Fl_Tree_Item_widget(self._inner);
+            if ptr.is_null() {
+                None
+            } else {
+                Some(Box::new(Widget::from_widget_ptr(
+                    ptr as *mut fltk_sys::widget::Fl_Widget,
+                )))
+            }
         }
     }
 
@@ -1586,6 +1680,19 @@ impl TreeItem {
         unsafe { Fl_Tree_Item_update_prev_next(self._inner, index as i32) }
     }
 
+    /// Returns whether the item is a descendant of another item
+    pub fn is_descendant_of(&self, other: &TreeItem) -> bool {
+        assert!(!self.was_deleted() && !other.was_deleted());
+        let mut parent = self.parent();
+        while let Some(p) = parent {
+            if p._inner == other._inner {
+                return true;
+            }
+            parent = p.parent();
+        }
+        false
+    }
+
     /// Return the parent of the item
     pub fn parent(&self) -> Option<TreeItem> {
         assert!(!self.was_deleted());
@@ -0,0 +1,496 @@
+use crate::fluid::{FluidNode, FluidUi, FLUID_FLAGS};
+use crate::prelude::*;
+use crate::window::Window;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt, fs, mem,
+    os::raw,
+    path::{Path, PathBuf},
+    rc::{Rc, Weak},
+    time::SystemTime,
+};
+
+/// A user interface built from a description, its widgets being looked up by id
+pub type Ui = FluidUi;
+
+/// A serde-friendly description of a widget and its children
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "use-serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "use-serde", serde(default))]
+pub struct WidgetDesc {
+    /// The widget type, named like its fltk-rs type (`Button`, `HoldBrowser`) or its FLTK class (`Fl_Button`).
+    /// Menu widgets take `MenuItem` and `Submenu` children
+    pub kind: String,
+    /// The id used to look up the widget
+    pub id: Option<String>,
+    /// The x coordinate, relative to the window
+    pub x: i32,
+    /// The y coordinate, relative to the window
+    pub y: i32,
+    /// The width
+    pub w: i32,
+    /// The height
+    pub h: i32,
+    /// The label
+    pub label: Option<String>,
+    /// The properties, named and written like in FLUID files (`box`, `color`, `labelsize`, `value`, `minimum`, `resizable`...).
+    /// Colors can also be written as `#rrggbb`, and flags like `resizable` take `true` or `false`
+    pub props: BTreeMap<String, String>,
+    /// The id of the callback, set using Builder::on
+    pub callback: Option<String>,
+    /// The children
+    pub children: Vec<WidgetDesc>,
+}
+
+/// The FLUID classes and types of the widget kinds
+const UI_KINDS: &[(&str, &str, Option<&str>)] = &[
+    ("Window", "Fl_Double_Window", None),
+    ("DoubleWindow", "Fl_Double_Window", None),
+    ("SingleWindow", "Fl_Window", None),
+    ("Group", "Fl_Group", None),
+    ("Pack", "Fl_Pack", None),
+    ("Tabs", "Fl_Tabs", None),
+    ("Scroll", "Fl_Scroll", None),
+    ("Tile", "Fl_Tile", None),
+    ("Wizard", "Fl_Wizard", None),
+    ("Frame", "Fl_Box", None),
+    ("Button", "Fl_Button", None),
+    ("ToggleButton", "Fl_Button", Some("Toggle")),
+    ("RadioButton", "Fl_Button", Some("Radio")),
+    ("ReturnButton", "Fl_Return_Button", None),
+    ("RepeatButton", "Fl_Repeat_Button", None),
+    ("LightButton", "Fl_Light_Button", None),
+    ("CheckButton", "Fl_Check_Button", None),
+    ("RoundButton", "Fl_Round_Button", None),
+    ("Input", "Fl_Input", None),
+    ("IntInput", "Fl_Int_Input", None),
+    ("FloatInput", "Fl_Float_Input", None),
+    ("SecretInput", "Fl_Secret_Input", None),
+    ("MultilineInput", "Fl_Multiline_Input", None),
+    ("FileInput", "Fl_File_Input", None),
+    ("Output", "Fl_Output", None),
+    ("MultilineOutput", "Fl_Multiline_Output", None),
+    ("TextDisplay", "Fl_Text_Display", None),
+    ("TextEditor", "Fl_Text_Editor", None),
+    ("Slider", "Fl_Slider", None),
+    ("HorSlider", "Fl_Slider", Some("Horizontal")),
+    ("FillSlider", "Fl_Slider", Some("Vert Fill")),
+    ("HorFillSlider", "Fl_Slider", Some("Horz Fill")),
+    ("ValueSlider", "Fl_Value_Slider", None),
+    ("HorValueSlider", "Fl_Value_Slider", Some("Horizontal")),
+    ("Dial", "Fl_Dial", None),
+    ("Roller", "Fl_Roller", None),
+    ("Counter", "Fl_Counter", None),
+    ("Adjuster", "Fl_Adjuster", None),
+    ("Scrollbar", "Fl_Scrollbar", None),
+    ("ValueInput", "Fl_Value_Input", None),
+    ("ValueOutput", "Fl_Value_Output", None),
+    ("Spinner", "Fl_Spinner", None),
+    ("Progress", "Fl_Progress", None),
+    ("Clock", "Fl_Clock", None),
+    ("Chart", "Fl_Chart", None),
+    ("MenuBar", "Fl_Menu_Bar", None),
+    ("MenuButton", "Fl_Menu_Button", None),
+    ("Choice", "Fl_Choice", None),
+    ("MenuItem", "MenuItem", None),
+    ("Submenu", "Submenu", None),
+    ("Browser", "Fl_Browser", None),
+    ("HoldBrowser", "Fl_Hold_Browser", None),
+    ("MultiBrowser", "Fl_Multi_Browser", None),
+    ("FileBrowser", "Fl_File_Browser", None),
+    ("CheckBrowser", "Fl_Check_Browser", None),
+    ("Tree", "Fl_Tree", None),
+];
+
+/// Converts a `#rrggbb` color to the FLUID format
+fn ui_color(val: &str) -> Option<String> {
+    let hex = val.strip_prefix('#')?;
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some((rgb << 8).to_string())
+}
+
+/// Builds widget trees from descriptions, applying per-kind styles and binding callbacks by id
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let desc = ui::WidgetDesc {
+///     kind: "Window".to_string(),
+///     w: 300,
+///     h: 200,
+///     children: vec![ui::WidgetDesc {
+///         kind: "Button".to_string(),
+///         id: Some("ok".to_string()),
+///         x: 110, y: 80, w: 80, h: 40,
+///         label: Some("Ok".to_string()),
+///         callback: Some("quit".to_string()),
+///         ..Default::default()
+///     }],
+///     ..Default::default()
+/// };
+/// let mut builder = ui::Builder::new();
+/// builder.set_style("Button", "box", "FLAT_BOX");
+/// builder.on("quit", app::quit);
+/// let ui = builder.build(&desc).unwrap();
+/// ui.windows()[0].show();
+/// app.run().unwrap();
+/// ```
+#[derive(Default, Clone)]
+pub struct Builder {
+    styles: BTreeMap<String, BTreeMap<String, String>>,
+    callbacks: HashMap<String, Rc<RefCell<dyn FnMut()>>>,
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("styles", &self.styles)
+            .field("callbacks", &self.callbacks.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Builder {
+    /// Creates a new builder
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Sets a property of all the widgets of a kind, unless their description sets it, which allows swapping themes
+    pub fn set_style(&mut self, kind: &str, key: &str, value: &str) {
+        self.styles
+            .entry(kind.to_string())
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    /// Removes all the styles
+    pub fn clear_styles(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Sets the callback of the widgets whose description has the callback id
+    pub fn on<F: FnMut() + 'static>(&mut self, id: &str, cb: F) {
+        self.callbacks
+            .insert(id.to_string(), Rc::new(RefCell::new(cb)));
+    }
+
+    /// Builds a description, outside of any group, which is usually a window
+    pub fn build(&self, desc: &WidgetDesc) -> Result<Ui, FltkError> {
+        let node = self.to_node(desc)?;
+        Ok(self.bind(FluidUi::build(&[node], false)))
+    }
+
+    /// Builds a description inside a group
+    pub fn build_in<G: GroupExt>(&self, parent: &G, desc: &WidgetDesc) -> Result<Ui, FltkError> {
+        let node = self.to_node(desc)?;
+        parent.begin();
+        let ui = FluidUi::build(&[node], true);
+        parent.end();
+        Ok(self.bind(ui))
+    }
+
+    fn bind(&self, mut ui: Ui) -> Ui {
+        for (id, cb) in &self.callbacks {
+            let cb = cb.clone();
+            ui.bind_callback(id, move || (*cb.borrow_mut())());
+        }
+        ui
+    }
+
+    pub(crate) fn to_node(&self, desc: &WidgetDesc) -> Result<FluidNode, FltkError> {
+        let (class, typ) = if desc.kind.starts_with("Fl_") {
+            (desc.kind.as_str(), None)
+        } else {
+            UI_KINDS
+                .iter()
+                .find(|(k, _, _)| *k == desc.kind)
+                .map(|(_, c, t)| (*c, *t))
+                .ok_or_else(|| FltkError::Unknown(format!("Unknown widget kind {}", desc.kind)))?
+        };
+        let mut props: BTreeMap<&str, &str> = BTreeMap::new();
+        if let Some(style) = self.styles.get(&desc.kind) {
+            props.extend(style.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        }
+        props.extend(desc.props.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        let mut node = FluidNode {
+            class: class.to_string(),
+            name: desc.id.clone().unwrap_or_default(),
+            props: vec![(
+                "xywh".to_string(),
+                Some(format!("{} {} {} {}", desc.x, desc.y, desc.w, desc.h)),
+            )],
+            children: vec![],
+        };
+        if let Some(typ) = typ {
+            node.props.push(("type".to_string(), Some(typ.to_string())));
+        }
+        if let Some(label) = &desc.label {
+            node.props.push(("label".to_string(), Some(label.clone())));
+        }
+        if let Some(cb) = &desc.callback {
+            node.props.push(("callback".to_string(), Some(cb.clone())));
+        }
+        for (k, v) in props {
+            if FLUID_FLAGS.contains(&k) {
+                if v != "false" {
+                    node.props.push((k.to_string(), None));
+                }
+            } else {
+                let v = if k.ends_with("color") {
+                    ui_color(v).unwrap_or_else(|| v.to_string())
+                } else {
+                    v.to_string()
+                };
+                node.props.push((k.to_string(), Some(v)));
+            }
+        }
+        for child in &desc.children {
+            node.children.push(self.to_node(child)?);
+        }
+        Ok(node)
+    }
+}
+
+/// How often, in seconds, the watched description files are checked
+const HOT_RELOAD_INTERVAL: f64 = 0.5;
+
+type DescParser = Box<dyn Fn(&str) -> Result<WidgetDesc, FltkError>>;
+
+struct HotReloadState {
+    path: PathBuf,
+    builder: Builder,
+    parse: DescParser,
+    ui: Ui,
+    modified: Option<SystemTime>,
+    error: Option<String>,
+    on_reload: Option<Box<dyn FnMut(&Ui)>>,
+}
+
+thread_local! {
+    static HOT_RELOADS: RefCell<Vec<Weak<RefCell<HotReloadState>>>> = RefCell::new(Vec::new());
+    static HOT_RELOAD_TIMER_ADDED: RefCell<bool> = RefCell::new(false);
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Checks the watched files, the timer stops once no HotReload is left
+unsafe extern "C" fn hot_reload_tick(_data: *mut raw::c_void) {
+    let states: Vec<_> = HOT_RELOADS.with(|h| {
+        let mut h = h.borrow_mut();
+        h.retain(|st| st.strong_count() > 0);
+        h.iter().filter_map(|st| st.upgrade()).collect()
+    });
+    for st in &states {
+        let changed = {
+            let mut st = st.borrow_mut();
+            let modified = modified_time(&st.path);
+            modified.is_some() && mem::replace(&mut st.modified, modified) != modified
+        };
+        if changed {
+            let res = HotReload::rebuild(st);
+            st.borrow_mut().error = res.err().map(|e| e.to_string());
+        }
+    }
+    if states.is_empty() {
+        HOT_RELOAD_TIMER_ADDED.with(|a| *a.borrow_mut() = false);
+    } else {
+        fltk_sys::fl::Fl_repeat_timeout(
+            HOT_RELOAD_INTERVAL,
+            Some(hot_reload_tick),
+            std::ptr::null_mut(),
+        );
+    }
+}
+
+/// Builds a user interface from a description file and, in debug builds, rebuilds its windows in place
+/// when the file changes. Widgets with the same id in both versions keep their values,
+/// and the builder's styles and callbacks are applied again.
+/// The parser is passed in, which allows any format.
+/// ```no_run
+/// use fltk::*;
+/// let app = app::App::default();
+/// let mut builder = ui::Builder::new();
+/// builder.on("quit", app::quit);
+/// // a window line followed by a line per widget, written as `kind id x y w h label`
+/// fn parse_layout(s: &str) -> Result<ui::WidgetDesc, FltkError> {
+///     let mut descs = s.lines().filter(|l| !l.trim().is_empty()).map(|l| {
+///         let f: Vec<&str> = l.trim().splitn(7, ' ').collect();
+///         let num = |i: usize| f.get(i).and_then(|v| v.parse().ok()).unwrap_or(0);
+///         ui::WidgetDesc {
+///             kind: f[0].to_string(),
+///             id: f.get(1).map(|id| id.to_string()),
+///             x: num(2),
+///             y: num(3),
+///             w: num(4),
+///             h: num(5),
+///             label: f.get(6).map(|l| l.to_string()),
+///             ..Default::default()
+///         }
+///     });
+///     let mut win = descs
+///         .next()
+///         .ok_or_else(|| FltkError::Unknown(String::from("Empty layout")))?;
+///     win.children = descs.collect();
+///     Ok(win)
+/// }
+/// let mut hot = ui::HotReload::new("layout.txt", builder, parse_layout).unwrap();
+/// hot.set_reload_callback(|ui| {
+///     if let Some(mut btn) = ui.get::<button::Button>("ok") {
+///         btn.set_callback(|| println!("Ok"));
+///     }
+/// });
+/// hot.ui().windows()[0].show();
+/// app.run().unwrap();
+/// ```
+pub struct HotReload {
+    state: Rc<RefCell<HotReloadState>>,
+}
+
+impl fmt::Debug for HotReload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let st = self.state.borrow();
+        f.debug_struct("HotReload")
+            .field("path", &st.path)
+            .field("builder", &st.builder)
+            .field("ui", &st.ui)
+            .field("error", &st.error)
+            .finish()
+    }
+}
+
+impl HotReload {
+    /// Reads, parses and builds a description file, watching it for changes in debug builds
+    pub fn new<P, F>(path: P, builder: Builder, parse: F) -> Result<HotReload, FltkError>
+    where
+        P: AsRef<Path>,
+        F: Fn(&str) -> Result<WidgetDesc, FltkError> + 'static,
+    {
+        let path = path.as_ref().to_path_buf();
+        let modified = modified_time(&path);
+        let desc = parse(&fs::read_to_string(&path)?)?;
+        let ui = builder.build(&desc)?;
+        let state = Rc::new(RefCell::new(HotReloadState {
+            path,
+            builder,
+            parse: Box::new(parse),
+            ui,
+            modified,
+            error: None,
+            on_reload: None,
+        }));
+        if cfg!(debug_assertions) {
+            HOT_RELOADS.with(|h| h.borrow_mut().push(Rc::downgrade(&state)));
+            let added = HOT_RELOAD_TIMER_ADDED.with(|a| mem::replace(&mut *a.borrow_mut(), true));
+            if !added {
+                unsafe {
+                    fltk_sys::fl::Fl_add_timeout(
+                        HOT_RELOAD_INTERVAL,
+                        Some(hot_reload_tick),
+                        std::ptr::null_mut(),
+                    )
+                }
+            }
+        }
+        Ok(HotReload { state })
+    }
+
+    /// Returns the current user interface, which changes on every reload
+    pub fn ui(&self) -> Ui {
+        self.state.borrow().ui.clone()
+    }
+
+    /// Sets a callback run after every reload, to set up the new widgets
+    pub fn set_reload_callback<F: FnMut(&Ui) + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().on_reload = Some(Box::new(cb));
+    }
+
+    /// Returns the error of the last automatic reload, the previous user interface being kept on error
+    pub fn last_error(&self) -> Option<String> {
+        self.state.borrow().error.clone()
+    }
+
+    /// Rebuilds the user interface from the file, whether it changed or not
+    pub fn reload(&mut self) -> Result<(), FltkError> {
+        let res = HotReload::rebuild(&self.state);
+        self.state.borrow_mut().error = None;
+        res
+    }
+
+    fn rebuild(state: &Rc<RefCell<HotReloadState>>) -> Result<(), FltkError> {
+        let (ui, old) = {
+            let st = state.borrow();
+            let desc = (st.parse)(&fs::read_to_string(&st.path)?)?;
+            (st.builder.build(&desc)?, st.ui.clone())
+        };
+        for name in old.names() {
+            if let Some(val) = old.widget_state(&name) {
+                ui.set_widget_state(&name, &val);
+            }
+        }
+        let mut new_wins = ui.windows();
+        for (i, mut old_win) in old.windows().into_iter().enumerate() {
+            if old_win.was_deleted() {
+                continue;
+            }
+            if let Some(win) = new_wins.get_mut(i) {
+                win.resize(old_win.x(), old_win.y(), win.w(), win.h());
+                if old_win.shown() {
+                    win.show();
+                }
+            }
+            old_win.hide();
+            Window::delete(old_win);
+        }
+        state.borrow_mut().ui = ui.clone();
+        // the callback is taken out so it can use the HotReload
+        let cb = state.borrow_mut().on_reload.take();
+        if let Some(mut cb) = cb {
+            cb(&ui);
+            let mut st = state.borrow_mut();
+            if st.on_reload.is_none() {
+                st.on_reload = Some(cb);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod ui {
+    use super::*;
+    #[test]
+    fn description_nodes() {
+        let desc = WidgetDesc {
+            kind: "ToggleButton".to_string(),
+            id: Some("bold".to_string()),
+            w: 30,
+            h: 20,
+            props: vec![
+                ("color".to_string(), "#ff0000".to_string()),
+                ("resizable".to_string(), "true".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let mut builder = Builder::new();
+        builder.set_style("ToggleButton", "labelsize", "12");
+        builder.set_style("ToggleButton", "color", "1");
+        let node = builder.to_node(&desc).unwrap();
+        assert_eq!(node.class, "Fl_Button");
+        assert_eq!(node.name, "bold");
+        assert_eq!(node.prop("type"), Some("Toggle"));
+        assert_eq!(node.prop("xywh"), Some("0 0 30 20"));
+        assert_eq!(node.prop("color"), Some("4278190080"));
+        assert_eq!(node.prop("labelsize"), Some("12"));
+        assert!(node.has("resizable"));
+        let bad = WidgetDesc {
+            kind: "Nope".to_string(),
+            ..Default::default()
+        };
+        assert!(builder.to_node(&bad).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use std::ffi::CString;
 
 pub(crate) trait FlString {
@@ -16,6 +17,138 @@ impl FlString for CString {
     }
 }
 
+/// Returns the bounds of the widget's parent in the coordinate space of the widget
+pub(crate) fn parent_bounds<W: crate::prelude::WidgetExt>(w: &W) -> (i32, i32, i32, i32) {
+    raw_parent_bounds(unsafe { w.as_widget_ptr() })
+        .expect("The widget should be added to a group before anchoring it to its parent!")
+}
+
+/// Returns the bounds of the parent of a widget pointer in the coordinate space of the widget.
+/// This doesn't wrap the parent, so it's cheap to call from handlers and draw methods
+pub(crate) fn raw_parent_bounds(
+    w: *mut fltk_sys::widget::Fl_Widget,
+) -> Option<(i32, i32, i32, i32)> {
+    use fltk_sys::widget::*;
+    unsafe {
+        let parent = Fl_Widget_parent(w) as *mut Fl_Widget;
+        if parent.is_null() {
+            return None;
+        }
+        let (pw, ph) = (Fl_Widget_width(parent), Fl_Widget_height(parent));
+        if Fl_Widget_as_window(parent).is_null() {
+            Some((Fl_Widget_x(parent), Fl_Widget_y(parent), pw, ph))
+        } else {
+            Some((0, 0, pw, ph))
+        }
+    }
+}
+
+/// Redraws the parent of a widget pointer, if any, without wrapping it.
+/// Returns whether the widget has a parent
+pub(crate) fn redraw_parent(w: *mut fltk_sys::widget::Fl_Widget) -> bool {
+    unsafe {
+        let parent = fltk_sys::widget::Fl_Widget_parent(w) as *mut fltk_sys::widget::Fl_Widget;
+        if !parent.is_null() {
+            fltk_sys::widget::Fl_Widget_redraw(parent);
+        }
+        !parent.is_null()
+    }
+}
+
+/// Per-widget state keyed by the widget's address.
+/// Each entry keeps a handle to its widget, so the entries of deleted widgets are dropped instead of being inherited by a new widget reusing the address
+#[derive(Debug)]
+pub(crate) struct WidgetMap<T> {
+    entries: std::collections::HashMap<usize, (crate::widget::Widget, T)>,
+}
+
+impl<T> Default for WidgetMap<T> {
+    fn default() -> Self {
+        WidgetMap {
+            entries: std::collections::HashMap::new(),
+        }
+    }
+}
+
+impl<T> WidgetMap<T> {
+    /// Returns the state of the widget, if any
+    pub(crate) fn get<W: WidgetExt + ?Sized>(&mut self, w: &W) -> Option<&mut T> {
+        let key = unsafe { w.as_widget_ptr() } as usize;
+        if self
+            .entries
+            .get(&key)
+            .map_or(false, |(wid, _)| wid.was_deleted())
+        {
+            self.entries.remove(&key);
+        }
+        self.entries.get_mut(&key).map(|(_, v)| v)
+    }
+
+    /// Sets the state of the widget, returning the previous one
+    pub(crate) fn insert<W: WidgetExt + ?Sized>(&mut self, w: &W, val: T) -> Option<T> {
+        if let Some(old) = self.get(w) {
+            return Some(std::mem::replace(old, val));
+        }
+        self.entries.retain(|_, (wid, _)| !wid.was_deleted());
+        let ptr = unsafe { w.as_widget_ptr() };
+        let wid = unsafe { crate::widget::Widget::from_widget_ptr(ptr) };
+        self.entries.insert(ptr as usize, (wid, val));
+        None
+    }
+
+    /// Removes the state of the widget
+    pub(crate) fn remove<W: WidgetExt + ?Sized>(&mut self, w: &W) -> Option<T> {
+        self.get(w)?;
+        let key = unsafe { w.as_widget_ptr() } as usize;
+        self.entries.remove(&key).map(|(_, v)| v)
+    }
+
+    /// Returns the widgets which weren't deleted along with their states
+    pub(crate) fn iter(&mut self) -> impl Iterator<Item = (&crate::widget::Widget, &mut T)> {
+        self.entries.retain(|_, (wid, _)| !wid.was_deleted());
+        self.entries.values_mut().map(|(wid, v)| (&*wid, v))
+    }
+}
+
+/// Returns the closest utf8 character boundary at or before the byte index ```idx```,
+/// the returned index is clamped to the length of the string
+/// Example:
+/// ```no_run
+/// use fltk::utils::utf8_align;
+/// assert_eq!(utf8_align("aé", 2), 1);
+/// ```
+pub fn utf8_align(s: &str, idx: usize) -> usize {
+    let mut idx = std::cmp::min(idx, s.len());
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+/// Converts a character index into a byte index, returns None if the index is out of bounds.
+/// The character index equal to the number of chars maps to the end of the string
+/// Example:
+/// ```no_run
+/// use fltk::utils::char_to_byte_index;
+/// assert_eq!(char_to_byte_index("éa", 1), Some(2));
+/// ```
+pub fn char_to_byte_index(s: &str, idx: usize) -> Option<usize> {
+    s.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()))
+        .nth(idx)
+}
+
+/// Converts a byte index into a character index, the byte index is first aligned to a utf8 character boundary
+/// Example:
+/// ```no_run
+/// use fltk::utils::byte_to_char_index;
+/// assert_eq!(byte_to_char_index("éa", 2), 1);
+/// ```
+pub fn byte_to_char_index(s: &str, idx: usize) -> usize {
+    s[..utf8_align(s, idx)].chars().count()
+}
+
 /// Convenience function to convert rgb to hex
 /// Example:
 /// ```no_run
@@ -54,3 +187,461 @@ pub fn hex2rgba(val: u64) -> (u8, u8, u8, u8) {
     let a = (val & 0xff) as u8;
     (r, g, b, a)
 }
+
+/// Mirrors an alignment horizontally, as used in right-to-left mode
+/// Example:
+/// ```no_run
+/// use fltk::{enums::Align, utils::mirror_align};
+/// assert_eq!(mirror_align(Align::Left | Align::Inside), Align::Right | Align::Inside);
+/// ```
+pub fn mirror_align(align: Align) -> Align {
+    let sides = Align::Left | Align::Right;
+    if align.contains(sides) {
+        // LeftBottom and RightBottom set both sides, the vertical bits telling them apart
+        let ends = Align::Top | Align::Bottom;
+        let swapped = if align.intersects(ends) && !align.contains(ends) {
+            ends.bits() & !(align & ends).bits()
+        } else {
+            (align & ends).bits()
+        };
+        Align::from_bits_truncate((align & !ends).bits() | swapped)
+    } else {
+        let mut mirrored = align & !sides;
+        if align.contains(Align::Left) {
+            mirrored |= Align::Right;
+        }
+        if align.contains(Align::Right) {
+            mirrored |= Align::Left;
+        }
+        mirrored
+    }
+}
+
+/// Mirrors an x coordinate inside the span of a reference widget when in right-to-left mode
+pub(crate) fn rtl_x(x: i32, width: i32, span_x: i32, span_width: i32) -> i32 {
+    if crate::app::is_rtl() {
+        2 * span_x + span_width - x - width
+    } else {
+        x
+    }
+}
+
+/// Mirrors the default alignments of a new widget, moving vertical scrollbars to the left
+pub(crate) fn mirror_widget<W: WidgetExt + 'static>(w: &mut W) {
+    use crate::{browser::*, group::Scroll, text::*};
+    use fltk_sys::{browser, group, text, widget};
+    use std::any::TypeId;
+    w.set_align(mirror_align(w.align()));
+    let id = TypeId::of::<W>();
+    let ptr = unsafe { w.as_widget_ptr() };
+    // the scrollbars are reached through the raw pointers, wrapping them would allocate trackers
+    let scrollbar = unsafe {
+        if id == TypeId::of::<Scroll>() {
+            group::Fl_Scroll_scrollbar(ptr as *mut group::Fl_Scroll)
+        } else if id == TypeId::of::<CheckBrowser>() {
+            browser::Fl_Check_Browser_scrollbar(ptr as *const browser::Fl_Check_Browser) as *mut _
+        } else if [
+            TypeId::of::<Browser>(),
+            TypeId::of::<SelectBrowser>(),
+            TypeId::of::<HoldBrowser>(),
+            TypeId::of::<MultiBrowser>(),
+            TypeId::of::<FileBrowser>(),
+        ]
+        .contains(&id)
+        {
+            browser::Fl_Browser_scrollbar(ptr as *mut browser::Fl_Browser)
+        } else {
+            std::ptr::null_mut()
+        }
+    };
+    if !scrollbar.is_null() {
+        unsafe {
+            widget::Fl_Widget_set_align(scrollbar as *mut widget::Fl_Widget, Align::Left.bits())
+        }
+    } else if [
+        TypeId::of::<TextDisplay>(),
+        TypeId::of::<TextEditor>(),
+        TypeId::of::<SimpleTerminal>(),
+    ]
+    .contains(&id)
+    {
+        unsafe {
+            let disp = ptr as *mut text::Fl_Text_Display;
+            let align = Align::from_bits_truncate(text::Fl_Text_Display_scrollbar_align(disp));
+            text::Fl_Text_Display_set_scrollbar_align(disp, mirror_align(align).bits());
+        }
+    }
+}
+
+/// The steps of a mouse drag reported by DragHelper, positions being relative to the window
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DragEvent {
+    /// A mouse button was pressed
+    Pressed {
+        /// The x coordinate
+        x: i32,
+        /// The y coordinate
+        y: i32,
+    },
+    /// The mouse moved past the threshold, starting a drag from the press position
+    Started {
+        /// The x coordinate of the press
+        x: i32,
+        /// The y coordinate of the press
+        y: i32,
+        /// The horizontal distance moved since the press
+        dx: i32,
+        /// The vertical distance moved since the press
+        dy: i32,
+    },
+    /// The mouse moved during the drag
+    Moved {
+        /// The horizontal distance moved since the last event
+        dx: i32,
+        /// The vertical distance moved since the last event
+        dy: i32,
+        /// The horizontal distance moved since the press
+        total_dx: i32,
+        /// The vertical distance moved since the press
+        total_dy: i32,
+    },
+    /// The button was released, ending the drag
+    Finished {
+        /// The horizontal distance moved since the press
+        total_dx: i32,
+        /// The vertical distance moved since the press
+        total_dy: i32,
+    },
+    /// The button was released without the mouse moving past the threshold
+    Clicked {
+        /// The x coordinate
+        x: i32,
+        /// The y coordinate
+        y: i32,
+    },
+}
+
+/// Tracks the press, drag and release events of a widget to implement draggable items or resize handles.
+/// The distances are measured in screen coordinates, so they aren't affected by moving the widget being dragged.
+/// Handling the Event::Push, by returning true, makes the widget receive the following drag and release events
+/// ```no_run
+/// use fltk::*;
+/// let mut handle = frame::Frame::new(100, 100, 20, 20, "");
+/// handle.set_frame(FrameType::UpBox);
+/// let mut drag = utils::DragHelper::default();
+/// handle.handle2(move |h, ev| match drag.handle(ev) {
+///     Some(utils::DragEvent::Moved { dx, dy, .. }) => {
+///         h.set_pos(h.x() + dx, h.y() + dy);
+///         h.parent().unwrap().redraw();
+///         true
+///     }
+///     Some(_) => true,
+///     None => false,
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct DragHelper {
+    threshold: i32,
+    button: Option<i32>,
+    press: Option<((i32, i32), (i32, i32))>,
+    last: (i32, i32),
+    dragging: bool,
+}
+
+impl Default for DragHelper {
+    fn default() -> Self {
+        DragHelper {
+            threshold: 4,
+            button: None,
+            press: None,
+            last: (0, 0),
+            dragging: false,
+        }
+    }
+}
+
+impl DragHelper {
+    /// Sets the distance in pixels the mouse has to move before a drag starts, 4 by default
+    pub fn set_threshold(&mut self, threshold: i32) {
+        self.threshold = threshold.max(0);
+    }
+
+    /// Returns the drag threshold
+    pub fn threshold(&self) -> i32 {
+        self.threshold
+    }
+
+    /// Restricts dragging to a mouse button, 1 being the left one, or allows any button with None
+    pub fn set_button(&mut self, button: Option<i32>) {
+        self.button = button;
+    }
+
+    /// Returns whether the mouse is being dragged past the threshold
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Returns whether a button was pressed and not yet released
+    pub fn is_pressed(&self) -> bool {
+        self.press.is_some()
+    }
+
+    /// Forgets the current press and drag, the next events being ignored until a button is pressed again
+    pub fn cancel(&mut self) {
+        self.press = None;
+        self.dragging = false;
+    }
+
+    /// Updates the drag state from an event of the widget's handler, returning the step it caused if any
+    pub fn handle(&mut self, ev: Event) -> Option<DragEvent> {
+        self.update(
+            ev,
+            crate::app::event_button(),
+            (crate::app::event_x(), crate::app::event_y()),
+            (crate::app::event_x_root(), crate::app::event_y_root()),
+        )
+    }
+
+    fn update(
+        &mut self,
+        ev: Event,
+        button: i32,
+        pos: (i32, i32),
+        root: (i32, i32),
+    ) -> Option<DragEvent> {
+        let button_ok = self.button.map_or(true, |b| b == button);
+        match ev {
+            Event::Push if button_ok => {
+                self.press = Some((pos, root));
+                self.last = root;
+                self.dragging = false;
+                Some(DragEvent::Pressed { x: pos.0, y: pos.1 })
+            }
+            Event::Drag => {
+                let (press, press_root) = self.press?;
+                let (total_dx, total_dy) = (root.0 - press_root.0, root.1 - press_root.1);
+                let (dx, dy) = (root.0 - self.last.0, root.1 - self.last.1);
+                if self.dragging {
+                    self.last = root;
+                    Some(DragEvent::Moved {
+                        dx,
+                        dy,
+                        total_dx,
+                        total_dy,
+                    })
+                } else if total_dx.abs().max(total_dy.abs()) >= self.threshold {
+                    self.last = root;
+                    self.dragging = true;
+                    Some(DragEvent::Started {
+                        x: press.0,
+                        y: press.1,
+                        dx: total_dx,
+                        dy: total_dy,
+                    })
+                } else {
+                    None
+                }
+            }
+            Event::Released if button_ok => {
+                let (press, press_root) = self.press.take()?;
+                if std::mem::replace(&mut self.dragging, false) {
+                    Some(DragEvent::Finished {
+                        total_dx: root.0 - press_root.0,
+                        total_dy: root.1 - press_root.1,
+                    })
+                } else {
+                    Some(DragEvent::Clicked {
+                        x: press.0,
+                        y: press.1,
+                    })
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses CSV text into records, handling quoted fields containing delimiters, quotes and line breaks
+pub(crate) fn parse_csv(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    assert!(delimiter != '"' && delimiter != '\n' && delimiter != '\r');
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' if field.is_empty() => quoted = true,
+                '\r' if chars.peek() == Some(&'\n') => (),
+                '\n' | '\r' => {
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                c if c == delimiter => record.push(std::mem::take(&mut field)),
+                _ => field.push(c),
+            }
+        }
+    }
+    // the last record may not end with a line break
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+/// Writes records as CSV text, quoting the fields which need it and ending each record with a line break
+pub(crate) fn write_csv<R: AsRef<[String]>>(records: &[R], delimiter: char) -> String {
+    assert!(delimiter != '"' && delimiter != '\n' && delimiter != '\r');
+    let mut out = String::new();
+    for record in records {
+        for (i, field) in record.as_ref().iter().enumerate() {
+            if i > 0 {
+                out.push(delimiter);
+            }
+            if field.contains(|c| c == delimiter || c == '"' || c == '\n' || c == '\r') {
+                out.push('"');
+                out.push_str(&field.replace('"', "\"\""));
+                out.push('"');
+            } else {
+                out.push_str(field);
+            }
+        }
+        out.push_str("\n");
+    }
+    out
+}
+
+/// Escapes backslashes, tabs and line breaks, so that the text fits in a tab-separated field
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+/// Reverts escape()
+pub(crate) fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => out.push('\t'),
+                Some('n') => out.push('\n'),
+                Some(c) => out.push(c),
+                None => out.push('\\'),
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod utils {
+    use super::*;
+    #[test]
+    fn utf8_indices() {
+        let s = "aé中b";
+        assert_eq!(utf8_align(s, 2), 1);
+        assert_eq!(utf8_align(s, 4), 3);
+        assert_eq!(utf8_align(s, 100), s.len());
+        assert_eq!(char_to_byte_index(s, 2), Some(3));
+        assert_eq!(char_to_byte_index(s, 4), Some(s.len()));
+        assert_eq!(char_to_byte_index(s, 5), None);
+        assert_eq!(byte_to_char_index(s, 4), 2);
+        assert_eq!(byte_to_char_index(s, 6), 3);
+    }
+
+    #[test]
+    fn mirrored_aligns() {
+        assert_eq!(mirror_align(Align::Center), Align::Center);
+        assert_eq!(mirror_align(Align::Left), Align::Right);
+        assert_eq!(
+            mirror_align(Align::TopRight | Align::Inside),
+            Align::TopLeft | Align::Inside
+        );
+        assert_eq!(mirror_align(Align::LeftTop), Align::RightTop);
+        assert_eq!(mirror_align(Align::LeftBottom), Align::RightBottom);
+        assert_eq!(mirror_align(Align::RightBottom), Align::LeftBottom);
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let text =
+            "name,notes\r\nBob,\"says \"\"hi\"\", twice\"\n\"Alice\",\"line 1\nline 2\"\n,\n";
+        let records = parse_csv(text, ',');
+        assert_eq!(
+            records,
+            vec![
+                vec!["name", "notes"],
+                vec!["Bob", "says \"hi\", twice"],
+                vec!["Alice", "line 1\nline 2"],
+                vec!["", ""],
+            ]
+        );
+        assert_eq!(parse_csv("a;b", ';'), vec![vec!["a", "b"]]);
+        assert!(parse_csv("", ',').is_empty());
+        let written = write_csv(&records, ',');
+        assert_eq!(parse_csv(&written, ','), records);
+        assert_eq!(
+            write_csv(&[vec!["a;b".to_string(), "c".to_string()]], ';'),
+            "\"a;b\";c\n"
+        );
+    }
+
+    #[test]
+    fn drag_helper() {
+        let mut drag = DragHelper::default();
+        drag.set_button(Some(1));
+        assert_eq!(drag.update(Event::Push, 3, (10, 10), (110, 110)), None);
+        assert_eq!(
+            drag.update(Event::Push, 1, (10, 10), (110, 110)),
+            Some(DragEvent::Pressed { x: 10, y: 10 })
+        );
+        assert_eq!(drag.update(Event::Drag, 1, (12, 11), (112, 111)), None);
+        assert_eq!(
+            drag.update(Event::Drag, 1, (15, 10), (115, 110)),
+            Some(DragEvent::Started {
+                x: 10,
+                y: 10,
+                dx: 5,
+                dy: 0
+            })
+        );
+        assert_eq!(
+            drag.update(Event::Drag, 1, (16, 12), (116, 112)),
+            Some(DragEvent::Moved {
+                dx: 1,
+                dy: 2,
+                total_dx: 6,
+                total_dy: 2
+            })
+        );
+        assert_eq!(
+            drag.update(Event::Released, 1, (16, 12), (116, 112)),
+            Some(DragEvent::Finished {
+                total_dx: 6,
+                total_dy: 2
+            })
+        );
+        assert!(!drag.is_dragging());
+        drag.update(Event::Push, 1, (10, 10), (110, 110));
+        assert_eq!(
+            drag.update(Event::Released, 1, (11, 10), (111, 110)),
+            Some(DragEvent::Clicked { x: 10, y: 10 })
+        );
+        assert_eq!(drag.update(Event::Drag, 1, (30, 30), (130, 130)), None);
+    }
+}
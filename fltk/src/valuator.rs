@@ -1,10 +1,15 @@
+use crate::app;
+use crate::draw;
 use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::valuator::*;
 use std::{
+    cell::RefCell,
     ffi::{CStr, CString},
     mem,
+    ops::{Deref, DerefMut},
     os::raw,
+    rc::Rc,
 };
 
 /// Creates a slider widget
@@ -351,3 +356,502 @@ impl HorValueSlider {
         unsafe { Fl_Hor_Value_Slider_set_text_color(self._inner, color.bits() as u32) }
     }
 }
+
+/// Returns where a value lies between the bounds of a valuator, from 0 to 1
+fn bounded_fraction(min: f64, max: f64, val: f64) -> f64 {
+    if max == min {
+        0.0
+    } else {
+        ((val - min) / (max - min)).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GaugeState {
+    zones: Vec<(f64, f64, Color)>,
+    needle_color: Color,
+    units: String,
+    decimals: usize,
+}
+
+/// Defines an analog gauge (custom widget), a read-only dial drawn with a needle, ticks and colored zones.
+/// The value and bounds are set through the dial it derefs to, like any other valuator
+/// ```no_run
+/// use fltk::*;
+/// let mut gauge = valuator::Gauge::new(5, 5, 200, 200, "Pressure");
+/// gauge.set_range(0., 120.);
+/// gauge.add_zone(90., 120., Color::Red);
+/// gauge.set_units("kPa");
+/// gauge.set_value(75.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Gauge {
+    dial: Dial,
+    state: Rc<RefCell<GaugeState>>,
+}
+
+impl Gauge {
+    /// Creates a new gauge with a range of 0 to 100
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Gauge {
+        let mut dial = Dial::new(x, y, w, h, label);
+        dial.set_range(0., 100.);
+        let state = Rc::new(RefCell::new(GaugeState {
+            zones: vec![],
+            needle_color: Color::Red,
+            units: String::new(),
+            decimals: 0,
+        }));
+        let st = state.clone();
+        dial.draw2(move |d| Gauge::draw_gauge(d, &st.borrow()));
+        dial.handle2(|_, ev| {
+            matches!(
+                ev,
+                Event::Push | Event::Drag | Event::Released | Event::MouseWheel
+            )
+        });
+        Gauge { dial, state }
+    }
+
+    fn draw_gauge(d: &Dial, st: &GaugeState) {
+        let (x, y, w, h) = (d.x(), d.y(), d.width(), d.height());
+        draw::draw_rect_fill(x, y, w, h, d.color());
+        let r = (w.min(h) - 8) / 2;
+        if r <= 0 {
+            return;
+        }
+        let (cx, cy) = (x + w / 2, y + h / 2);
+        let (min, max) = (d.minimum(), d.maximum());
+        let angle = |v: f64| 225. - bounded_fraction(min, max, v) * 270.;
+        let disk = |rad: i32, color: Color, a1: f64, a2: f64| {
+            draw::set_draw_color(color);
+            draw::draw_pie(cx - rad, cy - rad, 2 * rad, 2 * rad, a1, a2);
+        };
+        let point = |rad: f64, deg: f64| {
+            let a = deg.to_radians();
+            (cx + (rad * a.cos()) as i32, cy - (rad * a.sin()) as i32)
+        };
+
+        disk(r, Color::BackGround2, 0., 360.);
+        let (band_out, band_in) = (r * 92 / 100, r * 78 / 100);
+        for (from, to, color) in &st.zones {
+            let (a1, a2) = (angle(*from), angle(*to));
+            disk(band_out, *color, a1.min(a2), a1.max(a2));
+        }
+        disk(band_in, Color::BackGround2, 0., 360.);
+        draw::set_draw_color(Color::ForeGround);
+        draw::draw_arc(cx - r, cy - r, 2 * r, 2 * r, 0., 360.);
+
+        draw::set_font(Font::Helvetica, (r / 8).max(8) as u32);
+        let (lo, hi) = if min < max { (min, max) } else { (max, min) };
+        let (ticks, step) = crate::misc::nice_ticks(lo, hi, 5);
+        for v in ticks {
+            let a = angle(v);
+            let (x1, y1) = point(band_in as f64, a);
+            let (x2, y2) = point(band_out as f64, a);
+            draw::set_draw_color(Color::ForeGround);
+            draw::draw_line(x1, y1, x2, y2);
+            let (lx, ly) = point(r as f64 * 0.62, a);
+            draw::draw_text2(
+                &crate::misc::tick_label(v, step),
+                lx - 20,
+                ly - 7,
+                40,
+                14,
+                Align::Center,
+            );
+        }
+
+        let mut txt = format!("{:.*}", st.decimals, d.value());
+        if !st.units.is_empty() {
+            txt.push(' ');
+            txt.push_str(&st.units);
+        }
+        draw::set_draw_color(Color::ForeGround);
+        draw::draw_text2(&txt, cx - r, cy + r / 3, 2 * r, r / 4, Align::Center);
+
+        let a = angle(d.value());
+        let tip = point(r as f64 * 0.85, a);
+        let b1 = point(4., a + 90.);
+        let b2 = point(4., a - 90.);
+        draw::set_draw_color(st.needle_color);
+        draw::draw_polygon(tip.0, tip.1, b1.0, b1.1, b2.0, b2.1);
+        disk(5, st.needle_color, 0., 360.);
+    }
+
+    /// Adds a colored zone between two values, drawn as a band along the scale
+    pub fn add_zone(&mut self, from: f64, to: f64, color: Color) {
+        self.state.borrow_mut().zones.push((from, to, color));
+        self.dial.redraw();
+    }
+
+    /// Removes all zones
+    pub fn clear_zones(&mut self) {
+        self.state.borrow_mut().zones.clear();
+        self.dial.redraw();
+    }
+
+    /// Returns the zones as (from, to, color)
+    pub fn zones(&self) -> Vec<(f64, f64, Color)> {
+        self.state.borrow().zones.clone()
+    }
+
+    /// Gets the needle's color
+    pub fn needle_color(&self) -> Color {
+        self.state.borrow().needle_color
+    }
+
+    /// Sets the needle's color
+    pub fn set_needle_color(&mut self, color: Color) {
+        self.state.borrow_mut().needle_color = color;
+        self.dial.redraw();
+    }
+
+    /// Sets the units shown after the value
+    pub fn set_units(&mut self, units: &str) {
+        self.state.borrow_mut().units = units.to_string();
+        self.dial.redraw();
+    }
+
+    /// Sets the number of decimals of the shown value
+    pub fn set_decimals(&mut self, decimals: usize) {
+        self.state.borrow_mut().decimals = decimals;
+        self.dial.redraw();
+    }
+}
+
+impl Deref for Gauge {
+    type Target = Dial;
+
+    fn deref(&self) -> &Self::Target {
+        &self.dial
+    }
+}
+
+impl DerefMut for Gauge {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.dial
+    }
+}
+
+/// Defines how dragging the mouse turns a knob
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum KnobMode {
+    /// Dragging up increases the value, dragging down decreases it
+    Vertical,
+    /// The value follows the angle of the pointer around the knob
+    Circular,
+}
+
+#[derive(Debug, Clone)]
+struct KnobState {
+    mode: KnobMode,
+    fine: f64,
+    last: Option<(i32, i32)>,
+    raw: f64,
+}
+
+/// Defines a rotary knob (custom widget), turned by dragging the mouse or with the mouse wheel.
+/// Holding Shift turns it more finely. A knob with a step, set through the dial it derefs to,
+/// moves between the steps, which are shown as ticks around it when there are no more than 50 of them
+/// ```no_run
+/// use fltk::*;
+/// let mut knob = valuator::Knob::new(5, 5, 60, 60, "Gain");
+/// knob.set_range(-12., 12.);
+/// knob.set_mode(valuator::KnobMode::Circular);
+/// knob.set_callback2(|k| println!("{}", k.value()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Knob {
+    dial: Dial,
+    state: Rc<RefCell<KnobState>>,
+}
+
+impl Knob {
+    /// Creates a new knob with a range of 0 to 1, dragged vertically
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> Knob {
+        let mut dial = Dial::new(x, y, w, h, label);
+        let state = Rc::new(RefCell::new(KnobState {
+            mode: KnobMode::Vertical,
+            fine: 10.,
+            last: None,
+            raw: 0.,
+        }));
+        dial.draw2(|d| Knob::draw_knob(d));
+        let st = state.clone();
+        dial.handle2(move |d, ev| Knob::handle_knob(d, &mut st.borrow_mut(), ev));
+        Knob { dial, state }
+    }
+
+    fn angle(d: &Dial, v: f64) -> f64 {
+        225. - bounded_fraction(d.minimum(), d.maximum(), v) * 270.
+    }
+
+    fn draw_knob(d: &Dial) {
+        let (x, y, w, h) = (d.x(), d.y(), d.width(), d.height());
+        draw::draw_rect_fill(x, y, w, h, d.color());
+        let r = (w.min(h) - 4) / 2;
+        if r <= 4 {
+            return;
+        }
+        let (cx, cy) = (x + w / 2, y + h / 2);
+        let point = |rad: f64, deg: f64| {
+            let a = deg.to_radians();
+            (cx + (rad * a.cos()) as i32, cy - (rad * a.sin()) as i32)
+        };
+        let (min, max, step) = (d.minimum(), d.maximum(), d.step());
+        let steps = if step > 0. {
+            ((max - min) / step).abs().round() as i32
+        } else {
+            0
+        };
+        draw::set_draw_color(Color::ForeGround);
+        if steps > 0 && steps <= 50 {
+            for i in 0..=steps {
+                let a = 225. - i as f64 / steps as f64 * 270.;
+                let (x1, y1) = point(r as f64 * 0.85, a);
+                let (x2, y2) = point(r as f64, a);
+                draw::draw_line(x1, y1, x2, y2);
+            }
+        }
+        let body = r * 3 / 4;
+        let (a0, a1) = (Knob::angle(d, d.value()), 225.);
+        draw::set_draw_color(d.selection_color());
+        draw::draw_pie(
+            cx - body - 3,
+            cy - body - 3,
+            2 * body + 6,
+            2 * body + 6,
+            a0,
+            a1,
+        );
+        draw::set_draw_color(Color::Light1);
+        draw::draw_pie(cx - body, cy - body, 2 * body, 2 * body, 0., 360.);
+        draw::set_draw_color(Color::Dark3);
+        draw::draw_arc(cx - body, cy - body, 2 * body, 2 * body, 0., 360.);
+        let (x1, y1) = point(body as f64 * 0.3, a0);
+        let (x2, y2) = point(body as f64 * 0.9, a0);
+        draw::set_line_style(draw::LineStyle::Solid, 2);
+        draw::set_draw_color(Color::ForeGround);
+        draw::draw_line(x1, y1, x2, y2);
+        draw::set_line_style(draw::LineStyle::Solid, 0);
+    }
+
+    fn handle_knob(d: &mut Dial, st: &mut KnobState, ev: Event) -> bool {
+        let (ex, ey) = app::event_coords();
+        let fine = if app::event_state().contains(Shortcut::Shift) {
+            st.fine
+        } else {
+            1.
+        };
+        let range = d.maximum() - d.minimum();
+        let delta = match ev {
+            Event::Push => {
+                st.last = Some((ex, ey));
+                st.raw = d.value();
+                let _ = d.take_focus();
+                return true;
+            }
+            Event::Drag => {
+                let (lx, ly) = match st.last {
+                    Some(last) => last,
+                    None => return true,
+                };
+                st.last = Some((ex, ey));
+                match st.mode {
+                    KnobMode::Vertical => (ly - ey) as f64 / 200. * range / fine,
+                    KnobMode::Circular => {
+                        let (cx, cy) = (d.x() + d.width() / 2, d.y() + d.height() / 2);
+                        let a1 = ((cy - ly) as f64).atan2((lx - cx) as f64).to_degrees();
+                        let a2 = ((cy - ey) as f64).atan2((ex - cx) as f64).to_degrees();
+                        let mut da = a1 - a2;
+                        if da > 180. {
+                            da -= 360.;
+                        } else if da < -180. {
+                            da += 360.;
+                        }
+                        da / 270. * range / fine
+                    }
+                }
+            }
+            Event::Released => {
+                st.last = None;
+                return true;
+            }
+            Event::MouseWheel => {
+                st.raw = d.value();
+                let step = if d.step() > 0. {
+                    d.step()
+                } else {
+                    range / 100.
+                };
+                -app::event_dy() as f64 * if fine > 1. { range / 1000. } else { step }
+            }
+            _ => return false,
+        };
+        st.raw = d.clamp(st.raw + delta);
+        let val = d.clamp(d.round(st.raw));
+        if val != d.value() {
+            d.set_value(val);
+            d.redraw();
+            d.do_callback();
+        }
+        true
+    }
+
+    /// Returns how dragging the mouse turns the knob
+    pub fn mode(&self) -> KnobMode {
+        self.state.borrow().mode
+    }
+
+    /// Sets how dragging the mouse turns the knob
+    pub fn set_mode(&mut self, mode: KnobMode) {
+        self.state.borrow_mut().mode = mode;
+    }
+
+    /// Sets how many times more finely the knob turns while Shift is held, 10 by default
+    pub fn set_fine_factor(&mut self, factor: f64) {
+        assert!(factor >= 1.);
+        self.state.borrow_mut().fine = factor;
+    }
+}
+
+impl Deref for Knob {
+    type Target = Dial;
+
+    fn deref(&self) -> &Self::Target {
+        &self.dial
+    }
+}
+
+impl DerefMut for Knob {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.dial
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LedBarState {
+    segments: u32,
+    thresholds: (Option<f64>, Option<f64>),
+    colors: (Color, Color, Color),
+    off_color: Color,
+}
+
+/// Defines an LED level bar (custom widget), a read-only slider drawn as a row of segments.
+/// Segments above the warning and alarm thresholds light in their own colors.
+/// The bar is vertical, filling upwards, when it's taller than wide, and horizontal otherwise.
+/// The value and bounds are set through the slider it derefs to, like any other valuator
+/// ```no_run
+/// use fltk::*;
+/// let mut bar = valuator::LedBar::new(5, 5, 30, 200, "");
+/// bar.set_thresholds(Some(70.), Some(90.));
+/// bar.set_value(80.);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LedBar {
+    slider: Slider,
+    state: Rc<RefCell<LedBarState>>,
+}
+
+impl LedBar {
+    /// Creates a new LED bar with 20 segments and a range of 0 to 100
+    pub fn new(x: i32, y: i32, w: i32, h: i32, label: &str) -> LedBar {
+        let mut slider = Slider::new(x, y, w, h, label);
+        slider.set_range(0., 100.);
+        let state = Rc::new(RefCell::new(LedBarState {
+            segments: 20,
+            thresholds: (None, None),
+            colors: (Color::Green, Color::Yellow, Color::Red),
+            off_color: Color::Dark3,
+        }));
+        let st = state.clone();
+        slider.draw2(move |s| LedBar::draw_bar(s, &st.borrow()));
+        slider.handle2(|_, ev| {
+            matches!(
+                ev,
+                Event::Push | Event::Drag | Event::Released | Event::MouseWheel
+            )
+        });
+        LedBar { slider, state }
+    }
+
+    fn draw_bar(s: &Slider, st: &LedBarState) {
+        let (x, y, w, h) = (s.x(), s.y(), s.width(), s.height());
+        draw::draw_rect_fill(x, y, w, h, Color::Black);
+        let n = st.segments.max(1) as i32;
+        let vertical = h > w;
+        let len = (if vertical { h } else { w }) - 4;
+        let (min, max) = (s.minimum(), s.maximum());
+        let lit = (bounded_fraction(min, max, s.value()) * n as f64).round() as i32;
+        for i in 0..n {
+            let (a, b) = (2 + len * i / n, 2 + len * (i + 1) / n - 2);
+            if b <= a {
+                continue;
+            }
+            let mid = min + (i as f64 + 0.5) / n as f64 * (max - min);
+            let color = if i >= lit {
+                st.off_color
+            } else if st.thresholds.1.map(|t| mid >= t).unwrap_or(false) {
+                st.colors.2
+            } else if st.thresholds.0.map(|t| mid >= t).unwrap_or(false) {
+                st.colors.1
+            } else {
+                st.colors.0
+            };
+            if vertical {
+                draw::draw_rect_fill(x + 2, y + h - b, w - 4, b - a, color);
+            } else {
+                draw::draw_rect_fill(x + a, y + 2, b - a, h - 4, color);
+            }
+        }
+    }
+
+    /// Returns the number of segments
+    pub fn segments(&self) -> u32 {
+        self.state.borrow().segments
+    }
+
+    /// Sets the number of segments
+    pub fn set_segments(&mut self, segments: u32) {
+        assert!(segments > 0);
+        self.state.borrow_mut().segments = segments;
+        self.slider.redraw();
+    }
+
+    /// Returns the warning and alarm thresholds
+    pub fn thresholds(&self) -> (Option<f64>, Option<f64>) {
+        self.state.borrow().thresholds
+    }
+
+    /// Sets the values from which segments light in the warning and alarm colors
+    pub fn set_thresholds(&mut self, warning: Option<f64>, alarm: Option<f64>) {
+        self.state.borrow_mut().thresholds = (warning, alarm);
+        self.slider.redraw();
+    }
+
+    /// Sets the colors of lit segments below the warning threshold, above it and above the alarm threshold
+    pub fn set_level_colors(&mut self, normal: Color, warning: Color, alarm: Color) {
+        self.state.borrow_mut().colors = (normal, warning, alarm);
+        self.slider.redraw();
+    }
+
+    /// Sets the color of unlit segments
+    pub fn set_off_color(&mut self, color: Color) {
+        self.state.borrow_mut().off_color = color;
+        self.slider.redraw();
+    }
+}
+
+impl Deref for LedBar {
+    type Target = Slider;
+
+    fn deref(&self) -> &Self::Target {
+        &self.slider
+    }
+}
+
+impl DerefMut for LedBar {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.slider
+    }
+}
@@ -1,6 +1,7 @@
 use crate::image::Image;
 pub use crate::prelude::*;
 use fltk_sys::widget::*;
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::mem;
 use std::os::raw;
@@ -11,3 +12,86 @@ pub struct Widget {
     _inner: *mut Fl_Widget,
     _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
 }
+
+/// Tracks a widget's lifetime, allowing checks on whether it was deleted.
+/// Useful in callbacks capturing widgets which might get destroyed, like when a window is closed
+/// ```no_run
+/// use fltk::*;
+/// let mut but = button::Button::new(0, 0, 80, 40, "Click");
+/// let frame = frame::Frame::new(0, 50, 80, 40, "");
+/// let tracker = widget::WidgetTracker::new(&frame);
+/// but.set_callback2(move |_| {
+///     if tracker.exists() {
+///         let mut frame = frame.clone();
+///         frame.set_label("Clicked");
+///     }
+/// });
+/// ```
+#[derive(Debug)]
+pub struct WidgetTracker {
+    inner: *mut fltk_sys::fl::Fl_Widget_Tracker,
+}
+
+impl WidgetTracker {
+    /// Creates a new widget tracker
+    pub fn new<W: WidgetExt>(w: &W) -> WidgetTracker {
+        assert!(!w.was_deleted());
+        let inner = unsafe {
+            fltk_sys::fl::Fl_Widget_Tracker_new(w.as_widget_ptr() as *mut fltk_sys::fl::Fl_Widget)
+        };
+        assert!(!inner.is_null());
+        WidgetTracker { inner }
+    }
+
+    /// Returns whether the tracked widget was deleted
+    pub fn deleted(&self) -> bool {
+        unsafe { fltk_sys::fl::Fl_Widget_Tracker_deleted(self.inner) != 0 }
+    }
+
+    /// Returns whether the tracked widget still exists
+    pub fn exists(&self) -> bool {
+        !self.deleted()
+    }
+}
+
+unsafe impl Send for WidgetTracker {}
+unsafe impl Sync for WidgetTracker {}
+
+impl Drop for WidgetTracker {
+    fn drop(&mut self) {
+        unsafe { fltk_sys::fl::Fl_Widget_Tracker_delete(self.inner) }
+    }
+}
+
+/// The accessible name and description of a widget
+type AccessibleInfo = (Option<String>, Option<String>);
+
+thread_local! {
+    static ACCESSIBLE_INFO: RefCell<crate::utils::WidgetMap<AccessibleInfo>> = RefCell::new(Default::default());
+}
+
+/// Sets the accessible name or description of a widget
+pub(crate) fn set_accessible_info(
+    w: &dyn WidgetExt,
+    name: Option<&str>,
+    description: Option<&str>,
+) {
+    ACCESSIBLE_INFO.with(|a| {
+        let mut a = a.borrow_mut();
+        if a.get(w).is_none() {
+            a.insert(w, AccessibleInfo::default());
+        }
+        let info = a.get(w).unwrap();
+        if let Some(name) = name {
+            info.0 = Some(name.to_string());
+        }
+        if let Some(description) = description {
+            info.1 = Some(description.to_string());
+        }
+    })
+}
+
+/// Returns the accessible name and description of a widget
+pub(crate) fn accessible_info(w: &dyn WidgetExt) -> AccessibleInfo {
+    ACCESSIBLE_INFO.with(|a| a.borrow_mut().get(w).cloned().unwrap_or_default())
+}
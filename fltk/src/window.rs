@@ -60,6 +60,96 @@ pub type RawHandle = u32;
 ))]
 pub type RawHandle = u64;
 
+#[cfg(target_os = "windows")]
+#[link(name = "user32")]
+extern "system" {
+    fn FlashWindow(hwnd: *mut raw::c_void, invert: i32) -> i32;
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+#[repr(C)]
+struct XWMHints {
+    flags: raw::c_long,
+    input: raw::c_int,
+    initial_state: raw::c_int,
+    icon_pixmap: raw::c_ulong,
+    icon_window: raw::c_ulong,
+    icon_x: raw::c_int,
+    icon_y: raw::c_int,
+    icon_mask: raw::c_ulong,
+    window_group: raw::c_ulong,
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+extern "C" {
+    fn XGetWMHints(display: *mut raw::c_void, w: raw::c_ulong) -> *mut XWMHints;
+    fn XAllocWMHints() -> *mut XWMHints;
+    fn XSetWMHints(display: *mut raw::c_void, w: raw::c_ulong, hints: *mut XWMHints) -> raw::c_int;
+    fn XFree(data: *mut raw::c_void) -> raw::c_int;
+}
+
+/// Asks the window manager to flag the window, used by WindowExt::request_attention
+fn request_window_attention(handle: RawHandle) {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        FlashWindow(handle, 1);
+    }
+
+    #[cfg(target_os = "macos")]
+    unsafe {
+        let _ = handle;
+        // NSInformationalRequest bounces the dock icon once
+        let ns_app: *mut objc::runtime::Object =
+            msg_send![class!(NSApplication), sharedApplication];
+        let _: isize = msg_send![ns_app, requestUserAttention: 10isize];
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    unsafe {
+        // XUrgencyHint
+        const URGENCY_HINT: raw::c_long = 1 << 8;
+        let disp = crate::app::display();
+        let xid = handle as raw::c_ulong;
+        let mut hints = XGetWMHints(disp, xid);
+        if hints.is_null() {
+            hints = XAllocWMHints();
+        }
+        if hints.is_null() {
+            return;
+        }
+        (*hints).flags |= URGENCY_HINT;
+        XSetWMHints(disp, xid, hints);
+        XFree(hints as _);
+    }
+
+    #[cfg(any(target_os = "android", target_os = "ios"))]
+    let _ = handle;
+}
+
+thread_local! {
+    /// The result slots of the windows shown using WindowExt::show_modal
+    static MODAL_RESULTS: std::cell::RefCell<crate::utils::WidgetMap<std::rc::Rc<std::cell::Cell<Option<i32>>>>> =
+        std::cell::RefCell::new(Default::default());
+}
+
 /// Creates a window widget
 pub type Window = DoubleWindow;
 
@@ -289,6 +379,116 @@ impl MenuWindow {
         win.free_position();
         win
     }
+
+    /// Shows the menu window as a popup at the screen coordinates x and y.
+    /// Menu windows are borderless, stay on top of the other windows, and have no taskbar entry
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut popup = window::MenuWindow::new(0, 0, 150, 100, "");
+    /// let _list = browser::HoldBrowser::new(0, 0, 150, 100, "");
+    /// popup.end();
+    /// let (x, y) = app::event_coords_root();
+    /// popup.popup(x, y);
+    /// ```
+    pub fn popup(&mut self, x: i32, y: i32) {
+        assert!(!self.was_deleted());
+        let (w, h) = (self.width(), self.height());
+        self.resize(x, y, w, h);
+        self.show();
+    }
+
+    /// Marks the menu window as a tooltip window, which doesn't take focus away from the active window
+    pub fn set_tooltip_window(&mut self) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Menu_Window_set_tooltip_window(self._inner) }
+    }
+
+    /// Returns whether the menu window is a tooltip window
+    pub fn is_tooltip_window(&self) -> bool {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Menu_Window_tooltip_window(self._inner) != 0 }
+    }
+
+    /// Draws the menu window in the overlay planes when available
+    pub fn set_overlay(&mut self) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Menu_Window_set_overlay(self._inner) }
+    }
+
+    /// Draws the menu window normally
+    pub fn clear_overlay(&mut self) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Menu_Window_clear_overlay(self._inner) }
+    }
+}
+
+/// Creates an overlay (buffered) window widget
+/// The overlay is drawn on top of the window's content and can be redrawn separately,
+/// which is useful for rubber-band selections and crosshairs
+#[derive(WidgetBase, WidgetExt, GroupExt, WindowExt, Debug)]
+pub struct OverlayWindow {
+    _inner: *mut Fl_Overlay_Window,
+    _tracker: *mut fltk_sys::fl::Fl_Widget_Tracker,
+}
+
+impl OverlayWindow {
+    /// Creates a default initialized overlay window
+    pub fn default() -> OverlayWindow {
+        let mut win = <OverlayWindow as Default>::default();
+        win.free_position();
+        win
+    }
+
+    /// Sets the overlay draw method, which is called whenever the overlay is redrawn
+    /// ```no_run
+    /// use fltk::*;
+    /// let mut win = window::OverlayWindow::new(100, 100, 400, 300, "");
+    /// win.draw_overlay(|w| {
+    ///     let (x, y) = app::event_coords();
+    ///     draw::set_draw_color(Color::Red);
+    ///     draw::draw_xyline(0, y, w.width());
+    ///     draw::draw_yxline(x, 0, w.height());
+    /// });
+    /// win.handle2(|w, ev| match ev {
+    ///     Event::Move => {
+    ///         w.redraw_overlay();
+    ///         true
+    ///     }
+    ///     _ => false,
+    /// });
+    /// ```
+    pub fn draw_overlay<F: FnMut(&mut Self) + 'static>(&mut self, mut cb: F) {
+        assert!(!self.was_deleted());
+        unsafe {
+            unsafe extern "C" fn shim(data: *mut raw::c_void) {
+                let a: *mut Box<dyn FnMut()> = data as *mut Box<dyn FnMut()>;
+                let f: &mut (dyn FnMut()) = &mut **a;
+                let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f()));
+            }
+            let old_data = Fl_Overlay_Window_draw_overlay_data(self._inner);
+            if !old_data.is_null() {
+                let _ = Box::from_raw(old_data as *mut Box<dyn FnMut()>);
+            }
+            let mut win = self.clone();
+            let f = move || cb(&mut win);
+            let a: *mut Box<dyn FnMut()> = Box::into_raw(Box::new(Box::new(f)));
+            let data: *mut raw::c_void = a as *mut raw::c_void;
+            let callback: fltk_sys::window::custom_draw_callback = Some(shim);
+            Fl_Overlay_Window_draw_overlay(self._inner, callback, data);
+        }
+    }
+
+    /// Redraws the overlay only, without redrawing the window's content
+    pub fn redraw_overlay(&mut self) {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Overlay_Window_redraw_overlay(self._inner) }
+    }
+
+    /// Returns whether the system supports hardware overlays, otherwise the overlay is emulated
+    pub fn can_do_overlay(&mut self) -> bool {
+        assert!(!self.was_deleted());
+        unsafe { Fl_Overlay_Window_can_do_overlay(self._inner) != 0 }
+    }
 }
 
 /// A wrapper around a raw OpenGL context
@@ -466,3 +666,300 @@ impl DerefMut for AndroidWindow {
         &mut self.win
     }
 }
+
+const MDI_BAR: i32 = 22;
+const MDI_BORDER: i32 = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum MdiDrag {
+    Move(i32, i32),
+    Resize(bool, bool),
+}
+
+#[derive(Default)]
+struct MdiState {
+    /// The title bar, the close button and the subwindow
+    parts: Vec<Widget>,
+    drag: Option<MdiDrag>,
+    close_cb: Option<Box<dyn FnMut(&mut MdiWindow) -> bool>>,
+    last: (i32, i32, i32, i32),
+}
+
+impl std::fmt::Debug for MdiState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MdiState")
+            .field("drag", &self.drag)
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+impl crate::group::GroupLayout for MdiState {
+    fn last_geometry(&mut self) -> &mut (i32, i32, i32, i32) {
+        &mut self.last
+    }
+
+    fn layout(&mut self, frame: &mut crate::group::Group) {
+        let (x, y, w, h) = (frame.x(), frame.y(), frame.width(), frame.height());
+        let b = MDI_BORDER - 1;
+        let rects = [
+            (x + b, y + b, w - 2 * b - MDI_BAR, MDI_BAR - b),
+            (x + w - b - MDI_BAR, y + b, MDI_BAR, MDI_BAR - b),
+            (x + b, y + MDI_BAR, w - 2 * b, h - MDI_BAR - b),
+        ];
+        for (c, r) in self.parts.iter_mut().zip(rects.iter()) {
+            c.resize(r.0, r.1, std::cmp::max(r.2, 1), std::cmp::max(r.3, 1));
+        }
+    }
+}
+
+/// Defines an MdiWindow (custom widget), a document window embedded inside a workspace window.
+/// It's made of a frame with a title bar and a close button around a subwindow holding the content.
+/// The title bar moves the window, the right and bottom edges resize it, and clicking it raises it above its siblings.
+/// Widgets added to the subwindow are positioned relative to it
+/// ```no_run
+/// use fltk::*;
+/// let mut workspace = window::Window::new(100, 100, 800, 600, "Workspace");
+/// let mut doc = window::MdiWindow::new(10, 10, 300, 200, "Untitled 1");
+/// doc.begin();
+/// let _editor = text::TextEditor::new(0, 0, 294, 175, "");
+/// doc.end();
+/// doc.set_close_callback(|_| true);
+/// let _doc2 = window::MdiWindow::new(200, 150, 300, 200, "Untitled 2");
+/// workspace.end();
+/// workspace.show();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MdiWindow {
+    frame: crate::group::Group,
+    win: DoubleWindow,
+    state: std::rc::Rc<std::cell::RefCell<MdiState>>,
+}
+
+impl MdiWindow {
+    /// Creates a new MdiWindow inside the current group, the geometry includes the frame and title bar
+    pub fn new(x: i32, y: i32, w: i32, h: i32, title: &str) -> MdiWindow {
+        let mut frame = crate::group::Group::new(x, y, w, h, "");
+        frame.set_frame(FrameType::UpBox);
+        let mut bar = crate::frame::Frame::new(0, 0, 0, 0, "").with_label(title);
+        bar.set_frame(FrameType::FlatBox);
+        bar.set_color(Color::Selection);
+        bar.set_label_color(Color::White);
+        bar.set_align(Align::Left | Align::Inside | Align::Clip);
+        let mut close = crate::button::Button::new(0, 0, 0, 0, "@1+");
+        close.set_tooltip("Close");
+        let mut win = DoubleWindow::new(0, 0, 1, 1, "");
+        win.end();
+        frame.end();
+        let parts = unsafe {
+            vec![
+                Widget::from_widget_ptr(bar.as_widget_ptr()),
+                Widget::from_widget_ptr(close.as_widget_ptr()),
+                Widget::from_widget_ptr(win.as_widget_ptr()),
+            ]
+        };
+        let state = std::rc::Rc::new(std::cell::RefCell::new(MdiState {
+            parts,
+            ..Default::default()
+        }));
+        let mdi = MdiWindow {
+            frame: frame.clone(),
+            win: win.clone(),
+            state: state.clone(),
+        };
+        crate::group::relayout(&mut frame, &mut *state.borrow_mut());
+        {
+            let mut mdi = mdi.clone();
+            close.set_callback(move || mdi.close());
+        }
+        {
+            let frame = frame.clone();
+            win.handle2(move |_, ev| {
+                if ev == Event::Push {
+                    MdiWindow::raise_(&frame);
+                }
+                false
+            });
+        }
+        crate::group::relayout_on_resize(&mut frame, &state);
+        let st = state;
+        frame.handle2(move |f, ev| {
+            let (ex, ey) = (event_x(), event_y());
+            let (fx, fy, fw, fh) = (f.x(), f.y(), f.width(), f.height());
+            let at_right = ex >= fx + fw - MDI_BORDER;
+            let at_bottom = ey >= fy + fh - MDI_BORDER;
+            match ev {
+                Event::Push => {
+                    MdiWindow::raise_(f);
+                    let drag = if at_right || at_bottom {
+                        Some(MdiDrag::Resize(at_right, at_bottom))
+                    } else if ey < fy + MDI_BAR && ex < fx + fw - MDI_BAR {
+                        Some(MdiDrag::Move(ex - fx, ey - fy))
+                    } else {
+                        None
+                    };
+                    st.borrow_mut().drag = drag;
+                    drag.is_some()
+                }
+                Event::Drag => {
+                    let drag = st.borrow().drag;
+                    let fptr = unsafe { f.as_widget_ptr() };
+                    let (px, py, pw, ph) = match crate::utils::raw_parent_bounds(fptr) {
+                        Some(bounds) => bounds,
+                        None => return false,
+                    };
+                    match drag {
+                        Some(MdiDrag::Move(dx, dy)) => {
+                            let nx = std::cmp::max(px, std::cmp::min(ex - dx, px + pw - fw));
+                            let ny = std::cmp::max(py, std::cmp::min(ey - dy, py + ph - fh));
+                            f.resize(nx, ny, fw, fh);
+                        }
+                        Some(MdiDrag::Resize(horiz, vert)) => {
+                            let min = 3 * MDI_BAR;
+                            let nw = if horiz {
+                                std::cmp::max(min, std::cmp::min(ex, px + pw) - fx)
+                            } else {
+                                fw
+                            };
+                            let nh = if vert {
+                                std::cmp::max(min, std::cmp::min(ey, py + ph) - fy)
+                            } else {
+                                fh
+                            };
+                            f.resize(fx, fy, nw, nh);
+                        }
+                        None => return false,
+                    }
+                    crate::group::relayout(f, &mut *st.borrow_mut());
+                    crate::utils::redraw_parent(fptr);
+                    true
+                }
+                Event::Released => st.borrow_mut().drag.take().is_some(),
+                Event::Move | Event::Enter => {
+                    let cursor = match (at_right, at_bottom) {
+                        (true, true) => Cursor::SE,
+                        (true, false) => Cursor::WE,
+                        (false, true) => Cursor::NS,
+                        _ => Cursor::Default,
+                    };
+                    if let Some(mut w) = f.window() {
+                        w.set_cursor(cursor);
+                    }
+                    true
+                }
+                Event::Leave => {
+                    if let Some(mut w) = f.window() {
+                        w.set_cursor(Cursor::Default);
+                    }
+                    true
+                }
+                _ => false,
+            }
+        });
+        mdi
+    }
+
+    /// Begins adding widgets to the subwindow
+    pub fn begin(&self) {
+        self.win.begin();
+    }
+
+    /// Ends adding widgets to the subwindow, the workspace becomes the current group again
+    pub fn end(&self) {
+        self.win.end();
+        self.frame.end();
+    }
+
+    /// Returns the outer frame, which is the child of the workspace
+    pub fn frame(&self) -> crate::group::Group {
+        self.frame.clone()
+    }
+
+    /// Returns the title
+    pub fn title(&self) -> String {
+        self.state.borrow().parts[0].label()
+    }
+
+    /// Sets the title
+    pub fn set_title(&mut self, title: &str) {
+        self.state.borrow_mut().parts[0].set_label(title);
+    }
+
+    /// Raises the window above its siblings
+    pub fn raise(&mut self) {
+        MdiWindow::raise_(&self.frame);
+    }
+
+    /// Sets a callback run when the close button is clicked, the window is closed if it returns true
+    pub fn set_close_callback<F: FnMut(&mut MdiWindow) -> bool + 'static>(&mut self, cb: F) {
+        self.state.borrow_mut().close_cb = Some(Box::new(cb));
+    }
+
+    /// Closes the window, running the close callback if any, and deletes it if the callback allows it
+    pub fn close(&mut self) {
+        assert!(!self.frame.was_deleted());
+        let cb = self.state.borrow_mut().close_cb.take();
+        let allow = match cb {
+            Some(mut cb) => {
+                let ret = cb(self);
+                self.state.borrow_mut().close_cb = Some(cb);
+                ret
+            }
+            None => true,
+        };
+        if allow {
+            crate::utils::redraw_parent(unsafe { self.frame.as_widget_ptr() });
+            self.frame.hide();
+            delete_widget(self.frame.clone());
+        }
+    }
+
+    fn raise_(frame: &crate::group::Group) {
+        assert!(!frame.was_deleted());
+        unsafe {
+            let p = fltk_sys::widget::Fl_Widget_parent(frame.as_widget_ptr());
+            if !p.is_null() {
+                fltk_sys::group::Fl_Group_add(
+                    p as *mut fltk_sys::group::Fl_Group,
+                    frame.as_widget_ptr() as *mut raw::c_void,
+                );
+                frame.clone().redraw();
+            }
+        }
+    }
+}
+
+impl Deref for MdiWindow {
+    type Target = DoubleWindow;
+
+    fn deref(&self) -> &Self::Target {
+        &self.win
+    }
+}
+
+impl DerefMut for MdiWindow {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.win
+    }
+}
+
+#[cfg(test)]
+mod window {
+    use super::*;
+    #[test]
+    fn mdi_parents() {
+        let workspace = Window::new(0, 0, 800, 600, "");
+        let doc = MdiWindow::new(10, 10, 300, 200, "Untitled 1");
+        doc.begin();
+        let editor = crate::frame::Frame::new(0, 0, 100, 100, "");
+        doc.end();
+        let doc2 = MdiWindow::new(200, 150, 300, 200, "Untitled 2");
+        workspace.end();
+        unsafe {
+            let parent = |w: &dyn WidgetExt| w.parent().unwrap().as_widget_ptr();
+            assert_eq!(parent(&doc.frame()), workspace.as_widget_ptr());
+            assert_eq!(parent(&doc2.frame()), workspace.as_widget_ptr());
+            assert_eq!(parent(&editor), doc.as_widget_ptr());
+        }
+    }
+}